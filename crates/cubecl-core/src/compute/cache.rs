@@ -0,0 +1,180 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Mutex, OnceLock},
+};
+
+use crate::{ir::KernelDefinition, KernelId};
+
+/// Memoization of expanded [kernel definitions](KernelDefinition) for a single kernel.
+///
+/// The `#[cube(launch)]` macro declares one cache as a `static` in the generated kernel type, so
+/// each kernel only locks and searches its own definitions. The [kernel id](KernelId) already
+/// encodes everything that affects the expansion: the generics, the cube dim, the comptime
+/// arguments and the compilation argument of each input and output (which holds its
+/// vectorization). It is therefore a sufficient key to skip re-expanding a kernel that was
+/// already defined.
+///
+/// The cache is bounded: once it holds [capacity](Self::with_capacity) definitions, the oldest
+/// one is evicted to make room for the new one.
+pub struct KernelDefinitionCache {
+    capacity: usize,
+    state: OnceLock<Mutex<CacheState>>,
+}
+
+#[derive(Default)]
+struct CacheState {
+    definitions: HashMap<KernelId, KernelDefinition>,
+    insertion_order: VecDeque<KernelId>,
+}
+
+impl KernelDefinitionCache {
+    /// Default number of definitions kept per kernel.
+    pub const DEFAULT_CAPACITY: usize = 64;
+
+    /// Create an empty cache with the [default capacity](Self::DEFAULT_CAPACITY).
+    pub const fn new() -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+
+    /// Create an empty cache holding at most `capacity` definitions.
+    pub const fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: OnceLock::new(),
+        }
+    }
+
+    /// Get the kernel definition associated with the given id, expanding it with `define` if it
+    /// isn't cached yet.
+    pub fn get_or_define<F: FnOnce() -> KernelDefinition>(
+        &self,
+        id: KernelId,
+        define: F,
+    ) -> KernelDefinition {
+        if let Some(definition) = self.state().lock().unwrap().definitions.get(&id) {
+            return definition.clone();
+        }
+
+        // The lock isn't held during the expansion, since it may define other kernels.
+        let definition = define();
+
+        if self.capacity == 0 {
+            return definition;
+        }
+
+        let mut state = self.state().lock().unwrap();
+        if let Some(definition) = state.definitions.get(&id) {
+            return definition.clone();
+        }
+
+        while state.definitions.len() >= self.capacity {
+            match state.insertion_order.pop_front() {
+                Some(oldest) => state.definitions.remove(&oldest),
+                None => break,
+            };
+        }
+
+        state.insertion_order.push_back(id.clone());
+        state.definitions.insert(id, definition.clone());
+
+        definition
+    }
+
+    /// Number of cached kernel definitions.
+    pub fn len(&self) -> usize {
+        self.state().lock().unwrap().definitions.len()
+    }
+
+    /// Whether no kernel definition is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all cached kernel definitions.
+    pub fn clear(&self) {
+        let mut state = self.state().lock().unwrap();
+        state.definitions.clear();
+        state.insertion_order.clear();
+    }
+
+    fn state(&self) -> &Mutex<CacheState> {
+        self.state.get_or_init(Default::default)
+    }
+}
+
+impl Default for KernelDefinitionCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ir::Scope, prelude::CubeDim};
+
+    struct Marker;
+
+    fn definition(cube_dim: CubeDim) -> KernelDefinition {
+        KernelDefinition {
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            named: Vec::new(),
+            cube_dim,
+            body: Scope::root(),
+        }
+    }
+
+    #[test]
+    fn kernel_definition_is_expanded_once_per_id() {
+        let cache = KernelDefinitionCache::new();
+        let mut expansions = 0;
+        let id_1 = KernelId::new::<Marker>().info(1u32);
+        let id_2 = KernelId::new::<Marker>().info(2u32);
+
+        for _ in 0..3 {
+            let def = cache.get_or_define(id_1.clone(), || {
+                expansions += 1;
+                definition(CubeDim::new(1, 1, 1))
+            });
+            assert_eq!(def.cube_dim, CubeDim::new(1, 1, 1));
+        }
+        let def = cache.get_or_define(id_2, || {
+            expansions += 1;
+            definition(CubeDim::new(2, 1, 1))
+        });
+
+        assert_eq!(expansions, 2);
+        assert_eq!(def.cube_dim, CubeDim::new(2, 1, 1));
+    }
+
+    #[test]
+    fn oldest_kernel_definition_is_evicted_at_capacity() {
+        let cache = KernelDefinitionCache::with_capacity(2);
+        let mut expansions = 0;
+        let ids: Vec<_> = (0..3u32)
+            .map(|i| KernelId::new::<Marker>().info(i))
+            .collect();
+
+        for id in ids.iter() {
+            cache.get_or_define(id.clone(), || {
+                expansions += 1;
+                definition(CubeDim::new(1, 1, 1))
+            });
+        }
+        assert_eq!(cache.len(), 2);
+
+        // The first id was evicted, the last one is still cached.
+        cache.get_or_define(ids[2].clone(), || {
+            expansions += 1;
+            definition(CubeDim::new(1, 1, 1))
+        });
+        cache.get_or_define(ids[0].clone(), || {
+            expansions += 1;
+            definition(CubeDim::new(1, 1, 1))
+        });
+
+        assert_eq!(expansions, 4);
+        assert_eq!(cache.len(), 2);
+    }
+}
@@ -1,7 +1,9 @@
 mod builder;
+mod cache;
 mod kernel;
 mod launcher;
 
 pub use builder::*;
+pub use cache::*;
 pub use kernel::*;
 pub use launcher::*;
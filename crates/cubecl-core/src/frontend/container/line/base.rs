@@ -1,7 +1,7 @@
 use std::num::NonZero;
 
 use crate::{
-    ir::{ConstantScalarValue, Item, Operator},
+    ir::{ConstantScalarValue, Instruction, Item, Operator, ShuffleOperator},
     prelude::{binary_expand_fixed_output, CubeContext, Dot, ExpandElement, Numeric},
     unexpanded,
 };
@@ -168,6 +168,213 @@ mod size {
     }
 }
 
+/// Module that contains the implementation details of the shuffle and swizzle functions.
+mod shuffle {
+    use super::*;
+
+    impl<P: CubePrimitive> Line<P> {
+        /// Permute the elements of the line, where `indices[i]` is the lane of `self` written to
+        /// lane `i` of the output.
+        ///
+        /// The indices are comptime and must be a permutation of the lanes of the line.
+        ///
+        /// ```rust, ignore
+        /// // [a, b, c, d] => [a, c, b, d]
+        /// let line = line.shuffle([0, 2, 1, 3]);
+        /// ```
+        #[allow(unused_variables)]
+        pub fn shuffle<const N: usize>(self, indices: [u32; N]) -> Self {
+            unexpanded!()
+        }
+
+        /// Select the elements of the line at the given comptime indices.
+        ///
+        /// Contrary to [shuffle](Self::shuffle), lanes can be repeated or omitted, and the size
+        /// of the output is the number of indices.
+        ///
+        /// ```rust, ignore
+        /// // [a, b, c, d] => [b, b, a]
+        /// let line = line.swizzle([1, 1, 0]);
+        /// ```
+        #[allow(unused_variables)]
+        pub fn swizzle<const N: usize>(self, indices: [u32; N]) -> Self {
+            unexpanded!()
+        }
+    }
+
+    impl<P: CubePrimitive> ExpandElementTyped<Line<P>> {
+        /// Expand method of [shuffle](Line::shuffle).
+        pub fn __expand_shuffle_method(self, context: &mut CubeContext, indices: Vec<u32>) -> Self {
+            let size = self.size();
+            let mut sorted = indices.clone();
+            sorted.sort();
+            assert!(
+                sorted.into_iter().eq(0..size),
+                "Shuffle indices {indices:?} must be a permutation of the {size} lanes of the line"
+            );
+
+            self.__expand_swizzle_method(context, indices)
+        }
+
+        /// Expand method of [swizzle](Line::swizzle).
+        pub fn __expand_swizzle_method(self, context: &mut CubeContext, indices: Vec<u32>) -> Self {
+            let size = self.size();
+            assert!(
+                indices.iter().all(|index| *index < size),
+                "Swizzle indices {indices:?} are out of bounds for a line of size {size}"
+            );
+
+            shuffle_expand::<P>(context, self.clone(), self, indices)
+        }
+    }
+}
+
+/// Module that contains the implementation details of the extract function.
+mod extract {
+    use crate::prelude::binary_expand_no_vec;
+
+    use super::*;
+
+    impl<P: CubePrimitive> Line<P> {
+        /// Get the element of the line at the given lane, which doesn't have to be known at
+        /// comptime.
+        #[allow(unused_variables)]
+        pub fn extract(&self, index: u32) -> P {
+            unexpanded!()
+        }
+    }
+
+    impl<P: CubePrimitive> ExpandElementTyped<Line<P>> {
+        /// Expand method of [extract](Line::extract).
+        pub fn __expand_extract_method(
+            &self,
+            context: &mut CubeContext,
+            index: ExpandElementTyped<u32>,
+        ) -> ExpandElementTyped<P> {
+            binary_expand_no_vec(context, self.expand.clone(), index.expand, Operator::Index).into()
+        }
+    }
+}
+
+/// Module that contains the implementation details of the insert function.
+mod insert {
+    use crate::ir::BinaryOperator;
+
+    use super::*;
+
+    impl<P: CubePrimitive> Line<P> {
+        /// Set the element of the line at the given lane, which doesn't have to be known at
+        /// comptime.
+        #[allow(unused_variables)]
+        pub fn insert(&mut self, index: u32, value: P) {
+            unexpanded!()
+        }
+    }
+
+    impl<P: CubePrimitive> ExpandElementTyped<Line<P>> {
+        /// Expand method of [insert](Line::insert).
+        pub fn __expand_insert_method(
+            &self,
+            context: &mut CubeContext,
+            index: ExpandElementTyped<u32>,
+            value: ExpandElementTyped<P>,
+        ) {
+            context.register(Instruction::new(
+                Operator::IndexAssign(BinaryOperator {
+                    lhs: *index.expand,
+                    rhs: *value.expand,
+                }),
+                *self.expand,
+            ));
+        }
+    }
+}
+
+/// Module that contains the implementation details of the concat and split functions.
+mod concat {
+    use super::*;
+
+    impl<P: CubePrimitive> Line<P> {
+        /// Create a line with the elements of `lhs` followed by the elements of `rhs`.
+        #[allow(unused_variables)]
+        pub fn concat(lhs: Self, rhs: Self) -> Self {
+            unexpanded!()
+        }
+
+        /// Split the line in two lines at the given comptime lane, the first one containing the
+        /// elements before `at` and the second one the remaining elements.
+        #[allow(unused_variables)]
+        pub fn split(self, at: u32) -> (Self, Self) {
+            unexpanded!()
+        }
+
+        /// Expand function of [concat](Self::concat).
+        pub fn __expand_concat(
+            context: &mut CubeContext,
+            lhs: ExpandElementTyped<Self>,
+            rhs: ExpandElementTyped<Self>,
+        ) -> ExpandElementTyped<Self> {
+            let indices = (0..lhs.size() + rhs.size()).collect();
+            shuffle_expand::<P>(context, lhs, rhs, indices)
+        }
+
+        /// Expand function of [split](Self::split).
+        pub fn __expand_split(
+            context: &mut CubeContext,
+            line: ExpandElementTyped<Self>,
+            at: ExpandElementTyped<u32>,
+        ) -> (ExpandElementTyped<Self>, ExpandElementTyped<Self>) {
+            line.__expand_split_method(context, at)
+        }
+    }
+
+    impl<P: CubePrimitive> ExpandElementTyped<Line<P>> {
+        /// Expand method of [split](Line::split).
+        pub fn __expand_split_method(
+            self,
+            context: &mut CubeContext,
+            at: ExpandElementTyped<u32>,
+        ) -> (Self, Self) {
+            let at = at
+                .constant()
+                .expect("Split lane must be known at comptime")
+                .as_u32();
+            let size = self.size();
+            assert!(
+                at > 0 && at < size,
+                "Can't split a line of size {size} at lane {at}"
+            );
+
+            let first = shuffle_expand::<P>(context, self.clone(), self.clone(), (0..at).collect());
+            let second = shuffle_expand::<P>(context, self.clone(), self, (at..size).collect());
+            (first, second)
+        }
+    }
+}
+
+/// Select the lanes of `lhs` and `rhs`, the lanes of `rhs` being indexed after the ones of `lhs`.
+fn shuffle_expand<P: CubePrimitive>(
+    context: &mut CubeContext,
+    lhs: ExpandElementTyped<Line<P>>,
+    rhs: ExpandElementTyped<Line<P>>,
+    indices: Vec<u32>,
+) -> ExpandElementTyped<Line<P>> {
+    let size = u8::try_from(indices.len()).expect("Line size should fit in a u8");
+    let vectorization = NonZero::new(size).filter(|size| size.get() > 1);
+    let output = context.create_local_binding(Item::vectorized(P::as_elem(), vectorization));
+
+    context.register(Instruction::new(
+        Operator::Shuffle(ShuffleOperator {
+            lhs: *lhs.expand,
+            rhs: *rhs.expand,
+            indices,
+        }),
+        *output,
+    ));
+
+    output.into()
+}
+
 impl<P: CubePrimitive> CubeType for Line<P> {
     type ExpandType = ExpandElementTyped<Self>;
 }
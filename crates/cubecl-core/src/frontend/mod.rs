@@ -9,6 +9,7 @@ mod context;
 mod element;
 mod indexation;
 mod operation;
+mod random;
 mod subcube;
 mod topology;
 
@@ -19,5 +20,6 @@ pub use context::*;
 pub use element::*;
 pub use indexation::*;
 pub use operation::*;
+pub use random::*;
 pub use subcube::*;
 pub use topology::*;
@@ -0,0 +1,108 @@
+//! Counter-based random number generation on device.
+//!
+//! [Philox4x32-10](philox4x32_10) maps a counter and a key to four random words without any
+//! state, so each unit draws its own numbers from its position. The same seed, offset and
+//! position always give the same bits on every runtime.
+
+use crate as cubecl;
+use crate::prelude::*;
+
+/// Four 32-bit words, used both as the counter and as the output of [philox4x32_10].
+#[derive(CubeType, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Philox4x32 {
+    pub x0: u32,
+    pub x1: u32,
+    pub x2: u32,
+    pub x3: u32,
+}
+
+/// The high and low words of the 64-bit product of two words.
+///
+/// The product is split in 16-bit halves, since not every runtime supports 64-bit integers.
+#[cube]
+pub fn mul_hi_lo(lhs: u32, rhs: u32) -> (u32, u32) {
+    let lhs_lo = lhs & 0xFFFFu32;
+    let lhs_hi = lhs >> 16;
+    let rhs_lo = rhs & 0xFFFFu32;
+    let rhs_hi = rhs >> 16;
+
+    let lo_lo = lhs_lo * rhs_lo;
+    let lo_hi = lhs_lo * rhs_hi;
+    let hi_lo = lhs_hi * rhs_lo;
+    let hi_hi = lhs_hi * rhs_hi;
+
+    let middle = (lo_lo >> 16) + (lo_hi & 0xFFFFu32) + (hi_lo & 0xFFFFu32);
+    let hi = hi_hi + (lo_hi >> 16) + (hi_lo >> 16) + (middle >> 16);
+
+    (hi, lhs * rhs)
+}
+
+/// The Philox4x32 block cipher with 10 rounds, applied to the counter with the key
+/// `(key0, key1)`.
+///
+/// The output matches the known answers of the reference implementation of Random123.
+#[cube]
+pub fn philox4x32_10(counter: Philox4x32, key0: u32, key1: u32) -> Philox4x32 {
+    let mut x0 = counter.x0;
+    let mut x1 = counter.x1;
+    let mut x2 = counter.x2;
+    let mut x3 = counter.x3;
+    let mut k0 = key0;
+    let mut k1 = key1;
+
+    #[unroll]
+    for round in 0..10 {
+        if round > 0 {
+            k0 += 0x9E37_79B9u32;
+            k1 += 0xBB67_AE85u32;
+        }
+
+        let (hi0, lo0) = mul_hi_lo(0xD251_1F53u32, x0);
+        let (hi1, lo1) = mul_hi_lo(0xCD9E_8D57u32, x2);
+
+        x0 = hi1 ^ x1 ^ k0;
+        x1 = lo1;
+        x2 = hi0 ^ x3 ^ k1;
+        x3 = lo0;
+    }
+
+    Philox4x32 { x0, x1, x2, x3 }
+}
+
+/// Four random words for the unit at [ABSOLUTE_POS], keyed by the 64-bit seed `(seed_lo, seed_hi)`.
+///
+/// Kernels drawing more than once from the same seed use a different 64-bit offset
+/// `(offset_lo, offset_hi)` for each draw.
+#[cube]
+pub fn random_bits(seed_lo: u32, seed_hi: u32, offset_lo: u32, offset_hi: u32) -> Philox4x32 {
+    let counter = Philox4x32 {
+        x0: ABSOLUTE_POS,
+        x1: 0u32,
+        x2: offset_lo,
+        x3: offset_hi,
+    };
+
+    philox4x32_10(counter, seed_lo, seed_hi)
+}
+
+/// Map random bits to a float uniformly distributed in `[0, 1)`.
+///
+/// Only the 24 most significant bits are kept, so that every value is exact in `f32`.
+#[cube]
+pub fn uniform_from_bits(bits: u32) -> f32 {
+    f32::cast_from(bits >> 8) * 5.960_464_5e-8f32
+}
+
+/// Map two random words to two independent floats with a standard normal distribution, with
+/// the Box-Muller transform.
+#[cube]
+pub fn normal_from_bits(bits0: u32, bits1: u32) -> (f32, f32) {
+    // Shifted to `(0, 1]`, so that the logarithm is finite.
+    let u0 = f32::cast_from((bits0 >> 8) + 1) * 5.960_464_5e-8f32;
+    let u1 = uniform_from_bits(bits1);
+
+    let radius = f32::sqrt(-2.0 * Log::log(u0));
+    let theta = 6.283_185_5f32 * u1;
+
+    (radius * f32::cos(theta), radius * f32::sin(theta))
+}
@@ -58,6 +58,44 @@ pub mod subcube_broadcast {
     }
 }
 
+/// Returns the value of the subcube unit at the given index, which can differ between units.
+///
+/// Unlike [subcube_broadcast()], whose index must be the same for all units, this allows
+/// data movement patterns such as scans and rotations within a subcube. All units of the
+/// subcube must call it, and the unit at the index must be active.
+///
+/// On SPIR-V it requires the `GroupNonUniformShuffle` capability, and on WGSL the `subgroups`
+/// extension.
+#[allow(unused_variables)]
+pub fn subcube_shuffle<E: CubePrimitive>(value: E, index: u32) -> E {
+    unexpanded!()
+}
+
+/// Module containing the expand function for [subcube_shuffle()].
+pub mod subcube_shuffle {
+
+    use super::*;
+
+    /// Expand method of [subcube_shuffle()].
+    pub fn expand<E: CubePrimitive>(
+        context: &mut CubeContext,
+        value: ExpandElementTyped<E>,
+        id: ExpandElementTyped<u32>,
+    ) -> ExpandElementTyped<E> {
+        let output = context.create_local_binding(value.expand.item);
+        let out = *output;
+        let lhs = *value.expand;
+        let rhs = *id.expand;
+
+        context.register(Instruction::new(
+            Subcube::Shuffle(crate::ir::BinaryOperator { lhs, rhs }),
+            out,
+        ));
+
+        output.into()
+    }
+}
+
 /// Perform a reduce sum operation across all units in a subcube.
 #[allow(unused_variables)]
 pub fn subcube_sum<E: CubePrimitive>(value: E) -> E {
@@ -0,0 +1,329 @@
+use std::num::NonZero;
+
+use half::{bf16, f16};
+
+use crate::{
+    frontend::{
+        Array, AtomicI32, AtomicI64, AtomicU32, CubeContext, CubePrimitive, CubeType,
+        ExpandElementTyped, Line, Slice, SliceMut, Tensor,
+    },
+    ir::{ConstantScalarValue, FloatKind, IntKind, Item, UIntKind},
+    prelude::{flex32, tf32},
+};
+
+use super::{value::*, HostExecution, HostLine};
+
+/// A [primitive](CubePrimitive) that can be converted from and to a host value.
+pub trait HostElement: CubePrimitive {
+    /// The value of the element on the host.
+    type Host: Clone;
+
+    /// The lanes of the host value.
+    fn to_line(value: &Self::Host) -> HostLine;
+    /// The host value of the lanes.
+    fn from_line(line: &[ConstantScalarValue]) -> Self::Host;
+}
+
+/// A [cube type](CubeType) that can be passed to and returned by a cube function executed on the
+/// host.
+pub trait HostArg: CubeType {
+    /// The value of the argument on the host.
+    type Host;
+
+    /// Register the host value as a variable of the execution.
+    fn register(
+        value: &Self::Host,
+        execution: &mut HostExecution,
+        context: &mut CubeContext,
+    ) -> Self::ExpandType;
+
+    /// Read the value of the variable after the execution.
+    fn read(expand: &Self::ExpandType, execution: &HostExecution) -> Self::Host;
+}
+
+/// A tensor on the host, with its shape and its strides.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HostTensor<T> {
+    pub data: Vec<T>,
+    pub shape: Vec<u32>,
+    pub strides: Vec<u32>,
+}
+
+impl<T> HostTensor<T> {
+    /// Create a contiguous tensor with the given shape.
+    pub fn new(data: Vec<T>, shape: Vec<u32>) -> Self {
+        let mut strides = vec![1; shape.len()];
+        for i in (0..shape.len().saturating_sub(1)).rev() {
+            strides[i] = strides[i + 1] * shape[i + 1];
+        }
+
+        Self::new_strided(data, shape, strides)
+    }
+
+    /// Create a tensor with the given shape and strides.
+    pub fn new_strided(data: Vec<T>, shape: Vec<u32>, strides: Vec<u32>) -> Self {
+        Self {
+            data,
+            shape,
+            strides,
+        }
+    }
+}
+
+macro_rules! host_element {
+    ($ty:ty, $host:ty, |$value:ident| $to:expr, |$scalar:ident| $from:expr) => {
+        impl HostElement for $ty {
+            type Host = $host;
+
+            fn to_line($value: &Self::Host) -> HostLine {
+                vec![cast($to, Self::as_elem())]
+            }
+
+            fn from_line(line: &[ConstantScalarValue]) -> Self::Host {
+                let $scalar = line[0];
+                $from
+            }
+        }
+    };
+}
+
+macro_rules! host_float {
+    ($ty:ty, |$value:ident| $to:expr, |$float:ident| $from:expr) => {
+        host_element!(
+            $ty,
+            $ty,
+            |$value| ConstantScalarValue::Float($to, FloatKind::F64),
+            |scalar| {
+                let $float = as_f64(scalar);
+                $from
+            }
+        );
+    };
+}
+
+macro_rules! host_int {
+    ($ty:ty, $host:ty) => {
+        host_element!(
+            $ty,
+            $host,
+            |value| ConstantScalarValue::Int(*value as i64, IntKind::I64),
+            |scalar| as_i64(scalar) as $host
+        );
+    };
+}
+
+macro_rules! host_uint {
+    ($ty:ty, $host:ty) => {
+        host_element!(
+            $ty,
+            $host,
+            |value| ConstantScalarValue::UInt(*value as u64, UIntKind::U64),
+            |scalar| as_u64(scalar) as $host
+        );
+    };
+}
+
+host_float!(f16, |value| value.to_f64(), |value| f16::from_f64(value));
+host_float!(bf16, |value| value.to_f64(), |value| bf16::from_f64(value));
+host_float!(flex32, |value| value.to_f64(), |value| flex32::from_f64(
+    value
+));
+host_float!(tf32, |value| value.to_f64(), |value| tf32::from_f64(value));
+host_float!(f32, |value| *value as f64, |value| value as f32);
+host_float!(f64, |value| *value, |value| value);
+
+host_int!(i8, i8);
+host_int!(i16, i16);
+host_int!(i32, i32);
+host_int!(i64, i64);
+host_int!(AtomicI32, i32);
+host_int!(AtomicI64, i64);
+
+host_uint!(u8, u8);
+host_uint!(u16, u16);
+host_uint!(u32, u32);
+host_uint!(u64, u64);
+host_uint!(AtomicU32, u32);
+
+host_element!(
+    bool,
+    bool,
+    |value| ConstantScalarValue::Bool(*value),
+    |scalar| as_bool(scalar)
+);
+
+impl<P: HostElement> HostElement for Line<P> {
+    type Host = Vec<P::Host>;
+
+    fn to_line(value: &Self::Host) -> HostLine {
+        value.iter().flat_map(P::to_line).collect()
+    }
+
+    fn from_line(line: &[ConstantScalarValue]) -> Self::Host {
+        line.iter()
+            .map(|value| P::from_line(core::slice::from_ref(value)))
+            .collect()
+    }
+}
+
+/// The item of the element, vectorized with the size of the line.
+fn item_of<E: HostElement>(line: &HostLine) -> Item {
+    let vectorization = NonZero::new(line.len() as u8).filter(|size| size.get() > 1);
+    Item::vectorized(E::as_elem(), vectorization)
+}
+
+fn register_buffer<E: HostElement, T: CubeType>(
+    data: &[E::Host],
+    shape: Vec<u32>,
+    strides: Vec<u32>,
+    execution: &mut HostExecution,
+) -> ExpandElementTyped<T> {
+    let data: Vec<HostLine> = data.iter().map(E::to_line).collect();
+    let item = match data.first() {
+        Some(line) => item_of::<E>(line),
+        None => Item::new(E::as_elem()),
+    };
+
+    execution.create_buffer(item, data, shape, strides).into()
+}
+
+fn read_buffer<E: HostElement, T: CubeType>(
+    expand: &ExpandElementTyped<T>,
+    execution: &HostExecution,
+) -> Vec<E::Host> {
+    execution
+        .read_buffer(&expand.expand)
+        .iter()
+        .map(|line| E::from_line(line))
+        .collect()
+}
+
+impl<E: HostElement> HostArg for E {
+    type Host = E::Host;
+
+    fn register(
+        value: &Self::Host,
+        execution: &mut HostExecution,
+        context: &mut CubeContext,
+    ) -> Self::ExpandType {
+        let line = E::to_line(value);
+        execution
+            .create_value(context, item_of::<E>(&line), line)
+            .into()
+    }
+
+    fn read(expand: &Self::ExpandType, execution: &HostExecution) -> Self::Host {
+        E::from_line(&execution.read_value(&expand.expand))
+    }
+}
+
+impl<E: HostElement> HostArg for Array<E> {
+    type Host = Vec<E::Host>;
+
+    fn register(
+        value: &Self::Host,
+        execution: &mut HostExecution,
+        _context: &mut CubeContext,
+    ) -> Self::ExpandType {
+        register_buffer::<E, _>(value, vec![value.len() as u32], vec![1], execution)
+    }
+
+    fn read(expand: &Self::ExpandType, execution: &HostExecution) -> Self::Host {
+        read_buffer::<E, _>(expand, execution)
+    }
+}
+
+impl<E: HostElement> HostArg for Tensor<E> {
+    type Host = HostTensor<E::Host>;
+
+    fn register(
+        value: &Self::Host,
+        execution: &mut HostExecution,
+        _context: &mut CubeContext,
+    ) -> Self::ExpandType {
+        register_buffer::<E, _>(
+            &value.data,
+            value.shape.clone(),
+            value.strides.clone(),
+            execution,
+        )
+    }
+
+    fn read(expand: &Self::ExpandType, execution: &HostExecution) -> Self::Host {
+        let data = read_buffer::<E, _>(expand, execution);
+        let (shape, strides) = execution.read_layout(&expand.expand);
+        HostTensor::new_strided(data, shape, strides)
+    }
+}
+
+impl<E: HostElement> HostArg for Slice<'_, E> {
+    type Host = Vec<E::Host>;
+
+    fn register(
+        value: &Self::Host,
+        execution: &mut HostExecution,
+        _context: &mut CubeContext,
+    ) -> Self::ExpandType {
+        register_buffer::<E, _>(value, vec![value.len() as u32], vec![1], execution)
+    }
+
+    fn read(expand: &Self::ExpandType, execution: &HostExecution) -> Self::Host {
+        read_buffer::<E, _>(expand, execution)
+    }
+}
+
+impl<E: HostElement> HostArg for SliceMut<'_, E> {
+    type Host = Vec<E::Host>;
+
+    fn register(
+        value: &Self::Host,
+        execution: &mut HostExecution,
+        _context: &mut CubeContext,
+    ) -> Self::ExpandType {
+        register_buffer::<E, _>(value, vec![value.len() as u32], vec![1], execution)
+    }
+
+    fn read(expand: &Self::ExpandType, execution: &HostExecution) -> Self::Host {
+        read_buffer::<E, _>(expand, execution)
+    }
+}
+
+impl HostArg for () {
+    type Host = ();
+
+    fn register(
+        _value: &Self::Host,
+        _execution: &mut HostExecution,
+        _context: &mut CubeContext,
+    ) -> Self::ExpandType {
+    }
+
+    fn read(_expand: &Self::ExpandType, _execution: &HostExecution) -> Self::Host {}
+}
+
+macro_rules! host_tuple {
+    ($($P:ident: $i:tt),*) => {
+        impl<$($P: HostArg),*> HostArg for ($($P,)*) {
+            type Host = ($($P::Host,)*);
+
+            fn register(
+                value: &Self::Host,
+                execution: &mut HostExecution,
+                context: &mut CubeContext,
+            ) -> Self::ExpandType {
+                ($($P::register(&value.$i, execution, context),)*)
+            }
+
+            fn read(expand: &Self::ExpandType, execution: &HostExecution) -> Self::Host {
+                ($($P::read(&expand.$i, execution),)*)
+            }
+        }
+    };
+}
+
+host_tuple!(P1: 0);
+host_tuple!(P1: 0, P2: 1);
+host_tuple!(P1: 0, P2: 1, P3: 2);
+host_tuple!(P1: 0, P2: 1, P3: 2, P4: 3);
+host_tuple!(P1: 0, P2: 1, P3: 2, P4: 3, P5: 4);
+host_tuple!(P1: 0, P2: 1, P3: 2, P4: 3, P5: 4, P6: 5);
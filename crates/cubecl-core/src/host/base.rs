@@ -0,0 +1,107 @@
+use crate::ir::{Builtin, CubeDim};
+
+/// The builtin values of the unit executing a cube function on the host.
+///
+/// Only a single unit is executed, so every other unit of its cube and its plane is assumed to
+/// not exist. Plane operations therefore behave as if the plane only contained this unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HostUnit {
+    /// Position of the unit in its cube, i.e. `UNIT_POS_X`, `UNIT_POS_Y` and `UNIT_POS_Z`.
+    pub unit_pos: (u32, u32, u32),
+    /// Position of the cube in the cube count, i.e. `CUBE_POS_X`, `CUBE_POS_Y` and `CUBE_POS_Z`.
+    pub cube_pos: (u32, u32, u32),
+    /// The cube dimension, i.e. `CUBE_DIM_X`, `CUBE_DIM_Y` and `CUBE_DIM_Z`.
+    pub cube_dim: CubeDim,
+    /// The number of cubes, i.e. `CUBE_COUNT_X`, `CUBE_COUNT_Y` and `CUBE_COUNT_Z`.
+    pub cube_count: (u32, u32, u32),
+    /// The plane dimension, i.e. `SUBCUBE_DIM`.
+    pub subcube_dim: u32,
+}
+
+impl Default for HostUnit {
+    fn default() -> Self {
+        Self {
+            unit_pos: (0, 0, 0),
+            cube_pos: (0, 0, 0),
+            cube_dim: CubeDim::new(1, 1, 1),
+            cube_count: (1, 1, 1),
+            subcube_dim: 1,
+        }
+    }
+}
+
+impl HostUnit {
+    /// Set the position of the unit in its cube.
+    pub fn with_unit_pos(mut self, x: u32, y: u32, z: u32) -> Self {
+        self.unit_pos = (x, y, z);
+        self
+    }
+
+    /// Set the position of the cube.
+    pub fn with_cube_pos(mut self, x: u32, y: u32, z: u32) -> Self {
+        self.cube_pos = (x, y, z);
+        self
+    }
+
+    /// Set the cube dimension.
+    pub fn with_cube_dim(mut self, cube_dim: CubeDim) -> Self {
+        self.cube_dim = cube_dim;
+        self
+    }
+
+    /// Set the number of cubes.
+    pub fn with_cube_count(mut self, x: u32, y: u32, z: u32) -> Self {
+        self.cube_count = (x, y, z);
+        self
+    }
+
+    /// Set the plane dimension.
+    pub fn with_subcube_dim(mut self, subcube_dim: u32) -> Self {
+        self.subcube_dim = subcube_dim;
+        self
+    }
+
+    /// The value of the given builtin for this unit.
+    pub fn builtin(&self, builtin: Builtin) -> u32 {
+        let (unit_x, unit_y, unit_z) = self.unit_pos;
+        let (cube_x, cube_y, cube_z) = self.cube_pos;
+        let CubeDim {
+            x: dim_x,
+            y: dim_y,
+            z: dim_z,
+        } = self.cube_dim;
+        let (count_x, count_y, count_z) = self.cube_count;
+
+        let absolute_x = cube_x * dim_x + unit_x;
+        let absolute_y = cube_y * dim_y + unit_y;
+        let absolute_z = cube_z * dim_z + unit_z;
+
+        match builtin {
+            Builtin::UnitPos => unit_z * dim_x * dim_y + unit_y * dim_x + unit_x,
+            Builtin::UnitPosX => unit_x,
+            Builtin::UnitPosY => unit_y,
+            Builtin::UnitPosZ => unit_z,
+            Builtin::CubePos => cube_z * count_x * count_y + cube_y * count_x + cube_x,
+            Builtin::CubePosX => cube_x,
+            Builtin::CubePosY => cube_y,
+            Builtin::CubePosZ => cube_z,
+            Builtin::CubeDim => dim_x * dim_y * dim_z,
+            Builtin::CubeDimX => dim_x,
+            Builtin::CubeDimY => dim_y,
+            Builtin::CubeDimZ => dim_z,
+            Builtin::CubeCount => count_x * count_y * count_z,
+            Builtin::CubeCountX => count_x,
+            Builtin::CubeCountY => count_y,
+            Builtin::CubeCountZ => count_z,
+            Builtin::SubcubeDim => self.subcube_dim,
+            Builtin::AbsolutePos => {
+                let num_x = count_x * dim_x;
+                let num_y = count_y * dim_y;
+                absolute_z * num_x * num_y + absolute_y * num_x + absolute_x
+            }
+            Builtin::AbsolutePosX => absolute_x,
+            Builtin::AbsolutePosY => absolute_y,
+            Builtin::AbsolutePosZ => absolute_z,
+        }
+    }
+}
@@ -0,0 +1,900 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use crate::{
+    frontend::{AtomicOp, CubeContext, ExpandElement},
+    ir::{
+        BinaryOperator, Branch, ConstantScalarValue, Elem, Instruction, IntKind, Item, Metadata,
+        Operation, Operator, Scope, Subcube, UIntKind, UnaryOperator, Variable, VariableKind,
+    },
+};
+
+use super::{value::*, HostUnit};
+
+/// The value of every lane of an item.
+pub type HostLine = Vec<ConstantScalarValue>;
+
+/// Executes the [scope](Scope) of a cube function for a single [unit](HostUnit) on the host.
+///
+/// Inputs are registered with [create_value](Self::create_value) and
+/// [create_buffer](Self::create_buffer) before expanding the function, and read back with
+/// [read_value](Self::read_value) and [read_buffer](Self::read_buffer) after its
+/// [execution](Self::execute).
+pub struct HostExecution {
+    unit: HostUnit,
+    values: HashMap<Key, Value>,
+    buffers: HashMap<Key, Buffer>,
+    num_buffers: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Key {
+    Input(u16),
+    Output(u16),
+    Scalar(u16),
+    Local(u16, u8),
+    Versioned(u16, u8, u16),
+    Binding(u16, u8),
+    ConstantArray(u16),
+    SharedMemory(u16),
+    LocalArray(u16, u8),
+    Matrix(u16, u8),
+    Slice(u16, u8),
+}
+
+impl Key {
+    fn of(var: &Variable) -> Option<Self> {
+        let key = match var.kind {
+            VariableKind::GlobalInputArray(id) => Key::Input(id),
+            VariableKind::GlobalOutputArray(id) => Key::Output(id),
+            VariableKind::GlobalScalar(id) => Key::Scalar(id),
+            VariableKind::Local { id, depth } => Key::Local(id, depth),
+            VariableKind::Versioned { id, depth, version } => Key::Versioned(id, depth, version),
+            VariableKind::LocalBinding { id, depth } => Key::Binding(id, depth),
+            VariableKind::ConstantArray { id, .. } => Key::ConstantArray(id),
+            VariableKind::SharedMemory { id, .. } => Key::SharedMemory(id),
+            VariableKind::LocalArray { id, depth, .. } => Key::LocalArray(id, depth),
+            VariableKind::Matrix { id, depth, .. } => Key::Matrix(id, depth),
+            VariableKind::Slice { id, depth } => Key::Slice(id, depth),
+            VariableKind::ConstantScalar(_) | VariableKind::Builtin(_) => return None,
+        };
+        Some(key)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Value {
+    Line(HostLine),
+    /// A view of a part of a buffer.
+    Slice {
+        buffer: Key,
+        offset: usize,
+        len: usize,
+    },
+    /// A reference to an atomic element of a buffer.
+    Reference {
+        buffer: Key,
+        index: usize,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct Buffer {
+    data: Vec<HostLine>,
+    shape: Vec<u32>,
+    strides: Vec<u32>,
+}
+
+enum Flow {
+    Continue,
+    Break,
+    Return,
+}
+
+impl HostExecution {
+    /// Create a new execution for the given unit.
+    pub fn new(unit: HostUnit) -> Self {
+        Self {
+            unit,
+            values: HashMap::new(),
+            buffers: HashMap::new(),
+            num_buffers: 0,
+        }
+    }
+
+    /// Create a variable holding the given value.
+    pub fn create_value(
+        &mut self,
+        context: &mut CubeContext,
+        item: Item,
+        value: HostLine,
+    ) -> ExpandElement {
+        let var = context.create_local_binding(item);
+        self.write(&var, value);
+        var
+    }
+
+    /// Create an array variable backed by the given data, with the shape and the strides
+    /// returned by the tensor metadata.
+    pub fn create_buffer(
+        &mut self,
+        item: Item,
+        data: Vec<HostLine>,
+        shape: Vec<u32>,
+        strides: Vec<u32>,
+    ) -> ExpandElement {
+        let var = Variable::new(VariableKind::GlobalOutputArray(self.num_buffers), item);
+        self.num_buffers += 1;
+        self.buffers.insert(
+            Key::Output(var.index().unwrap()),
+            Buffer {
+                data,
+                shape,
+                strides,
+            },
+        );
+        ExpandElement::Plain(var)
+    }
+
+    /// Read the current value of a variable.
+    pub fn read_value(&self, var: &Variable) -> HostLine {
+        match var.kind {
+            VariableKind::ConstantScalar(value) => return vec![value],
+            VariableKind::Builtin(builtin) => {
+                return vec![ConstantScalarValue::UInt(
+                    self.unit.builtin(builtin) as u64,
+                    UIntKind::U32,
+                )]
+            }
+            _ => {}
+        };
+
+        match self.values.get(&Self::key(var)) {
+            Some(Value::Line(line)) => line.clone(),
+            Some(Value::Reference { buffer, index }) => self.buffers[buffer].data[*index].clone(),
+            Some(Value::Slice { .. }) => panic!("Can't read the slice {var} as a value"),
+            None => panic!("Variable {var} is read before being assigned"),
+        }
+    }
+
+    /// Read the current content of an array variable.
+    pub fn read_buffer(&self, var: &Variable) -> Vec<HostLine> {
+        let (buffer, offset, len) = self.view(var);
+        self.buffers[&buffer].data[offset..offset + len].to_vec()
+    }
+
+    /// Read the shape and the strides of an array variable.
+    pub fn read_layout(&self, var: &Variable) -> (Vec<u32>, Vec<u32>) {
+        let (buffer, _, _) = self.view(var);
+        let buffer = &self.buffers[&buffer];
+        (buffer.shape.clone(), buffer.strides.clone())
+    }
+
+    /// Execute the scope, which must have been expanded with the variables of this execution.
+    pub fn execute(&mut self, scope: &Scope) {
+        self.execute_scope(scope);
+    }
+
+    fn key(var: &Variable) -> Key {
+        Key::of(var).unwrap_or_else(|| panic!("Variable {var} can't be assigned"))
+    }
+
+    fn execute_scope(&mut self, scope: &Scope) -> Flow {
+        for (var, data) in scope.const_arrays.iter() {
+            let data = data.iter().map(|value| self.read_value(value)).collect();
+            self.buffers.insert(
+                Self::key(var),
+                Buffer {
+                    data,
+                    shape: Vec::new(),
+                    strides: Vec::new(),
+                },
+            );
+        }
+
+        for instruction in scope.operations.iter() {
+            match self.execute_instruction(instruction) {
+                Flow::Continue => {}
+                flow => return flow,
+            }
+        }
+
+        Flow::Continue
+    }
+
+    fn execute_instruction(&mut self, instruction: &Instruction) -> Flow {
+        match &instruction.operation {
+            Operation::Copy(input) => self.copy(input, &instruction.out()),
+            Operation::Operator(operator) => self.operator(operator, &instruction.out()),
+            Operation::Atomic(op) => self.atomic(op, &instruction.out()),
+            Operation::Metadata(metadata) => self.metadata(metadata, &instruction.out()),
+            Operation::Branch(branch) => return self.branch(branch),
+            Operation::Synchronization(_) => {}
+            Operation::Subcube(op) => self.subcube(op, &instruction.out()),
+            Operation::CoopMma(_) => {
+                panic!("Cooperative matrix operations can't be executed on the host")
+            }
+        };
+
+        Flow::Continue
+    }
+
+    fn copy(&mut self, input: &Variable, out: &Variable) {
+        let value = match Key::of(input).and_then(|key| self.values.get(&key)) {
+            Some(Value::Reference { buffer, index }) if out.item.elem.is_atomic() => {
+                Some(Value::Reference {
+                    buffer: *buffer,
+                    index: *index,
+                })
+            }
+            Some(slice @ Value::Slice { .. }) => Some(slice.clone()),
+            _ if input.is_array() => {
+                let (buffer, offset, len) = self.view(input);
+                Some(Value::Slice {
+                    buffer,
+                    offset,
+                    len,
+                })
+            }
+            _ => None,
+        };
+
+        match value {
+            Some(value) => {
+                self.values.insert(Self::key(out), value);
+            }
+            None => self.write(out, self.read_value(input)),
+        }
+    }
+
+    /// Write the value to the variable, with the element and the line size of the variable.
+    fn write(&mut self, var: &Variable, value: HostLine) {
+        let key = Self::key(var);
+
+        if let Some(Value::Reference { buffer, index }) = self.values.get(&key) {
+            let (buffer, index) = (*buffer, *index);
+            let elem = var.item.elem;
+            let line = value.into_iter().map(|value| cast(value, elem)).collect();
+            self.buffers.get_mut(&buffer).unwrap().data[index] = line;
+            return;
+        }
+
+        let line = Self::normalize(value, var.item);
+        self.values.insert(key, Value::Line(line));
+    }
+
+    fn normalize(value: HostLine, item: Item) -> HostLine {
+        let line_size = item.vectorization.map(|it| it.get() as usize).unwrap_or(1);
+        let value = match value.len() {
+            1 if line_size > 1 => vec![value[0]; line_size],
+            _ => value,
+        };
+
+        value
+            .into_iter()
+            .map(|value| cast(value, item.elem))
+            .collect()
+    }
+
+    /// Whether the variable is an array or a slice of one, rather than a line.
+    fn is_view(&self, var: &Variable) -> bool {
+        var.is_array()
+            || Key::of(var)
+                .is_some_and(|key| matches!(self.values.get(&key), Some(Value::Slice { .. })))
+    }
+
+    /// The buffer, the offset and the length of an array variable.
+    fn view(&self, var: &Variable) -> (Key, usize, usize) {
+        let key = Self::key(var);
+
+        if let Some(Value::Slice {
+            buffer,
+            offset,
+            len,
+        }) = self.values.get(&key)
+        {
+            return (*buffer, *offset, *len);
+        }
+
+        match self.buffers.get(&key) {
+            Some(buffer) => (key, 0, buffer.data.len()),
+            None => panic!("Variable {var} isn't an array"),
+        }
+    }
+
+    /// Same as [view](Self::view), but allocates the local and shared arrays on first use.
+    fn view_mut(&mut self, var: &Variable) -> (Key, usize, usize) {
+        let length = match var.kind {
+            VariableKind::LocalArray { length, .. } | VariableKind::SharedMemory { length, .. } => {
+                Some(length)
+            }
+            _ => None,
+        };
+
+        if let Some(length) = length {
+            let zero = Self::normalize(vec![ConstantScalarValue::UInt(0, UIntKind::U32)], var.item);
+            self.buffers
+                .entry(Self::key(var))
+                .or_insert_with(|| Buffer {
+                    data: vec![zero; length as usize],
+                    shape: Vec::new(),
+                    strides: Vec::new(),
+                });
+        }
+
+        self.view(var)
+    }
+
+    fn index(&mut self, array: &Variable, index: &Variable) -> (Key, usize) {
+        let (buffer, offset, len) = self.view_mut(array);
+        let index = as_index(self.read_value(index)[0]);
+
+        if index >= len {
+            panic!("Index {index} is out of bounds for {array} of length {len}");
+        }
+
+        (buffer, offset + index)
+    }
+
+    fn operator(&mut self, operator: &Operator, out: &Variable) {
+        let elem = out.item.elem;
+
+        match operator {
+            Operator::Add(op) => self.binary(op, out, |lhs, rhs| {
+                arithmetic(
+                    lhs,
+                    rhs,
+                    elem,
+                    |a, b| a + b,
+                    i64::wrapping_add,
+                    u64::wrapping_add,
+                )
+            }),
+            Operator::Sub(op) => self.binary(op, out, |lhs, rhs| {
+                arithmetic(
+                    lhs,
+                    rhs,
+                    elem,
+                    |a, b| a - b,
+                    i64::wrapping_sub,
+                    u64::wrapping_sub,
+                )
+            }),
+            Operator::Mul(op) => self.binary(op, out, |lhs, rhs| {
+                arithmetic(
+                    lhs,
+                    rhs,
+                    elem,
+                    |a, b| a * b,
+                    i64::wrapping_mul,
+                    u64::wrapping_mul,
+                )
+            }),
+            Operator::Div(op) => self.binary(op, out, |lhs, rhs| {
+                arithmetic(lhs, rhs, elem, |a, b| a / b, |a, b| a / b, |a, b| a / b)
+            }),
+            Operator::Modulo(op) => self.binary(op, out, |lhs, rhs| {
+                arithmetic(lhs, rhs, elem, |a, b| a % b, |a, b| a % b, |a, b| a % b)
+            }),
+            Operator::Remainder(op) => self.binary(op, out, |lhs, rhs| {
+                arithmetic(
+                    lhs,
+                    rhs,
+                    elem,
+                    |a, b| a - b * f64::floor(a / b),
+                    |a, b| ((a % b) + b) % b,
+                    |a, b| a % b,
+                )
+            }),
+            Operator::Powf(op) => self.binary(op, out, |lhs, rhs| {
+                arithmetic(
+                    lhs,
+                    rhs,
+                    elem,
+                    f64::powf,
+                    |a, b| a.pow(b as u32),
+                    |a, b| a.pow(b as u32),
+                )
+            }),
+            Operator::Max(op) => self.binary(op, out, |lhs, rhs| {
+                arithmetic(lhs, rhs, elem, f64::max, i64::max, u64::max)
+            }),
+            Operator::Min(op) => self.binary(op, out, |lhs, rhs| {
+                arithmetic(lhs, rhs, elem, f64::min, i64::min, u64::min)
+            }),
+            Operator::BitwiseAnd(op) => {
+                self.binary(op, out, |lhs, rhs| bitwise(lhs, rhs, elem, |a, b| a & b))
+            }
+            Operator::BitwiseOr(op) => {
+                self.binary(op, out, |lhs, rhs| bitwise(lhs, rhs, elem, |a, b| a | b))
+            }
+            Operator::BitwiseXor(op) => {
+                self.binary(op, out, |lhs, rhs| bitwise(lhs, rhs, elem, |a, b| a ^ b))
+            }
+            Operator::ShiftLeft(op) => self.binary(op, out, |lhs, rhs| {
+                let shift = as_u64(rhs) as u32;
+                arithmetic(
+                    lhs,
+                    rhs,
+                    elem,
+                    |_, _| panic!("Can't shift a float"),
+                    |a, _| a.wrapping_shl(shift),
+                    |a, _| a.wrapping_shl(shift),
+                )
+            }),
+            Operator::ShiftRight(op) => self.binary(op, out, |lhs, rhs| {
+                let shift = as_u64(rhs) as u32;
+                arithmetic(
+                    lhs,
+                    rhs,
+                    elem,
+                    |_, _| panic!("Can't shift a float"),
+                    |a, _| a.wrapping_shr(shift),
+                    |a, _| a.wrapping_shr(shift),
+                )
+            }),
+            Operator::Equal(op) => self.comparison(op, out, |ord| ord == Some(Ordering::Equal)),
+            Operator::NotEqual(op) => self.comparison(op, out, |ord| ord != Some(Ordering::Equal)),
+            Operator::Lower(op) => self.comparison(op, out, |ord| ord == Some(Ordering::Less)),
+            Operator::LowerEqual(op) => self.comparison(op, out, |ord| {
+                matches!(ord, Some(Ordering::Less | Ordering::Equal))
+            }),
+            Operator::Greater(op) => self.comparison(op, out, |ord| ord == Some(Ordering::Greater)),
+            Operator::GreaterEqual(op) => self.comparison(op, out, |ord| {
+                matches!(ord, Some(Ordering::Greater | Ordering::Equal))
+            }),
+            Operator::And(op) => self.binary(op, out, |lhs, rhs| {
+                ConstantScalarValue::Bool(as_bool(lhs) && as_bool(rhs))
+            }),
+            Operator::Or(op) => self.binary(op, out, |lhs, rhs| {
+                ConstantScalarValue::Bool(as_bool(lhs) || as_bool(rhs))
+            }),
+            Operator::Not(op) => self.unary(op, out, |value| match value {
+                ConstantScalarValue::Int(val, kind) => ConstantScalarValue::Int(!val, kind),
+                ConstantScalarValue::UInt(val, kind) => ConstantScalarValue::UInt(!val, kind),
+                value => ConstantScalarValue::Bool(!as_bool(value)),
+            }),
+            Operator::Neg(op) => self.unary(op, out, |value| match value {
+                ConstantScalarValue::Int(val, kind) => {
+                    ConstantScalarValue::Int(val.wrapping_neg(), kind)
+                }
+                ConstantScalarValue::UInt(val, kind) => {
+                    ConstantScalarValue::UInt(val.wrapping_neg(), kind)
+                }
+                value => float_fn(value, elem, |a| -a),
+            }),
+            Operator::Abs(op) => self.unary(op, out, |value| match value {
+                ConstantScalarValue::Int(val, kind) => {
+                    ConstantScalarValue::Int(val.wrapping_abs(), kind)
+                }
+                ConstantScalarValue::UInt(..) => value,
+                value => float_fn(value, elem, f64::abs),
+            }),
+            Operator::Exp(op) => self.unary(op, out, |value| float_fn(value, elem, f64::exp)),
+            Operator::Log(op) => self.unary(op, out, |value| float_fn(value, elem, f64::ln)),
+            Operator::Log1p(op) => self.unary(op, out, |value| float_fn(value, elem, f64::ln_1p)),
+            Operator::Cos(op) => self.unary(op, out, |value| float_fn(value, elem, f64::cos)),
+            Operator::Sin(op) => self.unary(op, out, |value| float_fn(value, elem, f64::sin)),
+            Operator::Tanh(op) => self.unary(op, out, |value| float_fn(value, elem, f64::tanh)),
+            Operator::Sqrt(op) => self.unary(op, out, |value| float_fn(value, elem, f64::sqrt)),
+            Operator::Round(op) => self.unary(op, out, |value| float_fn(value, elem, f64::round)),
+            Operator::Floor(op) => self.unary(op, out, |value| float_fn(value, elem, f64::floor)),
+            Operator::Ceil(op) => self.unary(op, out, |value| float_fn(value, elem, f64::ceil)),
+            Operator::Erf(op) => self.unary(op, out, |value| float_fn(value, elem, erf)),
+            Operator::Recip(op) => self.unary(op, out, |value| float_fn(value, elem, |a| 1.0 / a)),
+            Operator::Cast(op) => self.unary(op, out, |value| cast(value, elem)),
+            Operator::Bitcast(op) => self.unary(op, out, |value| from_bits(to_bits(value), elem)),
+            Operator::Clamp(op) => {
+                let input = self.read_value(&op.input);
+                let min = self.read_value(&op.min_value);
+                let max = self.read_value(&op.max_value);
+                let value = lanes(&[&input, &min, &max], |values| {
+                    let value =
+                        arithmetic(values[0], values[1], elem, f64::max, i64::max, u64::max);
+                    arithmetic(value, values[2], elem, f64::min, i64::min, u64::min)
+                });
+                self.write(out, value);
+            }
+            Operator::Fma(op) => {
+                let a = self.read_value(&op.a);
+                let b = self.read_value(&op.b);
+                let c = self.read_value(&op.c);
+                let value = lanes(&[&a, &b, &c], |values| {
+                    float_fn(values[0], elem, |a| {
+                        a.mul_add(as_f64(values[1]), as_f64(values[2]))
+                    })
+                });
+                self.write(out, value);
+            }
+            Operator::Select(op) => {
+                let cond = self.read_value(&op.cond);
+                let then = self.read_value(&op.then);
+                let or_else = self.read_value(&op.or_else);
+                let value = lanes(&[&cond, &then, &or_else], |values| {
+                    match as_bool(values[0]) {
+                        true => values[1],
+                        false => values[2],
+                    }
+                });
+                self.write(out, value);
+            }
+            Operator::Dot(op) => {
+                let lhs = self.read_value(&op.lhs);
+                let rhs = self.read_value(&op.rhs);
+                let products = lanes(&[&lhs, &rhs], |values| {
+                    arithmetic(
+                        values[0],
+                        values[1],
+                        elem,
+                        |a, b| a * b,
+                        i64::wrapping_mul,
+                        u64::wrapping_mul,
+                    )
+                });
+                let sum = products.into_iter().reduce(|acc, value| {
+                    arithmetic(
+                        acc,
+                        value,
+                        elem,
+                        |a, b| a + b,
+                        i64::wrapping_add,
+                        u64::wrapping_add,
+                    )
+                });
+                self.write(out, sum.into_iter().collect());
+            }
+            Operator::Magnitude(op) => {
+                let input = self.read_value(&op.input);
+                let magnitude = f64::sqrt(input.iter().map(|value| as_f64(*value).powi(2)).sum());
+                self.write(out, vec![float_fn(input[0], elem, |_| magnitude)]);
+            }
+            Operator::Normalize(op) => {
+                let input = self.read_value(&op.input);
+                let magnitude = f64::sqrt(input.iter().map(|value| as_f64(*value).powi(2)).sum());
+                let value = input
+                    .into_iter()
+                    .map(|value| float_fn(value, elem, |a| a / magnitude))
+                    .collect();
+                self.write(out, value);
+            }
+            Operator::InitLine(op) => {
+                let value = op
+                    .inputs
+                    .iter()
+                    .flat_map(|input| self.read_value(input))
+                    .collect();
+                self.write(out, value);
+            }
+            Operator::Shuffle(op) => {
+                let mut lanes = self.read_value(&op.lhs);
+                lanes.extend(self.read_value(&op.rhs));
+                let value = op
+                    .indices
+                    .iter()
+                    .map(|index| lanes[*index as usize])
+                    .collect();
+                self.write(out, value);
+            }
+            Operator::Index(op) | Operator::UncheckedIndex(op) => self.read_index(op, out),
+            Operator::IndexAssign(op) | Operator::UncheckedIndexAssign(op) => {
+                self.write_index(op, out)
+            }
+            Operator::Slice(op) => {
+                let (buffer, offset, len) = self.view_mut(&op.input);
+                let start = as_index(self.read_value(&op.start)[0]);
+                let end = as_index(self.read_value(&op.end)[0]);
+
+                if start > len {
+                    panic!(
+                        "Slice start {start} is out of bounds for {} of length {len}",
+                        op.input
+                    );
+                }
+
+                self.values.insert(
+                    Self::key(out),
+                    Value::Slice {
+                        buffer,
+                        offset: offset + start,
+                        len: end.saturating_sub(start),
+                    },
+                );
+            }
+            Operator::CopyMemory(op) => {
+                let (buffer_in, index_in) = self.index(&op.input, &op.in_index);
+                let (buffer_out, index_out) = self.index(out, &op.out_index);
+                let value = self.buffers[&buffer_in].data[index_in].clone();
+                self.buffers.get_mut(&buffer_out).unwrap().data[index_out] = value;
+            }
+            Operator::CopyMemoryBulk(op) => {
+                let (buffer_in, index_in) = self.index(&op.input, &op.in_index);
+                let (buffer_out, index_out) = self.index(out, &op.out_index);
+                for i in 0..op.len as usize {
+                    let value = self.buffers[&buffer_in].data[index_in + i].clone();
+                    self.buffers.get_mut(&buffer_out).unwrap().data[index_out + i] = value;
+                }
+            }
+        }
+    }
+
+    fn read_index(&mut self, op: &BinaryOperator, out: &Variable) {
+        if !self.is_view(&op.lhs) {
+            let line = self.read_value(&op.lhs);
+            let index = as_index(self.read_value(&op.rhs)[0]);
+            self.write(out, vec![line[index]]);
+            return;
+        }
+
+        let (buffer, index) = self.index(&op.lhs, &op.rhs);
+
+        if out.item.elem.is_atomic() {
+            self.values
+                .insert(Self::key(out), Value::Reference { buffer, index });
+        } else {
+            let value = self.buffers[&buffer].data[index].clone();
+            self.write(out, value);
+        }
+    }
+
+    fn write_index(&mut self, op: &BinaryOperator, out: &Variable) {
+        let value = self.read_value(&op.rhs);
+
+        if !self.is_view(out) {
+            let index = as_index(self.read_value(&op.lhs)[0]);
+            let mut line = match self.values.get(&Self::key(out)) {
+                Some(Value::Line(line)) => line.clone(),
+                _ => Self::normalize(vec![ConstantScalarValue::UInt(0, UIntKind::U32)], out.item),
+            };
+            line[index] = value[0];
+            self.write(out, line);
+            return;
+        }
+
+        let (buffer, index) = self.index(out, &op.lhs);
+        let buffer = self.buffers.get_mut(&buffer).unwrap();
+        buffer.data[index] = Self::normalize(value, out.item);
+    }
+
+    fn binary(
+        &mut self,
+        op: &BinaryOperator,
+        out: &Variable,
+        func: impl Fn(ConstantScalarValue, ConstantScalarValue) -> ConstantScalarValue,
+    ) {
+        let lhs = self.read_value(&op.lhs);
+        let rhs = self.read_value(&op.rhs);
+        let value = lanes(&[&lhs, &rhs], |values| func(values[0], values[1]));
+        self.write(out, value);
+    }
+
+    fn comparison(
+        &mut self,
+        op: &BinaryOperator,
+        out: &Variable,
+        func: impl Fn(Option<Ordering>) -> bool,
+    ) {
+        self.binary(op, out, |lhs, rhs| {
+            ConstantScalarValue::Bool(func(compare(lhs, rhs)))
+        });
+    }
+
+    fn unary(
+        &mut self,
+        op: &UnaryOperator,
+        out: &Variable,
+        func: impl Fn(ConstantScalarValue) -> ConstantScalarValue,
+    ) {
+        let value = self.read_value(&op.input).into_iter().map(func).collect();
+        self.write(out, value);
+    }
+
+    fn atomic(&mut self, op: &AtomicOp, out: &Variable) {
+        let elem = out.item.elem;
+        let read_modify_write = |this: &mut Self,
+                                 op: &BinaryOperator,
+                                 func: &dyn Fn(
+            ConstantScalarValue,
+            ConstantScalarValue,
+        ) -> ConstantScalarValue| {
+            let old = this.read_value(&op.lhs);
+            let rhs = this.read_value(&op.rhs);
+            let new = lanes(&[&old, &rhs], |values| func(values[0], values[1]));
+            this.write(&op.lhs, new);
+            this.write(out, old);
+        };
+
+        match op {
+            AtomicOp::Load(op) | AtomicOp::Store(op) => {
+                let value = self.read_value(&op.input);
+                self.write(out, value);
+            }
+            AtomicOp::Swap(op) => read_modify_write(self, op, &|_, new| new),
+            AtomicOp::Add(op) => read_modify_write(self, op, &|lhs, rhs| {
+                arithmetic(
+                    lhs,
+                    rhs,
+                    elem,
+                    |a, b| a + b,
+                    i64::wrapping_add,
+                    u64::wrapping_add,
+                )
+            }),
+            AtomicOp::Sub(op) => read_modify_write(self, op, &|lhs, rhs| {
+                arithmetic(
+                    lhs,
+                    rhs,
+                    elem,
+                    |a, b| a - b,
+                    i64::wrapping_sub,
+                    u64::wrapping_sub,
+                )
+            }),
+            AtomicOp::Max(op) => read_modify_write(self, op, &|lhs, rhs| {
+                arithmetic(lhs, rhs, elem, f64::max, i64::max, u64::max)
+            }),
+            AtomicOp::Min(op) => read_modify_write(self, op, &|lhs, rhs| {
+                arithmetic(lhs, rhs, elem, f64::min, i64::min, u64::min)
+            }),
+            AtomicOp::And(op) => {
+                read_modify_write(self, op, &|lhs, rhs| bitwise(lhs, rhs, elem, |a, b| a & b))
+            }
+            AtomicOp::Or(op) => {
+                read_modify_write(self, op, &|lhs, rhs| bitwise(lhs, rhs, elem, |a, b| a | b))
+            }
+            AtomicOp::Xor(op) => {
+                read_modify_write(self, op, &|lhs, rhs| bitwise(lhs, rhs, elem, |a, b| a ^ b))
+            }
+            AtomicOp::CompareAndSwap(op) => {
+                let old = self.read_value(&op.input);
+                let cmp = self.read_value(&op.cmp);
+
+                if compare(old[0], cmp[0]) == Some(Ordering::Equal) {
+                    let value = self.read_value(&op.val);
+                    self.write(&op.input, value);
+                }
+                self.write(out, old);
+            }
+        }
+    }
+
+    fn metadata(&mut self, metadata: &Metadata, out: &Variable) {
+        let value = match metadata {
+            Metadata::Rank { var } => {
+                let (buffer, _, _) = self.view_mut(var);
+                self.buffers[&buffer].shape.len()
+            }
+            Metadata::Stride { dim, var } => {
+                let (buffer, _, _) = self.view_mut(var);
+                let dim = as_index(self.read_value(dim)[0]);
+                self.buffers[&buffer].strides[dim] as usize
+            }
+            Metadata::Shape { dim, var } => {
+                let (buffer, _, _) = self.view_mut(var);
+                let dim = as_index(self.read_value(dim)[0]);
+                self.buffers[&buffer].shape[dim] as usize
+            }
+            Metadata::Length { var } => match self.is_view(var) {
+                true => self.view_mut(var).2,
+                false => var.vectorization_factor() as usize,
+            },
+            Metadata::BufferLength { var } => {
+                let (buffer, _, _) = self.view_mut(var);
+                self.buffers[&buffer].data.len()
+            }
+        };
+
+        self.write(
+            out,
+            vec![ConstantScalarValue::UInt(value as u64, UIntKind::U32)],
+        );
+    }
+
+    fn subcube(&mut self, op: &Subcube, out: &Variable) {
+        // The plane only contains the executed unit, so it is elected and every reduction is
+        // the identity.
+        let value = match op {
+            Subcube::Elect => vec![ConstantScalarValue::Bool(true)],
+            Subcube::All(op)
+            | Subcube::Any(op)
+            | Subcube::Sum(op)
+            | Subcube::Prod(op)
+            | Subcube::Min(op)
+            | Subcube::Max(op) => self.read_value(&op.input),
+            // Only correct because the subcube is the single executed unit, so any index
+            // designates it.
+            Subcube::Broadcast(op) | Subcube::Shuffle(op) => self.read_value(&op.lhs),
+        };
+        self.write(out, value);
+    }
+
+    fn branch(&mut self, branch: &Branch) -> Flow {
+        match branch {
+            Branch::If(op) => {
+                if as_bool(self.read_value(&op.cond)[0]) {
+                    return self.execute_scope(&op.scope);
+                }
+                Flow::Continue
+            }
+            Branch::IfElse(op) => match as_bool(self.read_value(&op.cond)[0]) {
+                true => self.execute_scope(&op.scope_if),
+                false => self.execute_scope(&op.scope_else),
+            },
+            Branch::Switch(op) => {
+                let value = self.read_value(&op.value)[0];
+                let case = op.cases.iter().find(|(case, _)| {
+                    compare(value, self.read_value(case)[0]) == Some(Ordering::Equal)
+                });
+
+                match case {
+                    Some((_, scope)) => self.execute_scope(scope),
+                    None => self.execute_scope(&op.scope_default),
+                }
+            }
+            Branch::RangeLoop(op) => {
+                let start = as_i64(self.read_value(&op.start)[0]);
+                let end = as_i64(self.read_value(&op.end)[0]);
+                let step = match &op.step {
+                    Some(step) => as_i64(self.read_value(step)[0]),
+                    None => 1,
+                };
+
+                let mut i = start;
+                while (op.inclusive && i <= end) || (!op.inclusive && i < end) {
+                    self.write(&op.i, vec![ConstantScalarValue::Int(i, IntKind::I64)]);
+
+                    match self.execute_scope(&op.scope) {
+                        Flow::Continue => {}
+                        Flow::Break => break,
+                        Flow::Return => return Flow::Return,
+                    }
+                    i += step;
+                }
+                Flow::Continue
+            }
+            Branch::Loop(op) => loop {
+                match self.execute_scope(&op.scope) {
+                    Flow::Continue => {}
+                    Flow::Break => return Flow::Continue,
+                    Flow::Return => return Flow::Return,
+                }
+            },
+            Branch::Return => Flow::Return,
+            Branch::Break => Flow::Break,
+        }
+    }
+}
+
+/// Apply the function to every lane, broadcasting the values of size 1.
+fn lanes(
+    values: &[&HostLine],
+    func: impl Fn(&[ConstantScalarValue]) -> ConstantScalarValue,
+) -> HostLine {
+    let line_size = values.iter().map(|value| value.len()).max().unwrap_or(1);
+    let mut lane = Vec::with_capacity(values.len());
+
+    (0..line_size)
+        .map(|i| {
+            lane.clear();
+            lane.extend(
+                values
+                    .iter()
+                    .map(|value| if value.len() == 1 { value[0] } else { value[i] }),
+            );
+            func(&lane)
+        })
+        .collect()
+}
+
+fn bitwise(
+    lhs: ConstantScalarValue,
+    rhs: ConstantScalarValue,
+    elem: Elem,
+    func: impl Fn(u64, u64) -> u64,
+) -> ConstantScalarValue {
+    match elem {
+        Elem::Bool => ConstantScalarValue::Bool(func(as_u64(lhs), as_u64(rhs)) != 0),
+        elem => from_bits(
+            func(to_bits(cast(lhs, elem)), to_bits(cast(rhs, elem))),
+            elem,
+        ),
+    }
+}
@@ -0,0 +1,28 @@
+//! Execution of cube functions on the host, for a single unit.
+//!
+//! With the `host` feature, every `#[cube]` function also generates a `host` function, which
+//! expands the function and executes the expanded IR on the CPU for the given [unit](HostUnit).
+//! Arrays and slices are backed by a [Vec], tensors by a [HostTensor] and lines by a [Vec] of
+//! their elements. Arguments passed by mutable reference are updated after the execution.
+//!
+//! ```ignore
+//! #[cube]
+//! fn offset(values: &Array<u32>) -> u32 {
+//!     values[UNIT_POS] + CUBE_POS
+//! }
+//!
+//! let unit = HostUnit::default().with_unit_pos(1, 0, 0).with_cube_pos(2, 0, 0);
+//! assert_eq!(offset::host(unit, &vec![5, 6]), 8);
+//! ```
+//!
+//! Trait functions are executed with the `__host_<name>` provided function generated by
+//! `#[cube]` on the trait.
+
+mod arg;
+mod base;
+mod execution;
+mod value;
+
+pub use arg::*;
+pub use base::*;
+pub use execution::*;
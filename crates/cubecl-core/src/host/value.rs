@@ -0,0 +1,212 @@
+use std::cmp::Ordering;
+
+use half::{bf16, f16};
+
+use crate::ir::{ConstantScalarValue, Elem, FloatKind, IntKind, UIntKind};
+
+/// Returns the value of the scalar as a float.
+pub(crate) fn as_f64(value: ConstantScalarValue) -> f64 {
+    match value {
+        ConstantScalarValue::Int(val, _) => val as f64,
+        ConstantScalarValue::Float(val, _) => val,
+        ConstantScalarValue::UInt(val, _) => val as f64,
+        ConstantScalarValue::Bool(val) => val as u32 as f64,
+    }
+}
+
+/// Returns the value of the scalar as a signed integer.
+pub(crate) fn as_i64(value: ConstantScalarValue) -> i64 {
+    match value {
+        ConstantScalarValue::Int(val, _) => val,
+        ConstantScalarValue::Float(val, _) => val as i64,
+        ConstantScalarValue::UInt(val, _) => val as i64,
+        ConstantScalarValue::Bool(val) => val as i64,
+    }
+}
+
+/// Returns the value of the scalar as an unsigned integer.
+pub(crate) fn as_u64(value: ConstantScalarValue) -> u64 {
+    match value {
+        ConstantScalarValue::Int(val, _) => val as u64,
+        ConstantScalarValue::Float(val, _) => val as u64,
+        ConstantScalarValue::UInt(val, _) => val,
+        ConstantScalarValue::Bool(val) => val as u64,
+    }
+}
+
+/// Returns the value of the scalar as a boolean, where any non-zero value is true.
+pub(crate) fn as_bool(value: ConstantScalarValue) -> bool {
+    match value {
+        ConstantScalarValue::Bool(val) => val,
+        value => !value.is_zero(),
+    }
+}
+
+/// Returns the value of the scalar as an index.
+pub(crate) fn as_index(value: ConstantScalarValue) -> usize {
+    as_u64(value) as usize
+}
+
+/// Cast the scalar to the given element, with the precision and the overflow behavior of that
+/// element.
+pub(crate) fn cast(value: ConstantScalarValue, elem: Elem) -> ConstantScalarValue {
+    match elem {
+        Elem::Float(kind) => ConstantScalarValue::Float(round_float(as_f64(value), kind), kind),
+        Elem::Int(kind) | Elem::AtomicInt(kind) => {
+            ConstantScalarValue::Int(wrap_int(as_i64(value), kind), kind)
+        }
+        Elem::UInt(kind) | Elem::AtomicUInt(kind) => {
+            ConstantScalarValue::UInt(wrap_uint(as_u64(value), kind), kind)
+        }
+        Elem::Bool => ConstantScalarValue::Bool(as_bool(value)),
+    }
+}
+
+fn round_float(value: f64, kind: FloatKind) -> f64 {
+    match kind {
+        FloatKind::F16 => f16::from_f64(value).to_f64(),
+        FloatKind::BF16 => bf16::from_f64(value).to_f64(),
+        FloatKind::Flex32 | FloatKind::F32 | FloatKind::TF32 => value as f32 as f64,
+        FloatKind::F64 => value,
+    }
+}
+
+fn wrap_int(value: i64, kind: IntKind) -> i64 {
+    match kind {
+        IntKind::I8 => value as i8 as i64,
+        IntKind::I16 => value as i16 as i64,
+        IntKind::I32 => value as i32 as i64,
+        IntKind::I64 => value,
+    }
+}
+
+fn wrap_uint(value: u64, kind: UIntKind) -> u64 {
+    match kind {
+        UIntKind::U8 => value as u8 as u64,
+        UIntKind::U16 => value as u16 as u64,
+        UIntKind::U32 => value as u32 as u64,
+        UIntKind::U64 => value,
+    }
+}
+
+/// Apply an arithmetic operation in the domain of the given element.
+pub(crate) fn arithmetic(
+    lhs: ConstantScalarValue,
+    rhs: ConstantScalarValue,
+    elem: Elem,
+    float: impl Fn(f64, f64) -> f64,
+    int: impl Fn(i64, i64) -> i64,
+    uint: impl Fn(u64, u64) -> u64,
+) -> ConstantScalarValue {
+    let (lhs, rhs) = (cast(lhs, elem), cast(rhs, elem));
+
+    let value = match elem {
+        Elem::Float(kind) => ConstantScalarValue::Float(float(as_f64(lhs), as_f64(rhs)), kind),
+        Elem::Int(kind) | Elem::AtomicInt(kind) => {
+            ConstantScalarValue::Int(int(as_i64(lhs), as_i64(rhs)), kind)
+        }
+        Elem::UInt(kind) | Elem::AtomicUInt(kind) => {
+            ConstantScalarValue::UInt(uint(as_u64(lhs), as_u64(rhs)), kind)
+        }
+        Elem::Bool => ConstantScalarValue::Bool(uint(as_u64(lhs), as_u64(rhs)) != 0),
+    };
+
+    cast(value, elem)
+}
+
+/// Apply a float function, casting the result to the given element.
+pub(crate) fn float_fn(
+    value: ConstantScalarValue,
+    elem: Elem,
+    func: impl Fn(f64) -> f64,
+) -> ConstantScalarValue {
+    cast(
+        ConstantScalarValue::Float(func(as_f64(value)), FloatKind::F64),
+        elem,
+    )
+}
+
+/// Compare two scalars in the most precise domain of both values.
+pub(crate) fn compare(lhs: ConstantScalarValue, rhs: ConstantScalarValue) -> Option<Ordering> {
+    match (lhs, rhs) {
+        (ConstantScalarValue::Float(..), _) | (_, ConstantScalarValue::Float(..)) => {
+            as_f64(lhs).partial_cmp(&as_f64(rhs))
+        }
+        (ConstantScalarValue::Int(..), _) | (_, ConstantScalarValue::Int(..)) => {
+            Some(as_i64(lhs).cmp(&as_i64(rhs)))
+        }
+        _ => Some(as_u64(lhs).cmp(&as_u64(rhs))),
+    }
+}
+
+/// The bits of the scalar, using the size of its own element.
+pub(crate) fn to_bits(value: ConstantScalarValue) -> u64 {
+    match value {
+        ConstantScalarValue::Float(val, kind) => match kind {
+            FloatKind::F16 => f16::from_f64(val).to_bits() as u64,
+            FloatKind::BF16 => bf16::from_f64(val).to_bits() as u64,
+            FloatKind::Flex32 | FloatKind::F32 | FloatKind::TF32 => (val as f32).to_bits() as u64,
+            FloatKind::F64 => val.to_bits(),
+        },
+        ConstantScalarValue::Int(val, kind) => match kind {
+            IntKind::I8 => val as u8 as u64,
+            IntKind::I16 => val as u16 as u64,
+            IntKind::I32 => val as u32 as u64,
+            IntKind::I64 => val as u64,
+        },
+        ConstantScalarValue::UInt(val, _) => val,
+        ConstantScalarValue::Bool(val) => val as u64,
+    }
+}
+
+/// Reinterpret the bits as a scalar of the given element.
+pub(crate) fn from_bits(bits: u64, elem: Elem) -> ConstantScalarValue {
+    match elem {
+        Elem::Float(kind) => {
+            let value = match kind {
+                FloatKind::F16 => f16::from_bits(bits as u16).to_f64(),
+                FloatKind::BF16 => bf16::from_bits(bits as u16).to_f64(),
+                FloatKind::Flex32 | FloatKind::F32 | FloatKind::TF32 => {
+                    f32::from_bits(bits as u32) as f64
+                }
+                FloatKind::F64 => f64::from_bits(bits),
+            };
+            ConstantScalarValue::Float(value, kind)
+        }
+        Elem::Int(kind) | Elem::AtomicInt(kind) => {
+            let value = match kind {
+                IntKind::I8 => bits as i8 as i64,
+                IntKind::I16 => bits as i16 as i64,
+                IntKind::I32 => bits as i32 as i64,
+                IntKind::I64 => bits as i64,
+            };
+            ConstantScalarValue::Int(value, kind)
+        }
+        Elem::UInt(kind) | Elem::AtomicUInt(kind) => {
+            ConstantScalarValue::UInt(wrap_uint(bits, kind), kind)
+        }
+        Elem::Bool => ConstantScalarValue::Bool(bits != 0),
+    }
+}
+
+/// An approximation of the error function, the same one used by the wgsl compiler.
+///
+/// See <https://en.wikipedia.org/wiki/Error_function#Numerical_approximations>.
+pub(crate) fn erf(x: f64) -> f64 {
+    let p = 0.3275911;
+    let a1 = 0.254829592;
+    let a2 = -0.284496736;
+    let a3 = 1.421413741;
+    let a4 = -1.453152027;
+    let a5 = 1.061405429;
+
+    let t = 1.0 / (1.0 + p * x.abs());
+    let tmp = ((((a5 * t + a4) * t) + a3) * t + a2) * t + a1;
+    let value = 1.0 - (tmp * t * f64::exp(-x * x));
+
+    if x < 0.0 {
+        -value
+    } else {
+        value
+    }
+}
@@ -163,6 +163,7 @@ pub enum Operator {
     Magnitude(UnaryOperator),
     Normalize(UnaryOperator),
     Dot(BinaryOperator),
+    Shuffle(ShuffleOperator),
     // A select statement/ternary
     Select(Select),
 }
@@ -231,6 +232,9 @@ impl Display for Operator {
             Operator::Magnitude(op) => write!(f, "{}.length()", op.input),
             Operator::Normalize(op) => write!(f, "{}.normalize()", op.input),
             Operator::Dot(op) => write!(f, "{}.dot({})", op.lhs, op.rhs),
+            Operator::Shuffle(op) => {
+                write!(f, "shuffle({}, {}, {:?})", op.lhs, op.rhs, op.indices)
+            }
             Operator::InitLine(init) => {
                 let inits = init
                     .inputs
@@ -312,6 +316,18 @@ pub struct CopyMemoryBulkOperator {
     pub len: u32,
 }
 
+/// Select the lanes of two lines, where the lanes of `rhs` are indexed after the lanes of `lhs`.
+///
+/// The output has one lane per index, so a single line can be swizzled by using it as both `lhs`
+/// and `rhs`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[allow(missing_docs)]
+pub struct ShuffleOperator {
+    pub lhs: Variable,
+    pub rhs: Variable,
+    pub indices: Vec<u32>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[allow(missing_docs)]
 pub struct ClampOperator {
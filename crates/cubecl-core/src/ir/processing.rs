@@ -209,6 +209,7 @@ impl ScopeProcessing {
                         sanitize_constant_scalar_ref_var(&mut op.lhs, &inst.out.unwrap());
                         sanitize_constant_scalar_ref_var(&mut op.rhs, &inst.out.unwrap());
                     }
+                    Operator::Shuffle(_) => {}
                     Operator::InitLine(_) => {
                         // TODO: Sanitize based on elem
                     }
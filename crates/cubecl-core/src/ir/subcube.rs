@@ -13,6 +13,7 @@ pub enum Subcube {
     All(UnaryOperator),
     Any(UnaryOperator),
     Broadcast(BinaryOperator),
+    Shuffle(BinaryOperator),
     Sum(UnaryOperator),
     Prod(UnaryOperator),
     Min(UnaryOperator),
@@ -28,6 +29,9 @@ impl Display for Subcube {
             Subcube::Broadcast(op) => {
                 writeln!(f, "subcube_broadcast({}, {})", op.lhs, op.rhs)
             }
+            Subcube::Shuffle(op) => {
+                writeln!(f, "subcube_shuffle({}, {})", op.lhs, op.rhs)
+            }
             Subcube::Sum(op) => writeln!(f, "subcube_sum({})", op.input),
             Subcube::Prod(op) => writeln!(f, "subcube_product({})", op.input),
             Subcube::Min(op) => writeln!(f, "subcube_min({})", op.input),
@@ -19,6 +19,10 @@ pub mod codegen;
 pub mod compute;
 pub mod prelude;
 
+/// Execution of cube functions on the host.
+#[cfg(feature = "host")]
+pub mod host;
+
 mod pod;
 mod runtime;
 
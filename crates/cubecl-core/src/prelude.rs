@@ -1,7 +1,9 @@
 pub use crate::{cube, CubeLaunch, CubeType, Kernel, RuntimeArg};
 
 pub use crate::codegen::{KernelExpansion, KernelIntegrator, KernelSettings};
-pub use crate::compute::{CompiledKernel, CubeTask, KernelBuilder, KernelLauncher, KernelTask};
+pub use crate::compute::{
+    CompiledKernel, CubeTask, KernelBuilder, KernelDefinitionCache, KernelLauncher, KernelTask,
+};
 pub use crate::frontend::cmma;
 pub use crate::frontend::{branch::*, synchronization::*, vectorization_of};
 pub use crate::ir::{CubeDim, KernelDefinition};
@@ -27,4 +29,9 @@ pub use cubecl_runtime::client::ComputeClient;
 pub use cubecl_runtime::server::CubeCount;
 
 pub use crate::comptime;
+pub use crate::comptime_dispatch;
+
 pub use crate::frontend::*;
+/// Host execution
+#[cfg(feature = "host")]
+pub use crate::host::{HostArg, HostExecution, HostTensor, HostUnit};
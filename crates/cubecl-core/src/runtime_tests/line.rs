@@ -0,0 +1,128 @@
+use crate::{self as cubecl, as_bytes, as_type};
+
+use cubecl::prelude::*;
+use cubecl_runtime::server::Handle;
+
+#[cube(launch)]
+pub fn kernel_line_shuffle<F: Float>(input: &Array<Line<F>>, output: &mut Array<Line<F>>) {
+    if UNIT_POS == 0 {
+        output[0] = input[0].shuffle([3, 2, 1, 0]);
+        output[1] = input[1].swizzle([1, 1, 0, 2]);
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_line_lanes<F: Float>(input: &Array<Line<F>>, output: &mut Array<Line<F>>) {
+    if UNIT_POS == 0 {
+        let mut line = input[0];
+        let lane = input[1].extract(UNIT_POS + 3);
+        line.insert(UNIT_POS + 1, lane);
+        output[0] = line;
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_line_concat<F: Float>(input: &Array<Line<F>>, output: &mut Array<Line<F>>) {
+    if UNIT_POS == 0 {
+        let (low, high) = input[0].split(2);
+        output[0] = Line::concat(high, low);
+    }
+}
+
+fn line_input<R: Runtime, F: Float + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+) -> (Handle, Handle) {
+    let input = client.create(as_bytes![F: 0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0]);
+    let output = client.empty(8 * core::mem::size_of::<F>());
+
+    (input, output)
+}
+
+fn line_output<R: Runtime, F: Float + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    output: Handle,
+) -> Vec<F> {
+    let actual = client.read(output.binding());
+    F::from_bytes(&actual).to_vec()
+}
+
+pub fn test_line_shuffle<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let (input, output) = line_input::<R, F>(&client);
+
+    kernel_line_shuffle::launch::<F, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts::<F>(&input, 8, 4) },
+        unsafe { ArrayArg::from_raw_parts::<F>(&output, 8, 4) },
+    );
+
+    let actual = line_output::<R, F>(&client, output);
+    let expected = as_type![F: 3.0, 2.0, 1.0, 0.0, 5.0, 5.0, 4.0, 6.0];
+
+    assert_eq!(actual, expected);
+}
+
+pub fn test_line_lanes<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let (input, output) = line_input::<R, F>(&client);
+
+    kernel_line_lanes::launch::<F, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts::<F>(&input, 8, 4) },
+        unsafe { ArrayArg::from_raw_parts::<F>(&output, 8, 4) },
+    );
+
+    let actual = line_output::<R, F>(&client, output);
+
+    assert_eq!(&actual[0..4], as_type![F: 0.0, 7.0, 2.0, 3.0]);
+}
+
+pub fn test_line_concat<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let (input, output) = line_input::<R, F>(&client);
+
+    kernel_line_concat::launch::<F, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts::<F>(&input, 8, 4) },
+        unsafe { ArrayArg::from_raw_parts::<F>(&output, 8, 4) },
+    );
+
+    let actual = line_output::<R, F>(&client, output);
+
+    assert_eq!(&actual[0..4], as_type![F: 2.0, 3.0, 0.0, 1.0]);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_line {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_line_shuffle() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::line::test_line_shuffle::<TestRuntime, FloatType>(client);
+        }
+
+        #[test]
+        fn test_line_lanes() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::line::test_line_lanes::<TestRuntime, FloatType>(client);
+        }
+
+        #[test]
+        fn test_line_concat() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::line::test_line_concat::<TestRuntime, FloatType>(client);
+        }
+    };
+}
@@ -6,7 +6,9 @@ pub mod const_match;
 pub mod constants;
 pub mod different_rank;
 pub mod launch;
+pub mod line;
 pub mod metadata;
+pub mod random;
 pub mod sequence;
 pub mod slice;
 pub mod subcube;
@@ -28,6 +30,7 @@ macro_rules! testgen_all {
         cubecl_core::testgen_const_match!();
         cubecl_core::testgen_different_rank!();
         cubecl_core::testgen_launch!();
+        cubecl_core::testgen_line!();
 
         $crate::testgen_untyped!();
     };
@@ -71,6 +74,7 @@ macro_rules! testgen_float {
         cubecl_core::testgen_const_match!();
         cubecl_core::testgen_different_rank!();
         cubecl_core::testgen_launch!();
+        cubecl_core::testgen_line!();
         cubecl_core::testgen_sequence!();
         cubecl_core::testgen_slice!();
         cubecl_core::testgen_subcube!();
@@ -98,6 +102,7 @@ macro_rules! testgen_untyped {
     () => {
         cubecl_core::testgen_cmma!();
         cubecl_core::testgen_metadata!();
+        cubecl_core::testgen_random!();
         cubecl_core::testgen_topology!();
 
         cubecl_core::testgen_constants!();
@@ -0,0 +1,70 @@
+use crate as cubecl;
+
+use cubecl::prelude::*;
+
+#[cube(launch)]
+pub fn kernel_philox(input: &Array<u32>, output: &mut Array<u32>) {
+    let counter = Philox4x32 {
+        x0: input[UNIT_POS * 6],
+        x1: input[UNIT_POS * 6 + 1],
+        x2: input[UNIT_POS * 6 + 2],
+        x3: input[UNIT_POS * 6 + 3],
+    };
+    let block = philox4x32_10(counter, input[UNIT_POS * 6 + 4], input[UNIT_POS * 6 + 5]);
+
+    output[UNIT_POS * 4] = block.x0;
+    output[UNIT_POS * 4 + 1] = block.x1;
+    output[UNIT_POS * 4 + 2] = block.x2;
+    output[UNIT_POS * 4 + 3] = block.x3;
+}
+
+pub fn test_philox_known_answers<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    // The counter and the key of each test, followed by the expected output.
+    let cases: [([u32; 6], [u32; 4]); 3] = [
+        ([0; 6], [0x6627e8d5, 0xe169c58d, 0xbc57ac4c, 0x9b00dbd8]),
+        (
+            [u32::MAX; 6],
+            [0x408f276d, 0x41c83b0e, 0xa20bc7c6, 0x6d5451fd],
+        ),
+        (
+            [
+                0x243f6a88, 0x85a308d3, 0x13198a2e, 0x03707344, 0xa4093822, 0x299f31d0,
+            ],
+            [0xd16cfe09, 0x94fdcceb, 0x5001e420, 0x24126ea1],
+        ),
+    ];
+    let input: Vec<u32> = cases.iter().flat_map(|(input, _)| *input).collect();
+    let expected: Vec<u32> = cases.iter().flat_map(|(_, output)| *output).collect();
+
+    let input_handle = client.create(u32::as_bytes(&input));
+    let output_handle = client.empty(expected.len() * core::mem::size_of::<u32>());
+
+    unsafe {
+        kernel_philox::launch::<R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(cases.len() as u32, 1, 1),
+            ArrayArg::from_raw_parts::<u32>(&input_handle, input.len(), 1),
+            ArrayArg::from_raw_parts::<u32>(&output_handle, expected.len(), 1),
+        )
+    };
+
+    let actual = client.read(output_handle.binding());
+    let actual = u32::from_bytes(&actual);
+
+    assert_eq!(actual, &expected);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_random {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_philox_known_answers() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::random::test_philox_known_answers::<TestRuntime>(client);
+        }
+    };
+}
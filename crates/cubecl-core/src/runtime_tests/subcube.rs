@@ -75,6 +75,14 @@ pub fn kernel_broadcast<F: Float>(output: &mut Tensor<F>) {
     }
 }
 
+#[cube(launch)]
+pub fn kernel_shuffle<F: Float>(output: &mut Tensor<F>) {
+    let val = output[UNIT_POS];
+    let val2 = subcube_shuffle(val, 3 - UNIT_POS);
+
+    output[UNIT_POS] = val2;
+}
+
 pub fn test_subcube_sum<TestRuntime: Runtime, F: Float + CubeElement>(
     client: ComputeClient<TestRuntime::Server, TestRuntime::Channel>,
 ) {
@@ -194,6 +202,19 @@ pub fn test_subcube_broadcast<TestRuntime: Runtime, F: Float + CubeElement>(
     );
 }
 
+pub fn test_subcube_shuffle<TestRuntime: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<TestRuntime::Server, TestRuntime::Channel>,
+) {
+    test_subcube_operation::<TestRuntime, F, _>(
+        as_type![F: 2.0, 1.0, -6.0, 3.0],
+        as_type![F: 3.0, -6.0, 1.0, 2.0],
+        client.clone(),
+        |cube_dim, settings, handle| {
+            kernel_shuffle::launch::<F, TestRuntime>(&client, cube_dim, settings, handle)
+        },
+    );
+}
+
 fn test_subcube_operation<TestRuntime: Runtime, F: Float + CubeElement, Launch>(
     input: &[F],
     expected: &[F],
@@ -284,5 +305,13 @@ macro_rules! testgen_subcube {
                 client,
             );
         }
+
+        #[test]
+        fn test_subcube_shuffle() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::subcube::test_subcube_shuffle::<TestRuntime, FloatType>(
+                client,
+            );
+        }
     };
 }
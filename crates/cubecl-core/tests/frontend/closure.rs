@@ -0,0 +1,140 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[cube]
+pub fn map<T: Numeric, F: Fn(T) -> T>(x: T, f: F) -> T {
+    f(x)
+}
+
+#[cube]
+pub fn reduce<T: Numeric>(x: T, y: T, f: impl Fn(T, T) -> T) -> T {
+    f(x, y)
+}
+
+#[cube]
+pub fn map_twice<T: Numeric, F>(x: T, f: F) -> T
+where
+    F: Fn(T) -> T,
+{
+    let y = map::<T, _>(x, &f);
+    f(y)
+}
+
+#[cube]
+pub fn closure_capture<T: Numeric>(x: T, y: T) {
+    let _ = map::<T, _>(x, |a| a * y);
+    let _ = y;
+}
+
+#[cube]
+pub fn closure_reduce<T: Numeric>(x: T, y: T) {
+    let _ = reduce::<T>(x, y, |a, b| a + b);
+}
+
+#[cube]
+pub fn closure_local<T: Numeric>(x: T, y: T) {
+    let f = |a: T| a * y;
+    let _ = map_twice::<T, _>(x, f);
+    let _ = y;
+}
+
+#[cube]
+pub fn closure_assoc_fn<F: Float>(x: F, y: F) {
+    let _ = map::<F, _>(x, |a| F::exp(a));
+    let _ = y;
+}
+
+#[cube]
+pub fn mul<T: Numeric>(a: T, y: T) -> T {
+    a * y
+}
+
+#[cube]
+pub fn no_closure_capture<T: Numeric>(x: T, y: T) {
+    let _ = mul::<T>(x, y);
+    let _ = y;
+}
+
+#[cube]
+pub fn no_closure_reduce<T: Numeric>(x: T, y: T) {
+    let _ = x + y;
+}
+
+#[cube]
+pub fn no_closure_local<T: Numeric>(x: T, y: T) {
+    let z = mul::<T>(x, y);
+    let _ = mul::<T>(z, y);
+    let _ = y;
+}
+
+#[cube]
+pub fn no_closure_assoc_fn<F: Float>(x: F, y: F) {
+    let _ = F::exp(x);
+    let _ = y;
+}
+
+mod tests {
+    use super::*;
+    use cubecl_core::{
+        frontend::{CubeContext, CubePrimitive},
+        ir::{Item, Scope},
+    };
+
+    type Expand = fn(
+        &mut CubeContext,
+        cubecl::frontend::ExpandElementTyped<f32>,
+        cubecl::frontend::ExpandElementTyped<f32>,
+    );
+
+    fn expand(kernel: Expand) -> Scope {
+        let mut context = CubeContext::default();
+        let x = context.create_local_binding(Item::new(f32::as_elem()));
+        let y = context.create_local_binding(Item::new(f32::as_elem()));
+        kernel(&mut context, x.into(), y.into());
+        context.into_scope()
+    }
+
+    #[test]
+    fn cube_closure_with_capture_test() {
+        let closure = expand(closure_capture::expand::<f32>);
+        let no_closure = expand(no_closure_capture::expand::<f32>);
+
+        assert_eq!(
+            format!("{:?}", closure.operations),
+            format!("{:?}", no_closure.operations)
+        );
+    }
+
+    #[test]
+    fn cube_closure_multiple_params_test() {
+        let closure = expand(closure_reduce::expand::<f32>);
+        let no_closure = expand(no_closure_reduce::expand::<f32>);
+
+        assert_eq!(
+            format!("{:?}", closure.operations),
+            format!("{:?}", no_closure.operations)
+        );
+    }
+
+    #[test]
+    fn cube_local_closure_forwarded_test() {
+        let closure = expand(closure_local::expand::<f32>);
+        let no_closure = expand(no_closure_local::expand::<f32>);
+
+        assert_eq!(
+            format!("{:?}", closure.operations),
+            format!("{:?}", no_closure.operations)
+        );
+    }
+
+    #[test]
+    fn cube_closure_assoc_fn_call_test() {
+        let closure = expand(closure_assoc_fn::expand::<f32>);
+        let no_closure = expand(no_closure_assoc_fn::expand::<f32>);
+
+        assert_eq!(
+            format!("{:?}", closure.operations),
+            format!("{:?}", no_closure.operations)
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[cube]
+pub trait Operation<T: Numeric>: 'static + Send + Sync {
+    fn apply(lhs: T, rhs: T) -> T;
+}
+
+pub struct Add<T: Numeric> {
+    _t: core::marker::PhantomData<T>,
+}
+pub struct Mul<T: Numeric> {
+    _t: core::marker::PhantomData<T>,
+}
+
+#[cube]
+impl<T: Numeric> Operation<T> for Add<T> {
+    fn apply(lhs: T, rhs: T) -> T {
+        lhs + rhs
+    }
+}
+
+#[cube]
+impl<T: Numeric> Operation<T> for Mul<T> {
+    fn apply(lhs: T, rhs: T) -> T {
+        lhs * rhs
+    }
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum OperationKind {
+    Add,
+    Mul,
+}
+
+#[cube]
+pub fn apply_dispatch<T: Numeric>(lhs: T, rhs: T, #[comptime] kind: OperationKind) -> T {
+    comptime_dispatch!(kind, {
+        OperationKind::Add => Add<T>,
+        OperationKind::Mul => Mul<T>,
+    }, |Op| apply_generic::<T, Op>(lhs, rhs))
+}
+
+#[cube]
+pub fn apply_dispatch_assoc<T: Numeric>(lhs: T, rhs: T, #[comptime] kind: OperationKind) -> T {
+    comptime_dispatch!(kind, {
+        OperationKind::Add => Add<T>,
+        OperationKind::Mul => Mul<T>,
+    }, |Op| Op::apply(lhs, rhs))
+}
+
+#[cube]
+pub fn apply_generic<T: Numeric, Op: Operation<T>>(lhs: T, rhs: T) -> T {
+    Op::apply(lhs, rhs)
+}
+
+#[cube]
+pub fn apply_match<T: Numeric>(lhs: T, rhs: T, #[comptime] kind: OperationKind) -> T {
+    match kind {
+        OperationKind::Add => apply_generic::<T, Add<T>>(lhs, rhs),
+        OperationKind::Mul => apply_generic::<T, Mul<T>>(lhs, rhs),
+    }
+}
+
+#[cube]
+pub fn apply_match_assoc<T: Numeric>(lhs: T, rhs: T, #[comptime] kind: OperationKind) -> T {
+    match kind {
+        OperationKind::Add => Add::<T>::apply(lhs, rhs),
+        OperationKind::Mul => Mul::<T>::apply(lhs, rhs),
+    }
+}
+
+mod tests {
+    use super::*;
+    use cubecl_core::ir::{Item, Scope};
+
+    type Expand = fn(
+        &mut CubeContext,
+        ExpandElementTyped<f32>,
+        ExpandElementTyped<f32>,
+        OperationKind,
+    ) -> ExpandElementTyped<f32>;
+
+    fn expand(kernel: Expand, kind: OperationKind) -> Scope {
+        let mut context = CubeContext::default();
+        let lhs = context.create_local_binding(Item::new(f32::as_elem()));
+        let rhs = context.create_local_binding(Item::new(f32::as_elem()));
+        kernel(&mut context, lhs.into(), rhs.into(), kind);
+        context.into_scope()
+    }
+
+    #[test]
+    fn cube_dispatch_selects_impl_test() {
+        for kind in [OperationKind::Add, OperationKind::Mul] {
+            let scope = expand(apply_dispatch::expand::<f32>, kind);
+            let expected = expand(apply_match::expand::<f32>, kind);
+
+            assert_eq!(
+                format!("{:?}", scope.operations),
+                format!("{:?}", expected.operations)
+            );
+        }
+    }
+
+    #[test]
+    fn cube_dispatch_associated_fn_test() {
+        for kind in [OperationKind::Add, OperationKind::Mul] {
+            let scope = expand(apply_dispatch_assoc::expand::<f32>, kind);
+            let expected = expand(apply_match_assoc::expand::<f32>, kind);
+
+            assert_eq!(
+                format!("{:?}", scope.operations),
+                format!("{:?}", expected.operations)
+            );
+        }
+    }
+
+    #[test]
+    fn cube_dispatch_expands_selected_arm_only_test() {
+        let add = expand(apply_dispatch::expand::<f32>, OperationKind::Add);
+        let mul = expand(apply_dispatch::expand::<f32>, OperationKind::Mul);
+
+        assert_eq!(add.operations.len(), 1);
+        assert_eq!(mul.operations.len(), 1);
+        assert!(format!("{:?}", add.operations).contains("Add("));
+        assert!(format!("{:?}", mul.operations).contains("Mul("));
+    }
+
+    #[test]
+    fn dispatch_on_host_test() {
+        let kind = OperationKind::Mul;
+        let result = comptime_dispatch!(kind, {
+            OperationKind::Add => Add<u32>,
+            OperationKind::Mul => Mul<u32>,
+        }, |Op| <Op as Operation<u32>>::apply(3, 4));
+
+        assert_eq!(result, 12);
+    }
+}
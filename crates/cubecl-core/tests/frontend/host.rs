@@ -0,0 +1,220 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+#[cube]
+pub fn host_add_positions(lhs: u32, rhs: u32) -> u32 {
+    lhs * rhs + UNIT_POS + CUBE_POS_X * CUBE_DIM_X
+}
+
+#[cube]
+pub fn host_scale_array<F: Float>(input: &Array<F>, output: &mut Array<F>, scale: F) {
+    for i in 0..input.len() {
+        output[i] = input[i] * scale;
+    }
+}
+
+#[cube]
+pub fn host_sum_until<F: Float>(input: &Array<F>, limit: F) -> F {
+    let mut sum = F::new(0.0);
+    let mut i = 0;
+
+    loop {
+        if i >= input.len() || sum >= limit {
+            break;
+        }
+        sum += input[i];
+        i += 1;
+    }
+
+    sum
+}
+
+#[cube]
+pub fn host_tensor_offset<F: Float>(tensor: &Tensor<Line<F>>, position: u32) -> u32 {
+    let mut offset = 0;
+    let mut remaining = position;
+
+    for i in 0..tensor.rank() {
+        let dim = tensor.rank() - i - 1;
+        offset += remaining % tensor.shape(dim) * tensor.stride(dim);
+        remaining /= tensor.shape(dim);
+    }
+
+    offset / tensor.line_size()
+}
+
+#[cube]
+pub fn host_line_sum(line: Line<f32>) -> f32 {
+    let mut sum = 0.0;
+
+    #[unroll]
+    for i in 0..4 {
+        sum += line[i];
+    }
+
+    sum
+}
+
+#[cube]
+pub fn host_line_permute(line: Line<f32>) -> (Line<f32>, Line<f32>, Line<f32>) {
+    let (low, high) = line.split(1);
+    let rotated = Line::concat(high, low);
+
+    (line.shuffle([3, 2, 1, 0]), line.swizzle([2, 2]), rotated)
+}
+
+#[cube]
+pub fn host_line_lanes(line: Line<u32>, index: u32) -> (u32, Line<u32>) {
+    let mut updated = line;
+    updated.insert(index, 10);
+
+    (line.extract(index), updated)
+}
+
+#[cube]
+pub fn host_reverse(#[comptime] size: u32) -> (u32, u32) {
+    let mut local = Array::<u32>::new(size);
+    let mut shared = SharedMemory::<u32>::new(size);
+
+    for i in 0..local.len() {
+        local[i] = i * 10;
+    }
+    for i in 0..local.len() {
+        shared[local.len() - i - 1] = local[i];
+    }
+
+    let first = shared[0];
+    let last = shared[local.len() - 1];
+    (first, last)
+}
+
+#[cube]
+pub fn host_atomic_add(counters: &mut Array<AtomicU32>, value: u32) -> u32 {
+    AtomicU32::add(&counters[UNIT_POS], value)
+}
+
+#[cube]
+pub fn host_select_abs(value: i32) -> i32 {
+    select(value < 0, -value, value)
+}
+
+#[cube]
+pub trait HostOrder {
+    fn host_index(x: u32, y: u32, width: u32) -> u32;
+}
+
+pub struct HostRowMajor;
+
+#[cube]
+impl HostOrder for HostRowMajor {
+    fn host_index(x: u32, y: u32, width: u32) -> u32 {
+        y * width + x
+    }
+}
+
+mod tests {
+    use super::*;
+    use cubecl_core::{host::HostUnit, ir::CubeDim};
+
+    #[test]
+    fn host_builtins_test() {
+        let unit = HostUnit::default()
+            .with_unit_pos(1, 2, 0)
+            .with_cube_dim(CubeDim::new(4, 4, 1))
+            .with_cube_pos(3, 0, 0);
+
+        assert_eq!(host_add_positions::host(unit, 2, 5), 10 + 9 + 12);
+    }
+
+    #[test]
+    fn host_mutable_array_test() {
+        let input = vec![1.0, 2.0, 3.0];
+        let mut output = vec![0.0; 3];
+
+        host_scale_array::host::<f32>(HostUnit::default(), &input, &mut output, 2.0);
+
+        assert_eq!(output, vec![2.0, 4.0, 6.0]);
+    }
+
+    #[test]
+    fn host_loop_break_test() {
+        let input = vec![1.0, 2.0, 3.0, 4.0];
+
+        let sum = host_sum_until::host::<f32>(HostUnit::default(), &input, 3.0);
+        let total = host_sum_until::host::<f32>(HostUnit::default(), &input, 100.0);
+
+        assert_eq!(sum, 3.0);
+        assert_eq!(total, 10.0);
+    }
+
+    #[test]
+    fn host_tensor_metadata_test() {
+        // Transposed 2x3 tensor with lines of 1 element.
+        let tensor = HostTensor::new_strided(vec![vec![0.0]; 6], vec![2, 3], vec![1, 2]);
+
+        let offset = host_tensor_offset::host::<f32>(HostUnit::default(), &tensor, 4);
+
+        assert_eq!(offset, 3);
+    }
+
+    #[test]
+    fn host_line_test() {
+        let sum = host_line_sum::host(HostUnit::default(), vec![1.0, 2.0, 3.0, 4.0]);
+
+        assert_eq!(sum, 10.0);
+    }
+
+    #[test]
+    fn host_line_permute_test() {
+        let (reversed, swizzled, rotated) =
+            host_line_permute::host(HostUnit::default(), vec![1.0, 2.0, 3.0, 4.0]);
+
+        assert_eq!(reversed, vec![4.0, 3.0, 2.0, 1.0]);
+        assert_eq!(swizzled, vec![3.0, 3.0]);
+        assert_eq!(rotated, vec![2.0, 3.0, 4.0, 1.0]);
+    }
+
+    #[test]
+    fn host_line_lanes_test() {
+        let (lane, updated) = host_line_lanes::host(HostUnit::default(), vec![1, 2, 3], 1);
+
+        assert_eq!(lane, 2);
+        assert_eq!(updated, vec![1, 10, 3]);
+    }
+
+    #[test]
+    fn host_local_and_shared_arrays_test() {
+        assert_eq!(host_reverse::host(HostUnit::default(), 4), (30, 0));
+    }
+
+    #[test]
+    fn host_atomic_test() {
+        let mut counters = vec![5, 7];
+        let unit = HostUnit::default().with_unit_pos(1, 0, 0);
+
+        assert_eq!(host_atomic_add::host(unit, &mut counters, 3), 7);
+        assert_eq!(counters, vec![5, 10]);
+    }
+
+    #[test]
+    fn host_select_test() {
+        assert_eq!(host_select_abs::host(HostUnit::default(), -4), 4);
+        assert_eq!(host_select_abs::host(HostUnit::default(), 6), 6);
+    }
+
+    #[test]
+    fn host_trait_fn_test() {
+        let index = HostRowMajor::__host_host_index(HostUnit::default(), 2, 3, 4);
+
+        assert_eq!(index, 14);
+        assert_eq!(index, HostRowMajor::host_index(2, 3, 4));
+    }
+
+    #[test]
+    #[should_panic]
+    fn host_out_of_bounds_test() {
+        let mut output = vec![0.0];
+
+        host_scale_array::host::<f32>(HostUnit::default(), &vec![1.0, 2.0], &mut output, 1.0);
+    }
+}
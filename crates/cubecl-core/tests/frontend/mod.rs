@@ -2,14 +2,18 @@ mod array;
 mod assign;
 mod cast_elem;
 mod cast_kind;
+mod closure;
 mod comptime;
 mod constants;
 mod cube_impl;
 mod cube_trait;
+mod dispatch;
 mod enum_type;
 mod for_loop;
 mod function_call;
 mod generic_kernel;
+#[cfg(feature = "host")]
+mod host;
 mod r#if;
 mod intrinsics;
 mod literal;
@@ -11,6 +11,16 @@ pub fn vectorization_cmp<T: Numeric>(rhs: T) {
     let _ = T::from_vec([4, 5]) > rhs;
 }
 
+#[cube]
+pub fn vectorization_shuffle<T: Numeric>(line: Line<T>) {
+    let _ = line.shuffle([1, 0, 3, 2]);
+}
+
+#[cube]
+pub fn vectorization_split<T: Numeric>(line: Line<T>, #[comptime] at: u32) {
+    let _ = line.split(at);
+}
+
 mod tests {
     use std::num::NonZero;
 
@@ -69,4 +79,36 @@ mod tests {
 
         vectorization_cmp::expand::<ElemType>(&mut context, lhs.into());
     }
+
+    #[test]
+    fn cube_vectorization_shuffle_with_permutation_does_not_fail() {
+        let mut context = CubeContext::default();
+
+        let line =
+            context.create_local_binding(Item::vectorized(ElemType::as_elem(), NonZero::new(4)));
+
+        vectorization_shuffle::expand::<ElemType>(&mut context, line.into());
+    }
+
+    #[test]
+    #[should_panic]
+    fn cube_vectorization_shuffle_with_different_size_fails() {
+        let mut context = CubeContext::default();
+
+        let line =
+            context.create_local_binding(Item::vectorized(ElemType::as_elem(), NonZero::new(2)));
+
+        vectorization_shuffle::expand::<ElemType>(&mut context, line.into());
+    }
+
+    #[test]
+    #[should_panic]
+    fn cube_vectorization_split_out_of_bounds_fails() {
+        let mut context = CubeContext::default();
+
+        let line =
+            context.create_local_binding(Item::vectorized(ElemType::as_elem(), NonZero::new(4)));
+
+        vectorization_split::expand::<ElemType>(&mut context, line.into(), 4);
+    }
 }
@@ -265,6 +265,14 @@ impl<D: Dialect> CppCompiler<D> {
                             out,
                         }))
                     }
+                    // Warp shuffles already take a different lane per thread.
+                    gpu::Subcube::Shuffle(op) => {
+                        instructions.push(Instruction::Wrap(WarpInstruction::Broadcast {
+                            input: self.compile_variable(op.lhs),
+                            id: self.compile_variable(op.rhs),
+                            out,
+                        }))
+                    }
                 }
             }
             gpu::Operation::CoopMma(cmma) => instructions.push(self.compile_cmma(cmma, out)),
@@ -698,6 +706,12 @@ impl<D: Dialect> CppCompiler<D> {
                     .collect(),
                 out: self.compile_variable(out),
             }),
+            gpu::Operator::Shuffle(op) => instructions.push(Instruction::Shuffle {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                indices: op.indices,
+                out: self.compile_variable(out),
+            }),
             gpu::Operator::CopyMemory(op) => instructions.push(Instruction::Copy {
                 input: self.compile_variable(op.input),
                 in_index: self.compile_variable(op.in_index),
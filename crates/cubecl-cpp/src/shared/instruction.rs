@@ -69,6 +69,12 @@ pub enum Instruction<D: Dialect> {
         inputs: Vec<Variable<D>>,
         out: Variable<D>,
     },
+    Shuffle {
+        lhs: Variable<D>,
+        rhs: Variable<D>,
+        indices: Vec<u32>,
+        out: Variable<D>,
+    },
     Loop {
         instructions: Vec<Self>,
     },
@@ -522,6 +528,29 @@ for ({i_ty} {i} = {start}; {i} {cmp} {end}; {increment}) {{
                 let out = out.fmt_left();
                 writeln!(f, "{out} = {item}{{{}}};", inputs.join(","))
             }
+            Instruction::Shuffle {
+                lhs,
+                rhs,
+                indices,
+                out,
+            } => {
+                let item = out.item();
+                let lhs_size = lhs.item().vectorization as u32;
+                let lanes = indices
+                    .iter()
+                    .map(|index| match *index < lhs_size {
+                        true => format!("{}", lhs.index(*index as usize)),
+                        false => format!("{}", rhs.index((*index - lhs_size) as usize)),
+                    })
+                    .collect::<Vec<_>>();
+                let out = out.fmt_left();
+
+                if item.vectorization > 1 {
+                    writeln!(f, "{out} = {item}{{{}}};", lanes.join(","))
+                } else {
+                    writeln!(f, "{out} = {};", lanes[0])
+                }
+            }
         }
     }
 }
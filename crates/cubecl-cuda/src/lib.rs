@@ -19,5 +19,20 @@ mod tests {
     cubecl_linalg::testgen_plane_mma!([f16, bf16, f32], f16);
     cubecl_linalg::testgen_plane_mma!([f16, bf16, f32], f32);
     cubecl_linalg::testgen_tiling2d!([f16, bf16, f32]);
+    cubecl_linalg::testgen_gemv!([f16, bf16, f32]);
+    cubecl_linalg::testgen_reduce!();
+    cubecl_linalg::testgen_normalization!([f16, bf16, f32]);
+    cubecl_linalg::testgen_conv!([f16, bf16, f32]);
+    cubecl_linalg::testgen_attention!([f16, bf16, f32]);
+    cubecl_linalg::testgen_scan!();
+    cubecl_linalg::testgen_sort!();
+    cubecl_linalg::testgen_random!();
+    cubecl_linalg::testgen_tensor!();
+    cubecl_linalg::testgen_index!();
+    cubecl_linalg::testgen_fft!();
+    cubecl_linalg::testgen_fusion!();
+    cubecl_linalg::testgen_solve!([f32, f64]);
+    cubecl_linalg::testgen_histogram!();
+    cubecl_linalg::testgen_sparse!();
     cubecl_linalg::testgen_cmma_old!([f16, bf16, f32 /*, f64*/]);
 }
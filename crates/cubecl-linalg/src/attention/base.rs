@@ -0,0 +1,356 @@
+use cubecl_core::prelude::*;
+
+use crate::matmul::components::global::Config as _;
+use crate::matmul::components::stage::{
+    self, S2x2x4, S2x2x8, S2x4x2, S2x8x2, StageSize, TilingOrderConfig,
+};
+use crate::matmul::components::tile::Matmul as _;
+use crate::matmul::components::{MatmulKernel, MatrixLayout};
+use crate::matmul::kernels::cmma_matmul::config::{create_stage_dim, CmmaSmmConfig};
+use crate::matmul::kernels::cmma_matmul::dispatch::{
+    check_availability, CmmaLaunchDispatch, MatmulLaunchDispatch, PlaneMmaLaunchDispatch,
+};
+use crate::tensor::{into_contiguous, TensorHandle};
+
+use super::attention_kernel;
+use super::config::{check_config, make_global_config, Config};
+
+/// Options of a scaled dot-product attention.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AttentionOptions {
+    /// Whether each query only attends to the keys at or before its own position.
+    pub causal: bool,
+    /// Factor applied to the scores before the softmax, `1 / sqrt(head_dim)` if not set.
+    pub scale: Option<f32>,
+}
+
+/// Scaled dot-product attention of a query of shape `[batch, heads, seq_q, head_dim]` with a key
+/// and a value of shape `[batch, heads, seq_kv, head_dim]`, returning an output of shape
+/// `[batch, heads, seq_q, head_dim]`.
+///
+/// The optional mask is added to the scores and must be broadcastable to
+/// `[batch, heads, seq_q, seq_kv]`. Only head dimensions of 64 and 128 are supported.
+///
+/// Cmma will be used if available, otherwise it will fall back on a non-cmma implementation.
+///
+/// # Panics
+///
+/// If the query isn't empty while the sequence of keys is.
+pub fn attention<R: Runtime, E: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    query: TensorHandle<R, E>,
+    key: TensorHandle<R, E>,
+    value: TensorHandle<R, E>,
+    mask: Option<TensorHandle<R, E>>,
+    options: AttentionOptions,
+) -> TensorHandle<R, E> {
+    launch(client, query, key, value, mask, options, false)
+}
+
+/// Launch the fused attention, using the plane matmul instead of cmma if `disable_cmma` is set.
+pub fn launch<R: Runtime, E: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    query: TensorHandle<R, E>,
+    key: TensorHandle<R, E>,
+    value: TensorHandle<R, E>,
+    mask: Option<TensorHandle<R, E>>,
+    options: AttentionOptions,
+    disable_cmma: bool,
+) -> TensorHandle<R, E> {
+    assert!(
+        query.shape.len() == 4 && key.shape.len() == 4 && value.shape.len() == 4,
+        "Attention expects a query, key and value of rank 4, got shapes {:?}, {:?} and {:?}",
+        query.shape,
+        key.shape,
+        value.shape
+    );
+    assert_eq!(
+        key.shape, value.shape,
+        "Attention expects a key and a value of the same shape"
+    );
+    assert!(
+        query.shape[0] == key.shape[0]
+            && query.shape[1] == key.shape[1]
+            && query.shape[3] == key.shape[3],
+        "The query of shape {:?} doesn't match the key of shape {:?}",
+        query.shape,
+        key.shape
+    );
+
+    let [batch_size, num_heads, seq_q, head_dim] = [0, 1, 2, 3].map(|dim| query.shape[dim]);
+    let seq_kv = key.shape[2];
+    let mask_shape = [batch_size, num_heads, seq_q, seq_kv];
+
+    if batch_size * num_heads * seq_q == 0 {
+        return TensorHandle::empty(client, query.shape.clone());
+    }
+    // The softmax over an empty sequence of keys is undefined.
+    assert!(
+        seq_kv > 0,
+        "Attention expects at least one key, got a key of shape {:?}",
+        key.shape
+    );
+
+    let query = last_dim_contiguous(client, query);
+    let key = last_dim_contiguous(client, key);
+    let value = last_dim_contiguous(client, value);
+    let mask = mask.map(|mask| broadcast_mask(mask, mask_shape));
+
+    let output = TensorHandle::empty(client, query.shape.clone());
+
+    let query_line_size = line_size::<R>(head_dim, &query.strides);
+    let kv_line_size = Ord::min(
+        line_size::<R>(head_dim, &key.strides),
+        line_size::<R>(head_dim, &value.strides),
+    );
+
+    // Safety: the placeholder is never read when there is no mask.
+    let mask_arg = match &mask {
+        Some((mask, strides)) => unsafe {
+            TensorArg::<R>::from_raw_parts::<E>(&mask.handle, strides, &mask_shape, 1)
+        },
+        None => unsafe { TensorArg::<R>::from_raw_parts::<E>(&query.handle, &[0; 4], &[1; 4], 1) },
+    };
+
+    let args = AttentionArgs {
+        query: query.as_arg(query_line_size),
+        key: key.as_arg(kv_line_size),
+        value: value.as_arg(kv_line_size),
+        mask: mask_arg,
+        out: output.as_arg(1),
+    };
+    let problem = AttentionProblem {
+        batch_size,
+        num_heads,
+        seq_q,
+        head_dim,
+        query_line_size: query_line_size as u32,
+        kv_line_size: kv_line_size as u32,
+        has_mask: mask.is_some(),
+        causal: options.causal,
+        scale: options
+            .scale
+            .unwrap_or_else(|| 1.0 / (head_dim as f32).sqrt()),
+    };
+
+    if !disable_cmma && check_availability::<CmmaLaunchDispatch, R>(client).is_ok() {
+        launch_attention::<R, E, CmmaLaunchDispatch>(client, args, &problem);
+    } else {
+        launch_attention::<R, E, PlaneMmaLaunchDispatch>(client, args, &problem);
+    }
+
+    output
+}
+
+struct AttentionArgs<'a, R: Runtime> {
+    query: TensorArg<'a, R>,
+    key: TensorArg<'a, R>,
+    value: TensorArg<'a, R>,
+    mask: TensorArg<'a, R>,
+    out: TensorArg<'a, R>,
+}
+
+struct AttentionProblem {
+    batch_size: usize,
+    num_heads: usize,
+    seq_q: usize,
+    head_dim: usize,
+    query_line_size: u32,
+    kv_line_size: u32,
+    has_mask: bool,
+    causal: bool,
+    scale: f32,
+}
+
+type TileConfig<D> = <<D as MatmulLaunchDispatch>::TileMatmul as MatmulKernel<
+    <D as MatmulLaunchDispatch>::ElementInput,
+    <D as MatmulLaunchDispatch>::ElementAccumulator,
+>>::Config;
+
+type StageMatmul<D, SS> = stage::row_accumulate::Matmul<
+    <D as MatmulLaunchDispatch>::ElementInput,
+    f32,
+    <D as MatmulLaunchDispatch>::ElementAccumulator,
+    <D as MatmulLaunchDispatch>::TileMatmul,
+    SS,
+>;
+
+/// Select the stage sizes of both matmuls for the head dimension, with blocks of 32 queries
+/// and 32 keys
+fn launch_attention<R: Runtime, E: Float, D: MatmulLaunchDispatch>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    args: AttentionArgs<'_, R>,
+    problem: &AttentionProblem,
+) {
+    match problem.head_dim {
+        64 => launch_blocks::<R, E, D, S2x2x4, S2x4x2>(client, args, problem),
+        128 => launch_blocks::<R, E, D, S2x2x8, S2x8x2>(client, args, problem),
+        head_dim => panic!(
+            "Attention only supports head dimensions of 64 and 128, got {}",
+            head_dim
+        ),
+    }
+}
+
+fn launch_blocks<R, E, D, SS1, SS2>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    args: AttentionArgs<'_, R>,
+    problem: &AttentionProblem,
+) where
+    R: Runtime,
+    E: Float,
+    D: MatmulLaunchDispatch,
+    SS1: StageSize,
+    SS2: StageSize,
+{
+    let cube_dim = CubeDim::new(D::PLANE_DIM, SS1::NUM_M, 1);
+    let config = make_attention_config::<D, SS1, SS2>(problem, &cube_dim);
+
+    StageMatmul::<D, SS1>::check_config(config.score_config().to_smm_config());
+    StageMatmul::<D, SS2>::check_config(config.value_config().to_smm_config());
+    check_config(&config);
+
+    let cube_count = CubeCount::Static(
+        (problem.seq_q as u32).div_ceil(config.block_q()),
+        problem.num_heads as u32,
+        problem.batch_size as u32,
+    );
+
+    unsafe {
+        attention_kernel::launch_unchecked::<
+            E,
+            D::ElementInput,
+            StageMatmul<D, SS1>,
+            StageMatmul<D, SS2>,
+            R,
+        >(
+            client,
+            cube_count,
+            cube_dim,
+            args.query,
+            args.key,
+            args.value,
+            args.mask,
+            args.out,
+            ScalarArg::new(problem.scale),
+            config,
+        );
+    }
+}
+
+/// Make a config for the fused attention, given the problem definition and cube settings
+fn make_attention_config<D, SS1, SS2>(
+    problem: &AttentionProblem,
+    cube_dim: &CubeDim,
+) -> Config<CmmaSmmConfig<TileConfig<D>>>
+where
+    D: MatmulLaunchDispatch,
+    SS1: StageSize,
+    SS2: StageSize,
+{
+    let plane_dim = cube_dim.x;
+    let num_planes = cube_dim.y;
+
+    let smm_config = |num_m, num_n, num_k, rhs_layout, lhs_line_size, rhs_line_size| {
+        let (tile_m, tile_n, tile_k) = (D::TileMatmul::M, D::TileMatmul::N, D::TileMatmul::K);
+        let (lhs_stage_dim, rhs_stage_dim, out_stage_dim) = create_stage_dim(
+            num_m * tile_m,
+            num_n * tile_n,
+            num_k * tile_k,
+            tile_m,
+            tile_n,
+            tile_k,
+        );
+
+        CmmaSmmConfig::new(
+            D::tile_config(
+                plane_dim,
+                MatrixLayout::RowMajor,
+                rhs_layout,
+                lhs_line_size,
+                rhs_line_size,
+                1,
+            ),
+            lhs_stage_dim,
+            rhs_stage_dim,
+            out_stage_dim,
+            num_planes,
+            TilingOrderConfig::XMajor,
+        )
+    };
+
+    // The key is read as the column major transposed rhs, so that the head dimension stays
+    // contiguous in both stages sharing the keys and values.
+    let score_smm = smm_config(
+        SS1::NUM_M,
+        SS1::NUM_N,
+        SS1::NUM_K,
+        MatrixLayout::ColMajor,
+        problem.query_line_size,
+        problem.kv_line_size,
+    );
+    let value_smm = smm_config(
+        SS2::NUM_M,
+        SS2::NUM_N,
+        SS2::NUM_K,
+        MatrixLayout::RowMajor,
+        1,
+        problem.kv_line_size,
+    );
+
+    Config::new(
+        make_global_config(score_smm, problem.query_line_size, problem.kv_line_size),
+        make_global_config(value_smm, 1, problem.kv_line_size),
+        problem.causal,
+        problem.has_mask,
+    )
+}
+
+/// Copies the tensor if its last dimension is not contiguous
+fn last_dim_contiguous<R: Runtime, E: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    tensor: TensorHandle<R, E>,
+) -> TensorHandle<R, E> {
+    match tensor.strides[3] == 1 {
+        true => tensor,
+        false => into_contiguous::<R, E>(client, tensor.as_ref()),
+    }
+}
+
+/// Returns the mask with its strides along `shape`, broadcasting the dimensions of size 1
+fn broadcast_mask<R: Runtime, E: Float>(
+    mask: TensorHandle<R, E>,
+    shape: [usize; 4],
+) -> (TensorHandle<R, E>, [usize; 4]) {
+    assert!(
+        mask.shape.len() == 4
+            && mask
+                .shape
+                .iter()
+                .zip(shape)
+                .all(|(mask_dim, dim)| *mask_dim == dim || *mask_dim == 1),
+        "The mask of shape {:?} can't be broadcast to {:?}",
+        mask.shape,
+        shape
+    );
+
+    let strides = [0, 1, 2, 3].map(|dim| match mask.shape[dim] {
+        1 => 0,
+        _ => mask.strides[dim],
+    });
+
+    (mask, strides)
+}
+
+/// The largest supported line size along the head dimension that divides every stride.
+fn line_size<R: Runtime>(head_dim: usize, strides: &[usize]) -> u8 {
+    R::supported_line_sizes()
+        .iter()
+        .copied()
+        .filter(|line_size| {
+            let line_size = *line_size as usize;
+            head_dim % line_size == 0 && strides[..3].iter().all(|stride| stride % line_size == 0)
+        })
+        .max()
+        .unwrap_or(1)
+}
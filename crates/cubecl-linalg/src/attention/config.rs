@@ -0,0 +1,159 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+use crate::matmul::components::global::{homogeneous, Config as _};
+use crate::matmul::components::stage;
+use crate::matmul::components::{Ident, MatmulConfig};
+
+#[derive(CubeType, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+/// Configuration for the fused attention
+///
+/// Holds the configs of the two matmuls of each block of keys and values:
+///  - scores: `(block_q, head_dim) · (head_dim, block_kv) = (block_q, block_kv)`, the query
+///    being the lhs and the transposed key the rhs
+///  - values: `(block_q, block_kv) · (block_kv, head_dim) = (block_q, head_dim)`, the
+///    probabilities being the lhs and the value the rhs
+///
+/// The global configs are only used to unload the output of the stage matmuls.
+pub struct Config<S: stage::Config> {
+    score_config: homogeneous::Config<S>,
+    value_config: homogeneous::Config<S>,
+    causal: bool,
+    has_mask: bool,
+}
+
+impl<S: stage::Config> MatmulConfig for Config<S> {}
+
+impl<S: stage::Config> Config<S> {
+    pub fn new(
+        score_config: homogeneous::Config<S>,
+        value_config: homogeneous::Config<S>,
+        causal: bool,
+        has_mask: bool,
+    ) -> Self {
+        Self {
+            score_config,
+            value_config,
+            causal,
+            has_mask,
+        }
+    }
+
+    /// Returns the config of the matmul of the query with the transposed key
+    pub fn score_config(&self) -> homogeneous::Config<S> {
+        self.score_config
+    }
+
+    /// Returns the config of the matmul of the probabilities with the value
+    pub fn value_config(&self) -> homogeneous::Config<S> {
+        self.value_config
+    }
+
+    /// Whether keys after the query are masked out
+    pub fn causal(&self) -> bool {
+        self.causal
+    }
+
+    /// Whether an additive mask is added to the scores
+    pub fn has_mask(&self) -> bool {
+        self.has_mask
+    }
+
+    /// Returns the number of queries handled by a cube
+    pub fn block_q(&self) -> u32 {
+        self.score_config.stage_dim(Ident::Out).num_elements_x_dim()
+    }
+
+    /// Returns the number of keys and values handled by each iteration
+    pub fn block_kv(&self) -> u32 {
+        self.score_config.stage_dim(Ident::Out).num_elements_y_dim()
+    }
+
+    /// Returns the size of the head dimension
+    pub fn head_dim(&self) -> u32 {
+        self.value_config.stage_dim(Ident::Out).num_elements_y_dim()
+    }
+
+    /// Returns the number of planes in the cube
+    pub fn num_planes(&self) -> u32 {
+        self.score_config.num_planes()
+    }
+
+    /// Returns the size of the plane dimension
+    pub fn plane_dim(&self) -> u32 {
+        self.score_config.plane_dim()
+    }
+}
+
+/// Asserts the configs of both matmuls agree on the blocks they share
+pub(crate) fn check_config<S: stage::Config>(config: &Config<S>) {
+    let score = config.score_config();
+    let value = config.value_config();
+
+    assert_eq!(
+        score.stage_dim(Ident::Out).num_elements_x_dim(),
+        value.stage_dim(Ident::Out).num_elements_x_dim(),
+        "Both matmuls must handle the same number of queries"
+    );
+    assert_eq!(
+        score.stage_dim(Ident::Out).num_elements_y_dim(),
+        value.stage_dim(Ident::Lhs).num_elements_y_dim(),
+        "The values must be reduced over as many elements as there are scores"
+    );
+    assert_eq!(
+        score.stage_dim(Ident::Lhs).num_elements_y_dim(),
+        value.stage_dim(Ident::Out).num_elements_y_dim(),
+        "Queries and values must have the same head dimension"
+    );
+    assert_eq!(
+        score.stage_dim(Ident::Rhs).num_elements(),
+        value.stage_dim(Ident::Rhs).num_elements(),
+        "Keys and values share the same stage"
+    );
+    assert_eq!(
+        score.stage_line_size(Ident::Rhs),
+        value.stage_line_size(Ident::Rhs),
+        "Keys and values share the same stage"
+    );
+    assert!(
+        config.block_kv() % config.plane_dim() == 0,
+        "The block of keys must be a multiple of the plane dimension"
+    );
+    assert!(
+        config.block_q() % config.num_planes() == 0,
+        "The block of queries must be split evenly among planes"
+    );
+    assert_eq!(
+        score.stage_line_size(Ident::Out),
+        1,
+        "Scores are unloaded element by element"
+    );
+    assert_eq!(
+        value.stage_line_size(Ident::Out),
+        1,
+        "Values are unloaded element by element"
+    );
+    assert_eq!(
+        value.stage_line_size(Ident::Lhs),
+        1,
+        "Probabilities are staged element by element"
+    );
+}
+
+/// Make the global config used to unload a stage matmul of the attention
+pub(crate) fn make_global_config<S: stage::Config>(
+    smm_config: S,
+    lhs_line_size: u32,
+    rhs_line_size: u32,
+) -> homogeneous::Config<S> {
+    homogeneous::Config::new(
+        smm_config,
+        false,
+        false,
+        stage::Config::layout(&smm_config, Ident::Lhs),
+        stage::Config::layout(&smm_config, Ident::Rhs),
+        lhs_line_size,
+        rhs_line_size,
+        1,
+    )
+}
@@ -0,0 +1,319 @@
+use crate::matmul::components::global::{homogeneous, Config as _};
+use crate::matmul::components::stage::{self, LhsReader, RhsReader, Stage};
+use crate::matmul::components::Ident;
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+use super::loader::{load_block, stage_index};
+use super::writer::{OutputAccumulator, ScoreWriter};
+use super::Config;
+
+/// Score given to masked out keys, low enough for their probability to vanish without
+/// producing NaNs when a whole row is masked.
+const MASKED_SCORE: f32 = -1e30;
+
+/// Fused attention of a block of queries of one head with all its keys and values.
+///
+/// For each block of keys and values,
+///  - the scores of the block are computed by the first stage matmul,
+///  - the probabilities are computed with an online softmax, rescaling the previous blocks
+///    when the maximum of a row increases,
+///  - the values weighted by the probabilities are accumulated by the second stage matmul.
+///
+/// The attention matrix is never written to global memory.
+#[cube(launch_unchecked)]
+pub(crate) fn attention_kernel<
+    E: Float,
+    ES: Numeric,
+    SMM1: stage::Matmul<ES, f32, LhsReader<ES>, RhsReader<ES>>,
+    SMM2: stage::Matmul<ES, f32, LhsReader<ES>, RhsReader<ES>, Config = SMM1::Config>,
+>(
+    query: &Tensor<Line<E>>,
+    key: &Tensor<Line<E>>,
+    value: &Tensor<Line<E>>,
+    mask: &Tensor<Line<E>>,
+    out: &mut Tensor<Line<E>>,
+    scale: f32,
+    #[comptime] config: Config<SMM1::Config>,
+) {
+    let score_config = config.score_config();
+    let value_config = config.value_config();
+    let block_q = config.block_q();
+
+    let batch = CUBE_POS_Z;
+    let head = CUBE_POS_Y;
+    let q_offset = CUBE_POS_X * block_q;
+
+    let seq_q = query.shape(2);
+    let seq_kv = key.shape(2);
+    let query_offset = batch * query.stride(0) + head * query.stride(1);
+    let key_offset = batch * key.stride(0) + head * key.stride(1);
+    let value_offset = batch * value.stride(0) + head * value.stride(1);
+
+    let mut query_stage = Stage::new::<SMM1::Config>(Ident::Lhs, score_config.to_smm_config());
+    let mut kv_stage = Stage::new::<SMM1::Config>(Ident::Rhs, score_config.to_smm_config());
+    let mut probs_stage = Stage::new::<SMM2::Config>(Ident::Lhs, value_config.to_smm_config());
+
+    let mut softmax = SoftmaxState::new(block_q, config);
+    let mut scores = ScoreWriter::new(block_q, config.block_kv());
+    let mut output = OutputAccumulator::new::<homogeneous::Config<SMM1::Config>>(
+        softmax.row_scale,
+        value_config,
+    );
+
+    load_block::<E, ES, SMM1::Config>(
+        query,
+        &mut query_stage,
+        query_offset,
+        q_offset,
+        Ident::Lhs,
+        score_config,
+    );
+
+    // With a causal mask, the keys after the last query of the block are never attended to.
+    let mut kv_end = seq_kv;
+    if config.causal() {
+        kv_end = Min::min(seq_kv, q_offset + block_q);
+    }
+    let num_blocks = (kv_end + config.block_kv() - 1) / config.block_kv();
+
+    for block in 0..num_blocks {
+        let kv_offset = block * config.block_kv();
+
+        load_block::<E, ES, SMM1::Config>(
+            key,
+            &mut kv_stage,
+            key_offset,
+            kv_offset,
+            Ident::Rhs,
+            score_config,
+        );
+
+        sync_units();
+
+        let mut acc = SMM1::acc_init_zeros(score_config.to_smm_config());
+        SMM1::execute(
+            &LhsReader::new(query_stage),
+            &RhsReader::new(kv_stage),
+            &mut acc,
+            score_config.to_smm_config(),
+        );
+        SMM1::acc_read::<ScoreWriter, homogeneous::Config<SMM1::Config>>(
+            &acc,
+            &mut scores,
+            score_config.to_smm_config(),
+            score_config,
+        );
+
+        sync_units();
+
+        load_block::<E, ES, SMM1::Config>(
+            value,
+            &mut kv_stage,
+            value_offset,
+            kv_offset,
+            Ident::Rhs,
+            value_config,
+        );
+        softmax.update::<E, ES, SMM1::Config>(
+            &scores.scores,
+            &mut probs_stage,
+            mask,
+            q_offset,
+            kv_offset,
+            seq_q,
+            seq_kv,
+            scale,
+            config,
+        );
+
+        sync_units();
+
+        let mut acc = SMM2::acc_init_zeros(value_config.to_smm_config());
+        SMM2::execute(
+            &LhsReader::new(probs_stage),
+            &RhsReader::new(kv_stage),
+            &mut acc,
+            value_config.to_smm_config(),
+        );
+        SMM2::acc_read::<OutputAccumulator, homogeneous::Config<SMM1::Config>>(
+            &acc,
+            &mut output,
+            value_config.to_smm_config(),
+            value_config,
+        );
+
+        sync_units();
+    }
+
+    write_output::<E, SMM1::Config>(out, &output, &softmax, q_offset, config);
+}
+
+#[derive(CubeType)]
+/// Statistics of the online softmax of each row of the block of queries
+pub struct SoftmaxState {
+    /// Maximum of the scores seen so far
+    pub row_max: SharedMemory<f32>,
+    /// Sum of the exponentials of the scores seen so far, minus the maximum
+    pub row_sum: SharedMemory<f32>,
+    /// Correction to apply to the values accumulated for the previous blocks
+    pub row_scale: SharedMemory<f32>,
+}
+
+#[cube]
+impl SoftmaxState {
+    pub fn new<S: stage::Config>(
+        #[comptime] block_q: u32,
+        #[comptime] config: Config<S>,
+    ) -> SoftmaxState {
+        let mut row_max = SharedMemory::new(block_q);
+        let mut row_sum = SharedMemory::new(block_q);
+        let mut row_scale = SharedMemory::new(block_q);
+
+        let num_units = comptime!(config.num_planes() * config.plane_dim());
+        let num_inits = comptime!(block_q.div_ceil(num_units));
+        let unit_id = UNIT_POS_Y * config.plane_dim() + UNIT_POS_X;
+
+        for i in 0..num_inits {
+            let row = unit_id + i * num_units;
+            if row < block_q {
+                row_max[row] = f32::new(MASKED_SCORE);
+                row_sum[row] = 0.0;
+                row_scale[row] = 1.0;
+            }
+        }
+
+        SoftmaxState {
+            row_max,
+            row_sum,
+            row_scale,
+        }
+    }
+
+    /// Updates the statistics with the scores of a block, and writes the probabilities of the
+    /// block to the stage relative to the new maximum of each row.
+    ///
+    /// Each plane takes care of whole rows, so that the statistics are reduced within a plane.
+    #[allow(clippy::too_many_arguments)]
+    pub fn update<E: Float, ES: Numeric, S: stage::Config>(
+        &mut self,
+        scores: &SharedMemory<f32>,
+        probs_stage: &mut Stage<ES>,
+        mask: &Tensor<Line<E>>,
+        q_offset: u32,
+        kv_offset: u32,
+        seq_q: u32,
+        seq_kv: u32,
+        scale: f32,
+        #[comptime] config: Config<S>,
+    ) {
+        let block_q = config.block_q();
+        let block_kv = config.block_kv();
+        let plane_dim = config.plane_dim();
+        let num_planes = config.num_planes();
+        let rows_per_plane = comptime!(block_q / num_planes);
+        let cols_per_unit = comptime!(block_kv / plane_dim);
+        let probs_config = config.value_config().to_smm_config();
+
+        let mask_offset = CUBE_POS_Z * mask.stride(0) + CUBE_POS_Y * mask.stride(1);
+
+        for r in 0..rows_per_plane {
+            let row = UNIT_POS_Y + r * num_planes;
+            let q_index = q_offset + row;
+
+            let mut block_max = f32::new(MASKED_SCORE);
+            let mut row_scores = Array::<f32>::new(cols_per_unit);
+
+            for c in 0..cols_per_unit {
+                let col = UNIT_POS_X + c * plane_dim;
+                let k_index = kv_offset + col;
+
+                let mut score = f32::new(MASKED_SCORE);
+                let mut valid = k_index < seq_kv;
+                if config.causal() {
+                    valid = valid && k_index <= q_index;
+                }
+
+                if valid {
+                    score = scores[row * block_kv + col] * scale;
+
+                    if config.has_mask() {
+                        // Queries past the end only differ in their unused output.
+                        let mask_q = Min::min(q_index, seq_q - 1);
+                        let mask_index =
+                            mask_offset + mask_q * mask.stride(2) + k_index * mask.stride(3);
+                        score += f32::cast_from(mask[mask_index][0]);
+                    }
+
+                    block_max = f32::max(block_max, score);
+                }
+
+                row_scores[c] = score;
+            }
+
+            let prev_max = self.row_max[row];
+            let new_max = f32::max(prev_max, subcube_max(block_max));
+
+            let mut block_sum = 0.0;
+            for c in 0..cols_per_unit {
+                let col = UNIT_POS_X + c * plane_dim;
+
+                let mut prob = 0.0;
+                if row_scores[c] > f32::new(MASKED_SCORE) {
+                    prob = f32::exp(row_scores[c] - new_max);
+                }
+                block_sum += prob;
+
+                let index = stage_index::<S>(row, col, Ident::Lhs, probs_config);
+                probs_stage.smem[index] = Line::new(ES::cast_from(prob));
+            }
+
+            let block_sum = subcube_sum(block_sum);
+
+            if UNIT_POS_X == 0 {
+                let correction = f32::exp(prev_max - new_max);
+                self.row_max[row] = new_max;
+                self.row_sum[row] = self.row_sum[row] * correction + block_sum;
+                self.row_scale[row] = correction;
+            }
+        }
+    }
+}
+
+#[cube]
+/// Normalizes the accumulated values by the sum of the probabilities of their row and
+/// writes them to the output
+fn write_output<E: Float, S: stage::Config>(
+    out: &mut Tensor<Line<E>>,
+    output: &OutputAccumulator,
+    softmax: &SoftmaxState,
+    q_offset: u32,
+    #[comptime] config: Config<S>,
+) {
+    let value_config = config.value_config();
+    let stage_dim = value_config.stage_dim(Ident::Out);
+    let plane_dim = config.plane_dim();
+    let num_unit_writes = comptime!(stage_dim.tile_num_elements() / plane_dim);
+
+    let seq_q = out.shape(2);
+    let out_offset = CUBE_POS_Z * out.stride(0) + CUBE_POS_Y * out.stride(1);
+
+    for tile in 0..stage_dim.num_tiles_y {
+        for i in 0..num_unit_writes {
+            let index = UNIT_POS_X + i * plane_dim;
+            let row = UNIT_POS_Y * stage_dim.tile_size_x + index / stage_dim.tile_size_y;
+            let col = tile * stage_dim.tile_size_y + index % stage_dim.tile_size_y;
+            let q_index = q_offset + row;
+
+            if q_index < seq_q {
+                let sum = softmax.row_sum[row];
+                let mut result = 0.0;
+                if sum > 0.0 {
+                    result = output.values[tile * num_unit_writes + i] / sum;
+                }
+
+                out[out_offset + q_index * out.stride(2) + col] = Line::new(E::cast_from(result));
+            }
+        }
+    }
+}
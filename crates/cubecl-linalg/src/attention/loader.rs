@@ -0,0 +1,116 @@
+use crate::matmul::components::global::{homogeneous, Config as _};
+use crate::matmul::components::stage::{
+    self, Stage, TilingOrder, TilingOrderConfig, XMajorTiling, YMajorTiling,
+};
+use crate::matmul::components::{Ident, MatrixLayout};
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[cube]
+/// Loads a block of a `[batch, heads, seq, head_dim]` tensor to the stage, cycling over the
+/// stage positions with all units of the cube.
+///
+/// The sequence is along the rows of row major stages and along the columns of column major
+/// stages, so that the head dimension is always contiguous. Positions past the end of the
+/// sequence are filled with zeros.
+pub(crate) fn load_block<E: Numeric, ES: Numeric, S: stage::Config>(
+    tensor: &Tensor<Line<E>>,
+    stage: &mut Stage<ES>,
+    head_offset: u32,
+    seq_offset: u32,
+    #[comptime] ident: Ident,
+    #[comptime] config: homogeneous::Config<S>,
+) {
+    let stage_dim = config.stage_dim(ident);
+    let line_size = config.global_line_size(ident);
+
+    let num_stage_elements = stage_dim.num_elements();
+    let total_units = comptime!(config.num_planes() * config.plane_dim());
+    let jump_length = comptime!(total_units * line_size);
+    let num_loads_per_unit = num_stage_elements / jump_length;
+
+    #[allow(clippy::all)]
+    let _ = comptime!(check_jump_divides_well(num_stage_elements, jump_length));
+
+    let unit_id = UNIT_POS_Y * config.plane_dim() + UNIT_POS_X;
+    let seq_len = tensor.shape(2);
+    let seq_stride = tensor.stride(2);
+
+    let tile_num_elements = stage_dim.tile_num_elements();
+    let tile_size_x = stage_dim.tile_size_x;
+    let tile_size_y = stage_dim.tile_size_y;
+
+    for i in 0..num_loads_per_unit {
+        let unit_position = unit_id * line_size + i * jump_length;
+
+        let nth_tile = unit_position / tile_num_elements;
+        let pos_within_tile = unit_position % tile_num_elements;
+
+        let (tile_x, tile_y) = match config.tiling_order() {
+            TilingOrderConfig::XMajor => {
+                XMajorTiling::to_x_y(nth_tile, stage_dim.num_tiles_x, stage_dim.num_tiles_y)
+            }
+            TilingOrderConfig::YMajor => {
+                YMajorTiling::to_x_y(nth_tile, stage_dim.num_tiles_x, stage_dim.num_tiles_y)
+            }
+        };
+
+        let (seq, dim) = match config.layout(ident) {
+            MatrixLayout::RowMajor => (
+                tile_x * tile_size_x + pos_within_tile / tile_size_y,
+                tile_y * tile_size_y + pos_within_tile % tile_size_y,
+            ),
+            MatrixLayout::ColMajor => (
+                tile_y * tile_size_y + pos_within_tile / tile_size_x,
+                tile_x * tile_size_x + pos_within_tile % tile_size_x,
+            ),
+        };
+
+        let seq = seq_offset + seq;
+        let mut line = Line::empty(line_size).fill(E::from_int(0));
+        if seq < seq_len {
+            line = tensor[(head_offset + seq * seq_stride + dim) / line_size];
+        }
+
+        stage.smem[unit_position / line_size] = Line::cast_from(line);
+    }
+}
+
+#[cube]
+/// Returns the index in the stage of the element at row `x` and column `y`
+pub(crate) fn stage_index<S: stage::Config>(
+    x: u32,
+    y: u32,
+    #[comptime] ident: Ident,
+    #[comptime] config: S,
+) -> u32 {
+    let stage_dim = config.stage_dim(ident);
+    let tile_size_x = stage_dim.tile_size_x;
+    let tile_size_y = stage_dim.tile_size_y;
+
+    let tile_x = x / tile_size_x;
+    let tile_y = y / tile_size_y;
+    let nth_tile = match config.tiling_order() {
+        TilingOrderConfig::XMajor => {
+            XMajorTiling::to_nth_tile(tile_x, tile_y, stage_dim.num_tiles_x, stage_dim.num_tiles_y)
+        }
+        TilingOrderConfig::YMajor => {
+            YMajorTiling::to_nth_tile(tile_x, tile_y, stage_dim.num_tiles_x, stage_dim.num_tiles_y)
+        }
+    };
+
+    let pos_within_tile = match config.layout(ident) {
+        MatrixLayout::RowMajor => (x % tile_size_x) * tile_size_y + y % tile_size_y,
+        MatrixLayout::ColMajor => (y % tile_size_y) * tile_size_x + x % tile_size_x,
+    };
+
+    nth_tile * stage_dim.tile_num_elements() + pos_within_tile
+}
+
+fn check_jump_divides_well(num_stage_elements: u32, jump_length: u32) {
+    assert!(
+        num_stage_elements % jump_length == 0,
+        "Too many data will be loaded, resulting in out of bounds.
+        Try setting line size and number of planes so that jump_length divides num_stage_elements."
+    );
+}
@@ -0,0 +1,13 @@
+mod base;
+mod config;
+mod kernel;
+mod loader;
+mod writer;
+
+/// Tests for attention kernels
+#[cfg(feature = "export_tests")]
+pub mod tests;
+
+pub use base::*;
+pub use config::Config;
+pub(crate) use kernel::attention_kernel;
@@ -0,0 +1,273 @@
+#![allow(missing_docs)]
+
+use std::fmt::Display;
+
+use cubecl_core::{prelude::*, CubeElement};
+
+use crate::{
+    attention::{self, AttentionOptions},
+    matmul::tests::test_utils::{
+        assert_equals_approx, create_tensor, generate_random_data, should_skip, to_f32,
+    },
+};
+
+/// An attention of a `[batch, heads, seq_q, head_dim]` query with `[batch, heads, seq_kv,
+/// head_dim]` keys and values.
+struct AttentionTestCase {
+    batch_size: usize,
+    num_heads: usize,
+    seq_q: usize,
+    seq_kv: usize,
+    head_dim: usize,
+    causal: bool,
+    mask_shape: Option<[usize; 4]>,
+    disable_cmma: bool,
+}
+
+impl AttentionTestCase {
+    fn query_shape(&self) -> Vec<usize> {
+        vec![self.batch_size, self.num_heads, self.seq_q, self.head_dim]
+    }
+
+    fn kv_shape(&self) -> Vec<usize> {
+        vec![self.batch_size, self.num_heads, self.seq_kv, self.head_dim]
+    }
+
+    /// Naive attention, materializing the scores of each query.
+    fn reference(&self, query: &[f32], key: &[f32], value: &[f32], mask: &[f32]) -> Vec<f32> {
+        let scale = 1.0 / (self.head_dim as f32).sqrt();
+        let mut output = Vec::new();
+
+        for b in 0..self.batch_size {
+            for h in 0..self.num_heads {
+                let head = b * self.num_heads + h;
+
+                for q in 0..self.seq_q {
+                    let query_row = &query[(head * self.seq_q + q) * self.head_dim..];
+                    let mut scores = Vec::new();
+
+                    for k in 0..self.seq_kv {
+                        if self.causal && k > q {
+                            scores.push(f32::NEG_INFINITY);
+                            continue;
+                        }
+
+                        let key_row = &key[(head * self.seq_kv + k) * self.head_dim..];
+                        let mut score = (0..self.head_dim)
+                            .map(|d| query_row[d] * key_row[d])
+                            .sum::<f32>()
+                            * scale;
+
+                        if let Some(mask_shape) = self.mask_shape {
+                            score += mask[self.mask_index(mask_shape, [b, h, q, k])];
+                        }
+                        scores.push(score);
+                    }
+
+                    let max = scores.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+                    let probs: Vec<f32> = scores.iter().map(|s| (s - max).exp()).collect();
+                    let sum: f32 = probs.iter().sum();
+
+                    for d in 0..self.head_dim {
+                        let weighted = (0..self.seq_kv)
+                            .map(|k| probs[k] * value[(head * self.seq_kv + k) * self.head_dim + d])
+                            .sum::<f32>();
+                        output.push(weighted / sum);
+                    }
+                }
+            }
+        }
+
+        output
+    }
+
+    /// Index of the broadcast mask element at the given position of the scores
+    fn mask_index(&self, mask_shape: [usize; 4], position: [usize; 4]) -> usize {
+        (0..4).fold(0, |index, dim| {
+            let position = match mask_shape[dim] {
+                1 => 0,
+                _ => position[dim],
+            };
+            index * mask_shape[dim] + position
+        })
+    }
+}
+
+fn test_attention_case<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+    case: AttentionTestCase,
+) {
+    let client = R::client(device);
+    if should_skip::<R, F>(&client) {
+        return;
+    }
+
+    let query_shape = case.query_shape();
+    let kv_shape = case.kv_shape();
+    let query_data = generate_random_data::<F>(query_shape.iter().product());
+    let key_data = generate_random_data::<F>(kv_shape.iter().product());
+    let value_data = generate_random_data::<F>(kv_shape.iter().product());
+    let mask_data = match case.mask_shape {
+        Some(mask_shape) => generate_random_data::<F>(mask_shape.iter().product()),
+        None => Vec::new(),
+    };
+
+    let query = create_tensor::<R, F>(&client, query_shape.clone(), &query_data);
+    let key = create_tensor::<R, F>(&client, kv_shape.clone(), &key_data);
+    let value = create_tensor::<R, F>(&client, kv_shape, &value_data);
+    let mask = case
+        .mask_shape
+        .map(|mask_shape| create_tensor::<R, F>(&client, mask_shape.to_vec(), &mask_data));
+
+    let options = AttentionOptions {
+        causal: case.causal,
+        scale: None,
+    };
+    let output =
+        attention::launch::<R, F>(&client, query, key, value, mask, options, case.disable_cmma);
+
+    assert_eq!(output.shape, query_shape);
+
+    let expected: Vec<F> = case
+        .reference(
+            &to_f32(&query_data),
+            &to_f32(&key_data),
+            &to_f32(&value_data),
+            &to_f32(&mask_data),
+        )
+        .into_iter()
+        .map(F::new)
+        .collect();
+
+    // We cannot assume the inner precision of the matmuls, therefore we need a permissive epsilon
+    if let Err(e) = assert_equals_approx::<R, F>(&client, output.handle, &expected, 10e-2) {
+        panic!("{}", e);
+    }
+}
+
+pub fn test_attention_d64<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    test_attention_case::<R, F>(
+        device,
+        AttentionTestCase {
+            batch_size: 2,
+            num_heads: 3,
+            seq_q: 50,
+            seq_kv: 50,
+            head_dim: 64,
+            causal: false,
+            mask_shape: None,
+            disable_cmma: false,
+        },
+    );
+}
+
+pub fn test_attention_d128_causal<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    test_attention_case::<R, F>(
+        device,
+        AttentionTestCase {
+            batch_size: 1,
+            num_heads: 2,
+            seq_q: 70,
+            seq_kv: 70,
+            head_dim: 128,
+            causal: true,
+            mask_shape: None,
+            disable_cmma: false,
+        },
+    );
+}
+
+pub fn test_attention_broadcast_mask<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    test_attention_case::<R, F>(
+        device,
+        AttentionTestCase {
+            batch_size: 2,
+            num_heads: 2,
+            seq_q: 40,
+            seq_kv: 90,
+            head_dim: 64,
+            causal: false,
+            mask_shape: Some([2, 1, 40, 90]),
+            disable_cmma: false,
+        },
+    );
+}
+
+pub fn test_attention_plane_mma_causal_mask<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    test_attention_case::<R, F>(
+        device,
+        AttentionTestCase {
+            batch_size: 1,
+            num_heads: 2,
+            seq_q: 45,
+            seq_kv: 45,
+            head_dim: 128,
+            causal: true,
+            mask_shape: Some([1, 2, 45, 45]),
+            disable_cmma: true,
+        },
+    );
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_attention {
+    () => {
+        mod attention {
+            $crate::testgen_attention!(f32);
+        }
+    };
+    ($float:ident) => {
+            use super::*;
+            use cubecl_linalg::attention::tests;
+            use cubecl_core::flex32;
+
+            pub type FloatT = $float;
+
+            $crate::testgen_attention_ops!();
+    };
+    ([$($float:ident),*]) => {
+        mod attention {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$float _ty>] {
+                    use super::*;
+
+                    $crate::testgen_attention!($float);
+                })*
+            }
+        }
+    };
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_attention_ops {
+    () => {
+        #[test]
+        pub fn test_attention_d64() {
+            tests::test_attention_d64::<TestRuntime, FloatT>(&Default::default())
+        }
+
+        #[test]
+        pub fn test_attention_d128_causal() {
+            tests::test_attention_d128_causal::<TestRuntime, FloatT>(&Default::default())
+        }
+
+        #[test]
+        pub fn test_attention_broadcast_mask() {
+            tests::test_attention_broadcast_mask::<TestRuntime, FloatT>(&Default::default())
+        }
+
+        #[test]
+        pub fn test_attention_plane_mma_causal_mask() {
+            tests::test_attention_plane_mma_causal_mask::<TestRuntime, FloatT>(&Default::default())
+        }
+    };
+}
@@ -0,0 +1,93 @@
+use crate::matmul::components::global;
+use crate::matmul::components::stage::StageWriter;
+use crate::matmul::components::Ident;
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[derive(CubeType)]
+/// Stage writer copying the scores of a block to a row major shared memory
+pub struct ScoreWriter {
+    pub scores: SharedMemory<f32>,
+}
+
+#[derive(CubeType)]
+/// Stage writer accumulating the values of each block in the registers of the unit that
+/// owns them, after rescaling the previous blocks by the correction of their row
+pub struct OutputAccumulator {
+    pub values: Array<f32>,
+    pub row_scale: SharedMemory<f32>,
+}
+
+#[cube]
+impl ScoreWriter {
+    pub fn new(#[comptime] block_q: u32, #[comptime] block_kv: u32) -> ScoreWriter {
+        ScoreWriter {
+            scores: SharedMemory::new(block_q * block_kv),
+        }
+    }
+}
+
+#[cube]
+impl OutputAccumulator {
+    pub fn new<G: global::Config>(
+        row_scale: SharedMemory<f32>,
+        #[comptime] config: G,
+    ) -> OutputAccumulator {
+        let stage_dim = config.stage_dim(Ident::Out);
+        let num_values =
+            comptime!(stage_dim.tile_num_elements() / config.plane_dim() * stage_dim.num_tiles_y);
+
+        let mut values = Array::new(num_values);
+        for i in 0..num_values {
+            values[i] = 0.0;
+        }
+
+        OutputAccumulator { values, row_scale }
+    }
+}
+
+#[cube]
+impl StageWriter<f32> for ScoreWriter {
+    fn write<ES: Numeric, G: global::Config>(
+        this: &mut Self,
+        slice: &Slice<'_, Line<ES>>,
+        compute_plane_offset: u32,
+        accumulator_offset: u32,
+        #[comptime] config: G,
+    ) {
+        let stage_dim = config.stage_dim(Ident::Out);
+        let block_kv = stage_dim.num_elements_y_dim();
+        let num_unit_writes = comptime!(stage_dim.tile_num_elements() / config.plane_dim());
+
+        for i in 0..num_unit_writes {
+            let index = UNIT_POS_X + i * config.plane_dim();
+            let row = compute_plane_offset * stage_dim.tile_size_x + index / stage_dim.tile_size_y;
+            let col = accumulator_offset * stage_dim.tile_size_y + index % stage_dim.tile_size_y;
+
+            this.scores[row * block_kv + col] = f32::cast_from(slice[index][0]);
+        }
+    }
+}
+
+#[cube]
+impl StageWriter<f32> for OutputAccumulator {
+    fn write<ES: Numeric, G: global::Config>(
+        this: &mut Self,
+        slice: &Slice<'_, Line<ES>>,
+        compute_plane_offset: u32,
+        accumulator_offset: u32,
+        #[comptime] config: G,
+    ) {
+        let stage_dim = config.stage_dim(Ident::Out);
+        let num_unit_writes = comptime!(stage_dim.tile_num_elements() / config.plane_dim());
+
+        for i in 0..num_unit_writes {
+            let index = UNIT_POS_X + i * config.plane_dim();
+            let row = compute_plane_offset * stage_dim.tile_size_x + index / stage_dim.tile_size_y;
+            let value_index = accumulator_offset * num_unit_writes + i;
+
+            this.values[value_index] =
+                this.values[value_index] * this.row_scale[row] + f32::cast_from(slice[index][0]);
+        }
+    }
+}
@@ -0,0 +1,374 @@
+use std::marker::PhantomData;
+
+use cubecl_core::prelude::*;
+use cubecl_core::server::Handle;
+
+use crate::matmul::components::global::homogeneous;
+use crate::matmul::components::stage::{self, Matmul as _, TilingOrderConfig};
+use crate::matmul::components::tile::Matmul as _;
+use crate::matmul::components::{MatmulKernel, MatrixLayout};
+use crate::matmul::kernels::cmma_matmul::config::{create_stage_dim, CmmaGmmConfig, CmmaSmmConfig};
+use crate::matmul::kernels::cmma_matmul::dispatch::{
+    check_availability, CmmaLaunchDispatch, MatmulLaunchDispatch, PlaneMmaLaunchDispatch,
+};
+use crate::tensor::{into_contiguous, TensorHandle};
+
+use super::{implicit_gemm_kernel, Config, ConvProblem, ImplicitGemm};
+
+/// Options of a convolution over `D` spatial dimensions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ConvOptions<const D: usize> {
+    /// Step between two consecutive kernel positions.
+    pub stride: [usize; D],
+    /// Number of implicit zeros added on both sides of the input.
+    pub padding: [usize; D],
+    /// Spacing between the elements of the kernel.
+    pub dilation: [usize; D],
+    /// Number of groups the channels are split into, each group being convolved independently.
+    pub groups: usize,
+}
+
+impl<const D: usize> Default for ConvOptions<D> {
+    fn default() -> Self {
+        Self {
+            stride: [1; D],
+            padding: [0; D],
+            dilation: [1; D],
+            groups: 1,
+        }
+    }
+}
+
+/// 2D convolution of an input of shape `[batch, height, width, in_channels]` with a weight of
+/// shape `[out_channels, kernel_h, kernel_w, in_channels / groups]`, returning an output of shape
+/// `[batch, out_h, out_w, out_channels]`.
+///
+/// Cmma will be used if available, otherwise it will fall back on a non-cmma implementation.
+pub fn conv2d<R: Runtime, EG: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, EG>,
+    weight: TensorHandle<R, EG>,
+    options: ConvOptions<2>,
+) -> TensorHandle<R, EG> {
+    assert!(
+        input.shape.len() == 4 && weight.shape.len() == 4,
+        "Conv2d expects an input and a weight of rank 4, got shapes {:?} and {:?}",
+        input.shape,
+        weight.shape
+    );
+
+    let kernel_size = [weight.shape[1], weight.shape[2]];
+
+    launch(
+        client,
+        &input.handle,
+        [
+            input.shape[0],
+            input.shape[1],
+            input.shape[2],
+            input.shape[3],
+        ],
+        [
+            input.strides[0],
+            input.strides[1],
+            input.strides[2],
+            input.strides[3],
+        ],
+        weight,
+        kernel_size,
+        options,
+    )
+}
+
+/// 1D convolution of an input of shape `[batch, length, in_channels]` with a weight of shape
+/// `[out_channels, kernel_size, in_channels / groups]`, returning an output of shape
+/// `[batch, out_length, out_channels]`.
+///
+/// Cmma will be used if available, otherwise it will fall back on a non-cmma implementation.
+pub fn conv1d<R: Runtime, EG: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, EG>,
+    weight: TensorHandle<R, EG>,
+    options: ConvOptions<1>,
+) -> TensorHandle<R, EG> {
+    assert!(
+        input.shape.len() == 3 && weight.shape.len() == 3,
+        "Conv1d expects an input and a weight of rank 3, got shapes {:?} and {:?}",
+        input.shape,
+        weight.shape
+    );
+
+    // A 1D convolution is a 2D convolution with a height of 1.
+    let options = ConvOptions {
+        stride: [1, options.stride[0]],
+        padding: [0, options.padding[0]],
+        dilation: [1, options.dilation[0]],
+        groups: options.groups,
+    };
+
+    let kernel_size = [1, weight.shape[1]];
+
+    let output = launch(
+        client,
+        &input.handle,
+        [input.shape[0], 1, input.shape[1], input.shape[2]],
+        [
+            input.strides[0],
+            input.strides[0],
+            input.strides[1],
+            input.strides[2],
+        ],
+        weight,
+        kernel_size,
+        options,
+    );
+
+    TensorHandle::new_contiguous(
+        vec![output.shape[0], output.shape[2], output.shape[3]],
+        output.handle,
+    )
+}
+
+/// Launch the implicit GEMM convolution of an NHWC input described by its shape and strides.
+fn launch<R: Runtime, EG: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &Handle,
+    input_shape: [usize; 4],
+    input_strides: [usize; 4],
+    weight: TensorHandle<R, EG>,
+    kernel_size: [usize; 2],
+    options: ConvOptions<2>,
+) -> TensorHandle<R, EG> {
+    let [batch_size, in_h, in_w, in_channels] = input_shape;
+    let out_channels = weight.shape[0];
+
+    let mut problem = ConvProblem::<EG> {
+        batch_size,
+        in_shape: [in_h, in_w],
+        in_channels,
+        out_channels,
+        kernel_size,
+        options,
+        lhs_line_size: 1,
+        rhs_line_size: 1,
+        out_line_size: 1,
+        _element: PhantomData,
+    };
+    problem.check_options();
+
+    let groups = options.groups;
+    let weight_channels = weight.shape[weight.shape.len() - 1];
+    assert!(
+        weight_channels * groups == in_channels,
+        "The weight has {} input channels per group, but the input has {} channels for {} groups",
+        weight_channels,
+        in_channels,
+        groups
+    );
+
+    // The weight of each group is read as a column major (k, n) matrix.
+    let weight = match is_contiguous(&weight.shape, &weight.strides) {
+        true => weight,
+        false => into_contiguous::<R, EG>(client, weight.as_ref()),
+    };
+
+    let [out_h, out_w] = problem.out_shape();
+    let output = TensorHandle::empty(client, vec![batch_size, out_h, out_w, out_channels]);
+
+    let (m, n, k) = (problem.m(), problem.n(), problem.k());
+    let weight_shape = [groups, k, n];
+    let weight_strides = [n * k, 1, k];
+    let out_shape = [groups, m, n];
+    let out_strides = [n, out_channels, 1];
+
+    problem.lhs_line_size = line_size::<R>(in_channels / groups, &input_strides, 3);
+    problem.rhs_line_size = line_size::<R>(k, &weight_strides, 1);
+    problem.out_line_size = line_size::<R>(n, &out_strides, 2);
+
+    let args = unsafe {
+        ConvArgs {
+            input: TensorArg::<R>::from_raw_parts::<EG>(
+                input,
+                &input_strides,
+                &input_shape,
+                problem.lhs_line_size,
+            ),
+            weight: TensorArg::<R>::from_raw_parts::<EG>(
+                &weight.handle,
+                &weight_strides,
+                &weight_shape,
+                problem.rhs_line_size,
+            ),
+            out: TensorArg::<R>::from_raw_parts::<EG>(
+                &output.handle,
+                &out_strides,
+                &out_shape,
+                problem.out_line_size,
+            ),
+        }
+    };
+
+    if check_availability::<CmmaLaunchDispatch, R>(client).is_ok() {
+        launch_implicit_gemm::<R, EG, CmmaLaunchDispatch>(client, args, &problem);
+    } else {
+        launch_implicit_gemm::<R, EG, PlaneMmaLaunchDispatch>(client, args, &problem);
+    }
+
+    output
+}
+
+struct ConvArgs<'a, R: Runtime> {
+    input: TensorArg<'a, R>,
+    weight: TensorArg<'a, R>,
+    out: TensorArg<'a, R>,
+}
+
+type TileConfig<D> = <<D as MatmulLaunchDispatch>::TileMatmul as MatmulKernel<
+    <D as MatmulLaunchDispatch>::ElementInput,
+    <D as MatmulLaunchDispatch>::ElementAccumulator,
+>>::Config;
+
+type StageMatmul<D, EG> = stage::row_accumulate::Matmul<
+    <D as MatmulLaunchDispatch>::ElementInput,
+    EG,
+    <D as MatmulLaunchDispatch>::ElementAccumulator,
+    <D as MatmulLaunchDispatch>::TileMatmul,
+    <D as MatmulLaunchDispatch>::StageSize,
+>;
+
+fn launch_implicit_gemm<R: Runtime, EG: Numeric, D: MatmulLaunchDispatch>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    args: ConvArgs<'_, R>,
+    problem: &ConvProblem<EG>,
+) {
+    let cube_dim = D::cube_dim();
+    let cube_count = CubeCount::Static(
+        (problem.m() as u32).div_ceil(StageMatmul::<D, EG>::M),
+        (problem.n() as u32).div_ceil(StageMatmul::<D, EG>::N),
+        problem.options.groups as u32,
+    );
+
+    let config = make_conv_config::<EG, D>(problem, &cube_dim, &cube_count);
+    ImplicitGemm::<EG, D::ElementInput, StageMatmul<D, EG>>::check_config(config);
+
+    unsafe {
+        implicit_gemm_kernel::launch_unchecked::<EG, D::ElementInput, StageMatmul<D, EG>, R>(
+            client,
+            cube_count,
+            cube_dim,
+            args.input,
+            args.weight,
+            args.out,
+            config,
+        );
+    }
+}
+
+/// Make a config for the implicit GEMM convolution, given problem definition and cube settings
+fn make_conv_config<EG, D>(
+    problem: &ConvProblem<EG>,
+    cube_dim: &CubeDim,
+    cube_count: &CubeCount,
+) -> Config<CmmaSmmConfig<TileConfig<D>>>
+where
+    EG: Numeric,
+    D: MatmulLaunchDispatch,
+{
+    type Tmm<D> = <D as MatmulLaunchDispatch>::TileMatmul;
+
+    let (stage_m, stage_n, stage_k) = (
+        StageMatmul::<D, EG>::M,
+        StageMatmul::<D, EG>::N,
+        StageMatmul::<D, EG>::K,
+    );
+    let (lhs_stage_dim, rhs_stage_dim, out_stage_dim) = create_stage_dim(
+        stage_m,
+        stage_n,
+        stage_k,
+        Tmm::<D>::M,
+        Tmm::<D>::N,
+        Tmm::<D>::K,
+    );
+
+    let check_m_bounds = (problem.m() as u32) % stage_m != 0;
+    let check_n_bounds = (problem.n() as u32) % stage_n != 0;
+
+    let plane_dim = cube_dim.x;
+    let num_planes = cube_dim.y;
+
+    let (cube_count_x, cube_count_y, cube_count_z) = if let CubeCount::Static(x, y, z) = cube_count
+    {
+        (x, y, z)
+    } else {
+        panic!("Dynamic cube count unsupported")
+    };
+
+    let s = CmmaSmmConfig::new(
+        D::tile_config(
+            plane_dim,
+            MatrixLayout::RowMajor,
+            MatrixLayout::ColMajor,
+            problem.lhs_line_size as u32,
+            problem.rhs_line_size as u32,
+            problem.out_line_size as u32,
+        ),
+        lhs_stage_dim,
+        rhs_stage_dim,
+        out_stage_dim,
+        num_planes,
+        TilingOrderConfig::XMajor,
+    );
+    let g: CmmaGmmConfig<TileConfig<D>> = homogeneous::Config::new(
+        s,
+        check_m_bounds,
+        check_n_bounds,
+        MatrixLayout::RowMajor,
+        MatrixLayout::ColMajor,
+        problem.lhs_line_size as u32,
+        problem.rhs_line_size as u32,
+        problem.out_line_size as u32,
+    );
+    let c = Config::new(
+        g,
+        problem.params(),
+        *cube_count_x,
+        *cube_count_y,
+        *cube_count_z,
+    );
+    problem.check_config(&c);
+
+    c
+}
+
+fn is_contiguous(shape: &[usize], strides: &[usize]) -> bool {
+    let mut expected = 1;
+    for (size, stride) in shape.iter().zip(strides).rev() {
+        if *size != 1 && *stride != expected {
+            return false;
+        }
+        expected *= size;
+    }
+
+    true
+}
+
+/// The largest supported line size along `axis` that divides its `size` and every other stride.
+fn line_size<R: Runtime>(size: usize, strides: &[usize], axis: usize) -> u8 {
+    if strides[axis] != 1 {
+        return 1;
+    }
+
+    R::supported_line_sizes()
+        .iter()
+        .copied()
+        .filter(|line_size| {
+            let line_size = *line_size as usize;
+            size % line_size == 0
+                && strides
+                    .iter()
+                    .enumerate()
+                    .all(|(i, stride)| i == axis || stride % line_size == 0)
+        })
+        .max()
+        .unwrap_or(1)
+}
@@ -0,0 +1,151 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+use crate::matmul::components::global::{self, homogeneous, Config as _, Epilogue};
+use crate::matmul::components::stage::{self, TilingOrderConfig};
+use crate::matmul::components::MatmulConfig;
+use crate::matmul::components::{Ident, MatrixLayout, StageDim};
+
+#[derive(CubeType, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+/// Parameters of a 2D convolution, known at compile time.
+///
+/// A 1D convolution is a 2D convolution with a height of 1.
+pub struct ConvParams {
+    pub kernel_h: u32,
+    pub kernel_w: u32,
+    pub stride_h: u32,
+    pub stride_w: u32,
+    pub padding_h: u32,
+    pub padding_w: u32,
+    pub dilation_h: u32,
+    pub dilation_w: u32,
+    pub groups: u32,
+}
+
+impl ConvParams {
+    /// Returns the size of the output along an input dimension of the given size,
+    /// `dim` being 0 for the height and 1 for the width.
+    pub fn out_size(&self, in_size: u32, dim: u32) -> u32 {
+        let (kernel, stride, padding, dilation) = match dim {
+            0 => (
+                self.kernel_h,
+                self.stride_h,
+                self.padding_h,
+                self.dilation_h,
+            ),
+            _ => (
+                self.kernel_w,
+                self.stride_w,
+                self.padding_w,
+                self.dilation_w,
+            ),
+        };
+
+        (in_size + 2 * padding - dilation * (kernel - 1) - 1) / stride + 1
+    }
+}
+
+#[derive(CubeType, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+/// Configuration for the implicit GEMM convolution
+///
+/// Wraps the homogeneous global matmul config, with one cube per stage of the
+/// output and one cube per group along z.
+pub struct Config<S: stage::Config> {
+    gmm_config: homogeneous::Config<S>,
+    params: ConvParams,
+    cube_count_x: u32,
+    cube_count_y: u32,
+    cube_count_z: u32,
+}
+
+impl<S: stage::Config> global::Config for Config<S> {
+    type SmmConfig = S;
+
+    fn to_smm_config(&self) -> Self::SmmConfig {
+        self.gmm_config.to_smm_config()
+    }
+
+    fn global_line_size(&self, ident: Ident) -> u32 {
+        self.gmm_config.global_line_size(ident)
+    }
+
+    fn stage_line_size(&self, ident: Ident) -> u32 {
+        self.gmm_config.stage_line_size(ident)
+    }
+
+    fn stage_dim(&self, ident: Ident) -> StageDim {
+        self.gmm_config.stage_dim(ident)
+    }
+
+    fn layout(&self, ident: Ident) -> MatrixLayout {
+        self.gmm_config.layout(ident)
+    }
+
+    fn num_planes(&self) -> u32 {
+        self.gmm_config.num_planes()
+    }
+
+    fn plane_dim(&self) -> u32 {
+        self.gmm_config.plane_dim()
+    }
+
+    fn tiling_order(&self) -> TilingOrderConfig {
+        self.gmm_config.tiling_order()
+    }
+
+    fn check_m_bounds(&self) -> bool {
+        self.gmm_config.check_m_bounds()
+    }
+
+    fn check_n_bounds(&self) -> bool {
+        self.gmm_config.check_n_bounds()
+    }
+
+    fn transpose_load(&self, ident: Ident) -> bool {
+        self.gmm_config.transpose_load(ident)
+    }
+
+    fn epilogue(&self) -> Epilogue {
+        self.gmm_config.epilogue()
+    }
+}
+
+impl<S: stage::Config> MatmulConfig for Config<S> {}
+
+impl<S: stage::Config> Config<S> {
+    pub fn new(
+        gmm_config: homogeneous::Config<S>,
+        params: ConvParams,
+        cube_count_x: u32,
+        cube_count_y: u32,
+        cube_count_z: u32,
+    ) -> Self {
+        Self {
+            gmm_config,
+            params,
+            cube_count_x,
+            cube_count_y,
+            cube_count_z,
+        }
+    }
+
+    /// Returns the parameters of the convolution
+    pub fn params(&self) -> ConvParams {
+        self.params
+    }
+
+    /// Returns the largest m, i.e. number of output pixels, these configs can handle
+    pub fn max_m(&self) -> u32 {
+        self.cube_count_x * self.stage_dim(Ident::Out).num_elements_x_dim()
+    }
+
+    /// Returns the largest n, i.e. number of output channels per group, these configs can handle
+    pub fn max_n(&self) -> u32 {
+        self.cube_count_y * self.stage_dim(Ident::Out).num_elements_y_dim()
+    }
+
+    /// Returns the largest number of groups these configs can handle
+    pub fn max_groups(&self) -> u32 {
+        self.cube_count_z
+    }
+}
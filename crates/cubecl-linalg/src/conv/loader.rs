@@ -0,0 +1,273 @@
+use crate::matmul::components::global::{self, Config as _, Loader};
+use crate::matmul::components::stage::{
+    self, LhsReader, Stage, TilingOrder, TilingOrderConfig, XMajorTiling, YMajorTiling,
+};
+use crate::matmul::components::PlaneMapper;
+use crate::matmul::components::{Ident, MatrixLayout};
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+use super::{Config, ConvParams};
+
+#[derive(CubeType)]
+/// A view of an NHWC input as the lhs matrix of the implicit GEMM of one group,
+/// with one row per output pixel and one column per kernel position and input channel.
+///
+/// The matrix is never materialized: coordinates are mapped back to the input on the fly,
+/// reading zeros for padding and out-of-bounds positions.
+pub struct Im2colReader<E: Numeric> {
+    pub tensor: *const Tensor<Line<E>>,
+    pub m_offset: u32,
+    pub k_offset: u32,
+    pub channel_offset: u32,
+    pub stride_batch: u32,
+    pub stride_y: u32,
+    pub stride_x: u32,
+    pub stride_channel: u32,
+    pub shape_y: u32,
+    pub shape_x: u32,
+    pub shape_channel: u32,
+    pub out_h: u32,
+    pub out_w: u32,
+    pub shape_m: u32,
+    pub shape_k: u32,
+    pub params: ConvParams,
+}
+
+unsafe impl<E: Numeric> Sync for Im2colReader<E> {}
+unsafe impl<E: Numeric> Send for Im2colReader<E> {}
+
+#[cube]
+impl<EG: Numeric> Im2colReader<EG> {
+    /// Instantiate a read view over the channels of the given group of the input
+    pub fn new<S: stage::Config>(
+        tensor: &Tensor<Line<EG>>,
+        m_offset: u32,
+        k_offset: u32,
+        group: u32,
+        #[comptime] config: Config<S>,
+    ) -> Self {
+        let params = config.params();
+        let shape_y = tensor.shape(1);
+        let shape_x = tensor.shape(2);
+        let shape_channel = tensor.shape(3) / params.groups;
+
+        let out_h =
+            (shape_y + 2 * params.padding_h - params.dilation_h * (params.kernel_h - 1) - 1)
+                / params.stride_h
+                + 1;
+        let out_w =
+            (shape_x + 2 * params.padding_w - params.dilation_w * (params.kernel_w - 1) - 1)
+                / params.stride_w
+                + 1;
+
+        Im2colReader::<EG> {
+            tensor,
+            m_offset,
+            k_offset,
+            channel_offset: group * shape_channel,
+            stride_batch: tensor.stride(0),
+            stride_y: tensor.stride(1),
+            stride_x: tensor.stride(2),
+            stride_channel: tensor.stride(3),
+            shape_y,
+            shape_x,
+            shape_channel,
+            out_h,
+            out_w,
+            shape_m: tensor.shape(0) * out_h * out_w,
+            shape_k: params.kernel_h * params.kernel_w * shape_channel,
+            params: params.runtime(),
+        }
+    }
+
+    /// Advance the view along the k dimension by a specified offset, `k_offset`.
+    pub fn update_view(&mut self, k_offset: u32) {
+        self.k_offset += k_offset;
+    }
+
+    /// Reads data from the view at the specified tile coordinates (tile_x, tile_y).
+    ///
+    /// Each unit loads one line of consecutive input channels, which never crosses
+    /// a kernel position since the line size divides the channels of a group.
+    ///
+    /// # Note
+    ///
+    /// Padding and out-of-bounds reads will be translated to zeros.
+    pub fn load_coalesced<G: global::Config>(
+        &self,
+        tile_x: u32,
+        tile_y: u32,
+        unit_id: u32,
+        #[comptime] config: G,
+    ) -> Line<EG> {
+        let line_size = config.global_line_size(Ident::Lhs);
+        let tile_size_x = config.stage_dim(Ident::Lhs).tile_size_x;
+        let tile_size_y = config.stage_dim(Ident::Lhs).tile_size_y;
+
+        let view_m = tile_x * tile_size_x + self.m_offset + unit_id / tile_size_y;
+        let view_k = tile_y * tile_size_y + self.k_offset + unit_id % tile_size_y;
+
+        let out_x = view_m % self.out_w;
+        let out_y = view_m / self.out_w % self.out_h;
+        let batch = view_m / (self.out_w * self.out_h);
+
+        let channel = view_k % self.shape_channel;
+        let kernel_x = view_k / self.shape_channel % self.params.kernel_w;
+        let kernel_y = view_k / (self.shape_channel * self.params.kernel_w);
+
+        // Coordinates in the padded input, which can't underflow.
+        let padded_y = out_y * self.params.stride_h + kernel_y * self.params.dilation_h;
+        let padded_x = out_x * self.params.stride_w + kernel_x * self.params.dilation_w;
+
+        let in_bounds = view_m < self.shape_m
+            && view_k < self.shape_k
+            && padded_y >= self.params.padding_h
+            && padded_x >= self.params.padding_w
+            && padded_y - self.params.padding_h < self.shape_y
+            && padded_x - self.params.padding_w < self.shape_x;
+
+        let read_pos = (batch * self.stride_batch
+            + (padded_y - self.params.padding_h) * self.stride_y
+            + (padded_x - self.params.padding_w) * self.stride_x
+            + (self.channel_offset + channel) * self.stride_channel)
+            / line_size;
+
+        // Positions outside the input are never dereferenced.
+        let line_read = self.read(select(in_bounds, read_pos, 0));
+
+        select(
+            in_bounds,
+            line_read,
+            Line::empty(line_size).fill(EG::from_int(0)),
+        )
+    }
+
+    fn read(&self, position: u32) -> Line<EG> {
+        unsafe { *(*self.tensor).index_unchecked(position) }
+    }
+}
+
+#[derive(CubeType)]
+/// Loads the lhs stage of the implicit GEMM from an NHWC input, doing the im2col
+/// transformation while loading.
+pub struct Im2colLoader<EG: Numeric, ES: Numeric> {
+    pub tensor_view: Im2colReader<EG>,
+    pub stage: Stage<ES>,
+}
+
+#[cube]
+impl<EG: Numeric, ES: Numeric> Im2colLoader<EG, ES> {
+    pub fn new<S: stage::Config>(
+        tensor: &Tensor<Line<EG>>,
+        m_offset: u32,
+        k_offset: u32,
+        group: u32,
+        #[comptime] config: Config<S>,
+    ) -> Self {
+        let stage = Stage::new::<S>(Ident::Lhs, config.to_smm_config());
+        let tensor_view = Im2colReader::new::<S>(tensor, m_offset, k_offset, group, config);
+
+        Im2colLoader::<EG, ES> { tensor_view, stage }
+    }
+}
+
+#[cube]
+impl<EG: Numeric, ES: Numeric> Loader<EG, ES> for Im2colLoader<EG, ES> {
+    type StageReader = LhsReader<ES>;
+
+    fn fill_stage<G: global::Config>(this: &mut Self, #[comptime] config: G) -> Self::StageReader {
+        Im2colLoading::load_to_slice::<EG, ES, G>(
+            &this.tensor_view,
+            this.stage.as_slice_mut(),
+            config,
+        );
+        LhsReader::new(this.stage)
+    }
+
+    fn advance_view(this: &mut Self, k_offset: u32) {
+        this.tensor_view.update_view(k_offset);
+    }
+}
+
+#[derive(CubeType, Clone, Copy)]
+/// Loads the content of all tiles of the im2col view using all planes,
+/// iterating with steps determined by the plane's dimension.
+pub struct Im2colLoading {}
+
+#[cube]
+impl PlaneMapper for Im2colLoading {
+    fn plane_id() -> u32 {
+        UNIT_POS_Y
+    }
+
+    fn plane_unit() -> u32 {
+        UNIT_POS_X
+    }
+}
+
+#[cube]
+impl Im2colLoading {
+    pub fn load_to_slice<EG: Numeric, ES: Numeric, G: global::Config>(
+        read_view: &Im2colReader<EG>,
+        slice: &mut SliceMut<'_, Line<ES>>,
+        #[comptime] config: G,
+    ) {
+        let stage_dim = config.stage_dim(Ident::Lhs);
+        let line_size = config.global_line_size(Ident::Lhs);
+
+        let num_stage_elements = stage_dim.num_elements();
+        let total_units = comptime!(config.num_planes() * config.plane_dim());
+        let jump_length = comptime!(total_units * line_size);
+        let num_loads_per_unit = num_stage_elements / jump_length;
+
+        #[allow(clippy::all)]
+        let _ = comptime!(check_layouts(
+            config.layout(Ident::Lhs),
+            config.transpose_load(Ident::Lhs),
+            num_stage_elements,
+            jump_length
+        ));
+
+        let unit_id = Self::plane_id() * config.plane_dim() + Self::plane_unit();
+        let unit_position_base = unit_id * line_size;
+
+        for i in 0..num_loads_per_unit {
+            let unit_position = unit_position_base + i * jump_length;
+
+            let tile_num_elements = stage_dim.tile_num_elements();
+            let nth_tile = unit_position / tile_num_elements;
+            let pos_within_tile = unit_position % tile_num_elements;
+
+            let (tile_x, tile_y) = match config.tiling_order() {
+                TilingOrderConfig::XMajor => {
+                    XMajorTiling::to_x_y(nth_tile, stage_dim.num_tiles_x, stage_dim.num_tiles_y)
+                }
+                TilingOrderConfig::YMajor => {
+                    YMajorTiling::to_x_y(nth_tile, stage_dim.num_tiles_x, stage_dim.num_tiles_y)
+                }
+            };
+
+            let line_read = read_view.load_coalesced::<G>(tile_x, tile_y, pos_within_tile, config);
+
+            slice[unit_position / line_size] = Line::cast_from(line_read);
+        }
+    }
+}
+
+fn check_layouts(
+    layout: MatrixLayout,
+    transpose_load: bool,
+    num_stage_elements: u32,
+    jump_length: u32,
+) {
+    assert!(
+        layout == MatrixLayout::RowMajor && !transpose_load,
+        "The im2col view is row major and can't be transposed while loading."
+    );
+    assert!(
+        num_stage_elements % jump_length == 0,
+        "Too many data will be loaded, resulting in out of bounds.
+        Try setting line size and number of planes so that jump_length divides num_stage_elements."
+    );
+}
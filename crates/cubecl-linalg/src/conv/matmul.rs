@@ -0,0 +1,132 @@
+use crate::matmul::components::global::{self, tensor_view, Config as _, Loader, Matmul as _};
+use crate::matmul::components::stage::{self, LhsReader, RhsReader};
+use crate::matmul::components::{Ident, MatmulKernel};
+
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+use std::marker::PhantomData;
+
+use super::{Config, Im2colLoader};
+
+/// Performs a convolution as a matrix multiplication at the global level, where the lhs is
+/// the im2col view of the input, the rhs is the weight and the output is NHWC.
+///
+/// Like the homogeneous global matmul, all planes load data to the stage and all planes are
+/// used in the stage matmul computation.
+pub struct ImplicitGemm<
+    EG: Numeric,
+    ES: Numeric,
+    SMM: stage::Matmul<ES, EG, LhsReader<ES>, RhsReader<ES>>,
+> {
+    _eg: PhantomData<EG>,
+    _es: PhantomData<ES>,
+    _stage_matmul: PhantomData<SMM>,
+}
+
+#[cube]
+impl<EG, ES, SMM>
+    global::Matmul<
+        EG,
+        EG,
+        ES,
+        Im2colLoader<EG, ES>,
+        tensor_view::RhsLoader<EG, ES>,
+        tensor_view::Unloader<EG>,
+    > for ImplicitGemm<EG, ES, SMM>
+where
+    EG: Numeric,
+    ES: Numeric,
+    SMM: stage::Matmul<ES, EG, LhsReader<ES>, RhsReader<ES>>,
+{
+    fn execute(
+        mut lhs_loader: Im2colLoader<EG, ES>,
+        mut rhs_loader: tensor_view::RhsLoader<EG, ES>,
+        mut out_unloader: tensor_view::Unloader<EG>,
+        k_range: (u32, u32),
+        #[comptime] config: Self::Config,
+    ) {
+        let k_step = SMM::K;
+        let range = k_range.1 - k_range.0;
+        // The kernel always has at least one element per output channel.
+        let num_loops = (range - 1) / k_step + 1;
+
+        let mut acc = SMM::acc_init_zeros(config.to_smm_config());
+
+        for _ in 0..num_loops {
+            let lhs_stage_reader =
+                &Im2colLoader::fill_stage::<Self::Config>(&mut lhs_loader, config);
+            let rhs_stage_reader =
+                &tensor_view::RhsLoader::fill_stage::<Self::Config>(&mut rhs_loader, config);
+
+            sync_units();
+
+            SMM::execute(
+                lhs_stage_reader,
+                rhs_stage_reader,
+                &mut acc,
+                config.to_smm_config(),
+            );
+
+            sync_units();
+
+            Im2colLoader::advance_view(&mut lhs_loader, k_step);
+            tensor_view::RhsLoader::advance_view(&mut rhs_loader, k_step);
+        }
+
+        SMM::acc_read::<tensor_view::Unloader<EG>, Self::Config>(
+            &acc,
+            &mut out_unloader,
+            config.to_smm_config(),
+            config,
+        );
+    }
+}
+
+impl<EG, ES, SMM> MatmulKernel<EG, EG> for ImplicitGemm<EG, ES, SMM>
+where
+    EG: Numeric,
+    ES: Numeric,
+    SMM: stage::Matmul<ES, EG, LhsReader<ES>, RhsReader<ES>>,
+{
+    type Config = Config<SMM::Config>;
+
+    fn check_config(config: Self::Config) {
+        SMM::check_config(config.to_smm_config());
+    }
+}
+
+/// Convolves an NHWC input with a weight viewed as `[groups, k, n]`, writing to an output
+/// viewed as `[groups, m, n]`, with one cube per stage of the output of a group.
+#[cube(launch_unchecked)]
+pub(crate) fn implicit_gemm_kernel<
+    EG: Numeric,
+    ES: Numeric,
+    SMM: stage::Matmul<ES, EG, LhsReader<ES>, RhsReader<ES>>,
+>(
+    input: &Tensor<Line<EG>>,
+    weight: &Tensor<Line<EG>>,
+    out: &mut Tensor<Line<EG>>,
+    #[comptime] config: Config<SMM::Config>,
+) {
+    let m_offset = CUBE_POS_X * config.stage_dim(Ident::Lhs).num_elements_x_dim();
+    let n_offset = CUBE_POS_Y * config.stage_dim(Ident::Rhs).num_elements_y_dim();
+    let group = CUBE_POS_Z;
+    let k_range = (0, weight.shape(1));
+
+    let weight_offset = tensor_view::batch_offset(weight, out, group);
+    let out_offset = tensor_view::batch_offset(out, out, group);
+
+    ImplicitGemm::<EG, ES, SMM>::execute(
+        Im2colLoader::new::<SMM::Config>(input, m_offset, k_range.0, group, config),
+        tensor_view::RhsLoader::new::<Config<SMM::Config>>(
+            weight,
+            k_range.0,
+            n_offset,
+            weight_offset,
+            config,
+        ),
+        tensor_view::Unloader::new(out, m_offset, n_offset, out_offset),
+        k_range,
+        config,
+    );
+}
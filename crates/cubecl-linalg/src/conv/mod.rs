@@ -0,0 +1,16 @@
+mod base;
+mod config;
+mod loader;
+mod matmul;
+mod problem;
+
+/// Tests for convolution kernels
+#[cfg(feature = "export_tests")]
+pub mod tests;
+
+pub use base::*;
+pub use config::{Config, ConvParams};
+pub use loader::{Im2colLoader, Im2colReader};
+pub(crate) use matmul::implicit_gemm_kernel;
+pub use matmul::ImplicitGemm;
+pub use problem::ConvProblem;
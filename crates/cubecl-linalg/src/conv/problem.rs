@@ -0,0 +1,179 @@
+use std::marker::PhantomData;
+
+use cubecl_core::prelude::Numeric;
+
+use crate::matmul::components::stage;
+
+use super::{Config, ConvOptions, ConvParams};
+
+#[derive(Clone)]
+/// Description of a 2D convolution problem to solve, regardless of actual data
+///
+/// The input is `[batch_size, height, width, in_channels]`, the weight is
+/// `[out_channels, kernel_h, kernel_w, in_channels / groups]` and the output is
+/// `[batch_size, out_h, out_w, out_channels]`.
+///
+/// Each group is solved as a matrix multiplication of size (m, k) · (k, n) = (m, n).
+pub struct ConvProblem<EG: Numeric> {
+    pub batch_size: usize,
+    pub in_shape: [usize; 2],
+    pub in_channels: usize,
+    pub out_channels: usize,
+    pub kernel_size: [usize; 2],
+    pub options: ConvOptions<2>,
+    pub lhs_line_size: u8,
+    pub rhs_line_size: u8,
+    pub out_line_size: u8,
+    pub _element: PhantomData<EG>,
+}
+
+impl<EG: Numeric> ConvProblem<EG> {
+    /// Returns the height and width of the output
+    pub fn out_shape(&self) -> [usize; 2] {
+        [0, 1].map(|dim| {
+            let dilated_kernel = self.options.dilation[dim] * (self.kernel_size[dim] - 1) + 1;
+            (self.in_shape[dim] + 2 * self.options.padding[dim] - dilated_kernel)
+                / self.options.stride[dim]
+                + 1
+        })
+    }
+
+    /// Returns the number of rows of each matrix multiplication, i.e. the number of output pixels
+    pub fn m(&self) -> usize {
+        let [out_h, out_w] = self.out_shape();
+        self.batch_size * out_h * out_w
+    }
+
+    /// Returns the number of columns of each matrix multiplication, i.e. the number of output
+    /// channels per group
+    pub fn n(&self) -> usize {
+        self.out_channels / self.options.groups
+    }
+
+    /// Returns the reduced dimension of each matrix multiplication, i.e. the number of kernel
+    /// elements per output channel
+    pub fn k(&self) -> usize {
+        self.kernel_size[0] * self.kernel_size[1] * self.in_channels / self.options.groups
+    }
+
+    /// Returns the parameters of the convolution to compile in the kernel
+    pub(crate) fn params(&self) -> ConvParams {
+        ConvParams {
+            kernel_h: self.kernel_size[0] as u32,
+            kernel_w: self.kernel_size[1] as u32,
+            stride_h: self.options.stride[0] as u32,
+            stride_w: self.options.stride[1] as u32,
+            padding_h: self.options.padding[0] as u32,
+            padding_w: self.options.padding[1] as u32,
+            dilation_h: self.options.dilation[0] as u32,
+            dilation_w: self.options.dilation[1] as u32,
+            groups: self.options.groups as u32,
+        }
+    }
+
+    /// Asserts that the options of the convolution are consistent with its shapes
+    ///
+    /// # Panics:
+    ///
+    ///  - If the groups don't divide the input and output channels
+    ///  - If a stride, dilation, kernel size or number of channels is zero
+    ///  - If the dilated kernel is larger than the padded input
+    pub(crate) fn check_options(&self) {
+        let groups = self.options.groups;
+
+        assert!(
+            self.in_channels > 0 && self.out_channels > 0,
+            "Problem must have channels, got in_channels={} and out_channels={}",
+            self.in_channels,
+            self.out_channels
+        );
+        assert!(
+            groups > 0 && self.in_channels % groups == 0 && self.out_channels % groups == 0,
+            "Problem has {} groups, which must divide in_channels={} and out_channels={}",
+            groups,
+            self.in_channels,
+            self.out_channels
+        );
+
+        for dim in 0..2 {
+            assert!(
+                self.options.stride[dim] > 0 && self.options.dilation[dim] > 0,
+                "Stride and dilation must be positive, got stride={:?} and dilation={:?}",
+                self.options.stride,
+                self.options.dilation
+            );
+            assert!(
+                self.kernel_size[dim] > 0,
+                "Kernel size must be positive, got {:?}",
+                self.kernel_size
+            );
+
+            let dilated_kernel = self.options.dilation[dim] * (self.kernel_size[dim] - 1) + 1;
+            let padded_input = self.in_shape[dim] + 2 * self.options.padding[dim];
+            assert!(
+                dilated_kernel <= padded_input,
+                "Dilated kernel of size {} is larger than the padded input of size {} in dimension {}",
+                dilated_kernel,
+                padded_input,
+                dim
+            );
+        }
+    }
+
+    /// Asserts that the problem can be solved with the given configs
+    ///
+    /// # Panics:
+    ///
+    ///  - If the options are inconsistent with the shapes
+    ///  - If dimensions of the problem are larger than allowed by the config
+    ///  - If line sizes do not divide well the dimension in which they are aligned
+    pub(crate) fn check_config<S: stage::Config>(&self, config: &Config<S>) {
+        self.check_options();
+
+        assert!(
+            self.params() == config.params(),
+            "Problem has parameters {:?} but these configs were made for {:?}",
+            self.params(),
+            config.params()
+        );
+        assert!(
+            self.m() <= config.max_m() as usize,
+            "Problem has m={} but these configs can only have m<={}",
+            self.m(),
+            config.max_m()
+        );
+        assert!(
+            self.n() <= config.max_n() as usize,
+            "Problem has n={} but these configs can only have n<={}",
+            self.n(),
+            config.max_n()
+        );
+        assert!(
+            self.options.groups <= config.max_groups() as usize,
+            "Problem has {} groups but these configs can only have groups<={}",
+            self.options.groups,
+            config.max_groups()
+        );
+
+        // Lines of the input are read along the channels of a single group.
+        let channels_per_group = self.in_channels / self.options.groups;
+        assert!(
+            channels_per_group % (self.lhs_line_size as usize) == 0,
+            "in_channels per group ({}) must be a multiple of the lhs line size {}",
+            channels_per_group,
+            self.lhs_line_size
+        );
+        assert!(
+            self.k() % (self.rhs_line_size as usize) == 0,
+            "Problem has k={} which must be a multiple of the rhs line size {}",
+            self.k(),
+            self.rhs_line_size
+        );
+        assert!(
+            self.n() % (self.out_line_size as usize) == 0,
+            "Problem has n={} which must be a multiple of the out line size {}",
+            self.n(),
+            self.out_line_size
+        );
+    }
+}
@@ -0,0 +1,352 @@
+#![allow(missing_docs)]
+
+use std::fmt::Display;
+
+use cubecl_core::{prelude::*, CubeElement};
+
+use crate::{
+    conv::{self, ConvOptions},
+    matmul::tests::test_utils::{
+        assert_equals_approx, create_tensor, generate_random_data, should_skip, to_f32,
+    },
+};
+
+/// A 2D convolution with an NHWC input and an output channels first weight.
+struct ConvTestCase {
+    batch_size: usize,
+    in_shape: [usize; 2],
+    in_channels: usize,
+    out_channels: usize,
+    kernel_size: [usize; 2],
+    options: ConvOptions<2>,
+}
+
+impl ConvTestCase {
+    fn input_shape(&self) -> Vec<usize> {
+        vec![
+            self.batch_size,
+            self.in_shape[0],
+            self.in_shape[1],
+            self.in_channels,
+        ]
+    }
+
+    fn weight_shape(&self) -> Vec<usize> {
+        vec![
+            self.out_channels,
+            self.kernel_size[0],
+            self.kernel_size[1],
+            self.in_channels / self.options.groups,
+        ]
+    }
+
+    fn out_shape(&self) -> [usize; 2] {
+        [0, 1].map(|dim| {
+            let dilated_kernel = self.options.dilation[dim] * (self.kernel_size[dim] - 1) + 1;
+            (self.in_shape[dim] + 2 * self.options.padding[dim] - dilated_kernel)
+                / self.options.stride[dim]
+                + 1
+        })
+    }
+
+    /// Naive convolution, returning the NHWC output.
+    fn reference(&self, input: &[f32], weight: &[f32]) -> Vec<f32> {
+        let [in_h, in_w] = self.in_shape;
+        let [out_h, out_w] = self.out_shape();
+        let [kernel_h, kernel_w] = self.kernel_size;
+        let group_in = self.in_channels / self.options.groups;
+        let group_out = self.out_channels / self.options.groups;
+        let mut output = Vec::new();
+
+        for b in 0..self.batch_size {
+            for oy in 0..out_h {
+                for ox in 0..out_w {
+                    for oc in 0..self.out_channels {
+                        let group = oc / group_out;
+                        let mut sum = 0.0;
+
+                        for ky in 0..kernel_h {
+                            for kx in 0..kernel_w {
+                                let y = (oy * self.options.stride[0]
+                                    + ky * self.options.dilation[0])
+                                    as isize
+                                    - self.options.padding[0] as isize;
+                                let x = (ox * self.options.stride[1]
+                                    + kx * self.options.dilation[1])
+                                    as isize
+                                    - self.options.padding[1] as isize;
+
+                                if y < 0 || x < 0 || y >= in_h as isize || x >= in_w as isize {
+                                    continue;
+                                }
+
+                                for c in 0..group_in {
+                                    let input_index = ((b * in_h + y as usize) * in_w + x as usize)
+                                        * self.in_channels
+                                        + group * group_in
+                                        + c;
+                                    let weight_index =
+                                        ((oc * kernel_h + ky) * kernel_w + kx) * group_in + c;
+                                    sum += input[input_index] * weight[weight_index];
+                                }
+                            }
+                        }
+
+                        output.push(sum);
+                    }
+                }
+            }
+        }
+
+        output
+    }
+}
+
+fn test_conv2d_case<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+    case: ConvTestCase,
+) {
+    let client = R::client(device);
+    if should_skip::<R, F>(&client) {
+        return;
+    }
+
+    let input_shape = case.input_shape();
+    let weight_shape = case.weight_shape();
+    let input_data = generate_random_data::<F>(input_shape.iter().product());
+    let weight_data = generate_random_data::<F>(weight_shape.iter().product());
+    let input = create_tensor::<R, F>(&client, input_shape, &input_data);
+    let weight = create_tensor::<R, F>(&client, weight_shape, &weight_data);
+
+    let output = conv::conv2d::<R, F>(&client, input, weight, case.options);
+
+    let [out_h, out_w] = case.out_shape();
+    assert_eq!(
+        output.shape,
+        vec![case.batch_size, out_h, out_w, case.out_channels]
+    );
+
+    let expected: Vec<F> = case
+        .reference(&to_f32(&input_data), &to_f32(&weight_data))
+        .into_iter()
+        .map(F::new)
+        .collect();
+
+    // We cannot assume the inner precision of the matmul, therefore we need a permissive epsilon
+    if let Err(e) = assert_equals_approx::<R, F>(&client, output.handle, &expected, 10e-2) {
+        panic!("{}", e);
+    }
+}
+
+pub fn test_conv2d<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    test_conv2d_case::<R, F>(
+        device,
+        ConvTestCase {
+            batch_size: 2,
+            in_shape: [9, 7],
+            in_channels: 4,
+            out_channels: 8,
+            kernel_size: [3, 3],
+            options: ConvOptions::default(),
+        },
+    );
+}
+
+pub fn test_conv2d_stride_padding<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    test_conv2d_case::<R, F>(
+        device,
+        ConvTestCase {
+            batch_size: 3,
+            in_shape: [12, 11],
+            in_channels: 8,
+            out_channels: 20,
+            kernel_size: [3, 2],
+            options: ConvOptions {
+                stride: [2, 3],
+                padding: [1, 2],
+                ..Default::default()
+            },
+        },
+    );
+}
+
+pub fn test_conv2d_dilation<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    test_conv2d_case::<R, F>(
+        device,
+        ConvTestCase {
+            batch_size: 1,
+            in_shape: [16, 16],
+            in_channels: 3,
+            out_channels: 5,
+            kernel_size: [3, 3],
+            options: ConvOptions {
+                padding: [2, 1],
+                dilation: [2, 3],
+                ..Default::default()
+            },
+        },
+    );
+}
+
+pub fn test_conv2d_groups<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    test_conv2d_case::<R, F>(
+        device,
+        ConvTestCase {
+            batch_size: 2,
+            in_shape: [8, 8],
+            in_channels: 8,
+            out_channels: 12,
+            kernel_size: [3, 3],
+            options: ConvOptions {
+                padding: [1, 1],
+                groups: 2,
+                ..Default::default()
+            },
+        },
+    );
+}
+
+pub fn test_conv2d_depthwise<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    test_conv2d_case::<R, F>(
+        device,
+        ConvTestCase {
+            batch_size: 2,
+            in_shape: [10, 6],
+            in_channels: 6,
+            out_channels: 6,
+            kernel_size: [3, 3],
+            options: ConvOptions {
+                stride: [2, 1],
+                padding: [1, 1],
+                groups: 6,
+                ..Default::default()
+            },
+        },
+    );
+}
+
+pub fn test_conv1d<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let client = R::client(device);
+    if should_skip::<R, F>(&client) {
+        return;
+    }
+
+    let options = ConvOptions {
+        stride: [2],
+        padding: [3],
+        dilation: [2],
+        groups: 2,
+    };
+    // The same problem as a 2D convolution with a height of 1.
+    let case = ConvTestCase {
+        batch_size: 2,
+        in_shape: [1, 40],
+        in_channels: 8,
+        out_channels: 6,
+        kernel_size: [1, 4],
+        options: ConvOptions {
+            stride: [1, options.stride[0]],
+            padding: [0, options.padding[0]],
+            dilation: [1, options.dilation[0]],
+            groups: options.groups,
+        },
+    };
+
+    let input_shape = vec![case.batch_size, case.in_shape[1], case.in_channels];
+    let weight_shape = vec![
+        case.out_channels,
+        case.kernel_size[1],
+        case.in_channels / options.groups,
+    ];
+    let input_data = generate_random_data::<F>(input_shape.iter().product());
+    let weight_data = generate_random_data::<F>(weight_shape.iter().product());
+    let input = create_tensor::<R, F>(&client, input_shape, &input_data);
+    let weight = create_tensor::<R, F>(&client, weight_shape, &weight_data);
+
+    let output = conv::conv1d::<R, F>(&client, input, weight, options);
+
+    let [_, out_length] = case.out_shape();
+    assert_eq!(
+        output.shape,
+        vec![case.batch_size, out_length, case.out_channels]
+    );
+
+    let expected: Vec<F> = case
+        .reference(&to_f32(&input_data), &to_f32(&weight_data))
+        .into_iter()
+        .map(F::new)
+        .collect();
+
+    if let Err(e) = assert_equals_approx::<R, F>(&client, output.handle, &expected, 10e-2) {
+        panic!("{}", e);
+    }
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_conv {
+    () => {
+        mod conv {
+            $crate::testgen_conv!(f32);
+        }
+    };
+    ($float:ident) => {
+            use super::*;
+            use cubecl_linalg::conv::tests;
+            use cubecl_core::flex32;
+
+            pub type FloatT = $float;
+
+            $crate::testgen_conv_ops!();
+    };
+    ([$($float:ident),*]) => {
+        mod conv {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$float _ty>] {
+                    use super::*;
+
+                    $crate::testgen_conv!($float);
+                })*
+            }
+        }
+    };
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_conv_ops {
+    () => {
+        #[test]
+        pub fn test_conv2d() {
+            tests::test_conv2d::<TestRuntime, FloatT>(&Default::default())
+        }
+
+        #[test]
+        pub fn test_conv2d_stride_padding() {
+            tests::test_conv2d_stride_padding::<TestRuntime, FloatT>(&Default::default())
+        }
+
+        #[test]
+        pub fn test_conv2d_dilation() {
+            tests::test_conv2d_dilation::<TestRuntime, FloatT>(&Default::default())
+        }
+
+        #[test]
+        pub fn test_conv2d_groups() {
+            tests::test_conv2d_groups::<TestRuntime, FloatT>(&Default::default())
+        }
+
+        #[test]
+        pub fn test_conv2d_depthwise() {
+            tests::test_conv2d_depthwise::<TestRuntime, FloatT>(&Default::default())
+        }
+
+        #[test]
+        pub fn test_conv1d() {
+            tests::test_conv1d::<TestRuntime, FloatT>(&Default::default())
+        }
+    };
+}
@@ -0,0 +1,230 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise, CubeElement};
+
+use crate::tensor::TensorHandle;
+
+use super::bluestein;
+use super::real::{
+    hermitian_pack_kernel, real_extract_kernel, real_pack_kernel, real_unpack_kernel,
+};
+use super::stockham;
+
+/// The direction of a Fourier transform.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FftDirection {
+    /// The transform `X[k] = sum(x[j] * exp(-2πi * j * k / n))`.
+    #[default]
+    Forward,
+    /// The transform `x[j] = sum(X[k] * exp(2πi * j * k / n)) / n`, which undoes the forward
+    /// transform.
+    Inverse,
+}
+
+/// Transform the given axis of the complex input into a new contiguous tensor of the same shape.
+///
+/// Complex tensors have a last axis of size 2 holding the real and imaginary parts, which must
+/// be contiguous so that each value is read as a [Line] of size 2.
+pub fn fft<R: Runtime, F: Float + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, F>,
+    axis: usize,
+    direction: FftDirection,
+) -> TensorHandle<R, F> {
+    let output = TensorHandle::empty(client, input.shape.clone());
+
+    launch::<R, F>(client, input.as_ref(), output.as_ref(), axis, direction);
+
+    output
+}
+
+/// Transform the two last axes of the complex input into a new contiguous tensor of the same
+/// shape, the axes before them being batches.
+pub fn fft2<R: Runtime, F: Float + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, F>,
+    direction: FftDirection,
+) -> TensorHandle<R, F> {
+    let rank = input.shape.len();
+    assert!(
+        rank >= 3,
+        "A 2D transform requires a complex tensor of rank 3 or more, got {rank}"
+    );
+
+    let output = fft::<R, F>(client, input, rank - 2, direction);
+    fft::<R, F>(client, output, rank - 3, direction)
+}
+
+/// Transform the given axis of the complex input into the output, which must have the shape of
+/// the input.
+///
+/// Power of two sizes are transformed with radix 8, 4 and 2 Stockham stages, in shared memory
+/// when a row fits in a cube. Other sizes use Bluestein's algorithm on top of power of two
+/// transforms.
+pub fn launch<R: Runtime, F: Float + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    output: TensorHandleRef<'_, R>,
+    axis: usize,
+    direction: FftDirection,
+) {
+    check_complex("input", input.shape, input.strides);
+    check_complex("output", output.shape, output.strides);
+    assert!(
+        axis < input.shape.len() - 1,
+        "Can't transform axis {axis} of a complex tensor of rank {}",
+        input.shape.len()
+    );
+    assert_eq!(
+        input.shape, output.shape,
+        "The output shape doesn't match the input shape"
+    );
+
+    let size = input.shape[axis];
+    let num_elements: usize = input.shape.iter().product();
+    if num_elements == 0 {
+        return;
+    }
+
+    let inverse = direction == FftDirection::Inverse;
+    if size.is_power_of_two() {
+        stockham::launch::<R, F>(client, input, axis, output, axis, inverse);
+    } else {
+        bluestein::launch::<R, F>(client, input, output, axis, inverse);
+    }
+}
+
+/// Transform the given axis of the real input into a new complex tensor, keeping the
+/// `size / 2 + 1` first values of the spectrum since the others are their conjugates.
+///
+/// Even sizes are transformed as complex values of half their size, each packing two
+/// consecutive real values.
+pub fn rfft<R: Runtime, F: Float + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, F>,
+    axis: usize,
+) -> TensorHandle<R, F> {
+    assert!(
+        axis < input.shape.len(),
+        "Can't transform axis {axis} of a tensor of rank {}",
+        input.shape.len()
+    );
+
+    let size = input.shape[axis];
+    let mut shape = input.shape.clone();
+    shape[axis] = size / 2 + 1;
+    shape.push(2);
+    let output = TensorHandle::<R, F>::empty(client, shape);
+
+    let num_elements: usize = input.shape.iter().product();
+    if num_elements == 0 {
+        return output;
+    }
+
+    let num_rows = num_elements / size;
+    let pairs = size % 2 == 0;
+    let packed_size = if pairs { size / 2 } else { size };
+    let packed = TensorHandle::<R, F>::empty(client, vec![num_rows, packed_size, 2]);
+
+    let cube_dim = CubeDim::default();
+    unsafe {
+        real_pack_kernel::launch_unchecked::<F, R>(
+            client,
+            calculate_cube_count_elemwise(num_rows * packed_size, cube_dim),
+            cube_dim,
+            input.as_arg(1),
+            packed.as_arg(2),
+            ScalarArg::new(axis as u32),
+            pairs,
+        );
+    }
+
+    let spectrum = fft::<R, F>(client, packed, 1, FftDirection::Forward);
+
+    unsafe {
+        real_unpack_kernel::launch_unchecked::<F, R>(
+            client,
+            calculate_cube_count_elemwise(num_rows * (size / 2 + 1), cube_dim),
+            cube_dim,
+            spectrum.as_arg(2),
+            output.as_arg(2),
+            ScalarArg::new(axis as u32),
+            pairs,
+        );
+    }
+
+    output
+}
+
+/// Inverse of [rfft], transforming the `size / 2 + 1` first values of the spectrum along the
+/// given axis of the complex input into a new real tensor of the given size along that axis.
+pub fn irfft<R: Runtime, F: Float + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, F>,
+    axis: usize,
+    size: usize,
+) -> TensorHandle<R, F> {
+    check_complex("input", &input.shape, &input.strides);
+    assert!(
+        axis < input.shape.len() - 1,
+        "Can't transform axis {axis} of a complex tensor of rank {}",
+        input.shape.len()
+    );
+    assert_eq!(
+        input.shape[axis],
+        size / 2 + 1,
+        "A real transform of size {size} has {} complex values",
+        size / 2 + 1
+    );
+
+    let mut shape = input.shape.clone();
+    shape.pop();
+    shape[axis] = size;
+    let output = TensorHandle::<R, F>::empty(client, shape);
+
+    let num_elements: usize = output.shape.iter().product();
+    if num_elements == 0 {
+        return output;
+    }
+
+    let num_rows = num_elements / size;
+    let pairs = size % 2 == 0;
+    let packed_size = if pairs { size / 2 } else { size };
+    let packed = TensorHandle::<R, F>::empty(client, vec![num_rows, packed_size, 2]);
+
+    let cube_dim = CubeDim::default();
+    unsafe {
+        hermitian_pack_kernel::launch_unchecked::<F, R>(
+            client,
+            calculate_cube_count_elemwise(num_rows * packed_size, cube_dim),
+            cube_dim,
+            input.as_arg(2),
+            packed.as_arg(2),
+            ScalarArg::new(axis as u32),
+            pairs,
+        );
+    }
+
+    let signal = fft::<R, F>(client, packed, 1, FftDirection::Inverse);
+
+    unsafe {
+        real_extract_kernel::launch_unchecked::<F, R>(
+            client,
+            calculate_cube_count_elemwise(num_elements, cube_dim),
+            cube_dim,
+            signal.as_arg(2),
+            output.as_arg(1),
+            ScalarArg::new(axis as u32),
+            pairs,
+        );
+    }
+
+    output
+}
+
+fn check_complex(name: &str, shape: &[usize], strides: &[usize]) {
+    assert!(
+        shape.len() >= 2 && shape[shape.len() - 1] == 2 && strides[strides.len() - 1] == 1,
+        "The {name} must be a complex tensor, with a contiguous last axis of size 2, got shape \
+         {shape:?} and strides {strides:?}"
+    );
+}
@@ -0,0 +1,193 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise, CubeElement};
+
+use crate::tensor::TensorHandle;
+
+use super::complex::{complex, complex_mul, complex_scale};
+use super::stockham::{self, row_offset};
+
+/// Multiply each row of the input by the chirp into the padded rows, of shape
+/// `[num_rows, padded_size, 2]`, the values past the size of the input being zeros.
+#[cube(launch_unchecked)]
+pub(crate) fn chirp_premultiply_kernel<F: Float>(
+    input: &Tensor<Line<F>>,
+    chirp: &Tensor<Line<F>>,
+    padded: &mut Tensor<Line<F>>,
+    axis: u32,
+) {
+    let padded_size = padded.shape(1);
+    let row = ABSOLUTE_POS / padded_size;
+    let j = ABSOLUTE_POS % padded_size;
+
+    if row >= padded.shape(0) {
+        return;
+    }
+
+    let mut value = complex::<F>(F::new(0.0), F::new(0.0));
+    if j < input.shape(axis) {
+        let offset = row_offset(input, row, axis, true) + j * input.stride(axis) / 2;
+        value = complex_mul::<F>(input[offset], chirp[j]);
+    }
+
+    padded[ABSOLUTE_POS] = value;
+}
+
+/// Multiply each padded row by the transformed filter, which is the pointwise product of the
+/// circular convolution with the conjugate chirp.
+#[cube(launch_unchecked)]
+pub(crate) fn chirp_convolve_kernel<F: Float>(
+    padded: &mut Tensor<Line<F>>,
+    filter: &Tensor<Line<F>>,
+) {
+    let padded_size = padded.shape(1);
+
+    if ABSOLUTE_POS >= padded.shape(0) * padded_size {
+        return;
+    }
+
+    padded[ABSOLUTE_POS] =
+        complex_mul::<F>(padded[ABSOLUTE_POS], filter[ABSOLUTE_POS % padded_size]);
+}
+
+/// Multiply the first values of each convolved row by the chirp into the rows of the output
+/// along its axis.
+///
+/// With `inverse`, the output is scaled by `1 / size`.
+#[cube(launch_unchecked)]
+pub(crate) fn chirp_postmultiply_kernel<F: Float>(
+    convolved: &Tensor<Line<F>>,
+    chirp: &Tensor<Line<F>>,
+    output: &mut Tensor<Line<F>>,
+    axis: u32,
+    #[comptime] inverse: bool,
+) {
+    let size = output.shape(axis);
+    let row = ABSOLUTE_POS / size;
+    let k = ABSOLUTE_POS % size;
+
+    if row >= convolved.shape(0) {
+        return;
+    }
+
+    let mut value = complex_mul::<F>(convolved[row * convolved.shape(1) + k], chirp[k]);
+    if comptime!(inverse) {
+        value = complex_scale::<F>(value, F::new(1.0) / F::cast_from(size));
+    }
+
+    let offset = row_offset(output, row, axis, true) + k * output.stride(axis) / 2;
+    output[offset] = value;
+}
+
+/// The chirp `exp(-πi * j² / size)` of each position, conjugated when `inverse`, as interleaved
+/// real and imaginary parts.
+///
+/// The squares are reduced modulo `2 * size` in integers, so that the angles stay exact for
+/// large sizes.
+fn chirp(size: usize, inverse: bool) -> Vec<[f64; 2]> {
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let period = 2 * size as u64;
+
+    (0..size as u64)
+        .map(|j| {
+            let angle = sign * core::f64::consts::PI * ((j * j) % period) as f64 / size as f64;
+            [angle.cos(), angle.sin()]
+        })
+        .collect()
+}
+
+fn create_complex<R: Runtime, F: Float + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    shape: Vec<usize>,
+    values: &[[f64; 2]],
+) -> TensorHandle<R, F> {
+    let data: Vec<F> = values
+        .iter()
+        .flatten()
+        .map(|value| F::new(*value as f32))
+        .collect();
+
+    TensorHandle::new_contiguous(shape, client.create(F::as_bytes(&data)))
+}
+
+/// Transform the rows of the input along `axis` into the rows of the output, for any size.
+///
+/// The transform is rewritten as a convolution of the input multiplied by a chirp with the
+/// conjugate chirp, which is computed with power of two transforms of at least `2 * size - 1`
+/// values.
+pub(crate) fn launch<R: Runtime, F: Float + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    output: TensorHandleRef<'_, R>,
+    axis: usize,
+    inverse: bool,
+) {
+    let size = output.shape[axis];
+    let num_rows: usize = output.shape[..output.shape.len() - 1]
+        .iter()
+        .product::<usize>()
+        / size;
+    let padded_size = (2 * size - 1).next_power_of_two();
+
+    let chirp_values = chirp(size, inverse);
+    let chirp = create_complex::<R, F>(client, vec![size, 2], &chirp_values);
+
+    let mut filter_values = vec![[0.0; 2]; padded_size];
+    for (j, [re, im]) in chirp_values.iter().enumerate() {
+        filter_values[j] = [*re, -im];
+        filter_values[(padded_size - j) % padded_size] = [*re, -im];
+    }
+    let filter = create_complex::<R, F>(client, vec![1, padded_size, 2], &filter_values);
+    let filter_spectrum = TensorHandle::<R, F>::empty(client, vec![1, padded_size, 2]);
+    stockham::launch::<R, F>(
+        client,
+        filter.as_ref(),
+        1,
+        filter_spectrum.as_ref(),
+        1,
+        false,
+    );
+
+    let cube_dim = CubeDim::default();
+    let padded = TensorHandle::<R, F>::empty(client, vec![num_rows, padded_size, 2]);
+    let cube_count = calculate_cube_count_elemwise(num_rows * padded_size, cube_dim);
+    unsafe {
+        chirp_premultiply_kernel::launch_unchecked::<F, R>(
+            client,
+            cube_count.clone(),
+            cube_dim,
+            input.as_tensor_arg(2),
+            chirp.as_arg(2),
+            padded.as_arg(2),
+            ScalarArg::new(axis as u32),
+        );
+    }
+
+    let spectrum = TensorHandle::<R, F>::empty(client, vec![num_rows, padded_size, 2]);
+    stockham::launch::<R, F>(client, padded.as_ref(), 1, spectrum.as_ref(), 1, false);
+
+    unsafe {
+        chirp_convolve_kernel::launch_unchecked::<F, R>(
+            client,
+            cube_count,
+            cube_dim,
+            spectrum.as_arg(2),
+            filter_spectrum.as_arg(2),
+        );
+    }
+
+    // The padded rows aren't read anymore, so they receive the convolution.
+    stockham::launch::<R, F>(client, spectrum.as_ref(), 1, padded.as_ref(), 1, true);
+
+    unsafe {
+        chirp_postmultiply_kernel::launch_unchecked::<F, R>(
+            client,
+            calculate_cube_count_elemwise(num_rows * size, cube_dim),
+            cube_dim,
+            padded.as_arg(2),
+            chirp.as_arg(2),
+            output.as_tensor_arg(2),
+            ScalarArg::new(axis as u32),
+            inverse,
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+/// Create a complex value, as a line of size 2 with the real part followed by the imaginary part.
+#[cube]
+pub fn complex<F: Float>(re: F, im: F) -> Line<F> {
+    let mut value = Line::empty(2);
+    value.insert(0, re);
+    value.insert(1, im);
+    value
+}
+
+/// Multiply two complex values.
+#[cube]
+pub fn complex_mul<F: Float>(lhs: Line<F>, rhs: Line<F>) -> Line<F> {
+    let lhs_re = lhs.extract(0);
+    let lhs_im = lhs.extract(1);
+    let rhs_re = rhs.extract(0);
+    let rhs_im = rhs.extract(1);
+
+    complex::<F>(
+        lhs_re * rhs_re - lhs_im * rhs_im,
+        lhs_re * rhs_im + lhs_im * rhs_re,
+    )
+}
+
+/// The conjugate of a complex value.
+#[cube]
+pub fn complex_conj<F: Float>(value: Line<F>) -> Line<F> {
+    complex::<F>(value.extract(0), F::new(0.0) - value.extract(1))
+}
+
+/// Multiply a complex value by `-i`, or by `i` when `inverse`, which is the twiddle of a quarter
+/// turn in the direction of the transform.
+#[cube]
+pub(crate) fn rotate<F: Float>(value: Line<F>, #[comptime] inverse: bool) -> Line<F> {
+    let re = value.extract(0);
+    let im = value.extract(1);
+
+    if comptime!(inverse) {
+        complex::<F>(F::new(0.0) - im, re)
+    } else {
+        complex::<F>(im, F::new(0.0) - re)
+    }
+}
+
+/// The twiddle `exp(-2πi * numerator / denominator)`, or its conjugate when `inverse`.
+///
+/// The angle and its cosine and sine are computed in `f32`, so that half precision transforms
+/// don't accumulate the error of their twiddles.
+#[cube]
+pub(crate) fn twiddle<F: Float>(
+    numerator: u32,
+    denominator: u32,
+    #[comptime] inverse: bool,
+) -> Line<F> {
+    let sign = comptime!(if inverse { 1.0f32 } else { -1.0f32 });
+    let angle = 6.283_185_5f32 * f32::cast_from(numerator) / f32::cast_from(denominator) * sign;
+
+    complex::<F>(F::cast_from(f32::cos(angle)), F::cast_from(f32::sin(angle)))
+}
+
+/// Scale both parts of a complex value.
+#[cube]
+pub(crate) fn complex_scale<F: Float>(value: Line<F>, factor: F) -> Line<F> {
+    value * Line::empty(2).fill(factor)
+}
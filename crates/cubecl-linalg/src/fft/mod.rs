@@ -0,0 +1,11 @@
+mod base;
+mod bluestein;
+pub mod complex;
+mod real;
+mod stockham;
+
+/// Tests for fft kernels
+#[cfg(feature = "export_tests")]
+pub mod tests;
+
+pub use base::*;
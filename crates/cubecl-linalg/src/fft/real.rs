@@ -0,0 +1,142 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+use super::complex::{complex, complex_conj, complex_mul, complex_scale, rotate, twiddle};
+use super::stockham::row_offset;
+
+/// Pack each row of the real input along its axis into the complex rows, of shape
+/// `[num_rows, packed_size, 2]`.
+///
+/// With `pairs`, the consecutive values `2j` and `2j + 1` are the real and imaginary parts of
+/// the packed value `j`, otherwise each value is the real part of a packed value.
+#[cube(launch_unchecked)]
+pub(crate) fn real_pack_kernel<F: Float>(
+    input: &Tensor<F>,
+    packed: &mut Tensor<Line<F>>,
+    axis: u32,
+    #[comptime] pairs: bool,
+) {
+    let packed_size = packed.shape(1);
+    let row = ABSOLUTE_POS / packed_size;
+    let j = ABSOLUTE_POS % packed_size;
+
+    if row >= packed.shape(0) {
+        return;
+    }
+
+    let offset = row_offset(input, row, axis, false);
+    let stride = input.stride(axis);
+
+    if comptime!(pairs) {
+        let position = offset + 2 * j * stride;
+        packed[ABSOLUTE_POS] = complex::<F>(input[position], input[position + stride]);
+    } else {
+        packed[ABSOLUTE_POS] = complex::<F>(input[offset + j * stride], F::new(0.0));
+    }
+}
+
+/// Write the first `size / 2 + 1` values of the spectrum of each real row into the rows of the
+/// output along its axis, `size` being the size of the real rows.
+///
+/// With `pairs`, the spectrum is the one of the values packed in pairs, which is split into the
+/// spectra of the even and odd values before they are combined.
+#[cube(launch_unchecked)]
+pub(crate) fn real_unpack_kernel<F: Float>(
+    spectrum: &Tensor<Line<F>>,
+    output: &mut Tensor<Line<F>>,
+    axis: u32,
+    #[comptime] pairs: bool,
+) {
+    let num_values = output.shape(axis);
+    let row = ABSOLUTE_POS / num_values;
+    let k = ABSOLUTE_POS % num_values;
+
+    if row >= spectrum.shape(0) {
+        return;
+    }
+
+    let packed_size = spectrum.shape(1);
+    let start = row * packed_size;
+    let mut value = spectrum[start + k % packed_size];
+
+    if comptime!(pairs) {
+        let mirror = complex_conj::<F>(spectrum[start + (packed_size - k) % packed_size]);
+        let half = F::new(0.5);
+        let even = complex_scale::<F>(value + mirror, half);
+        let odd = complex_scale::<F>(rotate::<F>(value - mirror, false), half);
+        value = even + complex_mul::<F>(twiddle::<F>(k, 2 * packed_size, false), odd);
+    }
+
+    let offset = row_offset(output, row, axis, true) + k * output.stride(axis) / 2;
+    output[offset] = value;
+}
+
+/// Expand the first `size / 2 + 1` values of the spectrum of each real row of the input along
+/// its axis into the packed rows, of shape `[num_rows, packed_size, 2]`.
+///
+/// With `pairs`, the packed rows are the spectrum of the even values plus `i` times the spectrum
+/// of the odd values, whose inverse transform has the real values packed in pairs. Otherwise,
+/// the packed rows are the full spectrum, completed with its Hermitian symmetry.
+#[cube(launch_unchecked)]
+pub(crate) fn hermitian_pack_kernel<F: Float>(
+    input: &Tensor<Line<F>>,
+    packed: &mut Tensor<Line<F>>,
+    axis: u32,
+    #[comptime] pairs: bool,
+) {
+    let packed_size = packed.shape(1);
+    let row = ABSOLUTE_POS / packed_size;
+    let k = ABSOLUTE_POS % packed_size;
+
+    if row >= packed.shape(0) {
+        return;
+    }
+
+    let offset = row_offset(input, row, axis, true);
+    let stride = input.stride(axis) / 2;
+
+    if comptime!(pairs) {
+        let value = input[offset + k * stride];
+        let mirror = complex_conj::<F>(input[offset + (packed_size - k) * stride]);
+        let half = F::new(0.5);
+        let even = complex_scale::<F>(value + mirror, half);
+        let odd = complex_mul::<F>(
+            twiddle::<F>(k, 2 * packed_size, true),
+            complex_scale::<F>(value - mirror, half),
+        );
+        packed[ABSOLUTE_POS] = even + rotate::<F>(odd, true);
+    } else if k < input.shape(axis) {
+        packed[ABSOLUTE_POS] = input[offset + k * stride];
+    } else {
+        packed[ABSOLUTE_POS] = complex_conj::<F>(input[offset + (packed_size - k) * stride]);
+    }
+}
+
+/// Write the real values of the packed signal into the rows of the output along its axis.
+///
+/// With `pairs`, the packed value `j` holds the real values `2j` and `2j + 1`, otherwise its real
+/// part is the real value `j`.
+#[cube(launch_unchecked)]
+pub(crate) fn real_extract_kernel<F: Float>(
+    signal: &Tensor<Line<F>>,
+    output: &mut Tensor<F>,
+    axis: u32,
+    #[comptime] pairs: bool,
+) {
+    let size = output.shape(axis);
+    let row = ABSOLUTE_POS / size;
+    let j = ABSOLUTE_POS % size;
+
+    if row >= signal.shape(0) {
+        return;
+    }
+
+    let start = row * signal.shape(1);
+    let offset = row_offset(output, row, axis, false) + j * output.stride(axis);
+
+    if comptime!(pairs) {
+        output[offset] = signal[start + j / 2].extract(j % 2);
+    } else {
+        output[offset] = signal[start + j].extract(0);
+    }
+}
@@ -0,0 +1,505 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise};
+
+use crate::tensor::TensorHandle;
+
+use super::complex::{complex, complex_mul, complex_scale, rotate, twiddle};
+
+/// The number of bytes of the two shared buffers of a transform staged in shared memory.
+const MAX_SHARED_BYTES: usize = 32 * 1024;
+/// The maximum number of units of a cube transforming a row in shared memory.
+const MAX_CUBE_SIZE: usize = 256;
+/// The number of units of the cubes of the stages in global memory.
+const STAGE_CUBE_SIZE: u32 = 256;
+
+/// The number of independent transforms, which is the number of complex values for a size of 1
+/// along the transformed axis.
+///
+/// With `complex`, the last axis holds the real and imaginary parts and isn't counted.
+#[cube]
+pub(crate) fn num_rows<E: CubePrimitive>(
+    tensor: &Tensor<E>,
+    axis: u32,
+    #[comptime] complex: bool,
+) -> u32 {
+    let rank = if comptime!(complex) {
+        tensor.rank() - 1
+    } else {
+        tensor.rank()
+    };
+    let mut num_rows = 1;
+    for i in 0..rank {
+        if i != axis {
+            num_rows *= tensor.shape(i);
+        }
+    }
+    num_rows
+}
+
+/// The offset of the first value of the given row, the rows being ordered as a contiguous
+/// tensor with a size of 1 along the transformed axis.
+///
+/// With `complex`, the tensor is read as lines of size 2 and the offset is counted in lines.
+#[cube]
+pub(crate) fn row_offset<E: CubePrimitive>(
+    tensor: &Tensor<E>,
+    row: u32,
+    axis: u32,
+    #[comptime] complex: bool,
+) -> u32 {
+    let rank = if comptime!(complex) {
+        tensor.rank() - 1
+    } else {
+        tensor.rank()
+    };
+    let mut remaining = row;
+    let mut offset = 0;
+
+    for i in 0..rank {
+        let dim = rank - 1 - i;
+        if dim != axis {
+            offset += remaining % tensor.shape(dim) * tensor.stride(dim);
+            remaining /= tensor.shape(dim);
+        }
+    }
+
+    if comptime!(complex) {
+        offset / 2
+    } else {
+        offset
+    }
+}
+
+/// DFT of size 2 of the values at `offset` and `offset + stride`, in place.
+#[cube]
+fn dft2<F: Float>(values: &mut Array<Line<F>>, offset: u32, stride: u32) {
+    let a = values[offset];
+    let b = values[offset + stride];
+    values[offset] = a + b;
+    values[offset + stride] = a - b;
+}
+
+/// DFT of size 4 of the values at `offset + i * stride`, in place and in natural order.
+#[cube]
+fn dft4<F: Float>(
+    values: &mut Array<Line<F>>,
+    offset: u32,
+    stride: u32,
+    #[comptime] inverse: bool,
+) {
+    let x0 = values[offset];
+    let x1 = values[offset + stride];
+    let x2 = values[offset + 2 * stride];
+    let x3 = values[offset + 3 * stride];
+
+    let a0 = x0 + x2;
+    let a1 = x0 - x2;
+    let a2 = x1 + x3;
+    let a3 = rotate::<F>(x1 - x3, inverse);
+
+    values[offset] = a0 + a2;
+    values[offset + stride] = a1 + a3;
+    values[offset + 2 * stride] = a0 - a2;
+    values[offset + 3 * stride] = a1 - a3;
+}
+
+/// DFT of size 8 of the values, in place and in natural order, as two DFTs of size 4 of the
+/// even and odd values combined with the twiddles of the eighth turns.
+#[cube]
+fn dft8<F: Float>(values: &mut Array<Line<F>>, #[comptime] inverse: bool) {
+    dft4::<F>(values, 0, 2, inverse);
+    dft4::<F>(values, 1, 2, inverse);
+
+    let half_sqrt2 = F::new(0.707_106_77);
+    let sign = comptime!(if inverse { 1.0f32 } else { -1.0f32 });
+    let w1 = complex::<F>(half_sqrt2, half_sqrt2 * F::new(sign));
+    let w3 = complex::<F>(F::new(0.0) - half_sqrt2, half_sqrt2 * F::new(sign));
+
+    let odd0 = values[1];
+    let odd1 = complex_mul::<F>(values[3], w1);
+    let odd2 = rotate::<F>(values[5], inverse);
+    let odd3 = complex_mul::<F>(values[7], w3);
+    let even0 = values[0];
+    let even1 = values[2];
+    let even2 = values[4];
+    let even3 = values[6];
+
+    values[0] = even0 + odd0;
+    values[1] = even1 + odd1;
+    values[2] = even2 + odd2;
+    values[3] = even3 + odd3;
+    values[4] = even0 - odd0;
+    values[5] = even1 - odd1;
+    values[6] = even2 - odd2;
+    values[7] = even3 - odd3;
+}
+
+/// A butterfly of a Stockham stage, in place.
+///
+/// The values are the `radix` inputs spaced by `size / radix` in the previous stage, which are
+/// twiddled for the position `k` in the sub-transforms of length `stride * radix` before their
+/// DFT. The output `q` is written at `(j - k) * radix + k + q * stride`, `j` being the index of
+/// the butterfly, so that the sub-transforms stay contiguous without a bit reversal.
+#[cube]
+pub(crate) fn butterfly<F: Float>(
+    values: &mut Array<Line<F>>,
+    k: u32,
+    stride: u32,
+    #[comptime] radix: u32,
+    #[comptime] inverse: bool,
+) {
+    #[unroll]
+    for t in 1..radix {
+        values[t] = complex_mul::<F>(values[t], twiddle::<F>(k * t, stride * radix, inverse));
+    }
+
+    if comptime!(radix == 2) {
+        dft2::<F>(values, 0, 1);
+    } else if comptime!(radix == 4) {
+        dft4::<F>(values, 0, 1, inverse);
+    } else {
+        dft8::<F>(values, inverse);
+    }
+}
+
+/// Transform each row of the input along its axis with one cube per row, all the stages being
+/// staged in shared memory.
+///
+/// The two halves of the shared memory are the input and the output of each stage, in turn.
+/// Inverse transforms are scaled by `1 / size`.
+#[cube(launch_unchecked)]
+pub(crate) fn fft_shared_kernel<F: Float>(
+    input: &Tensor<Line<F>>,
+    output: &mut Tensor<Line<F>>,
+    input_axis: u32,
+    output_axis: u32,
+    #[comptime] size: u32,
+    #[comptime] cube_size: u32,
+    #[comptime] inverse: bool,
+) {
+    let row = CUBE_POS;
+    // The whole cube returns, so the synchronizations are still reached by every unit.
+    if row >= num_rows(output, output_axis, true) {
+        return;
+    }
+
+    let input_offset = row_offset(input, row, input_axis, true);
+    let input_stride = input.stride(input_axis) / 2;
+    let mut buffer = SharedMemory::<F>::new_lined(comptime!(2 * size), 2u32);
+
+    for i in range_stepped(UNIT_POS, size, cube_size) {
+        buffer[i] = input[input_offset + i * input_stride];
+    }
+    sync_units();
+
+    let log_size = comptime!(size.trailing_zeros());
+    let mut source = 0;
+    let mut stride = 1;
+
+    for _ in 0..comptime!(log_size / 3) {
+        shared_stage::<F>(&mut buffer, source, stride, size, cube_size, 8u32, inverse);
+        source = size - source;
+        stride *= 8;
+    }
+
+    if comptime!(log_size % 3 == 2) {
+        shared_stage::<F>(&mut buffer, source, stride, size, cube_size, 4u32, inverse);
+        source = size - source;
+    } else if comptime!(log_size % 3 == 1) {
+        shared_stage::<F>(&mut buffer, source, stride, size, cube_size, 2u32, inverse);
+        source = size - source;
+    }
+
+    let output_offset = row_offset(output, row, output_axis, true);
+    let output_stride = output.stride(output_axis) / 2;
+    let scale = F::new(1.0) / F::cast_from(size);
+
+    for i in range_stepped(UNIT_POS, size, cube_size) {
+        let mut value = buffer[source + i];
+        if comptime!(inverse) {
+            value = complex_scale::<F>(value, scale);
+        }
+        output[output_offset + i * output_stride] = value;
+    }
+}
+
+/// A Stockham stage from the half of the buffer starting at `source` into the other half.
+#[cube]
+fn shared_stage<F: Float>(
+    buffer: &mut SharedMemory<Line<F>>,
+    source: u32,
+    stride: u32,
+    #[comptime] size: u32,
+    #[comptime] cube_size: u32,
+    #[comptime] radix: u32,
+    #[comptime] inverse: bool,
+) {
+    let target = size - source;
+    let num_butterflies = comptime!(size / radix);
+    let mut values = Array::<Line<F>>::vectorized(radix, 2u32);
+
+    for j in range_stepped(UNIT_POS, num_butterflies, cube_size) {
+        #[unroll]
+        for t in 0..radix {
+            values[t] = buffer[source + j + t * num_butterflies];
+        }
+
+        let k = j % stride;
+        butterfly::<F>(&mut values, k, stride, radix, inverse);
+
+        let start = target + (j - k) * radix + k;
+        #[unroll]
+        for q in 0..radix {
+            buffer[start + q * stride] = values[q];
+        }
+    }
+    sync_units();
+}
+
+/// A Stockham stage of the rows of the input along their axis in global memory, with one
+/// butterfly per unit.
+///
+/// With `last`, inverse transforms are scaled by `1 / size`.
+#[cube(launch_unchecked)]
+pub(crate) fn fft_stage_kernel<F: Float>(
+    input: &Tensor<Line<F>>,
+    output: &mut Tensor<Line<F>>,
+    input_axis: u32,
+    output_axis: u32,
+    stride: u32,
+    #[comptime] radix: u32,
+    #[comptime] inverse: bool,
+    #[comptime] last: bool,
+) {
+    let size = output.shape(output_axis);
+    let num_butterflies = size / radix;
+    let row = ABSOLUTE_POS / num_butterflies;
+    let j = ABSOLUTE_POS % num_butterflies;
+
+    if row >= num_rows(output, output_axis, true) {
+        return;
+    }
+
+    let input_offset = row_offset(input, row, input_axis, true);
+    let input_stride = input.stride(input_axis) / 2;
+    let mut values = Array::<Line<F>>::vectorized(radix, 2u32);
+
+    #[unroll]
+    for t in 0..radix {
+        values[t] = input[input_offset + (j + t * num_butterflies) * input_stride];
+    }
+
+    let k = j % stride;
+    butterfly::<F>(&mut values, k, stride, radix, inverse);
+
+    if comptime!(inverse && last) {
+        let scale = F::new(1.0) / F::cast_from(size);
+        #[unroll]
+        for q in 0..radix {
+            values[q] = complex_scale::<F>(values[q], scale);
+        }
+    }
+
+    let output_offset = row_offset(output, row, output_axis, true);
+    let output_stride = output.stride(output_axis) / 2;
+    let start = (j - k) * radix + k;
+
+    #[unroll]
+    for q in 0..radix {
+        output[output_offset + (start + q * stride) * output_stride] = values[q];
+    }
+}
+
+/// The radices of the stages of a transform of the given power of two size, as many radix 8
+/// stages as possible followed by a radix 4 or radix 2 stage.
+pub(crate) fn radices(size: usize) -> Vec<u32> {
+    let log_size = size.trailing_zeros();
+    let mut radices = vec![8; (log_size / 3) as usize];
+    match log_size % 3 {
+        2 => radices.push(4),
+        1 => radices.push(2),
+        _ => {}
+    }
+    radices
+}
+
+/// Transform the rows of the input along `input_axis` into the rows of the output along
+/// `output_axis`, whose size must be a power of two.
+///
+/// The rows are transformed in shared memory when both buffers fit, and with one launch per
+/// stage otherwise, the intermediate stages going through contiguous buffers.
+pub(crate) fn launch<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    input_axis: usize,
+    output: TensorHandleRef<'_, R>,
+    output_axis: usize,
+    inverse: bool,
+) {
+    let size = output.shape[output_axis];
+    let num_rows: usize = output.shape[..output.shape.len() - 1]
+        .iter()
+        .product::<usize>()
+        / size;
+
+    if 4 * size * F::as_elem().size() <= MAX_SHARED_BYTES {
+        let cube_size = (size / 2).clamp(1, MAX_CUBE_SIZE) as u32;
+
+        unsafe {
+            fft_shared_kernel::launch_unchecked::<F, R>(
+                client,
+                calculate_cube_count_elemwise(num_rows, CubeDim::new(1, 1, 1)),
+                CubeDim::new(cube_size, 1, 1),
+                input.as_tensor_arg(2),
+                output.as_tensor_arg(2),
+                ScalarArg::new(input_axis as u32),
+                ScalarArg::new(output_axis as u32),
+                size as u32,
+                cube_size,
+                inverse,
+            );
+        }
+        return;
+    }
+
+    let radices = radices(size);
+    let buffers = [
+        TensorHandle::<R, F>::empty(client, vec![num_rows, size, 2]),
+        TensorHandle::<R, F>::empty(client, vec![num_rows, size, 2]),
+    ];
+    let cube_dim = CubeDim::new(STAGE_CUBE_SIZE, 1, 1);
+    let mut stride = 1;
+
+    for (stage, radix) in radices.iter().enumerate() {
+        let last = stage == radices.len() - 1;
+        let (source, source_axis) = match stage {
+            0 => (input.as_tensor_arg(2), input_axis),
+            _ => (buffers[(stage - 1) % 2].as_arg(2), 1),
+        };
+        let (target, target_axis) = match last {
+            true => (output.as_tensor_arg(2), output_axis),
+            false => (buffers[stage % 2].as_arg(2), 1),
+        };
+        let num_butterflies = num_rows * size / *radix as usize;
+
+        unsafe {
+            fft_stage_kernel::launch_unchecked::<F, R>(
+                client,
+                calculate_cube_count_elemwise(num_butterflies, cube_dim),
+                cube_dim,
+                source,
+                target,
+                ScalarArg::new(source_axis as u32),
+                ScalarArg::new(target_axis as u32),
+                ScalarArg::new(stride),
+                *radix,
+                inverse,
+                last,
+            );
+        }
+        stride *= radix;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cubecl_core::host::{HostTensor, HostUnit};
+
+    fn signal(size: usize) -> Vec<Vec<f32>> {
+        (0..size)
+            .map(|i| vec![(i as f32 * 0.7).sin(), (i as f32 * 0.3).cos() - 0.5])
+            .collect()
+    }
+
+    fn dft(signal: &[Vec<f32>]) -> Vec<Vec<f32>> {
+        let size = signal.len();
+        (0..size)
+            .map(|k| {
+                let (mut re, mut im) = (0.0f64, 0.0f64);
+                for (j, value) in signal.iter().enumerate() {
+                    let angle = -2.0 * std::f64::consts::PI * ((j * k) % size) as f64 / size as f64;
+                    let (x_re, x_im) = (value[0] as f64, value[1] as f64);
+                    re += x_re * angle.cos() - x_im * angle.sin();
+                    im += x_re * angle.sin() + x_im * angle.cos();
+                }
+                vec![re as f32, im as f32]
+            })
+            .collect()
+    }
+
+    fn assert_close(actual: &[Vec<f32>], expected: &[Vec<f32>]) {
+        for (i, (actual, expected)) in actual.iter().zip(expected).enumerate() {
+            for lane in 0..2 {
+                assert!(
+                    (actual[lane] - expected[lane]).abs() < 1e-3,
+                    "Values at {i} differ: {actual:?} != {expected:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn stages_in_global_memory_match_dft() {
+        // A radix 8 stage followed by a radix 4 stage.
+        let size = 32;
+        let shape = vec![size as u32, 2];
+        let mut source = HostTensor::new(signal(size), shape.clone());
+        let mut stride = 1;
+
+        for (stage, radix) in radices(size).into_iter().enumerate() {
+            let mut target = HostTensor::new(vec![vec![0.0; 2]; size], shape.clone());
+            for position in 0..(size as u32 / radix) {
+                fft_stage_kernel::host::<f32>(
+                    HostUnit::default().with_unit_pos(position, 0, 0),
+                    &source,
+                    &mut target,
+                    0,
+                    0,
+                    stride,
+                    radix,
+                    false,
+                    stage == 1,
+                );
+            }
+            source = target;
+            stride *= radix;
+        }
+
+        assert_close(&source.data, &dft(&signal(size)));
+    }
+
+    #[test]
+    fn shared_memory_round_trip() {
+        // Two radix 8 stages followed by a radix 2 stage.
+        let size = 128;
+        let shape = vec![size as u32, 2];
+        let input = HostTensor::new(signal(size), shape.clone());
+        let mut spectrum = HostTensor::new(vec![vec![0.0; 2]; size], shape.clone());
+        let mut output = HostTensor::new(vec![vec![0.0; 2]; size], shape);
+
+        fft_shared_kernel::host::<f32>(
+            HostUnit::default(),
+            &input,
+            &mut spectrum,
+            0,
+            0,
+            size as u32,
+            1,
+            false,
+        );
+        assert_close(&spectrum.data, &dft(&signal(size)));
+
+        fft_shared_kernel::host::<f32>(
+            HostUnit::default(),
+            &spectrum,
+            &mut output,
+            0,
+            0,
+            size as u32,
+            1,
+            true,
+        );
+        assert_close(&output.data, &signal(size));
+    }
+}
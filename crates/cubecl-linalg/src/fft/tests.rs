@@ -0,0 +1,241 @@
+#![allow(missing_docs)]
+
+use cubecl_core::{prelude::*, CubeElement};
+
+use crate::{
+    fft::{self, FftDirection},
+    matmul::tests::test_utils::{create_float_tensor, read_f32},
+};
+
+fn signal(num_values: usize) -> Vec<f32> {
+    (0..num_values)
+        .map(|i| ((i * 7919) % 97) as f32 / 48.0 - 1.0)
+        .collect()
+}
+
+/// The naive DFT of each row of interleaved complex values.
+fn dft(data: &[f32], size: usize, inverse: bool) -> Vec<f32> {
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let scale = if inverse { 1.0 / size as f64 } else { 1.0 };
+
+    data.chunks(2 * size)
+        .flat_map(|row| {
+            (0..size).flat_map(move |k| {
+                let (mut re, mut im) = (0.0f64, 0.0f64);
+                for j in 0..size {
+                    let angle =
+                        sign * 2.0 * std::f64::consts::PI * ((j * k) % size) as f64 / size as f64;
+                    let (x_re, x_im) = (row[2 * j] as f64, row[2 * j + 1] as f64);
+                    re += x_re * angle.cos() - x_im * angle.sin();
+                    im += x_re * angle.sin() + x_im * angle.cos();
+                }
+                [(re * scale) as f32, (im * scale) as f32]
+            })
+        })
+        .collect()
+}
+
+fn assert_close(actual: &[f32], expected: &[f32], epsilon: f32) {
+    assert_eq!(actual.len(), expected.len());
+    for (i, (actual, expected)) in actual.iter().zip(expected).enumerate() {
+        assert!(
+            (actual - expected).abs() <= epsilon * expected.abs().max(1.0),
+            "Values differ at {i}: {actual} != {expected}"
+        );
+    }
+}
+
+fn test_fft_of_size<R: Runtime, F: Float + CubeElement>(
+    device: &R::Device,
+    num_rows: usize,
+    size: usize,
+    epsilon: f32,
+) {
+    let client = R::client(device);
+    let data = signal(num_rows * size * 2);
+    let input = create_float_tensor::<R, F>(&client, vec![num_rows, size, 2], &data);
+
+    let output = fft::fft::<R, F>(&client, input, 1, FftDirection::Forward);
+
+    assert_eq!(output.shape, vec![num_rows, size, 2]);
+    assert_close(
+        &read_f32(&client, &output),
+        &dft(&data, size, false),
+        epsilon,
+    );
+}
+
+pub fn test_fft_shared<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    test_fft_of_size::<R, F>(device, 3, 64, 1e-4);
+}
+
+pub fn test_fft_stages<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    test_fft_of_size::<R, F>(device, 2, 8192, 1e-3);
+}
+
+pub fn test_fft_bluestein<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    test_fft_of_size::<R, F>(device, 3, 100, 1e-3);
+}
+
+pub fn test_fft_strided_axis<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    let data = signal(8 * 3 * 2);
+    let input = create_float_tensor::<R, F>(&client, vec![8, 3, 2], &data);
+
+    let output = fft::fft::<R, F>(&client, input, 0, FftDirection::Forward);
+
+    // Transpose the rows of the naive transform back to the layout of the input.
+    let mut rows = vec![0.0; data.len()];
+    for j in 0..8 {
+        for column in 0..3 {
+            rows[(column * 8 + j) * 2] = data[(j * 3 + column) * 2];
+            rows[(column * 8 + j) * 2 + 1] = data[(j * 3 + column) * 2 + 1];
+        }
+    }
+    let transformed = dft(&rows, 8, false);
+    let mut expected = vec![0.0; data.len()];
+    for k in 0..8 {
+        for column in 0..3 {
+            expected[(k * 3 + column) * 2] = transformed[(column * 8 + k) * 2];
+            expected[(k * 3 + column) * 2 + 1] = transformed[(column * 8 + k) * 2 + 1];
+        }
+    }
+    assert_close(&read_f32(&client, &output), &expected, 1e-4);
+}
+
+pub fn test_ifft_round_trip<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+
+    for size in [12, 32] {
+        let data = signal(2 * size * 2);
+        let input = create_float_tensor::<R, F>(&client, vec![2, size, 2], &data);
+
+        let spectrum = fft::fft::<R, F>(&client, input, 1, FftDirection::Forward);
+        let output = fft::fft::<R, F>(&client, spectrum, 1, FftDirection::Inverse);
+
+        assert_close(&read_f32(&client, &output), &data, 1e-4);
+    }
+}
+
+pub fn test_fft2<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    let (height, width) = (4, 6);
+    let data = signal(2 * height * width * 2);
+    let input = create_float_tensor::<R, F>(&client, vec![2, height, width, 2], &data);
+
+    let output = fft::fft2::<R, F>(&client, input, FftDirection::Forward);
+
+    // The 2D transform is the transform of the rows followed by the transform of the columns.
+    let rows = dft(&data, width, false);
+    let mut columns = vec![0.0; rows.len()];
+    for batch in 0..2 {
+        for y in 0..height {
+            for x in 0..width {
+                let from = ((batch * height + y) * width + x) * 2;
+                let to = ((batch * width + x) * height + y) * 2;
+                columns[to] = rows[from];
+                columns[to + 1] = rows[from + 1];
+            }
+        }
+    }
+    let transformed = dft(&columns, height, false);
+    let mut expected = vec![0.0; rows.len()];
+    for batch in 0..2 {
+        for y in 0..height {
+            for x in 0..width {
+                let from = ((batch * width + x) * height + y) * 2;
+                let to = ((batch * height + y) * width + x) * 2;
+                expected[to] = transformed[from];
+                expected[to + 1] = transformed[from + 1];
+            }
+        }
+    }
+    assert_close(&read_f32(&client, &output), &expected, 1e-4);
+}
+
+pub fn test_rfft<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+
+    for size in [16, 9] {
+        let data = signal(2 * size);
+        let input = create_float_tensor::<R, F>(&client, vec![2, size], &data);
+
+        let output = fft::rfft::<R, F>(&client, input, 1);
+
+        assert_eq!(output.shape, vec![2, size / 2 + 1, 2]);
+        let complex: Vec<f32> = data.iter().flat_map(|value| [*value, 0.0]).collect();
+        let expected: Vec<f32> = dft(&complex, size, false)
+            .chunks(2 * size)
+            .flat_map(|row| row[..2 * (size / 2 + 1)].to_vec())
+            .collect();
+        assert_close(&read_f32(&client, &output), &expected, 1e-4);
+    }
+}
+
+pub fn test_irfft_round_trip<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+
+    for size in [16, 9] {
+        let data = signal(3 * size);
+        let input = create_float_tensor::<R, F>(&client, vec![size, 3], &data);
+
+        let spectrum = fft::rfft::<R, F>(&client, input, 0);
+        let output = fft::irfft::<R, F>(&client, spectrum, 0, size);
+
+        assert_eq!(output.shape, vec![size, 3]);
+        assert_close(&read_f32(&client, &output), &data, 1e-4);
+    }
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_fft {
+    () => {
+        mod fft {
+            use super::*;
+            use cubecl_linalg::fft::tests;
+
+            pub type FloatT = f32;
+
+            #[test]
+            pub fn test_fft_shared() {
+                tests::test_fft_shared::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_fft_stages() {
+                tests::test_fft_stages::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_fft_bluestein() {
+                tests::test_fft_bluestein::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_fft_strided_axis() {
+                tests::test_fft_strided_axis::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_ifft_round_trip() {
+                tests::test_ifft_round_trip::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_fft2() {
+                tests::test_fft2::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_rfft() {
+                tests::test_rfft::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_irfft_round_trip() {
+                tests::test_irfft_round_trip::<TestRuntime, FloatT>(&Default::default())
+            }
+        }
+    };
+}
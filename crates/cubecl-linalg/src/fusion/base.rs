@@ -0,0 +1,442 @@
+use cubecl_core::{
+    calculate_cube_count_elemwise, ir::Elem, prelude::*, tensor_line_size, CubeDim, KernelSettings,
+};
+
+use super::kernel::FusionKernel;
+
+/// A value of a [fusion graph](FusionGraph).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FusionValue(pub(crate) usize);
+
+/// An operation on one value of a [fusion graph](FusionGraph).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnaryOp {
+    Neg,
+    Abs,
+    Exp,
+    Log,
+    Log1p,
+    Cos,
+    Sin,
+    Tanh,
+    Sqrt,
+    Round,
+    Floor,
+    Ceil,
+    Erf,
+    Recip,
+    /// The logical negation of a boolean value.
+    Not,
+}
+
+/// An operation on two values of the same element type of a [fusion graph](FusionGraph).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Powf,
+    Max,
+    Min,
+    Equal,
+    NotEqual,
+    Lower,
+    LowerEqual,
+    Greater,
+    GreaterEqual,
+    /// The logical and of two boolean values.
+    And,
+    /// The logical or of two boolean values.
+    Or,
+}
+
+impl BinaryOp {
+    /// Whether the operation compares its values, producing booleans.
+    pub fn is_comparison(&self) -> bool {
+        matches!(
+            self,
+            BinaryOp::Equal
+                | BinaryOp::NotEqual
+                | BinaryOp::Lower
+                | BinaryOp::LowerEqual
+                | BinaryOp::Greater
+                | BinaryOp::GreaterEqual
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum FusionNode {
+    Input {
+        position: usize,
+        elem: Elem,
+    },
+    Scalar {
+        position: usize,
+        elem: Elem,
+    },
+    Unary {
+        op: UnaryOp,
+        input: FusionValue,
+        elem: Elem,
+    },
+    Binary {
+        op: BinaryOp,
+        lhs: FusionValue,
+        rhs: FusionValue,
+        elem: Elem,
+    },
+    Cast {
+        input: FusionValue,
+        elem: Elem,
+    },
+}
+
+impl FusionNode {
+    pub(crate) fn elem(&self) -> Elem {
+        match self {
+            FusionNode::Input { elem, .. }
+            | FusionNode::Scalar { elem, .. }
+            | FusionNode::Unary { elem, .. }
+            | FusionNode::Binary { elem, .. }
+            | FusionNode::Cast { elem, .. } => *elem,
+        }
+    }
+}
+
+/// A scalar argument of a [fusion graph](FusionGraph).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FusionScalar {
+    F16(half::f16),
+    BF16(half::bf16),
+    F32(f32),
+    F64(f64),
+    I32(i32),
+    I64(i64),
+    U32(u32),
+    U64(u64),
+}
+
+impl FusionScalar {
+    /// The element type of the scalar.
+    pub fn elem(&self) -> Elem {
+        match self {
+            FusionScalar::F16(_) => half::f16::as_elem(),
+            FusionScalar::BF16(_) => half::bf16::as_elem(),
+            FusionScalar::F32(_) => f32::as_elem(),
+            FusionScalar::F64(_) => f64::as_elem(),
+            FusionScalar::I32(_) => i32::as_elem(),
+            FusionScalar::I64(_) => i64::as_elem(),
+            FusionScalar::U32(_) => u32::as_elem(),
+            FusionScalar::U64(_) => u64::as_elem(),
+        }
+    }
+
+    fn register<R: Runtime>(&self, launcher: &mut KernelLauncher<R>) {
+        match self {
+            FusionScalar::F16(value) => launcher.register_f16(*value),
+            FusionScalar::BF16(value) => launcher.register_bf16(*value),
+            FusionScalar::F32(value) => launcher.register_f32(*value),
+            FusionScalar::F64(value) => launcher.register_f64(*value),
+            FusionScalar::I32(value) => launcher.register_i32(*value),
+            FusionScalar::I64(value) => launcher.register_i64(*value),
+            FusionScalar::U32(value) => launcher.register_u32(*value),
+            FusionScalar::U64(value) => launcher.register_u64(*value),
+        }
+    }
+}
+
+/// An expression graph of elementwise operations, executed as a single kernel.
+///
+/// Inputs are tensors broadcast to a common shape, following the usual rules: their shapes are
+/// aligned on the last axis, and axes of size 1 are repeated. Each output is written with the
+/// value of one node at every position of the broadcast shape.
+///
+/// Kernels are cached by the structure of the graph, so that graphs built the same way reuse the
+/// same kernel whatever the shapes they're launched with.
+///
+/// ```rust, ignore
+/// let mut graph = FusionGraph::default();
+/// let x = graph.input::<f32>();
+/// let bias = graph.input::<f32>();
+/// let scale = graph.scalar::<f32>();
+///
+/// let scaled = graph.binary(BinaryOp::Mul, x, scale);
+/// let shifted = graph.binary(BinaryOp::Add, scaled, bias);
+/// let output = graph.unary(UnaryOp::Exp, shifted);
+/// graph.output(output);
+///
+/// graph.launch::<R>(&client, &[x.as_ref(), bias.as_ref()], &[FusionScalar::F32(2.0)], &[out.as_ref()]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct FusionGraph {
+    pub(crate) nodes: Vec<FusionNode>,
+    pub(crate) inputs: Vec<Elem>,
+    pub(crate) scalars: Vec<Elem>,
+    pub(crate) outputs: Vec<FusionValue>,
+}
+
+impl FusionGraph {
+    /// Add an input tensor of the given element type.
+    pub fn input<E: CubePrimitive>(&mut self) -> FusionValue {
+        let elem = E::as_elem();
+        let position = self.inputs.len();
+        self.inputs.push(elem);
+        self.push(FusionNode::Input { position, elem })
+    }
+
+    /// Add a scalar argument of the given element type, broadcast to every position.
+    pub fn scalar<E: CubePrimitive>(&mut self) -> FusionValue {
+        let elem = E::as_elem();
+        let position = self.scalars.len();
+        self.scalars.push(elem);
+        self.push(FusionNode::Scalar { position, elem })
+    }
+
+    /// Apply an operation to a value.
+    pub fn unary(&mut self, op: UnaryOp, input: FusionValue) -> FusionValue {
+        let elem = self.elem(input);
+        if op == UnaryOp::Not {
+            assert_eq!(elem, Elem::Bool, "Can't negate a value of type {elem}");
+        }
+
+        self.push(FusionNode::Unary { op, input, elem })
+    }
+
+    /// Apply an operation to two values of the same element type.
+    pub fn binary(&mut self, op: BinaryOp, lhs: FusionValue, rhs: FusionValue) -> FusionValue {
+        let elem = self.elem(lhs);
+        assert_eq!(
+            elem,
+            self.elem(rhs),
+            "Can't apply {op:?} to values of different types, one of them must be cast"
+        );
+
+        let elem = if op.is_comparison() { Elem::Bool } else { elem };
+        self.push(FusionNode::Binary { op, lhs, rhs, elem })
+    }
+
+    /// Convert a value to the given element type.
+    pub fn cast<E: CubePrimitive>(&mut self, input: FusionValue) -> FusionValue {
+        self.push(FusionNode::Cast {
+            input,
+            elem: E::as_elem(),
+        })
+    }
+
+    /// Write the value into the next output tensor.
+    pub fn output(&mut self, value: FusionValue) {
+        let elem = self.elem(value);
+        assert_ne!(
+            elem,
+            Elem::Bool,
+            "Boolean values can't be written, they must be cast first"
+        );
+
+        self.outputs.push(value);
+    }
+
+    /// The element type of a value.
+    pub fn elem(&self, value: FusionValue) -> Elem {
+        self.nodes[value.0].elem()
+    }
+
+    /// Launch the graph on the given tensors and scalars, in the order they were added.
+    ///
+    /// The inputs are broadcast to the shape of the outputs, which must all have that shape. The
+    /// line size is the largest one supported by every tensor along the last axis.
+    pub fn launch<R: Runtime>(
+        &self,
+        client: &ComputeClient<R::Server, R::Channel>,
+        inputs: &[TensorHandleRef<'_, R>],
+        scalars: &[FusionScalar],
+        outputs: &[TensorHandleRef<'_, R>],
+    ) {
+        assert_eq!(
+            inputs.len(),
+            self.inputs.len(),
+            "The graph has {} inputs",
+            self.inputs.len()
+        );
+        assert_eq!(
+            scalars.len(),
+            self.scalars.len(),
+            "The graph has {} scalars",
+            self.scalars.len()
+        );
+        assert_eq!(
+            outputs.len(),
+            self.outputs.len(),
+            "The graph has {} outputs",
+            self.outputs.len()
+        );
+        assert!(!outputs.is_empty(), "The graph must have an output");
+
+        let shape = outputs[0].shape;
+        let rank = inputs
+            .iter()
+            .chain(outputs)
+            .map(|tensor| tensor.shape.len())
+            .max()
+            .unwrap()
+            .max(1);
+        let out_shape = aligned_shape(shape, rank);
+
+        let mut layouts = Vec::with_capacity(inputs.len() + outputs.len());
+        for (i, (input, elem)) in inputs.iter().zip(&self.inputs).enumerate() {
+            check_elem_size("input", i, input.elem_size, *elem);
+            layouts.push(broadcast_layout(i, input.shape, input.strides, &out_shape));
+        }
+        for (i, (output, value)) in outputs.iter().zip(&self.outputs).enumerate() {
+            check_elem_size("output", i, output.elem_size, self.elem(*value));
+            assert_eq!(
+                output.shape, shape,
+                "All outputs must have the same shape, got {:?} and {:?}",
+                output.shape, shape
+            );
+            layouts.push((
+                aligned_shape(output.shape, rank),
+                aligned_strides(output.strides, rank),
+            ));
+        }
+        for (scalar, elem) in scalars.iter().zip(&self.scalars) {
+            assert_eq!(
+                scalar.elem(),
+                *elem,
+                "The scalar {scalar:?} doesn't have the type {elem}"
+            );
+        }
+
+        let num_elems: usize = out_shape.iter().product();
+        if num_elems == 0 {
+            return;
+        }
+
+        let line_size = layouts
+            .iter()
+            .map(|(shape, strides)| {
+                tensor_line_size(R::supported_line_sizes(), shape, strides, rank - 1)
+            })
+            .min()
+            .unwrap();
+
+        let cube_dim = CubeDim::default();
+        let cube_count = calculate_cube_count_elemwise(num_elems / line_size as usize, cube_dim);
+        let kernel = FusionKernel::<R>::new(
+            self.clone(),
+            rank,
+            line_size,
+            KernelSettings::default().cube_dim(cube_dim),
+        );
+
+        let mut launcher = KernelLauncher::<R>::default();
+        let handles = inputs.iter().chain(outputs).map(|tensor| tensor.handle);
+        let elem_sizes = inputs.iter().chain(outputs).map(|tensor| tensor.elem_size);
+        for ((handle, elem_size), (shape, strides)) in handles.zip(elem_sizes).zip(&layouts) {
+            let arg = unsafe {
+                TensorArg::from_raw_parts_and_size(handle, strides, shape, line_size, elem_size)
+            };
+            launcher.register_tensor(&arg);
+        }
+        for scalar in scalars {
+            scalar.register(&mut launcher);
+        }
+
+        // The kernel reads and writes only the positions of the broadcast shape.
+        unsafe {
+            launcher.launch_unchecked(cube_count, kernel, client);
+        }
+    }
+
+    fn push(&mut self, node: FusionNode) -> FusionValue {
+        self.nodes.push(node);
+        FusionValue(self.nodes.len() - 1)
+    }
+}
+
+fn check_elem_size(name: &str, position: usize, elem_size: usize, elem: Elem) {
+    assert_eq!(
+        elem_size,
+        elem.size(),
+        "The {name} {position} doesn't have the size of an element of type {elem}"
+    );
+}
+
+/// The shape left padded with axes of size 1 up to the rank.
+fn aligned_shape(shape: &[usize], rank: usize) -> Vec<usize> {
+    let mut aligned = vec![1; rank - shape.len()];
+    aligned.extend_from_slice(shape);
+    aligned
+}
+
+/// The strides left padded with zeros up to the rank.
+fn aligned_strides(strides: &[usize], rank: usize) -> Vec<usize> {
+    let mut aligned = vec![0; rank - strides.len()];
+    aligned.extend_from_slice(strides);
+    aligned
+}
+
+/// The layout of an input read at every position of the output shape, where broadcast axes have
+/// a stride of 0.
+fn broadcast_layout(
+    position: usize,
+    shape: &[usize],
+    strides: &[usize],
+    out_shape: &[usize],
+) -> (Vec<usize>, Vec<usize>) {
+    let rank = out_shape.len();
+    let shape = aligned_shape(shape, rank);
+    let mut strides = aligned_strides(strides, rank);
+
+    for axis in 0..rank {
+        if shape[axis] != out_shape[axis] {
+            assert_eq!(
+                shape[axis], 1,
+                "The input {position} of shape {shape:?} can't be broadcast to {out_shape:?}"
+            );
+            strides[axis] = 0;
+        }
+    }
+
+    (out_shape.to_vec(), strides)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn broadcast_layout_zeroes_repeated_axes() {
+        let (shape, strides) = broadcast_layout(0, &[3, 1], &[1, 1], &[2, 3, 4]);
+
+        assert_eq!(shape, vec![2, 3, 4]);
+        assert_eq!(strides, vec![0, 1, 0]);
+    }
+
+    #[test]
+    fn graphs_built_the_same_way_are_equal() {
+        let build = |op| {
+            let mut graph = FusionGraph::default();
+            let lhs = graph.input::<f32>();
+            let rhs = graph.scalar::<f32>();
+            let value = graph.binary(op, lhs, rhs);
+            let value = graph.cast::<u32>(value);
+            graph.output(value);
+            graph
+        };
+
+        assert_eq!(build(BinaryOp::Add), build(BinaryOp::Add));
+        assert_ne!(build(BinaryOp::Add), build(BinaryOp::Mul));
+    }
+
+    #[test]
+    fn comparisons_produce_booleans() {
+        let mut graph = FusionGraph::default();
+        let lhs = graph.input::<f32>();
+        let rhs = graph.input::<f32>();
+        let value = graph.binary(BinaryOp::Lower, lhs, rhs);
+
+        assert_eq!(graph.elem(value), Elem::Bool);
+    }
+}
@@ -0,0 +1,235 @@
+use std::marker::PhantomData;
+
+use cubecl_core::{
+    compute::{KernelBuilder, KernelDefinitionCache},
+    cpa,
+    ir::{
+        BinaryOperator, Builtin, Elem, Instruction, Item, KernelDefinition, Operator, Scope,
+        UnaryOperator, Variable,
+    },
+    prelude::*,
+    Compiler, Kernel, KernelId, KernelSettings,
+};
+
+use super::base::{BinaryOp, FusionGraph, FusionNode, UnaryOp};
+
+/// The kernel of a [fusion graph](FusionGraph), lowered directly through the [KernelBuilder].
+pub(crate) struct FusionKernel<R: Runtime> {
+    graph: FusionGraph,
+    rank: usize,
+    line_size: u8,
+    settings: KernelSettings,
+    _runtime: PhantomData<R>,
+}
+
+impl<R: Runtime> FusionKernel<R> {
+    pub(crate) fn new(
+        graph: FusionGraph,
+        rank: usize,
+        line_size: u8,
+        settings: KernelSettings,
+    ) -> Self {
+        Self {
+            graph,
+            rank,
+            line_size,
+            settings,
+            _runtime: PhantomData,
+        }
+    }
+
+    fn item(&self, elem: Elem) -> Item {
+        Item::vectorized(elem, core::num::NonZero::new(self.line_size))
+    }
+
+    fn expand(&self, builder: &mut KernelBuilder) {
+        let inputs: Vec<Variable> = self
+            .graph
+            .inputs
+            .iter()
+            .map(|elem| *builder.input_tensor(self.item(*elem)))
+            .collect();
+        let outputs: Vec<Variable> = self
+            .graph
+            .outputs
+            .iter()
+            .map(|value| *builder.output_tensor(self.item(self.graph.elem(*value))))
+            .collect();
+        let scalars: Vec<Variable> = self
+            .graph
+            .scalars
+            .iter()
+            .map(|elem| *builder.scalar(*elem))
+            .collect();
+
+        let scope = &mut *builder.context.scope.borrow_mut();
+        let u32_item = Item::new(u32::as_elem());
+        let position = Variable::builtin(Builtin::AbsolutePos);
+        let line_size: Variable = (self.line_size as u32).into();
+
+        // Every tensor is laid out with the broadcast shape, so the first output gives the number
+        // of lines to compute.
+        let reference = outputs[0];
+        let num_lines = scope.create_with_value(1, u32_item);
+        let dim_size = scope.create_local(u32_item);
+        for dim in 0..self.rank as u32 {
+            cpa!(scope, dim_size = shape(reference, dim));
+            cpa!(scope, num_lines = num_lines * dim_size);
+        }
+        cpa!(scope, num_lines = num_lines / line_size);
+
+        let in_bounds = scope.create_local(Item::new(Elem::Bool));
+        cpa!(scope, in_bounds = position < num_lines);
+
+        cpa!(scope, if(in_bounds).then(|scope| {
+            let tensors: Vec<Variable> = inputs.iter().chain(&outputs).copied().collect();
+            let indices = self.indices(scope, position, line_size, reference, &tensors);
+            let (input_indices, output_indices) = indices.split_at(inputs.len());
+
+            let mut values = Vec::with_capacity(self.graph.nodes.len());
+            for node in self.graph.nodes.iter() {
+                let out = scope.create_local(self.item(node.elem()));
+                match node {
+                    FusionNode::Input { position, .. } => {
+                        let input = inputs[*position];
+                        let index = input_indices[*position];
+                        cpa!(scope, out = unchecked(input[index]));
+                    }
+                    FusionNode::Scalar { position, .. } => {
+                        let scalar = scalars[*position];
+                        cpa!(scope, out = cast(scalar));
+                    }
+                    FusionNode::Unary { op, input, .. } => {
+                        let input = UnaryOperator {
+                            input: values[input.0],
+                        };
+                        scope.register(Instruction::new(unary_operator(*op, input), out));
+                    }
+                    FusionNode::Binary { op, lhs, rhs, .. } => {
+                        let operands = BinaryOperator {
+                            lhs: values[lhs.0],
+                            rhs: values[rhs.0],
+                        };
+                        scope.register(Instruction::new(binary_operator(*op, operands), out));
+                    }
+                    FusionNode::Cast { input, .. } => {
+                        let input = values[input.0];
+                        cpa!(scope, out = cast(input));
+                    }
+                }
+                values.push(out);
+            }
+
+            for ((output, index), value) in outputs.iter().zip(output_indices).zip(&self.graph.outputs) {
+                let output = *output;
+                let index = *index;
+                let value = values[value.0];
+                cpa!(scope, unchecked(output[index]) = value);
+            }
+        }));
+    }
+
+    /// The line index in each tensor of the line at the given position of the broadcast shape.
+    fn indices(
+        &self,
+        scope: &mut Scope,
+        position: Variable,
+        line_size: Variable,
+        reference: Variable,
+        tensors: &[Variable],
+    ) -> Vec<Variable> {
+        let u32_item = Item::new(u32::as_elem());
+        let offsets: Vec<Variable> = tensors.iter().map(|_| scope.zero(u32_item)).collect();
+
+        let remainder = scope.create_local(u32_item);
+        let dim_size = scope.create_local(u32_item);
+        let coordinate = scope.create_local(u32_item);
+        let stride = scope.create_local(u32_item);
+        let term = scope.create_local(u32_item);
+        cpa!(scope, remainder = position * line_size);
+
+        for dim in (0..self.rank as u32).rev() {
+            cpa!(scope, dim_size = shape(reference, dim));
+            cpa!(scope, coordinate = remainder % dim_size);
+            cpa!(scope, remainder = remainder / dim_size);
+
+            for (tensor, offset) in tensors.iter().zip(&offsets) {
+                let tensor = *tensor;
+                let offset = *offset;
+                cpa!(scope, stride = stride(tensor, dim));
+                cpa!(scope, term = coordinate * stride);
+                cpa!(scope, offset = offset + term);
+            }
+        }
+
+        for offset in offsets.iter() {
+            let offset = *offset;
+            cpa!(scope, offset = offset / line_size);
+        }
+
+        offsets
+    }
+}
+
+fn unary_operator(op: UnaryOp, input: UnaryOperator) -> Operator {
+    match op {
+        UnaryOp::Neg => Operator::Neg(input),
+        UnaryOp::Abs => Operator::Abs(input),
+        UnaryOp::Exp => Operator::Exp(input),
+        UnaryOp::Log => Operator::Log(input),
+        UnaryOp::Log1p => Operator::Log1p(input),
+        UnaryOp::Cos => Operator::Cos(input),
+        UnaryOp::Sin => Operator::Sin(input),
+        UnaryOp::Tanh => Operator::Tanh(input),
+        UnaryOp::Sqrt => Operator::Sqrt(input),
+        UnaryOp::Round => Operator::Round(input),
+        UnaryOp::Floor => Operator::Floor(input),
+        UnaryOp::Ceil => Operator::Ceil(input),
+        UnaryOp::Erf => Operator::Erf(input),
+        UnaryOp::Recip => Operator::Recip(input),
+        UnaryOp::Not => Operator::Not(input),
+    }
+}
+
+fn binary_operator(op: BinaryOp, operands: BinaryOperator) -> Operator {
+    match op {
+        BinaryOp::Add => Operator::Add(operands),
+        BinaryOp::Sub => Operator::Sub(operands),
+        BinaryOp::Mul => Operator::Mul(operands),
+        BinaryOp::Div => Operator::Div(operands),
+        BinaryOp::Powf => Operator::Powf(operands),
+        BinaryOp::Max => Operator::Max(operands),
+        BinaryOp::Min => Operator::Min(operands),
+        BinaryOp::Equal => Operator::Equal(operands),
+        BinaryOp::NotEqual => Operator::NotEqual(operands),
+        BinaryOp::Lower => Operator::Lower(operands),
+        BinaryOp::LowerEqual => Operator::LowerEqual(operands),
+        BinaryOp::Greater => Operator::Greater(operands),
+        BinaryOp::GreaterEqual => Operator::GreaterEqual(operands),
+        BinaryOp::And => Operator::And(operands),
+        BinaryOp::Or => Operator::Or(operands),
+    }
+}
+
+impl<R: Runtime> Kernel for FusionKernel<R> {
+    fn define(&self) -> KernelDefinition {
+        static DEFINITIONS: KernelDefinitionCache = KernelDefinitionCache::new();
+
+        DEFINITIONS.get_or_define(self.id(), || {
+            let mut builder = KernelBuilder::with_local_allocator(
+                <<R as Runtime>::Compiler as Compiler>::local_allocator(),
+            );
+            self.expand(&mut builder);
+            builder.build(self.settings.clone())
+        })
+    }
+
+    fn id(&self) -> KernelId {
+        KernelId::new::<Self>().info((
+            self.settings.cube_dim,
+            self.rank,
+            self.line_size,
+            self.graph.clone(),
+        ))
+    }
+}
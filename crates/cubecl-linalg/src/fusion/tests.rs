@@ -0,0 +1,184 @@
+#![allow(missing_docs)]
+
+use cubecl_core::{prelude::*, CubeElement};
+
+use crate::{
+    fusion::{BinaryOp, FusionGraph, FusionScalar, UnaryOp},
+    matmul::tests::test_utils::{create_float_tensor, read_f32},
+    tensor::TensorHandle,
+};
+
+fn signal(num_values: usize) -> Vec<f32> {
+    (0..num_values)
+        .map(|i| ((i * 7919) % 97) as f32 / 48.0 - 1.0)
+        .collect()
+}
+
+fn assert_close(actual: &[f32], expected: &[f32], epsilon: f32) {
+    assert_eq!(actual.len(), expected.len());
+    for (i, (actual, expected)) in actual.iter().zip(expected).enumerate() {
+        assert!(
+            (actual - expected).abs() <= epsilon * expected.abs().max(1.0),
+            "Values differ at {i}: {actual} != {expected}"
+        );
+    }
+}
+
+pub fn test_fusion_elementwise<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    let lhs_data = signal(8 * 16);
+    let rhs_data: Vec<f32> = signal(8 * 16 + 3)[3..].to_vec();
+    let lhs = create_float_tensor::<R, F>(&client, vec![8, 16], &lhs_data);
+    let rhs = create_float_tensor::<R, F>(&client, vec![8, 16], &rhs_data);
+    let output = TensorHandle::<R, F>::empty(&client, vec![8, 16]);
+
+    let mut graph = FusionGraph::default();
+    let x = graph.input::<F>();
+    let y = graph.input::<F>();
+    let scale = graph.scalar::<u32>();
+    let scale = graph.cast::<F>(scale);
+    let value = graph.unary(UnaryOp::Exp, x);
+    let scaled = graph.binary(BinaryOp::Mul, y, scale);
+    let value = graph.binary(BinaryOp::Add, value, scaled);
+    graph.output(value);
+
+    graph.launch::<R>(
+        &client,
+        &[lhs.as_ref(), rhs.as_ref()],
+        &[FusionScalar::U32(3)],
+        &[output.as_ref()],
+    );
+
+    let expected: Vec<f32> = lhs_data
+        .iter()
+        .zip(&rhs_data)
+        .map(|(x, y)| x.exp() + y * 3.0)
+        .collect();
+    assert_close(&read_f32(&client, &output), &expected, 1e-4);
+}
+
+pub fn test_fusion_broadcast<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    let lhs_data = signal(2 * 3 * 4);
+    let rhs_data = [1.0, -2.0, 4.0];
+    let lhs = create_float_tensor::<R, F>(&client, vec![2, 3, 4], &lhs_data);
+    let rhs = create_float_tensor::<R, F>(&client, vec![3, 1], &rhs_data);
+    let output = TensorHandle::<R, F>::empty(&client, vec![2, 3, 4]);
+
+    let mut graph = FusionGraph::default();
+    let x = graph.input::<F>();
+    let y = graph.input::<F>();
+    let value = graph.binary(BinaryOp::Mul, x, y);
+    graph.output(value);
+
+    graph.launch::<R>(
+        &client,
+        &[lhs.as_ref(), rhs.as_ref()],
+        &[],
+        &[output.as_ref()],
+    );
+
+    let expected: Vec<f32> = lhs_data
+        .iter()
+        .enumerate()
+        .map(|(i, x)| x * rhs_data[(i / 4) % 3])
+        .collect();
+    assert_close(&read_f32(&client, &output), &expected, 1e-5);
+}
+
+pub fn test_fusion_strided_input<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    let data = signal(4 * 6);
+    let input = create_float_tensor::<R, F>(&client, vec![4, 6], &data);
+    let output = TensorHandle::<R, F>::empty(&client, vec![6, 4]);
+
+    // Read the input transposed, which can't be vectorized.
+    let shape = [6, 4];
+    let strides = [1, 6];
+    let transposed = unsafe {
+        TensorHandleRef::<R>::from_raw_parts(&input.handle, &strides, &shape, size_of::<F>())
+    };
+
+    let mut graph = FusionGraph::default();
+    let x = graph.input::<F>();
+    let value = graph.unary(UnaryOp::Neg, x);
+    graph.output(value);
+
+    graph.launch::<R>(&client, &[transposed], &[], &[output.as_ref()]);
+
+    let mut expected = vec![0.0; data.len()];
+    for row in 0..4 {
+        for col in 0..6 {
+            expected[col * 4 + row] = -data[row * 6 + col];
+        }
+    }
+    assert_close(&read_f32(&client, &output), &expected, 1e-6);
+}
+
+pub fn test_fusion_comparison_and_outputs<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    let data = signal(64);
+    let input = create_float_tensor::<R, F>(&client, vec![64], &data);
+    let mask = TensorHandle::<R, F>::empty(&client, vec![64]);
+    let clamped = TensorHandle::<R, F>::empty(&client, vec![64]);
+
+    let mut graph = FusionGraph::default();
+    let x = graph.input::<F>();
+    let zero = graph.scalar::<u32>();
+    let zero = graph.cast::<F>(zero);
+    let positive = graph.binary(BinaryOp::Greater, x, zero);
+    let positive = graph.cast::<F>(positive);
+    let value = graph.binary(BinaryOp::Max, x, zero);
+    graph.output(positive);
+    graph.output(value);
+
+    graph.launch::<R>(
+        &client,
+        &[input.as_ref()],
+        &[FusionScalar::U32(0)],
+        &[mask.as_ref(), clamped.as_ref()],
+    );
+
+    let expected_mask: Vec<f32> = data
+        .iter()
+        .map(|x| if *x > 0.0 { 1.0 } else { 0.0 })
+        .collect();
+    let expected_clamped: Vec<f32> = data.iter().map(|x| x.max(0.0)).collect();
+    assert_close(&read_f32(&client, &mask), &expected_mask, 1e-6);
+    assert_close(&read_f32(&client, &clamped), &expected_clamped, 1e-6);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_fusion {
+    () => {
+        mod fusion {
+            use super::*;
+            use cubecl_linalg::fusion::tests;
+
+            pub type FloatT = f32;
+
+            #[test]
+            pub fn test_fusion_elementwise() {
+                tests::test_fusion_elementwise::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_fusion_broadcast() {
+                tests::test_fusion_broadcast::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_fusion_strided_input() {
+                tests::test_fusion_strided_input::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_fusion_comparison_and_outputs() {
+                tests::test_fusion_comparison_and_outputs::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+        }
+    };
+}
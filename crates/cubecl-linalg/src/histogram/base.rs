@@ -0,0 +1,118 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise, Feature};
+
+use crate::tensor::{into_contiguous, TensorHandle};
+
+use super::kernel::{histogram_kernel, Binning, IntegerBins, RangeBins};
+
+/// The number of units counting values together.
+const CUBE_SIZE: u32 = 256;
+/// The number of values counted by each unit, so that the bins of a cube are merged into the
+/// output after enough values to be worth it.
+const ITEMS_PER_UNIT: u32 = 16;
+/// The largest number of bins that are privatized in the shared memory of each cube.
+///
+/// The shared memory is sized to the next power of two of the number of bins, so that only a
+/// handful of kernels are compiled for all bin counts.
+const MAX_SHARED_BINS: usize = 4096;
+
+/// Count the occurrences of each integer of `0..num_bins` in the input, into a contiguous
+/// tensor of shape `[num_bins]`.
+///
+/// Negative values and values of at least `num_bins` are ignored.
+pub fn bincount<R: Runtime, I: Int>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    num_bins: usize,
+) -> TensorHandle<R, u32> {
+    launch::<R, I, IntegerBins>(client, input, num_bins, 0.0, 0.0)
+}
+
+/// Count the values of the input into `num_bins` bins of equal width between `min` and `max`,
+/// into a contiguous tensor of shape `[num_bins]`.
+///
+/// Each bin includes its lower edge, and the last one also includes `max`. Values outside of
+/// the range and NaNs are ignored.
+///
+/// # Panics
+///
+/// If `min` isn't smaller than `max`.
+pub fn histc<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    num_bins: usize,
+    min: f32,
+    max: f32,
+) -> TensorHandle<R, u32> {
+    assert!(
+        min < max,
+        "The range of the histogram is empty, got {min}..={max}"
+    );
+
+    launch::<R, F, RangeBins>(client, input, num_bins, min, max)
+}
+
+/// Count the input into bins privatized in shared memory when they fit, aggregating the values
+/// of each subcube when the device supports it.
+fn launch<R: Runtime, E: Numeric, B: Binning<E>>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    num_bins: usize,
+    min: f32,
+    max: f32,
+) -> TensorHandle<R, u32> {
+    assert!(num_bins > 0, "A histogram needs at least one bin");
+    let output = TensorHandle::<R, u32>::zeros(client, vec![num_bins]);
+
+    let num_elements: usize = input.shape.iter().product();
+    if num_elements == 0 {
+        return output;
+    }
+
+    let contiguous;
+    let input = match is_contiguous(input.shape, input.strides) {
+        true => input,
+        false => {
+            contiguous = into_contiguous::<R, E>(client, input);
+            contiguous.as_ref()
+        }
+    };
+
+    let num_cubes = num_elements.div_ceil((CUBE_SIZE * ITEMS_PER_UNIT) as usize);
+    let privatized = num_bins <= MAX_SHARED_BINS;
+    let shared_bins = match privatized {
+        true => num_bins.next_power_of_two(),
+        false => 1,
+    };
+    let aggregated = client.properties().feature_enabled(Feature::Subcube);
+
+    unsafe {
+        histogram_kernel::launch_unchecked::<E, B, R>(
+            client,
+            calculate_cube_count_elemwise(num_cubes, CubeDim::new(1, 1, 1)),
+            CubeDim::new(CUBE_SIZE, 1, 1),
+            input.as_tensor_arg(1),
+            output.as_ref().as_tensor_arg(1),
+            ScalarArg::new(min),
+            ScalarArg::new(max),
+            ScalarArg::new(num_bins as u32),
+            shared_bins as u32,
+            privatized,
+            aggregated,
+        );
+    }
+
+    output
+}
+
+fn is_contiguous(shape: &[usize], strides: &[usize]) -> bool {
+    let mut expected = 1;
+    for (size, stride) in shape.iter().zip(strides).rev() {
+        if *size != 1 && *stride != expected {
+            return false;
+        }
+        expected *= size;
+    }
+
+    true
+}
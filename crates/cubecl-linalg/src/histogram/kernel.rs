@@ -0,0 +1,169 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+/// Maps the values of the input to their bins.
+#[cube]
+pub(crate) trait Binning<E: Numeric>: Send + Sync + 'static {
+    /// The bin of the value, or `num_bins` when it belongs to none of them.
+    fn bin(value: E, min: f32, max: f32, num_bins: u32) -> u32;
+}
+
+/// One bin per integer of `0..num_bins`.
+pub(crate) struct IntegerBins;
+
+/// Bins of equal width splitting `min..=max`, the maximum being part of the last bin.
+pub(crate) struct RangeBins;
+
+#[cube]
+impl<I: Int> Binning<I> for IntegerBins {
+    fn bin(value: I, _min: f32, _max: f32, num_bins: u32) -> u32 {
+        let mut bin = num_bins;
+        if value >= I::new(0) && u32::cast_from(value) < num_bins {
+            bin = u32::cast_from(value);
+        }
+        bin
+    }
+}
+
+#[cube]
+impl<F: Float> Binning<F> for RangeBins {
+    fn bin(value: F, min: f32, max: f32, num_bins: u32) -> u32 {
+        let value = f32::cast_from(value);
+        let mut bin = num_bins;
+        // NaNs fail both comparisons, so they are ignored.
+        if value >= min && value <= max {
+            let position = (value - min) * f32::cast_from(num_bins) / (max - min);
+            bin = u32::cast_from(position);
+            // Only the maximum reaches the end of the range.
+            if bin >= num_bins {
+                bin -= 1;
+            }
+        }
+        bin
+    }
+}
+
+/// Count the values of the contiguous input into the bins of the output, ignoring the values
+/// that belong to none of them.
+///
+/// The cubes stride over the input. With `privatized`, each cube counts into bins in shared
+/// memory that are added to the output once the cube is done, so that units only contend on
+/// the atomics of their own cube. The shared memory holds `shared_bins` bins, which must be at
+/// least `num_bins`, so that bin counts sharing the same size reuse the same kernel. With `aggregated`, the units of a subcube that fall into the
+/// same bin are counted together by one of them, which saves most atomics on skewed inputs
+/// at the cost of one iteration per distinct bin of the subcube.
+#[cube(launch_unchecked)]
+pub(crate) fn histogram_kernel<E: Numeric, B: Binning<E>>(
+    input: &Tensor<E>,
+    output: &mut Tensor<AtomicU32>,
+    min: f32,
+    max: f32,
+    num_bins: u32,
+    #[comptime] shared_bins: u32,
+    #[comptime] privatized: bool,
+    #[comptime] aggregated: bool,
+) {
+    let bins = SharedMemory::<AtomicU32>::new(comptime!(if privatized { shared_bins } else { 1 }));
+    if comptime!(privatized) {
+        for bin in range_stepped(UNIT_POS, num_bins, CUBE_DIM) {
+            AtomicU32::store(&bins[bin], 0);
+        }
+        sync_units();
+    }
+
+    // The start of each step is the same for all units of the cube, so they all take part in
+    // the subcube operations.
+    for start in range_stepped(CUBE_POS * CUBE_DIM, input.len(), CUBE_COUNT * CUBE_DIM) {
+        let index = start + UNIT_POS;
+        let mut bin = num_bins;
+        if index < input.len() {
+            bin = B::bin(input[index], min, max, num_bins);
+        }
+        let valid = bin < num_bins;
+
+        if comptime!(aggregated) {
+            let lane = UNIT_POS % SUBCUBE_DIM;
+            let mut pending = valid;
+
+            while subcube_any(pending) {
+                let current = subcube_min(select(pending, bin, 0xFFFF_FFFFu32));
+                let matches = pending && bin == current;
+                let count = subcube_sum(select(matches, 1u32, 0u32));
+                let leader = subcube_min(select(matches, lane, 0xFFFF_FFFFu32));
+
+                if lane == leader {
+                    if comptime!(privatized) {
+                        AtomicU32::add(&bins[bin], count);
+                    } else {
+                        AtomicU32::add(&output[bin], count);
+                    }
+                }
+                pending = pending && !matches;
+            }
+        } else if valid {
+            if comptime!(privatized) {
+                AtomicU32::add(&bins[bin], 1);
+            } else {
+                AtomicU32::add(&output[bin], 1);
+            }
+        }
+    }
+
+    if comptime!(privatized) {
+        sync_units();
+        for bin in range_stepped(UNIT_POS, num_bins, CUBE_DIM) {
+            let count = AtomicU32::load(&bins[bin]);
+            if count > 0 {
+                AtomicU32::add(&output[bin], count);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cubecl_core::host::{HostTensor, HostUnit};
+
+    #[test]
+    fn histogram_counts_values_in_range() {
+        let input = HostTensor::new(vec![0.5, -1.0, 2.0, 1.9, 4.0, 0.0, f32::NAN], vec![7]);
+
+        for (privatized, aggregated) in [(false, false), (true, false), (true, true)] {
+            let mut output = HostTensor::new(vec![0; 4], vec![4]);
+            histogram_kernel::host::<f32, RangeBins>(
+                HostUnit::default(),
+                &input,
+                &mut output,
+                0.0,
+                4.0,
+                4,
+                8,
+                privatized,
+                aggregated,
+            );
+
+            assert_eq!(output.data, vec![2, 1, 1, 1]);
+        }
+    }
+
+    #[test]
+    fn bincount_ignores_out_of_range_values() {
+        let input = HostTensor::new(vec![2, -1, 5, 2, 0, 7, 2], vec![7]);
+        let mut output = HostTensor::new(vec![0; 6], vec![6]);
+
+        histogram_kernel::host::<i32, IntegerBins>(
+            HostUnit::default(),
+            &input,
+            &mut output,
+            0.0,
+            0.0,
+            6,
+            8,
+            true,
+            true,
+        );
+
+        assert_eq!(output.data, vec![1, 0, 3, 0, 0, 1]);
+    }
+}
@@ -0,0 +1,128 @@
+#![allow(missing_docs)]
+
+use cubecl_core::{prelude::*, CubeElement};
+
+use crate::{
+    histogram,
+    matmul::tests::test_utils::{create_tensor, read_tensor},
+    tensor,
+};
+
+/// Uniform values in `[-1.5, 1.5]`.
+fn random(num_values: usize) -> Vec<f32> {
+    let mut state = 7u64;
+    (0..num_values)
+        .map(|_| {
+            state = (1664525 * state + 1013904223) % (1 << 32);
+            (state as f64 / (1u64 << 32) as f64 * 3.0 - 1.5) as f32
+        })
+        .collect()
+}
+
+fn host_bincount(values: &[i32], num_bins: usize) -> Vec<u32> {
+    let mut counts = vec![0; num_bins];
+    for value in values {
+        if *value >= 0 && (*value as usize) < num_bins {
+            counts[*value as usize] += 1;
+        }
+    }
+    counts
+}
+
+fn host_histc(values: &[f32], num_bins: usize, min: f32, max: f32) -> Vec<u32> {
+    let mut counts = vec![0; num_bins];
+    for value in values {
+        if *value >= min && *value <= max {
+            let bin = ((value - min) * num_bins as f32 / (max - min)) as usize;
+            counts[bin.min(num_bins - 1)] += 1;
+        }
+    }
+    counts
+}
+
+pub fn test_bincount<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    // Most values fall in a few bins, with some negative and too large values.
+    let values: Vec<i32> = (0..10_000).map(|i| (i * i % 41) % 37 - 3).collect();
+    let input = create_tensor::<R, i32>(&client, vec![100, 100], &values);
+
+    let counts = histogram::bincount::<R, i32>(&client, input.as_ref(), 32);
+
+    assert_eq!(counts.shape, vec![32]);
+    assert_eq!(read_tensor(&client, &counts), host_bincount(&values, 32));
+}
+
+pub fn test_bincount_many_bins<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    // Too many bins to fit in shared memory.
+    let values: Vec<i32> = (0..20_000).map(|i| i * 7 % 6000).collect();
+    let input = create_tensor::<R, i32>(&client, vec![values.len()], &values);
+
+    let counts = histogram::bincount::<R, i32>(&client, input.as_ref(), 5000);
+
+    assert_eq!(read_tensor(&client, &counts), host_bincount(&values, 5000));
+}
+
+pub fn test_histc<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    let mut values = random(20_000);
+    values[0] = -1.0;
+    values[1] = 1.0;
+    let data: Vec<F> = values.iter().map(|value| F::new(*value)).collect();
+    let input = create_tensor::<R, F>(&client, vec![values.len()], &data);
+
+    let counts = histogram::histc::<R, F>(&client, input.as_ref(), 50, -1.0, 1.0);
+
+    assert_eq!(
+        read_tensor(&client, &counts),
+        host_histc(&values, 50, -1.0, 1.0)
+    );
+}
+
+pub fn test_histc_permuted<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    let values = random(64 * 50);
+    let data: Vec<F> = values.iter().map(|value| F::new(*value)).collect();
+    let input = create_tensor::<R, F>(&client, vec![64, 50], &data);
+    let input = tensor::permute::<R, F>(input.as_ref(), &[1, 0]);
+
+    let counts = histogram::histc::<R, F>(&client, input.as_ref(), 10, -0.5, 1.5);
+
+    assert_eq!(
+        read_tensor(&client, &counts),
+        host_histc(&values, 10, -0.5, 1.5)
+    );
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_histogram {
+    () => {
+        mod histogram {
+            use super::*;
+            use cubecl_linalg::histogram::tests;
+
+            pub type FloatT = f32;
+
+            #[test]
+            pub fn test_bincount() {
+                tests::test_bincount::<TestRuntime>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_bincount_many_bins() {
+                tests::test_bincount_many_bins::<TestRuntime>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_histc() {
+                tests::test_histc::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_histc_permuted() {
+                tests::test_histc_permuted::<TestRuntime, FloatT>(&Default::default())
+            }
+        }
+    };
+}
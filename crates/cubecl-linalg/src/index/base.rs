@@ -0,0 +1,279 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise, ExecutionMode};
+
+use crate::tensor::TensorHandle;
+
+use super::kernel::{
+    gather_kernel, scatter_add_atomic_kernel, scatter_add_cas_kernel, scatter_kernel,
+};
+
+/// Gather the elements of the input along `axis` at the given indices.
+///
+/// The output has the shape of the indices, its element at coordinates `[.., j, ..]` being the
+/// element of the input at `[.., indices[.., j, ..], ..]` with `j` along `axis`. The indices
+/// must have the rank of the input and can't be larger than it along the other axes.
+///
+/// In [checked](ExecutionMode::Checked) mode, out of range indices read zeros. In
+/// [unchecked](ExecutionMode::Unchecked) mode, they are undefined behavior.
+pub fn gather<R: Runtime, E: Numeric, I: Int>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    axis: usize,
+    indices: TensorHandleRef<'_, R>,
+    mode: ExecutionMode,
+) -> TensorHandle<R, E> {
+    check_shapes("gather", input.shape, indices.shape, axis);
+    let output = TensorHandle::<R, E>::empty(client, indices.shape.to_vec());
+
+    let num_elements: usize = indices.shape.iter().product();
+    if num_elements == 0 {
+        return output;
+    }
+
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(num_elements, cube_dim);
+    let input = input.as_tensor_arg(1);
+    let indices = indices.as_tensor_arg(1);
+    let output_arg = output.as_arg(1);
+    let axis = ScalarArg::new(axis as u32);
+
+    match mode {
+        ExecutionMode::Checked => gather_kernel::launch::<E, I, R>(
+            client, cube_count, cube_dim, input, indices, output_arg, axis, true,
+        ),
+        ExecutionMode::Unchecked => unsafe {
+            gather_kernel::launch_unchecked::<E, I, R>(
+                client, cube_count, cube_dim, input, indices, output_arg, axis, false,
+            )
+        },
+    }
+
+    output
+}
+
+/// Select the slices of the input along `axis` at the given indices.
+///
+/// The indices are a tensor of rank 1, and the output has the shape of the input with the size
+/// of `axis` replaced by their number. The modes are the same as for [gather].
+pub fn index_select<R: Runtime, E: Numeric, I: Int>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    axis: usize,
+    indices: TensorHandleRef<'_, R>,
+    mode: ExecutionMode,
+) -> TensorHandle<R, E> {
+    assert_eq!(
+        indices.shape.len(),
+        1,
+        "The indices of index_select must have a rank of 1"
+    );
+    assert!(
+        axis < input.shape.len(),
+        "The axis {axis} is out of bounds of a tensor of rank {}",
+        input.shape.len()
+    );
+
+    // Broadcast the indices to the shape of the output, so that the selection is a gather.
+    let mut shape = input.shape.to_vec();
+    shape[axis] = indices.shape[0];
+    let mut strides = vec![0; shape.len()];
+    strides[axis] = indices.strides[0];
+    let indices = unsafe {
+        TensorHandleRef::<R>::from_raw_parts(indices.handle, &strides, &shape, I::as_elem().size())
+    };
+
+    gather::<R, E, I>(client, input, axis, indices, mode)
+}
+
+/// Write the elements of the source into the output along `axis` at the given indices.
+///
+/// The element of the source at coordinates `[.., j, ..]` is written at
+/// `[.., indices[.., j, ..], ..]` in the output, with `j` along `axis`. The indices must have the
+/// rank of the output, can't be larger than it along the other axes, and can't be larger than
+/// the source along any axis. When several elements are written at the same position, which one
+/// is kept is unspecified.
+///
+/// In [checked](ExecutionMode::Checked) mode, the elements with an out of range index are
+/// skipped. In [unchecked](ExecutionMode::Unchecked) mode, they are undefined behavior.
+pub fn scatter<R: Runtime, E: Numeric, I: Int>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    output: TensorHandleRef<'_, R>,
+    axis: usize,
+    indices: TensorHandleRef<'_, R>,
+    source: TensorHandleRef<'_, R>,
+    mode: ExecutionMode,
+) {
+    check_scatter_shapes("scatter", output.shape, indices.shape, source.shape, axis);
+
+    let num_elements: usize = indices.shape.iter().product();
+    if num_elements == 0 {
+        return;
+    }
+
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(num_elements, cube_dim);
+    let indices = indices.as_tensor_arg(1);
+    let source = source.as_tensor_arg(1);
+    let output = output.as_tensor_arg(1);
+    let axis = ScalarArg::new(axis as u32);
+
+    match mode {
+        ExecutionMode::Checked => scatter_kernel::launch::<E, I, R>(
+            client, cube_count, cube_dim, indices, source, output, axis, true,
+        ),
+        ExecutionMode::Unchecked => unsafe {
+            scatter_kernel::launch_unchecked::<E, I, R>(
+                client, cube_count, cube_dim, indices, source, output, axis, false,
+            )
+        },
+    }
+}
+
+/// Add the elements of the source to the output along `axis` at the given indices.
+///
+/// The layout of the indices and the modes are the same as for [scatter], all the elements
+/// written at the same position being accumulated.
+pub fn scatter_add<R: Runtime, E: ScatterAddElement, I: Int>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    output: TensorHandleRef<'_, R>,
+    axis: usize,
+    indices: TensorHandleRef<'_, R>,
+    source: TensorHandleRef<'_, R>,
+    mode: ExecutionMode,
+) {
+    check_scatter_shapes(
+        "scatter_add",
+        output.shape,
+        indices.shape,
+        source.shape,
+        axis,
+    );
+
+    let num_elements: usize = indices.shape.iter().product();
+    if num_elements == 0 {
+        return;
+    }
+
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(num_elements, cube_dim);
+
+    E::launch_scatter_add::<R, I>(
+        client,
+        cube_count,
+        cube_dim,
+        indices.as_tensor_arg(1),
+        source.as_tensor_arg(1),
+        output.as_tensor_arg(1),
+        ScalarArg::new(axis as u32),
+        mode,
+    );
+}
+
+/// An element that can be accumulated by [scatter_add].
+///
+/// Integers are added with the atomic add of the backend, while `f32` relies on a compare and
+/// swap loop over its bits since float atomics aren't available on every backend.
+pub trait ScatterAddElement: Numeric {
+    /// Launch the scatter add kernel for this element type.
+    #[allow(clippy::too_many_arguments)]
+    fn launch_scatter_add<'a, R: Runtime, I: Int>(
+        client: &ComputeClient<R::Server, R::Channel>,
+        cube_count: CubeCount,
+        cube_dim: CubeDim,
+        indices: TensorArg<'a, R>,
+        source: TensorArg<'a, R>,
+        output: TensorArg<'a, R>,
+        axis: ScalarArg<u32>,
+        mode: ExecutionMode,
+    );
+}
+
+macro_rules! impl_scatter_add_atomic {
+    ($primitive:ty, $atomic:ty) => {
+        impl ScatterAddElement for $primitive {
+            fn launch_scatter_add<'a, R: Runtime, I: Int>(
+                client: &ComputeClient<R::Server, R::Channel>,
+                cube_count: CubeCount,
+                cube_dim: CubeDim,
+                indices: TensorArg<'a, R>,
+                source: TensorArg<'a, R>,
+                output: TensorArg<'a, R>,
+                axis: ScalarArg<u32>,
+                mode: ExecutionMode,
+            ) {
+                match mode {
+                    ExecutionMode::Checked => scatter_add_atomic_kernel::launch::<$atomic, I, R>(
+                        client, cube_count, cube_dim, indices, source, output, axis, true,
+                    ),
+                    ExecutionMode::Unchecked => unsafe {
+                        scatter_add_atomic_kernel::launch_unchecked::<$atomic, I, R>(
+                            client, cube_count, cube_dim, indices, source, output, axis, false,
+                        )
+                    },
+                }
+            }
+        }
+    };
+}
+
+impl_scatter_add_atomic!(i32, AtomicI32);
+impl_scatter_add_atomic!(u32, AtomicU32);
+
+impl ScatterAddElement for f32 {
+    fn launch_scatter_add<'a, R: Runtime, I: Int>(
+        client: &ComputeClient<R::Server, R::Channel>,
+        cube_count: CubeCount,
+        cube_dim: CubeDim,
+        indices: TensorArg<'a, R>,
+        source: TensorArg<'a, R>,
+        output: TensorArg<'a, R>,
+        axis: ScalarArg<u32>,
+        mode: ExecutionMode,
+    ) {
+        match mode {
+            ExecutionMode::Checked => scatter_add_cas_kernel::launch::<I, R>(
+                client, cube_count, cube_dim, indices, source, output, axis, true,
+            ),
+            ExecutionMode::Unchecked => unsafe {
+                scatter_add_cas_kernel::launch_unchecked::<I, R>(
+                    client, cube_count, cube_dim, indices, source, output, axis, false,
+                )
+            },
+        }
+    }
+}
+
+fn check_shapes(name: &str, tensor: &[usize], indices: &[usize], axis: usize) {
+    assert_eq!(
+        tensor.len(),
+        indices.len(),
+        "The indices of {name} must have the rank of the tensor"
+    );
+    assert!(
+        axis < tensor.len(),
+        "The axis {axis} is out of bounds of a tensor of rank {}",
+        tensor.len()
+    );
+    for (dim, (size, num_indices)) in tensor.iter().zip(indices).enumerate() {
+        assert!(
+            dim == axis || num_indices <= size,
+            "The indices of {name} of shape {indices:?} are larger than the tensor of shape \
+             {tensor:?} along the axis {dim}"
+        );
+    }
+}
+
+fn check_scatter_shapes(
+    name: &str,
+    output: &[usize],
+    indices: &[usize],
+    source: &[usize],
+    axis: usize,
+) {
+    check_shapes(name, output, indices, axis);
+    assert!(
+        source.len() == indices.len() && indices.iter().zip(source).all(|(i, s)| i <= s),
+        "The indices of {name} of shape {indices:?} are larger than the source of shape \
+         {source:?}"
+    );
+}
@@ -0,0 +1,171 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+/// The offsets of the element at `position` in the row major order of the indices, in the
+/// indices, in the source and in the target tensor, the coordinate along `axis` of the target
+/// being left out.
+#[cube]
+fn offsets<I: Int, S: CubePrimitive, T: CubePrimitive>(
+    indices: &Tensor<I>,
+    source: &Tensor<S>,
+    target: &Tensor<T>,
+    position: u32,
+    axis: u32,
+) -> (u32, u32, u32) {
+    let rank = indices.rank();
+    let mut remaining = position;
+    let mut index_offset = 0;
+    let mut source_offset = 0;
+    let mut target_offset = 0;
+
+    for i in 0..rank {
+        let dim = rank - 1 - i;
+        let coordinate = remaining % indices.shape(dim);
+        remaining /= indices.shape(dim);
+
+        index_offset += coordinate * indices.stride(dim);
+        source_offset += coordinate * source.stride(dim);
+        target_offset += select(dim == axis, 0, coordinate * target.stride(dim));
+    }
+
+    (index_offset, source_offset, target_offset)
+}
+
+/// Gather the elements of the input along `axis` at the given indices, the output having the
+/// shape of the indices.
+///
+/// With `checked`, out of range indices read zeros.
+#[cube(launch, launch_unchecked)]
+pub(crate) fn gather_kernel<E: Numeric, I: Int>(
+    input: &Tensor<E>,
+    indices: &Tensor<I>,
+    output: &mut Tensor<E>,
+    axis: u32,
+    #[comptime] checked: bool,
+) {
+    if ABSOLUTE_POS >= indices.len() {
+        return;
+    }
+
+    let (index_offset, output_offset, input_offset) =
+        offsets::<I, E, E>(indices, output, input, ABSOLUTE_POS, axis);
+    let index = u32::cast_from(indices[index_offset]);
+    let input_offset = input_offset + index * input.stride(axis);
+
+    if comptime!(checked) {
+        if index < input.shape(axis) {
+            output[output_offset] = input[input_offset];
+        } else {
+            output[output_offset] = E::from_int(0);
+        }
+    } else {
+        output[output_offset] = input[input_offset];
+    }
+}
+
+/// Write the elements of the source into the output along `axis` at the given indices.
+///
+/// With `checked`, the elements with an out of range index are skipped.
+#[cube(launch, launch_unchecked)]
+pub(crate) fn scatter_kernel<E: Numeric, I: Int>(
+    indices: &Tensor<I>,
+    source: &Tensor<E>,
+    output: &mut Tensor<E>,
+    axis: u32,
+    #[comptime] checked: bool,
+) {
+    if ABSOLUTE_POS >= indices.len() {
+        return;
+    }
+
+    let (index_offset, source_offset, output_offset) =
+        offsets::<I, E, E>(indices, source, output, ABSOLUTE_POS, axis);
+    let index = u32::cast_from(indices[index_offset]);
+    let output_offset = output_offset + index * output.stride(axis);
+
+    if comptime!(checked) {
+        if index < output.shape(axis) {
+            output[output_offset] = source[source_offset];
+        }
+    } else {
+        output[output_offset] = source[source_offset];
+    }
+}
+
+/// Add the elements of the source to the output along `axis` at the given indices, with the
+/// atomic add of the element type.
+///
+/// With `checked`, the elements with an out of range index are skipped.
+#[cube(launch, launch_unchecked)]
+pub(crate) fn scatter_add_atomic_kernel<A: CubePrimitive + Atomic, I: Int>(
+    indices: &Tensor<I>,
+    source: &Tensor<A::Primitive>,
+    output: &mut Tensor<A>,
+    axis: u32,
+    #[comptime] checked: bool,
+) {
+    if ABSOLUTE_POS >= indices.len() {
+        return;
+    }
+
+    let (index_offset, source_offset, output_offset) =
+        offsets::<I, A::Primitive, A>(indices, source, output, ABSOLUTE_POS, axis);
+    let index = u32::cast_from(indices[index_offset]);
+    let output_offset = output_offset + index * output.stride(axis);
+
+    if comptime!(checked) {
+        if index < output.shape(axis) {
+            A::add(&output[output_offset], source[source_offset]);
+        }
+    } else {
+        A::add(&output[output_offset], source[source_offset]);
+    }
+}
+
+/// Add the `f32` elements of the source to the output along `axis` at the given indices.
+///
+/// The output is bound as `u32` words, each addition being retried with a compare and swap until
+/// no other unit wrote the same element in between, for the backends without float atomics.
+///
+/// With `checked`, the elements with an out of range index are skipped.
+#[cube(launch, launch_unchecked)]
+pub(crate) fn scatter_add_cas_kernel<I: Int>(
+    indices: &Tensor<I>,
+    source: &Tensor<f32>,
+    output: &mut Tensor<AtomicU32>,
+    axis: u32,
+    #[comptime] checked: bool,
+) {
+    if ABSOLUTE_POS >= indices.len() {
+        return;
+    }
+
+    let (index_offset, source_offset, output_offset) =
+        offsets::<I, f32, AtomicU32>(indices, source, output, ABSOLUTE_POS, axis);
+    let index = u32::cast_from(indices[index_offset]);
+    let output_offset = output_offset + index * output.stride(axis);
+
+    if comptime!(checked) {
+        if index < output.shape(axis) {
+            add_cas(output, output_offset, source[source_offset]);
+        }
+    } else {
+        add_cas(output, output_offset, source[source_offset]);
+    }
+}
+
+/// Atomically add the value to the `f32` stored at the offset, as a compare and swap loop.
+#[cube]
+fn add_cas(output: &mut Tensor<AtomicU32>, offset: u32, value: f32) {
+    let mut current = AtomicU32::load(&output[offset]);
+
+    loop {
+        let sum = f32::bitcast_from(current) + value;
+        let previous =
+            AtomicU32::compare_and_swap(&output[offset], current, u32::bitcast_from(sum));
+        if previous == current {
+            break;
+        }
+        current = previous;
+    }
+}
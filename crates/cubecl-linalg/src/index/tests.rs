@@ -0,0 +1,202 @@
+#![allow(missing_docs)]
+
+use cubecl_core::{prelude::*, CubeElement, ExecutionMode};
+
+use crate::{
+    index::{self, ScatterAddElement},
+    matmul::tests::test_utils::{create_float_tensor, create_tensor, read_f32, read_tensor},
+};
+
+fn range(num_elements: usize) -> Vec<f32> {
+    (0..num_elements).map(|i| i as f32).collect()
+}
+
+pub fn test_gather<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    let input = create_float_tensor::<R, F>(&client, vec![3, 4], &range(12));
+    let indices = create_tensor::<R, i32>(&client, vec![3, 2], &[3, 0, 1, 1, 2, 0]);
+
+    let output = index::gather::<R, F, i32>(
+        &client,
+        input.as_ref(),
+        1,
+        indices.as_ref(),
+        ExecutionMode::Unchecked,
+    );
+
+    assert_eq!(output.shape, vec![3, 2]);
+    assert_eq!(read_f32(&client, &output), [3.0, 0.0, 5.0, 5.0, 10.0, 8.0]);
+}
+
+pub fn test_gather_out_of_range_reads_zeros<R: Runtime, F: Float + CubeElement>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let input = create_float_tensor::<R, F>(&client, vec![2, 3], &range(6));
+    let indices = create_tensor::<R, i32>(&client, vec![3, 3], &[1, 0, 3, 0, -1, 1, 7, 1, 0]);
+
+    let output = index::gather::<R, F, i32>(
+        &client,
+        input.as_ref(),
+        0,
+        indices.as_ref(),
+        ExecutionMode::Checked,
+    );
+
+    assert_eq!(output.shape, vec![3, 3]);
+    assert_eq!(
+        read_f32(&client, &output),
+        [3.0, 1.0, 0.0, 0.0, 0.0, 5.0, 0.0, 4.0, 2.0]
+    );
+}
+
+pub fn test_index_select<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    let input = create_float_tensor::<R, F>(&client, vec![2, 4, 3], &range(24));
+    let indices = create_tensor::<R, u32>(&client, vec![3], &[3, 0, 3]);
+
+    let output = index::index_select::<R, F, u32>(
+        &client,
+        input.as_ref(),
+        1,
+        indices.as_ref(),
+        ExecutionMode::Checked,
+    );
+
+    assert_eq!(output.shape, vec![2, 3, 3]);
+    let expected: Vec<f32> = (0..2)
+        .flat_map(|batch| {
+            [3, 0, 3]
+                .into_iter()
+                .flat_map(move |row| (0..3).map(move |col| (batch * 12 + row * 3 + col) as f32))
+        })
+        .collect();
+    assert_eq!(read_f32(&client, &output), expected);
+}
+
+pub fn test_scatter<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    let output = create_float_tensor::<R, F>(&client, vec![3, 5], &[0.0; 15]);
+    // The source is larger than the indices, its extra column is ignored.
+    let source = create_float_tensor::<R, F>(&client, vec![3, 3], &range(10)[1..]);
+    let indices = create_tensor::<R, u32>(&client, vec![3, 2], &[4, 0, 1, 2, 3, 9]);
+
+    index::scatter::<R, F, u32>(
+        &client,
+        output.as_ref(),
+        1,
+        indices.as_ref(),
+        source.as_ref(),
+        ExecutionMode::Checked,
+    );
+
+    assert_eq!(
+        read_f32(&client, &output),
+        [
+            2.0, 0.0, 0.0, 0.0, 1.0, //
+            0.0, 4.0, 5.0, 0.0, 0.0, //
+            0.0, 0.0, 0.0, 7.0, 0.0,
+        ]
+    );
+}
+
+pub fn test_scatter_add<R: Runtime, F: Float + CubeElement + ScatterAddElement>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let num_elements = 4096;
+    let output = create_float_tensor::<R, F>(&client, vec![2, 5], &range(10));
+    let source: Vec<f32> = (0..2 * num_elements).map(|i| (i % 7) as f32).collect();
+    let source = create_float_tensor::<R, F>(&client, vec![2, num_elements], &source);
+    // Every fifth index is out of range and skipped.
+    let indices: Vec<i32> = (0..2 * num_elements).map(|i| (i % 5) as i32 - 1).collect();
+    let indices = create_tensor::<R, i32>(&client, vec![2, num_elements], &indices);
+
+    index::scatter_add::<R, F, i32>(
+        &client,
+        output.as_ref(),
+        1,
+        indices.as_ref(),
+        source.as_ref(),
+        ExecutionMode::Checked,
+    );
+
+    let mut expected = range(10);
+    for i in 0..2 * num_elements {
+        let index = (i % 5) as i32 - 1;
+        if index >= 0 {
+            expected[i / num_elements * 5 + index as usize] += (i % 7) as f32;
+        }
+    }
+    assert_eq!(read_f32(&client, &output), expected);
+}
+
+pub fn test_scatter_add_int<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let num_elements = 10_000;
+    let output = create_tensor::<R, u32>(&client, vec![3], &[0, 10, 20]);
+    let source: Vec<u32> = (0..num_elements as u32).collect();
+    let source = create_tensor::<R, u32>(&client, vec![num_elements], &source);
+    let indices: Vec<u32> = (0..num_elements as u32).map(|i| i % 3).collect();
+    let indices = create_tensor::<R, u32>(&client, vec![num_elements], &indices);
+
+    index::scatter_add::<R, u32, u32>(
+        &client,
+        output.as_ref(),
+        0,
+        indices.as_ref(),
+        source.as_ref(),
+        ExecutionMode::Unchecked,
+    );
+
+    let mut expected = [0, 10, 20];
+    for i in 0..num_elements as u32 {
+        expected[(i % 3) as usize] += i;
+    }
+    assert_eq!(read_tensor(&client, &output), expected);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_index {
+    () => {
+        mod index {
+            use super::*;
+            use cubecl_linalg::index::tests;
+
+            pub type FloatT = f32;
+
+            #[test]
+            pub fn test_gather() {
+                tests::test_gather::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_gather_out_of_range_reads_zeros() {
+                tests::test_gather_out_of_range_reads_zeros::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_index_select() {
+                tests::test_index_select::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_scatter() {
+                tests::test_scatter::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_scatter_add() {
+                tests::test_scatter_add::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_scatter_add_int() {
+                tests::test_scatter_add_int::<TestRuntime>(&Default::default())
+            }
+        }
+    };
+}
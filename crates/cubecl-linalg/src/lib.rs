@@ -1,5 +1,44 @@
+/// Contains fused attention kernels built on matmul components.
+pub mod attention;
+
+/// Contains implicit GEMM convolution kernels built on matmul components.
+pub mod conv;
+
+/// Contains fast Fourier transform kernels.
+pub mod fft;
+
+/// Contains a builder of fused elementwise kernels over an expression graph.
+pub mod fusion;
+
+/// Contains histogram and bincount kernels.
+pub mod histogram;
+
+/// Contains gather, scatter and index-select kernels.
+pub mod index;
+
 /// Contains matmul kernels and Cube components
 pub mod matmul;
 
+/// Contains softmax and normalization kernels along the last axis.
+pub mod normalization;
+
+/// Contains kernels filling tensors with random samples.
+pub mod random;
+
+/// Contains reduce kernels along a tensor axis.
+pub mod reduce;
+
+/// Contains prefix scan kernels along a tensor axis.
+pub mod scan;
+
+/// Contains Cholesky and LU factorizations and triangular solves of batched matrices.
+pub mod solve;
+
+/// Contains radix sort, argsort and top-k kernels.
+pub mod sort;
+
+/// Contains sparse matrix formats and sparse matrix products.
+pub mod sparse;
+
 /// Contains basic tensor helpers.
 pub mod tensor;
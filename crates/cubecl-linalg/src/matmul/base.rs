@@ -1,23 +1,41 @@
 use cubecl_core::{
     client::ComputeClient,
     prelude::{Float, TensorHandleRef},
-    Runtime,
+    CubeElement, Runtime,
 };
 
 use crate::tensor::TensorHandle;
 
 use super::kernels::{
-    cmma_matmul,
+    cmma_matmul::{self, BatchStrategy, MatmulEpilogue, MatmulUnavailable},
     cmma_old::{self, config::PredefinedCmmaConfig, is_available, CmmaConfig},
+    gemv,
     tiling2d::{self, Tiling2dConfig},
 };
+use super::tune::matmul_autotune;
 
+/// Matmul algorithm to launch.
+///
+/// With [launch], `Accelerated` and `PlaneMma` are routed to `Gemv` when m or n is 1, since
+/// their tiles would mostly be padding. The other strategies, and the launches with epilogues
+/// or quantized inputs, always use the algorithm they name.
 #[derive(Debug)]
 pub enum Strategy {
     Accelerated,
     PlaneMma,
     CmmaOld(CmmaConfig),
     Tiling2D(Tiling2dConfig),
+    /// Splits k into at most `num_splits` ranges solved by different cubes,
+    /// for problems with small m and n but a large k.
+    SplitK {
+        num_splits: u32,
+    },
+    /// Shares the k iterations of all stages of the output between at most `num_cubes` cubes.
+    StreamK {
+        num_cubes: u32,
+    },
+    /// Matrix-vector or vector-matrix product, for problems where m or n is 1.
+    Gemv,
 }
 
 pub fn launch<R: Runtime, EG: Float>(
@@ -28,19 +46,121 @@ pub fn launch<R: Runtime, EG: Float>(
     out: TensorHandle<R, EG>,
 ) {
     match strategy {
+        Strategy::Accelerated | Strategy::PlaneMma if gemv::is_gemv(&lhs.shape, &rhs.shape) => {
+            gemv::launch(client, lhs, rhs, out)
+        }
         Strategy::Accelerated => cmma_matmul::launch(client, lhs, rhs, out, false),
         Strategy::PlaneMma => cmma_matmul::launch(client, lhs, rhs, out, true),
         Strategy::CmmaOld(config) => cmma_old::launch(client, lhs, rhs, out, config.clone()),
         Strategy::Tiling2D(config) => tiling2d::launch(client, lhs, rhs, out, config.clone()),
+        Strategy::SplitK { num_splits } => cmma_matmul::launch_with_batch_strategy(
+            client,
+            lhs,
+            rhs,
+            out,
+            false,
+            BatchStrategy::SplitK {
+                num_splits: *num_splits,
+            },
+        ),
+        Strategy::StreamK { num_cubes } => cmma_matmul::launch_with_batch_strategy(
+            client,
+            lhs,
+            rhs,
+            out,
+            false,
+            BatchStrategy::StreamK {
+                num_cubes: *num_cubes,
+            },
+        ),
+        Strategy::Gemv => gemv::launch(client, lhs, rhs, out),
     };
 }
 
+/// Launch a matrix multiplication with the given strategy, fusing the epilogue to the
+/// writing of the output.
+///
+/// # Panics
+///
+/// If the strategy doesn't support fused epilogues. Only `Accelerated` and `PlaneMma` do.
+pub fn launch_with_epilogue<R: Runtime, EG: Float>(
+    strategy: &Strategy,
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandle<R, EG>,
+    rhs: TensorHandle<R, EG>,
+    out: TensorHandle<R, EG>,
+    epilogue: MatmulEpilogue<'_, R>,
+) {
+    match strategy {
+        Strategy::Accelerated => {
+            cmma_matmul::launch_with_epilogue(client, lhs, rhs, out, false, epilogue)
+        }
+        Strategy::PlaneMma => {
+            cmma_matmul::launch_with_epilogue(client, lhs, rhs, out, true, epilogue)
+        }
+        _ => panic!("Strategy {:?} doesn't support fused epilogues", strategy),
+    };
+}
+
+/// Launch a quantized matrix multiplication on `i8` inputs with the given strategy,
+/// dequantizing the product to `EO` with the epilogue.
+///
+/// # Errors
+///
+/// If the device doesn't support `i8` or `EO`, in which case nothing is launched.
+///
+/// # Panics
+///
+/// If the strategy doesn't support quantized inputs. Only `Accelerated` and `PlaneMma` do.
+pub fn launch_quantized<R: Runtime, EO: Float>(
+    strategy: &Strategy,
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandle<R, i8>,
+    rhs: TensorHandle<R, i8>,
+    out: TensorHandle<R, EO>,
+    epilogue: MatmulEpilogue<'_, R>,
+) -> Result<(), MatmulUnavailable> {
+    match strategy {
+        Strategy::Accelerated => {
+            cmma_matmul::launch_quantized(client, lhs, rhs, out, false, epilogue)?
+        }
+        Strategy::PlaneMma => cmma_matmul::launch_quantized(client, lhs, rhs, out, true, epilogue)?,
+        _ => panic!("Strategy {:?} doesn't support quantized inputs", strategy),
+    };
+
+    Ok(())
+}
+
+/// Launch the fastest matrix multiplication strategy for the given tensors.
+///
+/// The `Accelerated`, `PlaneMma`, `Tiling2D` and the block size presets of `CmmaOld`
+/// strategies are benchmarked the first time a problem of similar shape, layouts and
+/// element type is seen, and the fastest one is kept in the tune cache.
+///
+/// Problems where m or n is 1 always use the `Gemv` strategy.
+pub fn launch_autotune<R: Runtime, EG: Float + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandleRef<R>,
+    rhs: TensorHandleRef<R>,
+    out: TensorHandleRef<R>,
+) {
+    if gemv::is_gemv(lhs.shape, rhs.shape) {
+        return gemv::launch_ref::<R, EG>(client, lhs, rhs, out);
+    }
+
+    matmul_autotune::<R, EG>(client, lhs, rhs, out);
+}
+
 pub fn launch_ref<R: Runtime, EG: Float>(
     client: &ComputeClient<R::Server, R::Channel>,
     lhs: TensorHandleRef<R>,
     rhs: TensorHandleRef<R>,
     out: TensorHandleRef<R>,
 ) {
+    if gemv::is_gemv(lhs.shape, rhs.shape) {
+        return gemv::launch_ref::<R, EG>(client, lhs, rhs, out);
+    }
+
     let cmma_config = PredefinedCmmaConfig::M128K16.into();
 
     match is_available::<R, EG>(client, &cmma_config) {
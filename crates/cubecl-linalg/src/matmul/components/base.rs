@@ -1,6 +1,7 @@
 use cubecl_core::prelude::*;
 
 use super::config::MatmulConfig;
+use super::global::EpilogueArgs;
 
 /// Provides configuration for a matmul kernel at any level
 pub trait MatmulKernel<I: Numeric, O: Numeric> {
@@ -18,6 +19,7 @@ pub trait MatmulLaunch<I: Numeric, O: Numeric>: MatmulKernel<I, O> {
     /// # Safety
     ///
     /// Out-of-bounds can happen
+    #[allow(clippy::too_many_arguments)]
     unsafe fn launch_unchecked<R: Runtime>(
         client: &ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel>,
         cube_dim: CubeDim,
@@ -25,6 +27,7 @@ pub trait MatmulLaunch<I: Numeric, O: Numeric>: MatmulKernel<I, O> {
         lhs: TensorArg<'_, R>,
         rhs: TensorArg<'_, R>,
         out: TensorArg<'_, R>,
+        epilogue: EpilogueArgs<'_, R>,
         config: <Self as MatmulKernel<I, O>>::Config,
     );
 }
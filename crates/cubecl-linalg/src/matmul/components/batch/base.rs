@@ -1,6 +1,7 @@
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 
+use crate::matmul::components::global::EpilogueInputs;
 use crate::matmul::components::{
     config::MatmulConfig, global, Ident, MatmulKernel, MatmulLaunch, StageDim,
 };
@@ -23,14 +24,16 @@ use crate::matmul::components::{
 /// It is not assumed that the matmul's dimensions match its inputs dimensions perfectly.
 /// It is therefore important to use an underlying global matmul that performs check bounds,
 /// and to not launch more Cubes than necessary.
-pub trait Matmul<EG: Numeric>:
-    'static + Send + Sync + MatmulKernel<EG, EG, Config: Config> + MatmulLaunch<EG, EG>
+pub trait Matmul<EG: Numeric, EO: Numeric>:
+    'static + Send + Sync + MatmulKernel<EG, EO, Config: Config> + MatmulLaunch<EG, EO>
 {
-    /// Performs batchwise matrix multiplication over tensors.
+    /// Performs batchwise matrix multiplication over tensors,
+    /// reading the given inputs for the epilogue of the config.
     fn execute(
         lhs: &Tensor<Line<EG>>,
         rhs: &Tensor<Line<EG>>,
-        out: &mut Tensor<Line<EG>>,
+        out: &mut Tensor<Line<EO>>,
+        epilogue: EpilogueInputs<EO>,
         #[comptime] config: Self::Config,
     );
 }
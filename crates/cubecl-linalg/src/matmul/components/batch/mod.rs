@@ -1,4 +1,6 @@
 mod base;
 pub mod one_to_one;
+pub mod split_k;
+pub mod stream_k;
 
 pub use base::*;
@@ -1,5 +1,6 @@
 use std::marker::PhantomData;
 
+use crate::matmul::components::global::{EpilogueArgs, EpilogueInputs};
 use crate::matmul::components::{
     batch, config::MatmulConfig, global, Ident, MatmulKernel, MatmulLaunch, StageDim,
 };
@@ -12,16 +13,19 @@ use super::Config as _;
 /// with one cube assigned to each underlying global matmul
 pub struct Matmul<
     EG: Numeric,
+    EO: Numeric,
     ES: Numeric,
     GMM: global::Matmul<
         EG,
+        EO,
         ES,
         global::tensor_view::LhsLoader<EG, ES>,
         global::tensor_view::RhsLoader<EG, ES>,
-        global::tensor_view::Unloader<EG>,
+        global::tensor_view::Unloader<EO>,
     >,
 > {
     _eg: PhantomData<EG>,
+    _eo: PhantomData<EO>,
     _es: PhantomData<ES>,
     _gmm: PhantomData<GMM>,
 }
@@ -29,20 +33,23 @@ pub struct Matmul<
 #[cube]
 impl<
         EG: Numeric,
+        EO: Numeric,
         ES: Numeric,
         GMM: global::Matmul<
             EG,
+            EO,
             ES,
             global::tensor_view::LhsLoader<EG, ES>,
             global::tensor_view::RhsLoader<EG, ES>,
-            global::tensor_view::Unloader<EG>,
+            global::tensor_view::Unloader<EO>,
         >,
-    > batch::Matmul<EG> for Matmul<EG, ES, GMM>
+    > batch::Matmul<EG, EO> for Matmul<EG, EO, ES, GMM>
 {
     fn execute(
         lhs: &Tensor<Line<EG>>,
         rhs: &Tensor<Line<EG>>,
-        out: &mut Tensor<Line<EG>>,
+        out: &mut Tensor<Line<EO>>,
+        epilogue: EpilogueInputs<EO>,
         #[comptime] config: Self::Config,
     ) {
         // TODO row/col/swizzle
@@ -51,22 +58,32 @@ impl<
         let nth_batch = CUBE_POS_Z;
         let k_range = (0, lhs.shape(lhs.rank() - 1));
 
+        let lhs_batch_offset = global::tensor_view::batch_offset(lhs, out, nth_batch);
+        let rhs_batch_offset = global::tensor_view::batch_offset(rhs, out, nth_batch);
+        let out_batch_offset = global::tensor_view::batch_offset(out, out, nth_batch);
+
         GMM::execute(
             global::tensor_view::LhsLoader::new::<<Self::Config as batch::Config>::GmmConfig>(
                 lhs,
                 x_offset,
                 k_range.0,
-                nth_batch,
+                lhs_batch_offset,
                 config.to_gmm_config(),
             ),
             global::tensor_view::RhsLoader::new::<<Self::Config as batch::Config>::GmmConfig>(
                 rhs,
                 k_range.0,
                 y_offset,
-                nth_batch,
+                rhs_batch_offset,
                 config.to_gmm_config(),
             ),
-            global::tensor_view::Unloader::new(out, x_offset, y_offset, nth_batch),
+            global::tensor_view::Unloader::with_epilogue(
+                out,
+                epilogue,
+                x_offset,
+                y_offset,
+                out_batch_offset,
+            ),
             k_range,
             config.to_gmm_config(),
         );
@@ -75,15 +92,17 @@ impl<
 
 impl<
         EG: Numeric,
+        EO: Numeric,
         ES: Numeric,
         GMM: global::Matmul<
             EG,
+            EO,
             ES,
             global::tensor_view::LhsLoader<EG, ES>,
             global::tensor_view::RhsLoader<EG, ES>,
-            global::tensor_view::Unloader<EG>,
+            global::tensor_view::Unloader<EO>,
         >,
-    > MatmulKernel<EG, EG> for Matmul<EG, ES, GMM>
+    > MatmulKernel<EG, EO> for Matmul<EG, EO, ES, GMM>
 {
     type Config = Config<GMM::Config>;
 
@@ -94,15 +113,17 @@ impl<
 
 impl<
         EG: Numeric,
+        EO: Numeric,
         ES: Numeric,
         GMM: global::Matmul<
             EG,
+            EO,
             ES,
             global::tensor_view::LhsLoader<EG, ES>,
             global::tensor_view::RhsLoader<EG, ES>,
-            global::tensor_view::Unloader<EG>,
+            global::tensor_view::Unloader<EO>,
         >,
-    > MatmulLaunch<EG, EG> for Matmul<EG, ES, GMM>
+    > MatmulLaunch<EG, EO> for Matmul<EG, EO, ES, GMM>
 {
     unsafe fn launch_unchecked<R: Runtime>(
         client: &ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel>,
@@ -111,23 +132,46 @@ impl<
         lhs: TensorArg<'_, R>,
         rhs: TensorArg<'_, R>,
         out: TensorArg<'_, R>,
+        epilogue: EpilogueArgs<'_, R>,
         config: Self::Config,
     ) {
         Self::check_config(config);
-        launch::launch_unchecked::<EG, Self, R>(
-            client, cube_count, cube_dim, lhs, rhs, out, config,
+        launch::launch_unchecked::<EG, EO, Self, R>(
+            client,
+            cube_count,
+            cube_dim,
+            lhs,
+            rhs,
+            out,
+            epilogue.scale,
+            epilogue.bias,
+            epilogue.residual,
+            ScalarArg::new(epilogue.alpha),
+            ScalarArg::new(epilogue.beta),
+            config,
         );
     }
 }
 
 #[cube(launch_unchecked)]
-fn launch<EG: Numeric, BMM: batch::Matmul<EG>>(
+fn launch<EG: Numeric, EO: Numeric, BMM: batch::Matmul<EG, EO>>(
     lhs: &Tensor<Line<EG>>,
     rhs: &Tensor<Line<EG>>,
-    out: &mut Tensor<Line<EG>>,
+    out: &mut Tensor<Line<EO>>,
+    scale: &Tensor<Line<EO>>,
+    bias: &Tensor<Line<EO>>,
+    residual: &Tensor<Line<EO>>,
+    alpha: f32,
+    beta: f32,
     #[comptime] config: BMM::Config,
 ) {
-    BMM::execute(lhs, rhs, out, config);
+    BMM::execute(
+        lhs,
+        rhs,
+        out,
+        EpilogueInputs::new(scale, bias, residual, alpha, beta),
+        config,
+    );
 }
 
 #[derive(CubeType, Copy, Clone, Debug, Hash, PartialEq, Eq)]
@@ -0,0 +1,285 @@
+use std::marker::PhantomData;
+
+use crate::matmul::components::global::{EpilogueArgs, EpilogueInputs};
+use crate::matmul::components::{
+    batch, config::MatmulConfig, global, Ident, MatmulKernel, MatmulLaunch, StageDim,
+};
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+use super::Config as _;
+use global::Config as _;
+
+/// Performs matrix multiplication at the batch level,
+/// with the k dimension split into ranges that are solved by different cubes
+///
+/// Each cube computes the partial product of one stage of the output over its k range.
+/// The output tensor holds these partial results: it has the shape of the matmul output,
+/// preceded by an extra dimension of size `num_splits`, and must be contiguous.
+/// Partial results must then be summed along that first dimension.
+pub struct Matmul<
+    EG: Numeric,
+    EO: Numeric,
+    ES: Numeric,
+    GMM: global::Matmul<
+        EG,
+        EO,
+        ES,
+        global::tensor_view::LhsLoader<EG, ES>,
+        global::tensor_view::RhsLoader<EG, ES>,
+        global::tensor_view::Unloader<EO>,
+    >,
+> {
+    _eg: PhantomData<EG>,
+    _eo: PhantomData<EO>,
+    _es: PhantomData<ES>,
+    _gmm: PhantomData<GMM>,
+}
+
+#[cube]
+impl<
+        EG: Numeric,
+        EO: Numeric,
+        ES: Numeric,
+        GMM: global::Matmul<
+            EG,
+            EO,
+            ES,
+            global::tensor_view::LhsLoader<EG, ES>,
+            global::tensor_view::RhsLoader<EG, ES>,
+            global::tensor_view::Unloader<EO>,
+        >,
+    > batch::Matmul<EG, EO> for Matmul<EG, EO, ES, GMM>
+{
+    fn execute(
+        lhs: &Tensor<Line<EG>>,
+        rhs: &Tensor<Line<EG>>,
+        out: &mut Tensor<Line<EO>>,
+        _epilogue: EpilogueInputs<EO>,
+        #[comptime] config: Self::Config,
+    ) {
+        let x_offset = CUBE_POS_X * config.stage_dim(Ident::Lhs).num_elements_x_dim();
+        let y_offset = CUBE_POS_Y * config.stage_dim(Ident::Rhs).num_elements_y_dim();
+        let nth_batch = CUBE_POS_Z % config.num_batches();
+        let nth_split = CUBE_POS_Z / config.num_batches();
+
+        let k_start = nth_split * config.k_per_split();
+        let k_range = (
+            k_start,
+            Min::min(k_start + config.k_per_split(), lhs.shape(lhs.rank() - 1)),
+        );
+
+        // The output holds the partial results of each split with a leading axis, so its
+        // innermost batch axes are those of the problem.
+        let lhs_batch_offset = global::tensor_view::batch_offset(lhs, out, nth_batch);
+        let rhs_batch_offset = global::tensor_view::batch_offset(rhs, out, nth_batch);
+        // Partial results of a split are stored after those of all batches of previous splits.
+        let out_batch_offset = global::tensor_view::batch_offset(out, out, CUBE_POS_Z);
+
+        GMM::execute(
+            global::tensor_view::LhsLoader::new::<<Self::Config as batch::Config>::GmmConfig>(
+                lhs,
+                x_offset,
+                k_range.0,
+                lhs_batch_offset,
+                config.to_gmm_config(),
+            ),
+            global::tensor_view::RhsLoader::new::<<Self::Config as batch::Config>::GmmConfig>(
+                rhs,
+                k_range.0,
+                y_offset,
+                rhs_batch_offset,
+                config.to_gmm_config(),
+            ),
+            global::tensor_view::Unloader::new(out, x_offset, y_offset, out_batch_offset),
+            k_range,
+            config.to_gmm_config(),
+        );
+    }
+}
+
+impl<
+        EG: Numeric,
+        EO: Numeric,
+        ES: Numeric,
+        GMM: global::Matmul<
+            EG,
+            EO,
+            ES,
+            global::tensor_view::LhsLoader<EG, ES>,
+            global::tensor_view::RhsLoader<EG, ES>,
+            global::tensor_view::Unloader<EO>,
+        >,
+    > MatmulKernel<EG, EO> for Matmul<EG, EO, ES, GMM>
+{
+    type Config = Config<GMM::Config>;
+
+    fn check_config(config: Self::Config) {
+        assert!(
+            config.to_gmm_config().epilogue().is_identity(),
+            "Partial results can't go through an epilogue"
+        );
+
+        let k_step = config.stage_dim(Ident::Lhs).num_elements_y_dim();
+        assert!(
+            config.k_per_split % k_step == 0,
+            "Each split must cover whole stages, got k_per_split={} with stages of k={}",
+            config.k_per_split,
+            k_step
+        );
+
+        GMM::check_config(config.to_gmm_config())
+    }
+}
+
+impl<
+        EG: Numeric,
+        EO: Numeric,
+        ES: Numeric,
+        GMM: global::Matmul<
+            EG,
+            EO,
+            ES,
+            global::tensor_view::LhsLoader<EG, ES>,
+            global::tensor_view::RhsLoader<EG, ES>,
+            global::tensor_view::Unloader<EO>,
+        >,
+    > MatmulLaunch<EG, EO> for Matmul<EG, EO, ES, GMM>
+{
+    unsafe fn launch_unchecked<R: Runtime>(
+        client: &ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel>,
+        cube_dim: CubeDim,
+        cube_count: CubeCount,
+        lhs: TensorArg<'_, R>,
+        rhs: TensorArg<'_, R>,
+        out: TensorArg<'_, R>,
+        epilogue: EpilogueArgs<'_, R>,
+        config: Self::Config,
+    ) {
+        Self::check_config(config);
+        launch::launch_unchecked::<EG, EO, Self, R>(
+            client,
+            cube_count,
+            cube_dim,
+            lhs,
+            rhs,
+            out,
+            epilogue.scale,
+            epilogue.bias,
+            epilogue.residual,
+            ScalarArg::new(epilogue.alpha),
+            ScalarArg::new(epilogue.beta),
+            config,
+        );
+    }
+}
+
+#[cube(launch_unchecked)]
+fn launch<EG: Numeric, EO: Numeric, BMM: batch::Matmul<EG, EO>>(
+    lhs: &Tensor<Line<EG>>,
+    rhs: &Tensor<Line<EG>>,
+    out: &mut Tensor<Line<EO>>,
+    scale: &Tensor<Line<EO>>,
+    bias: &Tensor<Line<EO>>,
+    residual: &Tensor<Line<EO>>,
+    alpha: f32,
+    beta: f32,
+    #[comptime] config: BMM::Config,
+) {
+    BMM::execute(
+        lhs,
+        rhs,
+        out,
+        EpilogueInputs::new(scale, bias, residual, alpha, beta),
+        config,
+    );
+}
+
+#[derive(CubeType, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+/// Configuration for the SplitKBatchMatmul
+pub struct Config<G: global::Config> {
+    gmm_config: G,
+    cube_count_x: u32,
+    cube_count_y: u32,
+    num_batches: u32,
+    num_splits: u32,
+    k_per_split: u32,
+}
+
+impl<G: global::Config> batch::Config for Config<G> {
+    type GmmConfig = G;
+
+    fn to_gmm_config(&self) -> Self::GmmConfig {
+        self.gmm_config
+    }
+
+    fn stage_dim(&self, ident: Ident) -> StageDim {
+        self.gmm_config.stage_dim(ident)
+    }
+
+    fn cube_count_x(&self) -> u32 {
+        self.cube_count_x
+    }
+
+    fn cube_count_y(&self) -> u32 {
+        self.cube_count_y
+    }
+
+    fn max_m(&self) -> u32 {
+        self.cube_count_x() * self.stage_dim(Ident::Out).num_elements_x_dim()
+    }
+
+    fn max_n(&self) -> u32 {
+        self.cube_count_y() * self.stage_dim(Ident::Out).num_elements_y_dim()
+    }
+
+    fn max_batches(&self) -> u32 {
+        self.num_batches
+    }
+}
+
+impl<G: global::Config> MatmulConfig for Config<G> {}
+
+impl<G: global::Config> Config<G> {
+    /// Create a config where the cube at position z solves the split `z / num_batches`
+    /// of the batch `z % num_batches`, over a range of `k_per_split` elements of k.
+    ///
+    /// `k_per_split` must be a multiple of the stage size along k.
+    pub fn new(
+        gmm_config: G,
+        cube_count_x: u32,
+        cube_count_y: u32,
+        num_batches: u32,
+        num_splits: u32,
+        k_per_split: u32,
+    ) -> Self {
+        Self {
+            gmm_config,
+            cube_count_x,
+            cube_count_y,
+            num_batches,
+            num_splits,
+            k_per_split,
+        }
+    }
+
+    /// Returns the number of batches of the problem
+    pub fn num_batches(&self) -> u32 {
+        self.num_batches
+    }
+
+    /// Returns the number of ranges k is split into
+    pub fn num_splits(&self) -> u32 {
+        self.num_splits
+    }
+
+    /// Returns the number of elements of k in each split
+    pub fn k_per_split(&self) -> u32 {
+        self.k_per_split
+    }
+
+    /// Returns the number of cubes launched across the z dimension
+    pub fn cube_count_z(&self) -> u32 {
+        self.num_batches * self.num_splits
+    }
+}
@@ -0,0 +1,333 @@
+use std::marker::PhantomData;
+
+use crate::matmul::components::global::{EpilogueArgs, EpilogueInputs};
+use crate::matmul::components::{
+    batch, config::MatmulConfig, global, Ident, MatmulKernel, MatmulLaunch, StageDim,
+};
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+use super::Config as _;
+use global::Config as _;
+
+/// Performs matrix multiplication at the batch level,
+/// with the k iterations of all stages of the output shared evenly between cubes
+///
+/// The work is a sequence of iterations, one per stage of k for each stage of the output,
+/// and each cube solves a contiguous range of it, which may start or end in the middle
+/// of the k iterations of a stage. The cubes sharing a stage of the output write their
+/// partial products to distinct slots: the output tensor has the shape of the matmul output,
+/// preceded by an extra dimension of size `num_slots`, and must be contiguous and filled
+/// with zeros. Partial results must then be summed along that first dimension.
+pub struct Matmul<
+    EG: Numeric,
+    EO: Numeric,
+    ES: Numeric,
+    GMM: global::Matmul<
+        EG,
+        EO,
+        ES,
+        global::tensor_view::LhsLoader<EG, ES>,
+        global::tensor_view::RhsLoader<EG, ES>,
+        global::tensor_view::Unloader<EO>,
+    >,
+> {
+    _eg: PhantomData<EG>,
+    _eo: PhantomData<EO>,
+    _es: PhantomData<ES>,
+    _gmm: PhantomData<GMM>,
+}
+
+#[cube]
+impl<
+        EG: Numeric,
+        EO: Numeric,
+        ES: Numeric,
+        GMM: global::Matmul<
+            EG,
+            EO,
+            ES,
+            global::tensor_view::LhsLoader<EG, ES>,
+            global::tensor_view::RhsLoader<EG, ES>,
+            global::tensor_view::Unloader<EO>,
+        >,
+    > batch::Matmul<EG, EO> for Matmul<EG, EO, ES, GMM>
+{
+    fn execute(
+        lhs: &Tensor<Line<EG>>,
+        rhs: &Tensor<Line<EG>>,
+        out: &mut Tensor<Line<EO>>,
+        _epilogue: EpilogueInputs<EO>,
+        #[comptime] config: Self::Config,
+    ) {
+        let stage_m = config.stage_dim(Ident::Lhs).num_elements_x_dim();
+        let stage_n = config.stage_dim(Ident::Rhs).num_elements_y_dim();
+        let stage_k = config.stage_dim(Ident::Lhs).num_elements_y_dim();
+        let iters_per_tile = config.iters_per_tile();
+        let iters_per_cube = config.iters_per_cube();
+        let tiles_per_batch = comptime!(config.tiles_x() * config.tiles_y());
+        let total_iters = comptime!(
+            config.tiles_x() * config.tiles_y() * config.num_batches() * config.iters_per_tile()
+        );
+
+        let iter_end = Min::min((CUBE_POS_X + 1) * iters_per_cube, total_iters);
+        let mut iter = CUBE_POS_X * iters_per_cube;
+
+        while iter < iter_end {
+            let tile = iter / iters_per_tile;
+            let tile_start = tile * iters_per_tile;
+            let segment_end = Min::min(iter_end, tile_start + iters_per_tile);
+
+            // Cubes sharing a tile are consecutive, so the first one writes to slot 0.
+            let slot = CUBE_POS_X - tile_start / iters_per_cube;
+            let nth_batch = tile / tiles_per_batch;
+            let tile_in_batch = tile % tiles_per_batch;
+            let x_offset = tile_in_batch / config.tiles_y() * stage_m;
+            let y_offset = tile_in_batch % config.tiles_y() * stage_n;
+            let k_range = (
+                (iter - tile_start) * stage_k,
+                (segment_end - tile_start) * stage_k,
+            );
+
+            // The output holds the partial results of each slot with a leading axis, so its
+            // innermost batch axes are those of the problem.
+            let lhs_batch_offset = global::tensor_view::batch_offset(lhs, out, nth_batch);
+            let rhs_batch_offset = global::tensor_view::batch_offset(rhs, out, nth_batch);
+            let out_batch_offset = global::tensor_view::batch_offset(
+                out,
+                out,
+                slot * config.num_batches() + nth_batch,
+            );
+
+            GMM::execute(
+                global::tensor_view::LhsLoader::new::<<Self::Config as batch::Config>::GmmConfig>(
+                    lhs,
+                    x_offset,
+                    k_range.0,
+                    lhs_batch_offset,
+                    config.to_gmm_config(),
+                ),
+                global::tensor_view::RhsLoader::new::<<Self::Config as batch::Config>::GmmConfig>(
+                    rhs,
+                    k_range.0,
+                    y_offset,
+                    rhs_batch_offset,
+                    config.to_gmm_config(),
+                ),
+                global::tensor_view::Unloader::new(out, x_offset, y_offset, out_batch_offset),
+                k_range,
+                config.to_gmm_config(),
+            );
+
+            iter = segment_end;
+        }
+    }
+}
+
+impl<
+        EG: Numeric,
+        EO: Numeric,
+        ES: Numeric,
+        GMM: global::Matmul<
+            EG,
+            EO,
+            ES,
+            global::tensor_view::LhsLoader<EG, ES>,
+            global::tensor_view::RhsLoader<EG, ES>,
+            global::tensor_view::Unloader<EO>,
+        >,
+    > MatmulKernel<EG, EO> for Matmul<EG, EO, ES, GMM>
+{
+    type Config = Config<GMM::Config>;
+
+    fn check_config(config: Self::Config) {
+        assert!(
+            config.to_gmm_config().epilogue().is_identity(),
+            "Partial results can't go through an epilogue"
+        );
+
+        GMM::check_config(config.to_gmm_config())
+    }
+}
+
+impl<
+        EG: Numeric,
+        EO: Numeric,
+        ES: Numeric,
+        GMM: global::Matmul<
+            EG,
+            EO,
+            ES,
+            global::tensor_view::LhsLoader<EG, ES>,
+            global::tensor_view::RhsLoader<EG, ES>,
+            global::tensor_view::Unloader<EO>,
+        >,
+    > MatmulLaunch<EG, EO> for Matmul<EG, EO, ES, GMM>
+{
+    unsafe fn launch_unchecked<R: Runtime>(
+        client: &ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel>,
+        cube_dim: CubeDim,
+        cube_count: CubeCount,
+        lhs: TensorArg<'_, R>,
+        rhs: TensorArg<'_, R>,
+        out: TensorArg<'_, R>,
+        epilogue: EpilogueArgs<'_, R>,
+        config: Self::Config,
+    ) {
+        Self::check_config(config);
+        launch::launch_unchecked::<EG, EO, Self, R>(
+            client,
+            cube_count,
+            cube_dim,
+            lhs,
+            rhs,
+            out,
+            epilogue.scale,
+            epilogue.bias,
+            epilogue.residual,
+            ScalarArg::new(epilogue.alpha),
+            ScalarArg::new(epilogue.beta),
+            config,
+        );
+    }
+}
+
+#[cube(launch_unchecked)]
+fn launch<EG: Numeric, EO: Numeric, BMM: batch::Matmul<EG, EO>>(
+    lhs: &Tensor<Line<EG>>,
+    rhs: &Tensor<Line<EG>>,
+    out: &mut Tensor<Line<EO>>,
+    scale: &Tensor<Line<EO>>,
+    bias: &Tensor<Line<EO>>,
+    residual: &Tensor<Line<EO>>,
+    alpha: f32,
+    beta: f32,
+    #[comptime] config: BMM::Config,
+) {
+    BMM::execute(
+        lhs,
+        rhs,
+        out,
+        EpilogueInputs::new(scale, bias, residual, alpha, beta),
+        config,
+    );
+}
+
+#[derive(CubeType, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+/// Configuration for the StreamKBatchMatmul
+pub struct Config<G: global::Config> {
+    gmm_config: G,
+    num_cubes: u32,
+    tiles_x: u32,
+    tiles_y: u32,
+    num_batches: u32,
+    iters_per_tile: u32,
+    iters_per_cube: u32,
+    num_slots: u32,
+}
+
+impl<G: global::Config> batch::Config for Config<G> {
+    type GmmConfig = G;
+
+    fn to_gmm_config(&self) -> Self::GmmConfig {
+        self.gmm_config
+    }
+
+    fn stage_dim(&self, ident: Ident) -> StageDim {
+        self.gmm_config.stage_dim(ident)
+    }
+
+    fn cube_count_x(&self) -> u32 {
+        self.num_cubes
+    }
+
+    fn cube_count_y(&self) -> u32 {
+        1
+    }
+
+    fn max_m(&self) -> u32 {
+        self.tiles_x * self.stage_dim(Ident::Out).num_elements_x_dim()
+    }
+
+    fn max_n(&self) -> u32 {
+        self.tiles_y * self.stage_dim(Ident::Out).num_elements_y_dim()
+    }
+
+    fn max_batches(&self) -> u32 {
+        self.num_batches
+    }
+}
+
+impl<G: global::Config> MatmulConfig for Config<G> {}
+
+impl<G: global::Config> Config<G> {
+    /// Create a config sharing the `iters_per_tile` stages of k of each of the
+    /// `tiles_x * tiles_y * num_batches` stages of the output between at most `num_cubes` cubes
+    ///
+    /// Each cube solves `ceil(total / num_cubes)` iterations, and fewer cubes are launched
+    /// if it leaves the last ones without work.
+    pub fn new(
+        gmm_config: G,
+        num_cubes: u32,
+        tiles_x: u32,
+        tiles_y: u32,
+        num_batches: u32,
+        iters_per_tile: u32,
+    ) -> Self {
+        let total_iters = tiles_x * tiles_y * num_batches * iters_per_tile;
+        let iters_per_cube = Ord::max(total_iters.div_ceil(num_cubes), 1);
+        let num_cubes = total_iters.div_ceil(iters_per_cube);
+
+        // The number of cubes sharing a tile is the largest one over all tiles.
+        let num_slots = (0..tiles_x * tiles_y * num_batches)
+            .map(|tile| {
+                let first_cube = tile * iters_per_tile / iters_per_cube;
+                let last_cube = ((tile + 1) * iters_per_tile - 1) / iters_per_cube;
+                last_cube - first_cube + 1
+            })
+            .max()
+            .unwrap_or(1);
+
+        Self {
+            gmm_config,
+            num_cubes,
+            tiles_x,
+            tiles_y,
+            num_batches,
+            iters_per_tile,
+            iters_per_cube,
+            num_slots,
+        }
+    }
+
+    /// Returns the number of stages of the output along m
+    pub fn tiles_x(&self) -> u32 {
+        self.tiles_x
+    }
+
+    /// Returns the number of stages of the output along n
+    pub fn tiles_y(&self) -> u32 {
+        self.tiles_y
+    }
+
+    /// Returns the number of batches of the problem
+    pub fn num_batches(&self) -> u32 {
+        self.num_batches
+    }
+
+    /// Returns the number of stages of k needed to solve a stage of the output
+    pub fn iters_per_tile(&self) -> u32 {
+        self.iters_per_tile
+    }
+
+    /// Returns the number of iterations solved by each cube
+    pub fn iters_per_cube(&self) -> u32 {
+        self.iters_per_cube
+    }
+
+    /// Returns the largest number of cubes contributing to the same stage of the output,
+    /// i.e. the number of partial results to sum
+    pub fn num_slots(&self) -> u32 {
+        self.num_slots
+    }
+}
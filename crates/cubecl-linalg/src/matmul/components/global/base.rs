@@ -7,6 +7,8 @@ use crate::matmul::components::MatmulKernel;
 use crate::matmul::components::StageDim;
 use crate::matmul::components::{Ident, MatrixLayout};
 
+use super::Epilogue;
+
 #[cube]
 /// Provides matrix multiplication operations at the global level.
 ///
@@ -28,11 +30,12 @@ use crate::matmul::components::{Ident, MatrixLayout};
 /// before loading data.
 pub trait Matmul<
     EG: Numeric,
+    EO: Numeric,
     ES: Numeric,
     Lhs: Loader<EG, ES>,
     Rhs: Loader<EG, ES>,
-    Out: Unloader<EG>,
->: 'static + Send + Sync + MatmulKernel<EG, EG, Config: Config>
+    Out: Unloader<EO>,
+>: 'static + Send + Sync + MatmulKernel<EG, EO, Config: Config>
 {
     /// Performs the matrix multiplication over data loaded by the
     /// LHS and RHS loaders, over the range given for K, and stores with
@@ -113,4 +116,7 @@ pub trait Config: MatmulConfig {
 
     /// Whether we transpose data when loading to the stage
     fn transpose_load(&self, ident: Ident) -> bool;
+
+    /// Returns the operations fused to the unloading of the output
+    fn epilogue(&self) -> Epilogue;
 }
@@ -0,0 +1,205 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[derive(CubeType, Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+/// Activation applied elementwise by the [Epilogue]
+pub enum Activation {
+    /// Leaves values unchanged
+    #[default]
+    Identity,
+    /// `max(x, 0)`
+    Relu,
+    /// `x * Φ(x)`, where `Φ` is the cumulative distribution function of the standard normal
+    Gelu,
+    /// `x * sigmoid(x)`
+    Silu,
+}
+
+#[derive(CubeType, Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+/// Operations fused to the unloading of the output of a matmul, applied to each element in order:
+///
+/// `out = activation(alpha * scale[col] * acc + beta * out + bias[col]) + residual`
+///
+/// The chain is fixed at compile time, while the scalars and tensors it reads are given at
+/// launch with [EpilogueArgs]. The default epilogue writes the product unchanged.
+pub struct Epilogue {
+    /// Whether the product is scaled by `alpha`
+    pub alpha: bool,
+    /// Whether the product is scaled by one value per column, such as per-channel
+    /// dequantization scales
+    pub scale: bool,
+    /// Whether the previous content of the output, scaled by `beta`, is added
+    pub beta: bool,
+    /// Whether a bias of one value per column is added
+    pub bias: bool,
+    /// The activation to apply
+    pub activation: Activation,
+    /// Whether a residual tensor with the shape and strides of the output is added
+    /// after the activation
+    pub residual: bool,
+}
+
+impl Epilogue {
+    /// Scale the product by `alpha`
+    pub fn with_alpha(mut self) -> Self {
+        self.alpha = true;
+        self
+    }
+
+    /// Scale the product by one value per column
+    pub fn with_scale(mut self) -> Self {
+        self.scale = true;
+        self
+    }
+
+    /// Add the previous content of the output, scaled by `beta`
+    pub fn with_beta(mut self) -> Self {
+        self.beta = true;
+        self
+    }
+
+    /// Add a bias of one value per column
+    pub fn with_bias(mut self) -> Self {
+        self.bias = true;
+        self
+    }
+
+    /// Apply the given activation
+    pub fn with_activation(mut self, activation: Activation) -> Self {
+        self.activation = activation;
+        self
+    }
+
+    /// Add a residual after the activation
+    pub fn with_residual(mut self) -> Self {
+        self.residual = true;
+        self
+    }
+
+    /// Whether the epilogue writes the product unchanged
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(CubeType)]
+/// Runtime inputs of the [Epilogue], read while unloading the output
+pub struct EpilogueInputs<EG: Numeric> {
+    pub scale: *const Tensor<Line<EG>>,
+    pub bias: *const Tensor<Line<EG>>,
+    pub residual: *const Tensor<Line<EG>>,
+    pub alpha: f32,
+    pub beta: f32,
+}
+
+unsafe impl<EG: Numeric> Sync for EpilogueInputs<EG> {}
+unsafe impl<EG: Numeric> Send for EpilogueInputs<EG> {}
+
+#[cube]
+impl<EG: Numeric> EpilogueInputs<EG> {
+    pub fn new(
+        scale: &Tensor<Line<EG>>,
+        bias: &Tensor<Line<EG>>,
+        residual: &Tensor<Line<EG>>,
+        alpha: f32,
+        beta: f32,
+    ) -> Self {
+        EpilogueInputs::<EG> {
+            scale,
+            bias,
+            residual,
+            alpha,
+            beta,
+        }
+    }
+
+    /// Inputs for an identity epilogue, which never reads the given tensor
+    pub fn none(placeholder: &Tensor<Line<EG>>) -> Self {
+        EpilogueInputs::<EG> {
+            scale: placeholder,
+            bias: placeholder,
+            residual: placeholder,
+            alpha: 1.0,
+            beta: 0.0,
+        }
+    }
+
+    /// Reads the line of the scale at the given position
+    pub fn read_scale(&self, position: u32) -> Line<EG> {
+        unsafe { *(*self.scale).index_unchecked(position) }
+    }
+
+    /// Reads the line of the bias at the given position
+    pub fn read_bias(&self, position: u32) -> Line<EG> {
+        unsafe { *(*self.bias).index_unchecked(position) }
+    }
+
+    /// Reads the line of the residual at the given position
+    pub fn read_residual(&self, position: u32) -> Line<EG> {
+        unsafe { *(*self.residual).index_unchecked(position) }
+    }
+}
+
+/// Applies the activation to a line of values.
+#[cube]
+pub fn activate(
+    value: Line<f32>,
+    #[comptime] activation: Activation,
+    #[comptime] line_size: u32,
+) -> Line<f32> {
+    let zero = Line::empty(line_size).fill(0.0);
+    let one = Line::empty(line_size).fill(1.0);
+
+    match activation {
+        Activation::Identity => value,
+        Activation::Relu => Max::max(value, zero),
+        Activation::Gelu => {
+            let inv_sqrt_2 = Line::empty(line_size).fill(std::f32::consts::FRAC_1_SQRT_2);
+            let half = Line::empty(line_size).fill(0.5);
+            value * half * (one + Erf::erf(value * inv_sqrt_2))
+        }
+        Activation::Silu => value / (one + Exp::exp(zero - value)),
+    }
+}
+
+/// Runtime arguments of the [Epilogue] given at launch
+///
+/// Tensors that the epilogue doesn't use are never read, and are bound to a
+/// [placeholder](EpilogueArgs::placeholder) of the output element type.
+pub struct EpilogueArgs<'a, R: Runtime> {
+    /// Scale of shape `[n]`, vectorized like the output
+    pub scale: TensorArg<'a, R>,
+    /// Bias of shape `[n]`, vectorized like the output
+    pub bias: TensorArg<'a, R>,
+    /// Residual with the shape and strides of the output, vectorized like the output
+    pub residual: TensorArg<'a, R>,
+    /// Scale of the product
+    pub alpha: f32,
+    /// Scale of the previous content of the output
+    pub beta: f32,
+}
+
+impl<'a, R: Runtime> EpilogueArgs<'a, R> {
+    /// Allocate a buffer of a single element of `E`, to bind to the tensors that the
+    /// epilogue doesn't use
+    pub fn placeholder<E: CubePrimitive>(
+        client: &ComputeClient<R::Server, R::Channel>,
+    ) -> cubecl_core::server::Handle {
+        client.empty(E::as_elem().size())
+    }
+
+    /// Arguments for an identity epilogue, with tensors bound to a
+    /// [placeholder](Self::placeholder) of `E`
+    pub fn none<E: CubePrimitive>(placeholder: &'a cubecl_core::server::Handle) -> Self {
+        // Safety: the placeholder is never read.
+        unsafe {
+            Self {
+                scale: TensorArg::from_raw_parts::<E>(placeholder, &[1], &[1], 1),
+                bias: TensorArg::from_raw_parts::<E>(placeholder, &[1], &[1], 1),
+                residual: TensorArg::from_raw_parts::<E>(placeholder, &[1], &[1], 1),
+                alpha: 1.0,
+                beta: 0.0,
+            }
+        }
+    }
+}
@@ -12,39 +12,43 @@ use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 use std::marker::PhantomData;
 
-use super::{tensor_view, Config as _};
+use super::{tensor_view, Config as _, Epilogue};
 
 /// Performs matrix multiplication at the global level, with each plane sharing the same responsibilities
 /// - All planes load data to the stage
 /// - All planes are used in the stage matmul computation
 pub struct Matmul<
     EG: Numeric,
+    EO: Numeric,
     ES: Numeric,
-    SMM: stage::Matmul<ES, EG, LhsReader<ES>, RhsReader<ES>>,
+    SMM: stage::Matmul<ES, EO, LhsReader<ES>, RhsReader<ES>>,
 > {
     _eg: PhantomData<EG>,
+    _eo: PhantomData<EO>,
     _es: PhantomData<ES>,
     _stage_matmul: PhantomData<SMM>,
 }
 
 #[cube]
-impl<EG, ES, SMM>
+impl<EG, EO, ES, SMM>
     global::Matmul<
         EG,
+        EO,
         ES,
         tensor_view::LhsLoader<EG, ES>,
         tensor_view::RhsLoader<EG, ES>,
-        tensor_view::Unloader<EG>,
-    > for Matmul<EG, ES, SMM>
+        tensor_view::Unloader<EO>,
+    > for Matmul<EG, EO, ES, SMM>
 where
     EG: Numeric,
+    EO: Numeric,
     ES: Numeric,
-    SMM: stage::Matmul<ES, EG, LhsReader<ES>, RhsReader<ES>>,
+    SMM: stage::Matmul<ES, EO, LhsReader<ES>, RhsReader<ES>>,
 {
     fn execute(
         mut lhs_loader: tensor_view::LhsLoader<EG, ES>,
         mut rhs_loader: tensor_view::RhsLoader<EG, ES>,
-        mut out_unloader: tensor_view::Unloader<EG>,
+        mut out_unloader: tensor_view::Unloader<EO>,
         k_range: (u32, u32),
         #[comptime] config: Self::Config,
     ) {
@@ -75,7 +79,7 @@ where
             tensor_view::RhsLoader::advance_view(&mut rhs_loader, k_step);
         }
 
-        SMM::acc_read::<tensor_view::Unloader<EG>, Self::Config>(
+        SMM::acc_read::<tensor_view::Unloader<EO>, Self::Config>(
             &acc,
             &mut out_unloader,
             config.to_smm_config(),
@@ -84,11 +88,12 @@ where
     }
 }
 
-impl<EG, ES, SMM> MatmulKernel<EG, EG> for Matmul<EG, ES, SMM>
+impl<EG, EO, ES, SMM> MatmulKernel<EG, EO> for Matmul<EG, EO, ES, SMM>
 where
     EG: Numeric,
+    EO: Numeric,
     ES: Numeric,
-    SMM: stage::Matmul<ES, EG, LhsReader<ES>, RhsReader<ES>>,
+    SMM: stage::Matmul<ES, EO, LhsReader<ES>, RhsReader<ES>>,
 {
     type Config = Config<SMM::Config>;
 
@@ -108,6 +113,7 @@ pub struct Config<S: stage::Config> {
     lhs_line_size: u32,
     rhs_line_size: u32,
     out_line_size: u32,
+    epilogue: Epilogue,
 }
 
 impl<S: stage::Config> global::Config for Config<S> {
@@ -164,6 +170,10 @@ impl<S: stage::Config> global::Config for Config<S> {
     fn transpose_load(&self, ident: Ident) -> bool {
         self.layout(ident) != self.smm_config.layout(ident)
     }
+
+    fn epilogue(&self) -> Epilogue {
+        self.epilogue
+    }
 }
 
 impl<S: stage::Config> MatmulConfig for Config<S> {}
@@ -189,6 +199,13 @@ impl<S: stage::Config> Config<S> {
             lhs_line_size,
             rhs_line_size,
             out_line_size,
+            epilogue: Epilogue::default(),
         }
     }
+
+    /// Fuse the given epilogue to the unloading of the output
+    pub fn with_epilogue(mut self, epilogue: Epilogue) -> Self {
+        self.epilogue = epilogue;
+        self
+    }
 }
@@ -2,5 +2,7 @@ pub mod homogeneous;
 pub mod tensor_view;
 
 mod base;
+mod epilogue;
 
 pub use base::*;
+pub use epilogue::{activate, Activation, Epilogue, EpilogueArgs, EpilogueInputs};
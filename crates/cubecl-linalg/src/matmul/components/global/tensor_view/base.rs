@@ -1,4 +1,4 @@
-use crate::matmul::components::global;
+use crate::matmul::components::global::{self, activate, EpilogueInputs};
 use crate::matmul::components::{Ident, MatrixLayout};
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
@@ -31,6 +31,7 @@ pub struct TensorWriter<E: Numeric> {
     pub shape_x: u32,
     pub shape_y: u32,
     pub batch_offset: u32,
+    pub epilogue: EpilogueInputs<E>,
 }
 
 unsafe impl<E: Numeric> Sync for TensorReader<E> {}
@@ -38,16 +39,45 @@ unsafe impl<E: Numeric> Send for TensorReader<E> {}
 unsafe impl<E: Numeric> Sync for TensorWriter<E> {}
 unsafe impl<E: Numeric> Send for TensorWriter<E> {}
 
+/// The offset in elements of the matrix of the tensor that is multiplied into the given batch
+/// of the output.
+///
+/// The batch axes are aligned on the last one, and the batch index is decomposed along those of
+/// the output. Batch axes of the tensor of size 1 or of stride 0 are broadcast, as are the
+/// leading axes it lacks, and the strides of the other axes can be in any order.
+#[cube]
+pub fn batch_offset<E: Numeric, O: Numeric>(
+    tensor: &Tensor<Line<E>>,
+    out: &Tensor<Line<O>>,
+    nth_batch: u32,
+) -> u32 {
+    let rank = tensor.rank();
+    let out_rank = out.rank();
+    let mut remainder = nth_batch;
+    let mut offset = 0;
+
+    for i in 0..rank - 2 {
+        let axis = rank - 3 - i;
+        let out_axis = out_rank - 3 - i;
+        let coordinate = remainder % out.shape(out_axis);
+        remainder /= out.shape(out_axis);
+        offset += coordinate % tensor.shape(axis) * tensor.stride(axis);
+    }
+
+    offset
+}
+
 #[cube]
 impl<EG: Numeric> TensorReader<EG> {
     /// Instantiate a read view over the given tensor, pre-fetching needed strides and shapes
-    pub fn new(tensor: &Tensor<Line<EG>>, x_offset: u32, y_offset: u32, nth_batch: u32) -> Self {
+    ///
+    /// The batch offset is in elements, see [batch_offset].
+    pub fn new(tensor: &Tensor<Line<EG>>, x_offset: u32, y_offset: u32, batch_offset: u32) -> Self {
         let rank = tensor.rank();
         let stride_x = tensor.stride(rank - 2);
         let stride_y = tensor.stride(rank - 1);
         let shape_x = tensor.shape(rank - 2);
         let shape_y = tensor.shape(rank - 1);
-        let stride_b = tensor.stride(rank - 3);
 
         TensorReader::<EG> {
             tensor,
@@ -57,7 +87,7 @@ impl<EG: Numeric> TensorReader<EG> {
             stride_y,
             shape_x,
             shape_y,
-            batch_offset: nth_batch * stride_b,
+            batch_offset,
         }
     }
 
@@ -124,18 +154,32 @@ impl<EG: Numeric> TensorReader<EG> {
 #[cube]
 impl<EG: Numeric> TensorWriter<EG> {
     /// Instantiate a write view over the given tensor, pre-fetching needed strides and shapes
+    ///
+    /// The batch offset is in elements, see [batch_offset].
     pub fn new(
         tensor: &mut Tensor<Line<EG>>,
         x_offset: u32,
         y_offset: u32,
-        nth_batch: u32,
+        batch_offset: u32,
+    ) -> Self {
+        let epilogue = EpilogueInputs::none(tensor);
+        TensorWriter::with_epilogue(tensor, epilogue, x_offset, y_offset, batch_offset)
+    }
+
+    /// Instantiate a write view over the given tensor, reading the given inputs
+    /// for the epilogue of the config
+    pub fn with_epilogue(
+        tensor: &mut Tensor<Line<EG>>,
+        epilogue: EpilogueInputs<EG>,
+        x_offset: u32,
+        y_offset: u32,
+        batch_offset: u32,
     ) -> Self {
         let rank = tensor.rank();
         let stride_x = tensor.stride(rank - 2);
         let stride_y = tensor.stride(rank - 1);
         let shape_x = tensor.shape(rank - 2);
         let shape_y = tensor.shape(rank - 1);
-        let stride_b = tensor.stride(rank - 3);
 
         TensorWriter::<EG> {
             tensor,
@@ -145,13 +189,15 @@ impl<EG: Numeric> TensorWriter<EG> {
             stride_y,
             shape_x,
             shape_y,
-            batch_offset: nth_batch * stride_b,
+            batch_offset,
+            epilogue,
         }
     }
 
     /// Writes data into the tensor view at the specified coordinates (write_x, write_y).
     ///
     /// Each unit writes one line in a coalesced manner for improved efficiency, assuming row-major layout.
+    /// The epilogue of the config is applied to the line before writing it.
     pub fn write_coalesced<ES: Numeric, G: global::Config>(
         &mut self,
         tile_x: u32,
@@ -173,20 +219,63 @@ impl<EG: Numeric> TensorWriter<EG> {
         if config.check_m_bounds() {
             if config.check_n_bounds() {
                 if view_x < self.shape_x && view_y < self.shape_y {
-                    self.write(write_position, Line::cast_from(value));
+                    self.write_epilogue::<ES, G>(write_position, view_y, value, config);
                 }
             } else if view_x < self.shape_x {
-                self.write(write_position, Line::cast_from(value));
+                self.write_epilogue::<ES, G>(write_position, view_y, value, config);
             }
         } else if config.check_n_bounds() {
             if view_y < self.shape_y {
-                self.write(write_position, Line::cast_from(value));
+                self.write_epilogue::<ES, G>(write_position, view_y, value, config);
             }
         } else {
-            self.write(write_position, Line::cast_from(value));
+            self.write_epilogue::<ES, G>(write_position, view_y, value, config);
+        }
+    }
+
+    fn write_epilogue<ES: Numeric, G: global::Config>(
+        &mut self,
+        position: u32,
+        column: u32,
+        value: Line<ES>,
+        #[comptime] config: G,
+    ) {
+        let epilogue = config.epilogue();
+
+        if epilogue.is_identity() {
+            self.write(position, Line::cast_from(value));
+        } else {
+            let line_size = config.global_line_size(Ident::Out);
+            let mut value = Line::<f32>::cast_from(value);
+
+            if epilogue.alpha {
+                value *= Line::empty(line_size).fill(self.epilogue.alpha);
+            }
+            if epilogue.scale {
+                value *= Line::cast_from(self.epilogue.read_scale(column / line_size));
+            }
+            if epilogue.beta {
+                let previous = Line::<f32>::cast_from(self.read(position));
+                value += Line::empty(line_size).fill(self.epilogue.beta) * previous;
+            }
+            if epilogue.bias {
+                value += Line::cast_from(self.epilogue.read_bias(column / line_size));
+            }
+
+            value = activate(value, epilogue.activation, line_size);
+
+            if epilogue.residual {
+                value += Line::cast_from(self.epilogue.read_residual(position));
+            }
+
+            self.write(position, Line::cast_from(value));
         }
     }
 
+    fn read(&self, position: u32) -> Line<EG> {
+        unsafe { *(*self.tensor).index_unchecked(position) }
+    }
+
     fn write(&mut self, position: u32, value: Line<EG>) {
         unsafe { (*self.tensor).index_assign_unchecked(position, value) }
     }
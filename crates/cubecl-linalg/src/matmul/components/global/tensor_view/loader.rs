@@ -25,11 +25,11 @@ impl<EG: Numeric, ES: Numeric> LhsLoader<EG, ES> {
         tensor: &Tensor<Line<EG>>,
         x_offset: u32,
         y_offset: u32,
-        nth_batch: u32,
+        batch_offset: u32,
         #[comptime] config: G,
     ) -> Self {
         let stage = Stage::new::<G::SmmConfig>(Ident::Lhs, config.to_smm_config());
-        let tensor_view = TensorReader::new(tensor, x_offset, y_offset, nth_batch);
+        let tensor_view = TensorReader::new(tensor, x_offset, y_offset, batch_offset);
 
         LhsLoader::<EG, ES> { tensor_view, stage }
     }
@@ -41,11 +41,11 @@ impl<EG: Numeric, ES: Numeric> RhsLoader<EG, ES> {
         tensor: &Tensor<Line<EG>>,
         x_offset: u32,
         y_offset: u32,
-        nth_batch: u32,
+        batch_offset: u32,
         #[comptime] config: G,
     ) -> Self {
         let stage = Stage::new::<G::SmmConfig>(Ident::Rhs, config.to_smm_config());
-        let tensor_view = TensorReader::new(tensor, x_offset, y_offset, nth_batch);
+        let tensor_view = TensorReader::new(tensor, x_offset, y_offset, batch_offset);
 
         RhsLoader::<EG, ES> { tensor_view, stage }
     }
@@ -4,5 +4,6 @@ mod loader;
 mod tilewise_unloading;
 mod unloader;
 
+pub use base::batch_offset;
 pub use loader::{LhsLoader, RhsLoader};
 pub use unloader::Unloader;
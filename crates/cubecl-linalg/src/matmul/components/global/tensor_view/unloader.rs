@@ -1,9 +1,9 @@
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 
-use crate::matmul::components::global;
 use crate::matmul::components::global::tensor_view::base::TensorWriter;
 use crate::matmul::components::global::tensor_view::tilewise_unloading::TilewiseUnloading;
+use crate::matmul::components::global::{self, EpilogueInputs};
 use crate::matmul::components::stage::StageWriter;
 
 #[derive(CubeType)]
@@ -32,6 +32,24 @@ impl<EG: Numeric> Unloader<EG> {
             tensor_view: TensorWriter::new(tensor, x_offset, y_offset, batch_offset),
         }
     }
+
+    pub fn with_epilogue(
+        tensor: &mut Tensor<Line<EG>>,
+        epilogue: EpilogueInputs<EG>,
+        x_offset: u32,
+        y_offset: u32,
+        batch_offset: u32,
+    ) -> Self {
+        Unloader::<EG> {
+            tensor_view: TensorWriter::with_epilogue(
+                tensor,
+                epilogue,
+                x_offset,
+                y_offset,
+                batch_offset,
+            ),
+        }
+    }
 }
 
 #[cube]
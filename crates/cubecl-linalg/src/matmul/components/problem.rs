@@ -10,7 +10,8 @@ pub struct MatmulProblem<EG: Numeric> {
     pub m: usize,
     pub n: usize,
     pub k: usize,
-    pub batches: Vec<usize>,
+    /// Batch shapes of the lhs and rhs, broadcast against each other from their last axis
+    pub batches: (Vec<usize>, Vec<usize>),
     pub lhs_layout: MatrixLayout,
     pub rhs_layout: MatrixLayout,
     pub lhs_line_size: u8,
@@ -20,9 +21,32 @@ pub struct MatmulProblem<EG: Numeric> {
 }
 
 impl<EG: Numeric> MatmulProblem<EG> {
+    /// Returns the batch shape of the output, where axes of size 1 of the lhs and rhs are
+    /// broadcast, as are the leading axes one of them lacks
+    ///
+    /// # Panics:
+    ///
+    ///  - If the batch shapes of the lhs and rhs can't be broadcast
+    pub fn out_batches(&self) -> Vec<usize> {
+        let (lhs, rhs) = &self.batches;
+        let rank = usize::max(lhs.len(), rhs.len());
+        let axis = |batches: &[usize], i: usize| match i + batches.len() >= rank {
+            true => batches[i + batches.len() - rank],
+            false => 1,
+        };
+
+        (0..rank)
+            .map(|i| match (axis(lhs, i), axis(rhs, i)) {
+                (lhs, rhs) if lhs == rhs || rhs == 1 => lhs,
+                (1, rhs) => rhs,
+                _ => panic!("Batch shapes {lhs:?} and {rhs:?} can't be broadcast"),
+            })
+            .collect()
+    }
+
     /// Returns the total number of batches
     pub(crate) fn num_batches(&self) -> usize {
-        self.batches.iter().copied().product()
+        self.out_batches().iter().copied().product()
     }
 
     /// Asserts that the problem can be solved with the given batch matmul configs
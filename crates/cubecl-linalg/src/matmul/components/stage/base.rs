@@ -134,6 +134,10 @@ create_cmma_stage!(S1x2x1, 1, 2, 1);
 create_cmma_stage!(S2x1x1, 2, 1, 1);
 create_cmma_stage!(S2x2x1, 2, 2, 1);
 create_cmma_stage!(S2x2x2, 2, 2, 2);
+create_cmma_stage!(S2x2x4, 2, 2, 4);
+create_cmma_stage!(S2x2x8, 2, 2, 8);
+create_cmma_stage!(S2x4x2, 2, 4, 2);
+create_cmma_stage!(S2x8x2, 2, 8, 2);
 create_cmma_stage!(S4x4x1, 4, 4, 1);
 create_cmma_stage!(S4x4x2, 4, 4, 2);
 create_cmma_stage!(S8x1x1, 8, 1, 1);
@@ -45,3 +45,31 @@ impl TilingOrder for YMajorTiling {
         y * num_x + x
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cubecl_core::host::HostUnit;
+
+    #[test]
+    fn x_major_tiling_round_trip() {
+        let unit = HostUnit::default();
+
+        for nth in 0..6 {
+            let (x, y) = XMajorTiling::__host_to_x_y(unit, nth, 2, 3);
+            assert_eq!((x, y), (nth / 3, nth % 3));
+            assert_eq!(XMajorTiling::__host_to_nth_tile(unit, x, y, 2, 3), nth);
+        }
+    }
+
+    #[test]
+    fn y_major_tiling_round_trip() {
+        let unit = HostUnit::default();
+
+        for nth in 0..6 {
+            let (x, y) = YMajorTiling::__host_to_x_y(unit, nth, 2, 3);
+            assert_eq!((x, y), (nth % 2, nth / 2));
+            assert_eq!(YMajorTiling::__host_to_nth_tile(unit, x, y, 2, 3), nth);
+        }
+    }
+}
@@ -12,6 +12,7 @@ pub trait CmmaValid<I: Numeric, O: Numeric> {}
 impl CmmaValid<f16, f16> for (f16, f16) {}
 impl CmmaValid<f16, f32> for (f16, f32) {}
 impl CmmaValid<bf16, f32> for (bf16, f32) {}
+impl CmmaValid<i8, i32> for (i8, i32) {}
 
 #[derive(CubeType)]
 /// Wrapper over a CMMA matrix, containing the stride which implies the layout
@@ -72,7 +72,7 @@ impl<I: Numeric, O: Numeric, const M: u32, const N: u32, const K: u32> tile::Mat
                 for n_iter in 0..compute_width {
                     let unit_to_read = k_inner * Self::N + n_iter + unit_offset;
                     let b_kn = subcube_broadcast::<I>(b_kp, unit_to_read);
-                    out[n_iter] += O::cast_from(a_pk * b_kn);
+                    out[n_iter] += O::cast_from(a_pk) * O::cast_from(b_kn);
                 }
             }
         }
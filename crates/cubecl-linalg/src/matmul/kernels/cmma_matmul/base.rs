@@ -1,25 +1,65 @@
 use std::marker::PhantomData;
 
+use cubecl_core::ir::{Elem, FloatKind};
 use cubecl_core::prelude::*;
 
 use cubecl_core::{
     client::ComputeClient,
     frontend::{TensorArg, TensorHandleRef},
-    tensor_line_size, Runtime,
+    Feature, Runtime,
 };
 
 use crate::matmul;
+use crate::matmul::components::global::EpilogueArgs;
+use crate::matmul::components::tile::accelerated::CmmaValid;
 use crate::matmul::components::{batch, global, stage, MatmulLaunch, MatmulProblem};
-use crate::matmul::kernels::cmma_matmul::config::make_cmma_config;
-use crate::tensor::{into_contiguous, matrix_layout, MatrixLayout, TensorHandle};
+use crate::matmul::kernels::cmma_matmul::config::{
+    make_cmma_config_with_epilogue, make_split_k_config, make_stream_k_config,
+};
+use crate::reduce::{self, Sum};
+use crate::tensor::{into_contiguous, line_size_along, TensorHandle};
 
 use super::config::AdvancedConfig;
 use super::dispatch::{
     check_availability, CmmaLaunchDispatch, MatmulLaunchDispatch, PlaneMmaLaunchDispatch,
 };
+use super::epilogue::MatmulEpilogue;
+
+type StageMatmul<TMM, CSS, EO, ES, EA> = stage::row_accumulate::Matmul<ES, EO, EA, TMM, CSS>;
+type GlobalMatmul<TMM, CSS, EG, EO, ES, EA> =
+    global::homogeneous::Matmul<EG, EO, ES, StageMatmul<TMM, CSS, EO, ES, EA>>;
+type DispatchGlobalMatmul<D, EG, EO> = GlobalMatmul<
+    <D as MatmulLaunchDispatch>::TileMatmul,
+    <D as MatmulLaunchDispatch>::StageSize,
+    EG,
+    EO,
+    <D as MatmulLaunchDispatch>::ElementInput,
+    <D as MatmulLaunchDispatch>::ElementAccumulator,
+>;
+
+/// How the work of a matrix multiplication is distributed across cubes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BatchStrategy {
+    /// Each cube computes one stage of the output over the whole k dimension.
+    #[default]
+    OneToOne,
+    /// Each cube computes one stage of the output over one of at most `num_splits` ranges
+    /// of the k dimension, then partial results are summed.
+    ///
+    /// Suited to problems with small m and n but a large k, which would launch too few cubes.
+    SplitK { num_splits: u32 },
+    /// At most `num_cubes` cubes share evenly the k iterations of all stages of the output,
+    /// then partial results are summed.
+    ///
+    /// Suited to problems whose number of stages of the output doesn't fill the device well.
+    StreamK { num_cubes: u32 },
+}
 
 /// Launch a matrix multiplication kernel.
 ///
+/// Batch axes of the lhs and rhs of size 1 or of stride 0 are broadcast, and their strides can
+/// be in any order, so only matrices without a contiguous axis are copied.
+///
 /// Cmma will be used if available and enabled,
 /// otherwise it will fall back on a non-cmma implementation
 pub fn launch_ref<R: Runtime, EG: Numeric>(
@@ -29,13 +69,236 @@ pub fn launch_ref<R: Runtime, EG: Numeric>(
     out: TensorHandleRef<'_, R>,
     disable_cmma: bool,
 ) {
-    if !disable_cmma && check_availability::<CmmaLaunchDispatch, R>(client).is_ok() {
-        matmul_cmma_ref::<R, EG, CmmaLaunchDispatch>(client, lhs, rhs, out);
+    launch_ref_with_batch_strategy::<R, EG>(
+        client,
+        lhs,
+        rhs,
+        out,
+        disable_cmma,
+        BatchStrategy::OneToOne,
+    );
+}
+
+/// Launch a matrix multiplication kernel, distributing the work across cubes
+/// with the given batch strategy.
+///
+/// Cmma will be used if available and enabled,
+/// otherwise it will fall back on a non-cmma implementation
+///
+/// # Panics
+///
+/// With split-K and stream-K, if the output is not contiguous.
+pub fn launch_ref_with_batch_strategy<R: Runtime, EG: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandleRef<'_, R>,
+    rhs: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    disable_cmma: bool,
+    batch_strategy: BatchStrategy,
+) {
+    dispatch_cmma_ref::<R, EG>(
+        client,
+        lhs,
+        rhs,
+        out,
+        disable_cmma,
+        batch_strategy,
+        MatmulEpilogue::new(),
+    );
+}
+
+/// Launch a matrix multiplication kernel, fusing the given epilogue to the writing
+/// of the output.
+///
+/// Cmma will be used if available and enabled,
+/// otherwise it will fall back on a non-cmma implementation
+///
+/// # Panics
+///
+/// If the tensors read by the epilogue don't fit the output.
+pub fn launch_ref_with_epilogue<R: Runtime, EG: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandleRef<'_, R>,
+    rhs: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    disable_cmma: bool,
+    epilogue: MatmulEpilogue<'_, R>,
+) {
+    epilogue.check(&out);
+
+    dispatch_cmma_ref::<R, EG>(
+        client,
+        lhs,
+        rhs,
+        out,
+        disable_cmma,
+        BatchStrategy::OneToOne,
+        epilogue,
+    );
+}
+
+fn dispatch_cmma_ref<R: Runtime, EG: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandleRef<'_, R>,
+    rhs: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    disable_cmma: bool,
+    batch_strategy: BatchStrategy,
+    epilogue: MatmulEpilogue<'_, R>,
+) {
+    match EG::as_elem() {
+        Elem::Float(FloatKind::F16) => dispatch_mixed_precision::<R, EG, half::f16>(
+            client,
+            lhs,
+            rhs,
+            out,
+            disable_cmma,
+            batch_strategy,
+            epilogue,
+        ),
+        Elem::Float(FloatKind::BF16) => dispatch_mixed_precision::<R, EG, half::bf16>(
+            client,
+            lhs,
+            rhs,
+            out,
+            disable_cmma,
+            batch_strategy,
+            epilogue,
+        ),
+        _ => {
+            if !disable_cmma && check_availability::<CmmaLaunchDispatch, R>(client).is_ok() {
+                matmul_cmma_ref::<R, EG, EG, CmmaLaunchDispatch>(
+                    client,
+                    lhs,
+                    rhs,
+                    out,
+                    batch_strategy,
+                    epilogue,
+                );
+            } else {
+                matmul_cmma_ref::<R, EG, EG, PlaneMmaLaunchDispatch>(
+                    client,
+                    lhs,
+                    rhs,
+                    out,
+                    batch_strategy,
+                    epilogue,
+                );
+            }
+        }
+    }
+}
+
+/// Dispatch a matmul on half precision inputs, computing on `ES` with an f32 accumulator.
+///
+/// Without cmma instructions, falls back on the plane matmul on `ES` if the device
+/// supports it, and on f32 otherwise.
+fn dispatch_mixed_precision<R: Runtime, EG: Numeric, ES: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandleRef<'_, R>,
+    rhs: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    disable_cmma: bool,
+    batch_strategy: BatchStrategy,
+    epilogue: MatmulEpilogue<'_, R>,
+) where
+    (ES, f32): CmmaValid<ES, f32>,
+{
+    if !disable_cmma && check_availability::<CmmaLaunchDispatch<ES, f32>, R>(client).is_ok() {
+        matmul_cmma_ref::<R, EG, EG, CmmaLaunchDispatch<ES, f32>>(
+            client,
+            lhs,
+            rhs,
+            out,
+            batch_strategy,
+            epilogue,
+        );
+    } else if client
+        .properties()
+        .feature_enabled(Feature::Type(ES::as_elem()))
+    {
+        matmul_cmma_ref::<R, EG, EG, PlaneMmaLaunchDispatch<ES, f32>>(
+            client,
+            lhs,
+            rhs,
+            out,
+            batch_strategy,
+            epilogue,
+        );
     } else {
-        matmul_cmma_ref::<R, EG, PlaneMmaLaunchDispatch>(client, lhs, rhs, out);
+        matmul_cmma_ref::<R, EG, EG, PlaneMmaLaunchDispatch>(
+            client,
+            lhs,
+            rhs,
+            out,
+            batch_strategy,
+            epilogue,
+        );
     }
 }
 
+/// Reason why a matmul can't be launched on a device
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatmulUnavailable {
+    /// The device doesn't support the element type
+    TypeUnsupported(Elem),
+}
+
+/// Launch a quantized matrix multiplication kernel on `i8` inputs, accumulating in `i32`
+/// and writing the output as `EO`.
+///
+/// The product is dequantized by the epilogue: a per-tensor scale is given as its
+/// [alpha](MatmulEpilogue::alpha), and per-channel scales of the columns of the output
+/// as its [scale](MatmulEpilogue::scale).
+///
+/// Cmma will be used if available and enabled,
+/// otherwise it will fall back on a non-cmma implementation
+///
+/// # Errors
+///
+/// If the device doesn't support `i8` or `EO`, in which case nothing is launched.
+///
+/// # Panics
+///
+/// If the tensors read by the epilogue don't fit the output.
+pub fn launch_ref_quantized<R: Runtime, EO: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandleRef<'_, R>,
+    rhs: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    disable_cmma: bool,
+    epilogue: MatmulEpilogue<'_, R>,
+) -> Result<(), MatmulUnavailable> {
+    for elem in [i8::as_elem(), EO::as_elem()] {
+        if !client.properties().feature_enabled(Feature::Type(elem)) {
+            return Err(MatmulUnavailable::TypeUnsupported(elem));
+        }
+    }
+    epilogue.check(&out);
+
+    if !disable_cmma && check_availability::<CmmaLaunchDispatch<i8, i32>, R>(client).is_ok() {
+        matmul_cmma_ref::<R, i8, EO, CmmaLaunchDispatch<i8, i32>>(
+            client,
+            lhs,
+            rhs,
+            out,
+            BatchStrategy::OneToOne,
+            epilogue,
+        );
+    } else {
+        matmul_cmma_ref::<R, i8, EO, PlaneMmaLaunchDispatch<i8, i32>>(
+            client,
+            lhs,
+            rhs,
+            out,
+            BatchStrategy::OneToOne,
+            epilogue,
+        );
+    }
+
+    Ok(())
+}
+
 /// Launch a matrix multiplication kernel.
 ///
 /// Cmma will be used if available and enabled,
@@ -57,82 +320,203 @@ pub fn launch<R: Runtime, EG: Numeric>(
     out
 }
 
-fn matmul_cmma_ref<R: Runtime, E: Numeric, D: MatmulLaunchDispatch>(
+/// Launch a matrix multiplication kernel, distributing the work across cubes
+/// with the given batch strategy.
+///
+/// Cmma will be used if available and enabled,
+/// otherwise it will fall back on a non-cmma implementation
+///
+/// # Panics
+///
+/// With split-K and stream-K, if the output is not contiguous.
+pub fn launch_with_batch_strategy<R: Runtime, EG: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandle<R, EG>,
+    rhs: TensorHandle<R, EG>,
+    out: TensorHandle<R, EG>,
+    disable_cmma: bool,
+    batch_strategy: BatchStrategy,
+) -> TensorHandle<R, EG> {
+    launch_ref_with_batch_strategy::<R, EG>(
+        client,
+        lhs.as_ref(),
+        rhs.as_ref(),
+        out.as_ref(),
+        disable_cmma,
+        batch_strategy,
+    );
+    out
+}
+
+/// Launch a matrix multiplication kernel, fusing the given epilogue to the writing
+/// of the output.
+///
+/// Cmma will be used if available and enabled,
+/// otherwise it will fall back on a non-cmma implementation
+///
+/// # Panics
+///
+/// If the tensors read by the epilogue don't fit the output.
+pub fn launch_with_epilogue<R: Runtime, EG: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandle<R, EG>,
+    rhs: TensorHandle<R, EG>,
+    out: TensorHandle<R, EG>,
+    disable_cmma: bool,
+    epilogue: MatmulEpilogue<'_, R>,
+) -> TensorHandle<R, EG> {
+    launch_ref_with_epilogue::<R, EG>(
+        client,
+        lhs.as_ref(),
+        rhs.as_ref(),
+        out.as_ref(),
+        disable_cmma,
+        epilogue,
+    );
+    out
+}
+
+/// Launch a quantized matrix multiplication kernel on `i8` inputs, accumulating in `i32`
+/// and writing the output as `EO`.
+///
+/// The product is dequantized by the epilogue: a per-tensor scale is given as its
+/// [alpha](MatmulEpilogue::alpha), and per-channel scales of the columns of the output
+/// as its [scale](MatmulEpilogue::scale).
+///
+/// Cmma will be used if available and enabled,
+/// otherwise it will fall back on a non-cmma implementation
+///
+/// # Errors
+///
+/// If the device doesn't support `i8` or `EO`, in which case nothing is launched.
+///
+/// # Panics
+///
+/// If the tensors read by the epilogue don't fit the output.
+pub fn launch_quantized<R: Runtime, EO: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandle<R, i8>,
+    rhs: TensorHandle<R, i8>,
+    out: TensorHandle<R, EO>,
+    disable_cmma: bool,
+    epilogue: MatmulEpilogue<'_, R>,
+) -> Result<TensorHandle<R, EO>, MatmulUnavailable> {
+    launch_ref_quantized::<R, EO>(
+        client,
+        lhs.as_ref(),
+        rhs.as_ref(),
+        out.as_ref(),
+        disable_cmma,
+        epilogue,
+    )?;
+    Ok(out)
+}
+
+fn matmul_cmma_ref<R: Runtime, E: Numeric, EO: Numeric, D: MatmulLaunchDispatch>(
     client: &ComputeClient<R::Server, R::Channel>,
     lhs: TensorHandleRef<'_, R>,
     rhs: TensorHandleRef<'_, R>,
     out: TensorHandleRef<'_, R>,
+    batch_strategy: BatchStrategy,
+    epilogue: MatmulEpilogue<'_, R>,
 ) {
-    let check_layout = |tensor: &TensorHandleRef<'_, R>| match matrix_layout(tensor.strides) {
-        MatrixLayout::Contiguous => (false, false),
-        MatrixLayout::MildlyPermuted {
-            transposed,
-            batch_swap: _,
-        } => (false, transposed),
-        MatrixLayout::HighlyPermuted => (true, false),
+    // Batch axes are addressed with their own strides, so only matrices without a contiguous
+    // axis need to be copied.
+    let check_layout = |tensor: &TensorHandleRef<'_, R>| {
+        let rank = tensor.strides.len();
+        match (tensor.strides[rank - 1], tensor.strides[rank - 2]) {
+            (1, _) => (false, false),
+            (_, 1) => (false, true),
+            _ => (true, false),
+        }
     };
 
     let (lhs_make_contiguous, lhs_transposed) = check_layout(&lhs);
     let (rhs_make_contiguous, rhs_transposed) = check_layout(&rhs);
 
     match (lhs_make_contiguous, rhs_make_contiguous) {
-        (false, false) => matmul_cmma_ref_no_check::<R, E, D>(
+        (false, false) => matmul_cmma_ref_no_check::<R, E, EO, D>(
             client,
             lhs,
             rhs,
             out,
             (lhs_transposed, rhs_transposed),
+            batch_strategy,
+            epilogue,
         ),
-        (false, true) => matmul_cmma_ref_no_check::<R, E, D>(
+        (false, true) => matmul_cmma_ref_no_check::<R, E, EO, D>(
             client,
             lhs,
             into_contiguous::<R, E>(client, rhs).as_ref(),
             out,
             (lhs_transposed, rhs_transposed),
+            batch_strategy,
+            epilogue,
         ),
-        (true, false) => matmul_cmma_ref_no_check::<R, E, D>(
+        (true, false) => matmul_cmma_ref_no_check::<R, E, EO, D>(
             client,
             into_contiguous::<R, E>(client, lhs).as_ref(),
             rhs,
             out,
             (lhs_transposed, rhs_transposed),
+            batch_strategy,
+            epilogue,
         ),
-        (true, true) => matmul_cmma_ref_no_check::<R, E, D>(
+        (true, true) => matmul_cmma_ref_no_check::<R, E, EO, D>(
             client,
             into_contiguous::<R, E>(client, lhs).as_ref(),
             into_contiguous::<R, E>(client, rhs).as_ref(),
             out,
             (lhs_transposed, rhs_transposed),
+            batch_strategy,
+            epilogue,
         ),
     }
 }
 
-fn matmul_cmma_ref_no_check<R: Runtime, EG: Numeric, D: MatmulLaunchDispatch>(
+fn matmul_cmma_ref_no_check<R: Runtime, EG: Numeric, EO: Numeric, D: MatmulLaunchDispatch>(
     client: &ComputeClient<R::Server, R::Channel>,
     lhs: TensorHandleRef<'_, R>,
     rhs: TensorHandleRef<'_, R>,
     out: TensorHandleRef<'_, R>,
     transposed: (bool, bool),
+    batch_strategy: BatchStrategy,
+    epilogue: MatmulEpilogue<'_, R>,
 ) {
-    let rank = lhs.strides.len();
+    let lhs_rank = lhs.strides.len();
+    let rhs_rank = rhs.strides.len();
+    let out_rank = out.strides.len();
 
-    let m = lhs.shape[rank - 2] as u32;
-    let k = lhs.shape[rank - 1] as u32;
-    let n = rhs.shape[rank - 1] as u32;
+    let m = lhs.shape[lhs_rank - 2] as u32;
+    let k = lhs.shape[lhs_rank - 1] as u32;
+    let n = rhs.shape[rhs_rank - 1] as u32;
 
-    let available_vectorizations = R::supported_line_sizes();
-    let lhs_line_size =
-        tensor_line_size(available_vectorizations, lhs.shape, lhs.strides, rank - 1);
-    let rhs_line_size =
-        tensor_line_size(available_vectorizations, rhs.shape, rhs.strides, rank - 1);
-    let out_line_size =
-        tensor_line_size(available_vectorizations, out.shape, out.strides, rank - 1);
+    // Lines are read along the contiguous axis of each matrix.
+    let line_size = |tensor: &TensorHandleRef<'_, R>, transposed: bool| {
+        let rank = tensor.strides.len();
+        let axis = match transposed {
+            true => rank - 2,
+            false => rank - 1,
+        };
+        line_size_along(
+            R::supported_line_sizes(),
+            tensor.shape,
+            tensor.strides,
+            axis,
+        )
+    };
+    let lhs_line_size = line_size(&lhs, transposed.0);
+    let rhs_line_size = line_size(&rhs, transposed.1);
+    let out_line_size = line_size(&out, false);
 
     let problem = MatmulProblem::<EG> {
         m: m as usize,
         n: n as usize,
         k: k as usize,
-        batches: out.shape[..out.shape.len() - 2].to_vec(),
+        batches: (
+            lhs.shape[..lhs_rank - 2].to_vec(),
+            rhs.shape[..rhs_rank - 2].to_vec(),
+        ),
         lhs_layout: match transposed.0 {
             true => matmul::components::MatrixLayout::ColMajor,
             false => matmul::components::MatrixLayout::RowMajor,
@@ -146,26 +530,104 @@ fn matmul_cmma_ref_no_check<R: Runtime, EG: Numeric, D: MatmulLaunchDispatch>(
         out_line_size,
         _element: PhantomData,
     };
+    assert_eq!(
+        out.shape[..out_rank - 2],
+        problem.out_batches(),
+        "The output batch shape doesn't match the broadcast batches of the lhs and rhs"
+    );
 
     let cube_dim = D::cube_dim();
-    let cube_count = D::cube_count(&problem);
-
     let advanced_config = Default::default();
 
-    launch_matmul::<R, EG, D>(
-        client,
-        lhs,
-        rhs,
-        out,
-        problem,
-        cube_dim,
-        cube_count,
-        advanced_config,
-    );
+    match batch_strategy {
+        BatchStrategy::OneToOne => {
+            let cube_count = D::cube_count(&problem);
+
+            launch_matmul::<R, EG, EO, D>(
+                client,
+                lhs,
+                rhs,
+                out,
+                problem,
+                cube_dim,
+                cube_count,
+                advanced_config,
+                epilogue,
+            );
+        }
+        BatchStrategy::SplitK { num_splits } => {
+            assert!(
+                epilogue.epilogue().is_identity(),
+                "Split-K doesn't support fused epilogues"
+            );
+
+            let config =
+                make_split_k_config::<EG, D>(&problem, &cube_dim, num_splits, &advanced_config);
+            let cube_count = CubeCount::Static(
+                batch::Config::cube_count_x(&config),
+                batch::Config::cube_count_y(&config),
+                config.cube_count_z(),
+            );
+
+            launch_partitioned::<
+                R,
+                EG,
+                EO,
+                batch::split_k::Matmul<
+                    EG,
+                    PartialAccumulator,
+                    D::ElementInput,
+                    DispatchGlobalMatmul<D, EG, PartialAccumulator>,
+                >,
+            >(
+                client,
+                lhs,
+                rhs,
+                out,
+                &problem,
+                cube_dim,
+                cube_count,
+                config.num_splits(),
+                config,
+            );
+        }
+        BatchStrategy::StreamK { num_cubes } => {
+            assert!(
+                epilogue.epilogue().is_identity(),
+                "Stream-K doesn't support fused epilogues"
+            );
+
+            let config =
+                make_stream_k_config::<EG, D>(&problem, &cube_dim, num_cubes, &advanced_config);
+            let cube_count = CubeCount::Static(batch::Config::cube_count_x(&config), 1, 1);
+
+            launch_partitioned::<
+                R,
+                EG,
+                EO,
+                batch::stream_k::Matmul<
+                    EG,
+                    PartialAccumulator,
+                    D::ElementInput,
+                    DispatchGlobalMatmul<D, EG, PartialAccumulator>,
+                >,
+            >(
+                client,
+                lhs,
+                rhs,
+                out,
+                &problem,
+                cube_dim,
+                cube_count,
+                config.num_slots(),
+                config,
+            );
+        }
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
-fn launch_matmul<R: Runtime, EG: Numeric, D: MatmulLaunchDispatch>(
+fn launch_matmul<R: Runtime, EG: Numeric, EO: Numeric, D: MatmulLaunchDispatch>(
     client: &ComputeClient<R::Server, R::Channel>,
     lhs: TensorHandleRef<'_, R>,
     rhs: TensorHandleRef<'_, R>,
@@ -174,19 +636,20 @@ fn launch_matmul<R: Runtime, EG: Numeric, D: MatmulLaunchDispatch>(
     cube_dim: CubeDim,
     cube_count: CubeCount,
     advanced_config: AdvancedConfig,
+    epilogue: MatmulEpilogue<'_, R>,
 ) {
-    let config = make_cmma_config::<EG, D>(&problem, &cube_dim, &cube_count, &advanced_config);
+    let config = make_cmma_config_with_epilogue::<EG, D>(
+        &problem,
+        &cube_dim,
+        &cube_count,
+        &advanced_config,
+        epilogue.epilogue(),
+    );
 
-    type StageMatmul<TMM, CSS, EG, ES, EA> = stage::row_accumulate::Matmul<ES, EG, EA, TMM, CSS>;
-    type GlobalMatmul<TMM, CSS, EG, ES, EA> =
-        global::homogeneous::Matmul<EG, ES, StageMatmul<TMM, CSS, EG, ES, EA>>;
+    let placeholder = EpilogueArgs::<R>::placeholder::<EO>(client);
 
     unsafe {
-        batch::one_to_one::Matmul::<
-            EG,
-            D::ElementInput,
-            GlobalMatmul<D::TileMatmul, D::StageSize, EG, D::ElementInput, D::ElementAccumulator>,
-        >::launch_unchecked::<R>(
+        batch::one_to_one::Matmul::<EG, EO, D::ElementInput, DispatchGlobalMatmul<D, EG, EO>>::launch_unchecked::<R>(
             client,
             cube_dim,
             cube_count,
@@ -202,13 +665,99 @@ fn launch_matmul<R: Runtime, EG: Numeric, D: MatmulLaunchDispatch>(
                 rhs.shape,
                 problem.rhs_line_size,
             ),
-            TensorArg::<R>::from_raw_parts::<EG>(
+            TensorArg::<R>::from_raw_parts::<EO>(
                 out.handle,
                 out.strides,
                 out.shape,
                 problem.out_line_size,
             ),
+            epilogue.args::<EO>(&placeholder, problem.out_line_size),
             config,
         );
     }
 }
+
+/// Element type of the partial results of partitioned matmuls.
+///
+/// Partial results are kept and summed in full precision, and only cast to the output
+/// element type once, so that splitting the k dimension doesn't lose precision.
+type PartialAccumulator = f32;
+
+/// Launch a batch matmul writing `num_parts` partial results per element of the output
+/// to a workspace, then sum them into the output.
+///
+/// Floats don't support atomic addition, so partial results can't be accumulated
+/// directly in the output.
+#[allow(clippy::too_many_arguments)]
+fn launch_partitioned<
+    R: Runtime,
+    EG: Numeric,
+    EO: Numeric,
+    BMM: batch::Matmul<EG, PartialAccumulator>,
+>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandleRef<'_, R>,
+    rhs: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    problem: &MatmulProblem<EG>,
+    cube_dim: CubeDim,
+    cube_count: CubeCount,
+    num_parts: u32,
+    config: BMM::Config,
+) {
+    let rank = out.shape.len();
+    let mut contiguous_strides = vec![1; rank];
+    for dim in (0..rank - 1).rev() {
+        contiguous_strides[dim] = contiguous_strides[dim + 1] * out.shape[dim + 1];
+    }
+    assert!(
+        out.strides == contiguous_strides.as_slice(),
+        "Partial results can only be summed into a contiguous output, got strides {:?}",
+        out.strides
+    );
+
+    // Parts that no cube writes to must not contribute to the sum.
+    let workspace = TensorHandle::<R, PartialAccumulator>::zeros(
+        client,
+        [&[num_parts as usize], out.shape].concat(),
+    );
+
+    let placeholder = EpilogueArgs::<R>::placeholder::<PartialAccumulator>(client);
+
+    unsafe {
+        BMM::launch_unchecked::<R>(
+            client,
+            cube_dim,
+            cube_count,
+            TensorArg::<R>::from_raw_parts::<EG>(
+                lhs.handle,
+                lhs.strides,
+                lhs.shape,
+                problem.lhs_line_size,
+            ),
+            TensorArg::<R>::from_raw_parts::<EG>(
+                rhs.handle,
+                rhs.strides,
+                rhs.shape,
+                problem.rhs_line_size,
+            ),
+            workspace.as_arg(problem.out_line_size),
+            EpilogueArgs::none::<PartialAccumulator>(&placeholder),
+            config,
+        );
+    }
+
+    // View the output with a leading axis of size 1 to reduce the parts into.
+    let num_elements: usize = out.shape.iter().product();
+    let out_shape = [&[1], out.shape].concat();
+    let out_strides = [&[num_elements], out.strides].concat();
+    let out = unsafe {
+        TensorHandleRef::<R>::from_raw_parts(
+            out.handle,
+            &out_strides,
+            &out_shape,
+            EO::as_elem().size(),
+        )
+    };
+    reduce::naive::launch::<R, PartialAccumulator, EO, Sum>(client, workspace.as_ref(), out, 0);
+}
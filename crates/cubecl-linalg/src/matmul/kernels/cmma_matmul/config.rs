@@ -2,6 +2,7 @@ use cubecl_core::prelude::*;
 
 use crate::matmul::components::batch;
 use crate::matmul::components::global;
+use crate::matmul::components::global::Epilogue;
 use crate::matmul::components::stage;
 use crate::matmul::components::stage::Matmul as _;
 use crate::matmul::components::tile::Matmul as _;
@@ -42,6 +43,22 @@ impl Default for AdvancedConfig {
     }
 }
 
+pub(crate) type CmmaSplitKConfig<T> = batch::split_k::Config<CmmaGmmConfig<T>>;
+pub(crate) type CmmaStreamKConfig<T> = batch::stream_k::Config<CmmaGmmConfig<T>>;
+pub(crate) type CmmaTileConfig<D> = <<D as MatmulLaunchDispatch>::TileMatmul as MatmulKernel<
+    <D as MatmulLaunchDispatch>::ElementInput,
+    <D as MatmulLaunchDispatch>::ElementAccumulator,
+>>::Config;
+
+type Tmm<D> = <D as MatmulLaunchDispatch>::TileMatmul;
+type Smm<D, EG> = stage::row_accumulate::Matmul<
+    <D as MatmulLaunchDispatch>::ElementInput,
+    EG,
+    <D as MatmulLaunchDispatch>::ElementAccumulator,
+    Tmm<D>,
+    <D as MatmulLaunchDispatch>::StageSize,
+>;
+
 /// Make a config for the cmma batch kernel, given problem definition,
 /// cube settings and advanced config
 pub fn make_cmma_config<EG, D>(
@@ -49,20 +66,119 @@ pub fn make_cmma_config<EG, D>(
     cube_dim: &CubeDim,
     cube_count: &CubeCount,
     advanced_config: &AdvancedConfig,
-) -> CmmaBmmConfig<<D::TileMatmul as MatmulKernel<D::ElementInput, D::ElementAccumulator>>::Config>
+) -> CmmaBmmConfig<CmmaTileConfig<D>>
+where
+    EG: Numeric,
+    D: MatmulLaunchDispatch,
+{
+    make_cmma_config_with_epilogue::<EG, D>(
+        problem,
+        cube_dim,
+        cube_count,
+        advanced_config,
+        Epilogue::default(),
+    )
+}
+
+/// Make a config for the cmma batch kernel whose output goes through the given epilogue
+pub(crate) fn make_cmma_config_with_epilogue<EG, D>(
+    problem: &MatmulProblem<EG>,
+    cube_dim: &CubeDim,
+    cube_count: &CubeCount,
+    advanced_config: &AdvancedConfig,
+    epilogue: Epilogue,
+) -> CmmaBmmConfig<CmmaTileConfig<D>>
+where
+    EG: Numeric,
+    D: MatmulLaunchDispatch,
+{
+    let (cube_count_x, cube_count_y, cube_count_z) = if let CubeCount::Static(x, y, z) = cube_count
+    {
+        (x, y, z)
+    } else {
+        panic!("Dynamic cube count unsupported")
+    };
+
+    let g =
+        make_cmma_gmm_config::<EG, D>(problem, cube_dim, advanced_config).with_epilogue(epilogue);
+    let b = CmmaBmmConfig::new(g, *cube_count_x, *cube_count_y, *cube_count_z);
+    problem.check_config::<CmmaBmmConfig<CmmaTileConfig<D>>>(&b);
+
+    b
+}
+
+/// Make a config for the split-K batch kernel, where k is split into at most `num_splits`
+/// ranges of whole stages
+pub(crate) fn make_split_k_config<EG, D>(
+    problem: &MatmulProblem<EG>,
+    cube_dim: &CubeDim,
+    num_splits: u32,
+    advanced_config: &AdvancedConfig,
+) -> CmmaSplitKConfig<CmmaTileConfig<D>>
+where
+    EG: Numeric,
+    D: MatmulLaunchDispatch,
+{
+    assert!(num_splits > 0, "Split-K needs at least one split");
+
+    let (stage_m, stage_n, stage_k) = (Smm::<D, EG>::M, Smm::<D, EG>::N, Smm::<D, EG>::K);
+    let k = problem.k as u32;
+    let k_per_split = Ord::max(k.div_ceil(num_splits).next_multiple_of(stage_k), stage_k);
+
+    let g = make_cmma_gmm_config::<EG, D>(problem, cube_dim, advanced_config);
+    let b = CmmaSplitKConfig::new(
+        g,
+        (problem.m as u32).div_ceil(stage_m),
+        (problem.n as u32).div_ceil(stage_n),
+        problem.num_batches() as u32,
+        k.div_ceil(k_per_split),
+        k_per_split,
+    );
+    problem.check_config::<CmmaSplitKConfig<CmmaTileConfig<D>>>(&b);
+
+    b
+}
+
+/// Make a config for the stream-K batch kernel, where the work is shared by at most
+/// `num_cubes` cubes
+pub(crate) fn make_stream_k_config<EG, D>(
+    problem: &MatmulProblem<EG>,
+    cube_dim: &CubeDim,
+    num_cubes: u32,
+    advanced_config: &AdvancedConfig,
+) -> CmmaStreamKConfig<CmmaTileConfig<D>>
 where
     EG: Numeric,
     D: MatmulLaunchDispatch,
 {
-    type Tmm<D> = <D as MatmulLaunchDispatch>::TileMatmul;
-    type Smm<D, EG> = stage::row_accumulate::Matmul<
-        <D as MatmulLaunchDispatch>::ElementInput,
-        EG,
-        <D as MatmulLaunchDispatch>::ElementAccumulator,
-        Tmm<D>,
-        <D as MatmulLaunchDispatch>::StageSize,
-    >;
+    assert!(num_cubes > 0, "Stream-K needs at least one cube");
+
+    let (stage_m, stage_n, stage_k) = (Smm::<D, EG>::M, Smm::<D, EG>::N, Smm::<D, EG>::K);
+
+    let g = make_cmma_gmm_config::<EG, D>(problem, cube_dim, advanced_config);
+    let b = CmmaStreamKConfig::new(
+        g,
+        num_cubes,
+        (problem.m as u32).div_ceil(stage_m),
+        (problem.n as u32).div_ceil(stage_n),
+        problem.num_batches() as u32,
+        Ord::max((problem.k as u32).div_ceil(stage_k), 1),
+    );
+    problem.check_config::<CmmaStreamKConfig<CmmaTileConfig<D>>>(&b);
 
+    b
+}
+
+/// Make the config of the global matmul, shared by all batch kernels
+fn make_cmma_gmm_config<EG, D>(
+    problem: &MatmulProblem<EG>,
+    cube_dim: &CubeDim,
+    advanced_config: &AdvancedConfig,
+) -> CmmaGmmConfig<CmmaTileConfig<D>>
+where
+    EG: Numeric,
+    D: MatmulLaunchDispatch,
+{
     let (stage_m, stage_n, stage_k) = (Smm::<D, EG>::M, Smm::<D, EG>::N, Smm::<D, EG>::K);
     let (tile_m, tile_n, tile_k) = (Tmm::<D>::M, Tmm::<D>::N, Tmm::<D>::K);
     let (lhs_stage_dim, rhs_stage_dim, out_stage_dim) =
@@ -74,13 +190,6 @@ where
     let plane_dim = cube_dim.x;
     let num_planes = cube_dim.y;
 
-    let (cube_count_x, cube_count_y, cube_count_z) = if let CubeCount::Static(x, y, z) = cube_count
-    {
-        (x, y, z)
-    } else {
-        panic!("Dynamic cube count unsupported")
-    };
-
     let (lhs_tile_layout, lhs_tile_line_size) = match advanced_config.enforced_tile_layout.0 {
         Some(enforced_layout) if enforced_layout != problem.lhs_layout => (enforced_layout, 1),
         _ => (problem.lhs_layout, problem.lhs_line_size),
@@ -106,7 +215,8 @@ where
         num_planes,
         advanced_config.tiling_order,
     );
-    let g = CmmaGmmConfig::new(
+
+    CmmaGmmConfig::new(
         s,
         check_m_bounds,
         check_n_bounds,
@@ -115,16 +225,10 @@ where
         problem.lhs_line_size as u32,
         problem.rhs_line_size as u32,
         problem.out_line_size as u32,
-    );
-    let b = CmmaBmmConfig::new(g, *cube_count_x, *cube_count_y, *cube_count_z);
-    problem.check_config::<CmmaBmmConfig<
-        <D::TileMatmul as MatmulKernel<D::ElementInput, D::ElementAccumulator>>::Config,
-    >>(&b);
-
-    b
+    )
 }
 
-fn create_stage_dim(
+pub(crate) fn create_stage_dim(
     stage_m: u32,
     stage_n: u32,
     stage_k: u32,
@@ -1,7 +1,9 @@
+use std::marker::PhantomData;
+
 use cubecl_core::{prelude::*, Feature};
 
 use crate::matmul::components::stage::{S4x4x2, StageSize};
-use crate::matmul::components::tile::accelerated::Accelerated16x16x16;
+use crate::matmul::components::tile::accelerated::{Accelerated16x16x16, CmmaValid};
 use crate::matmul::components::tile::plane::PlaneMma16x16x16;
 use crate::matmul::components::tile::Matmul;
 use crate::matmul::components::{tile, MatrixLayout};
@@ -29,13 +31,18 @@ pub trait MatmulLaunchDispatch {
     ) -> <Self::TileMatmul as MatmulKernel<Self::ElementInput, Self::ElementAccumulator>>::Config;
 }
 
-pub struct PlaneMmaLaunchDispatch {}
+/// Launch information for the plane matmul, computing on inputs of `ES` with an
+/// accumulator of `EA`
+pub struct PlaneMmaLaunchDispatch<ES: Numeric = f32, EA: Numeric = f32> {
+    _es: PhantomData<ES>,
+    _ea: PhantomData<EA>,
+}
 
-impl MatmulLaunchDispatch for PlaneMmaLaunchDispatch {
+impl<ES: Numeric, EA: Numeric> MatmulLaunchDispatch for PlaneMmaLaunchDispatch<ES, EA> {
     const PLANE_DIM: u32 = 32;
     type StageSize = S4x4x2;
-    type ElementInput = f32;
-    type ElementAccumulator = f32;
+    type ElementInput = ES;
+    type ElementAccumulator = EA;
 
     type TileMatmul = PlaneMma16x16x16<Self::ElementInput, Self::ElementAccumulator>;
 
@@ -71,13 +78,21 @@ impl MatmulLaunchDispatch for PlaneMmaLaunchDispatch {
     }
 }
 
-pub struct CmmaLaunchDispatch {}
+/// Launch information for the accelerated matmul, computing on inputs of `ES` with an
+/// accumulator of `EA`
+pub struct CmmaLaunchDispatch<ES: Numeric = half::f16, EA: Numeric = f32> {
+    _es: PhantomData<ES>,
+    _ea: PhantomData<EA>,
+}
 
-impl MatmulLaunchDispatch for CmmaLaunchDispatch {
+impl<ES: Numeric, EA: Numeric> MatmulLaunchDispatch for CmmaLaunchDispatch<ES, EA>
+where
+    (ES, EA): CmmaValid<ES, EA>,
+{
     const PLANE_DIM: u32 = 32;
     type StageSize = S4x4x2;
-    type ElementInput = half::f16;
-    type ElementAccumulator = f32;
+    type ElementInput = ES;
+    type ElementAccumulator = EA;
 
     type TileMatmul = Accelerated16x16x16<Self::ElementInput, Self::ElementAccumulator>;
 
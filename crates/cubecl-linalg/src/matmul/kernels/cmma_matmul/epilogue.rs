@@ -0,0 +1,168 @@
+use cubecl_core::prelude::*;
+use cubecl_core::server::Handle;
+
+use crate::matmul::components::global::{Activation, Epilogue, EpilogueArgs};
+
+/// Operations fused to the output of a matrix multiplication, with the data they read
+///
+/// Operations are applied in the order described by [Epilogue]. An epilogue with no
+/// operation writes the product unchanged.
+pub struct MatmulEpilogue<'a, R: Runtime> {
+    alpha: Option<f32>,
+    beta: Option<f32>,
+    scale: Option<TensorHandleRef<'a, R>>,
+    bias: Option<TensorHandleRef<'a, R>>,
+    activation: Activation,
+    residual: Option<TensorHandleRef<'a, R>>,
+}
+
+impl<R: Runtime> Default for MatmulEpilogue<'_, R> {
+    fn default() -> Self {
+        Self {
+            alpha: None,
+            beta: None,
+            scale: None,
+            bias: None,
+            activation: Activation::Identity,
+            residual: None,
+        }
+    }
+}
+
+impl<'a, R: Runtime> MatmulEpilogue<'a, R> {
+    /// Create an epilogue with no operation
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Scale the product by `alpha`
+    pub fn alpha(mut self, alpha: f32) -> Self {
+        self.alpha = Some(alpha);
+        self
+    }
+
+    /// Scale the product by a contiguous tensor of shape `[n]`, holding one value per column
+    /// of the output.
+    ///
+    /// Suited to per-channel dequantization scales, while a per-tensor scale is an `alpha`.
+    pub fn scale(mut self, scale: TensorHandleRef<'a, R>) -> Self {
+        self.scale = Some(scale);
+        self
+    }
+
+    /// Add the previous content of the output, scaled by `beta`
+    pub fn beta(mut self, beta: f32) -> Self {
+        self.beta = Some(beta);
+        self
+    }
+
+    /// Add a contiguous bias of shape `[n]` to each row of the output
+    pub fn bias(mut self, bias: TensorHandleRef<'a, R>) -> Self {
+        self.bias = Some(bias);
+        self
+    }
+
+    /// Apply an activation to the output
+    pub fn activation(mut self, activation: Activation) -> Self {
+        self.activation = activation;
+        self
+    }
+
+    /// Add a residual after the activation.
+    ///
+    /// The residual must have the shape and strides of the output, and can't alias it.
+    pub fn residual(mut self, residual: TensorHandleRef<'a, R>) -> Self {
+        self.residual = Some(residual);
+        self
+    }
+
+    /// Returns the chain of operations to compile in the kernel
+    pub(crate) fn epilogue(&self) -> Epilogue {
+        Epilogue {
+            alpha: self.alpha.is_some(),
+            scale: self.scale.is_some(),
+            beta: self.beta.is_some(),
+            bias: self.bias.is_some(),
+            activation: self.activation,
+            residual: self.residual.is_some(),
+        }
+    }
+
+    /// Asserts that the tensors read by the epilogue fit the output
+    ///
+    /// # Panics:
+    ///
+    ///  - If the scale or the bias is not contiguous with one value per column of the output
+    ///  - If the residual doesn't have the shape and strides of the output
+    pub(crate) fn check(&self, out: &TensorHandleRef<'_, R>) {
+        let rank = out.shape.len();
+
+        for (name, tensor) in [("Scale", &self.scale), ("Bias", &self.bias)] {
+            if let Some(tensor) = tensor {
+                assert!(
+                    tensor.shape == [out.shape[rank - 1]] && tensor.strides == [1],
+                    "{name} must be contiguous of shape [{}], got shape {:?} and strides {:?}",
+                    out.shape[rank - 1],
+                    tensor.shape,
+                    tensor.strides
+                );
+            }
+        }
+
+        if let Some(residual) = &self.residual {
+            assert!(
+                residual.shape == out.shape && residual.strides == out.strides,
+                "Residual must have the shape {:?} and strides {:?} of the output, got shape {:?} and strides {:?}",
+                out.shape,
+                out.strides,
+                residual.shape,
+                residual.strides
+            );
+        }
+    }
+
+    /// Returns the arguments of the epilogue, with tensors vectorized like the output.
+    ///
+    /// Tensors that the epilogue doesn't read are bound to the placeholder, allocated with
+    /// [EpilogueArgs::placeholder] for `EG`.
+    pub(crate) fn args<'b, EG: Numeric>(
+        &'b self,
+        placeholder: &'b Handle,
+        out_line_size: u8,
+    ) -> EpilogueArgs<'b, R> {
+        let mut args = EpilogueArgs::none::<EG>(placeholder);
+
+        // Safety: the shapes and strides of the tensors are checked against the output.
+        unsafe {
+            if let Some(scale) = &self.scale {
+                args.scale = TensorArg::from_raw_parts::<EG>(
+                    scale.handle,
+                    scale.strides,
+                    scale.shape,
+                    out_line_size,
+                );
+            }
+            if let Some(bias) = &self.bias {
+                args.bias = TensorArg::from_raw_parts::<EG>(
+                    bias.handle,
+                    bias.strides,
+                    bias.shape,
+                    out_line_size,
+                );
+            }
+            if let Some(residual) = &self.residual {
+                args.residual = TensorArg::from_raw_parts::<EG>(
+                    residual.handle,
+                    residual.strides,
+                    residual.shape,
+                    out_line_size,
+                );
+            }
+        }
+
+        args.alpha = self.alpha.unwrap_or(1.0);
+        args.beta = self.beta.unwrap_or(0.0);
+
+        args
+    }
+}
@@ -1,8 +1,14 @@
 mod base;
-mod config;
-mod dispatch;
+pub(crate) mod config;
+pub(crate) mod dispatch;
+mod epilogue;
 
-pub use base::{launch, launch_ref};
+pub use base::{
+    launch, launch_quantized, launch_ref, launch_ref_quantized, launch_ref_with_batch_strategy,
+    launch_ref_with_epilogue, launch_with_batch_strategy, launch_with_epilogue, BatchStrategy,
+    MatmulUnavailable,
+};
+pub use epilogue::MatmulEpilogue;
 
 #[cfg(feature = "export_tests")]
 pub use {
@@ -0,0 +1,181 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+/// The axis of the rows of the matrix, which is also the axis of the vector along `k` and the
+/// axis of the output.
+///
+/// With `vector_matrix`, the matrix is the rhs and its rows are the columns of the output.
+#[cube]
+fn row_axis(rank: u32, #[comptime] vector_matrix: bool) -> u32 {
+    if comptime!(vector_matrix) {
+        rank - 1
+    } else {
+        rank - 2
+    }
+}
+
+/// The axis of the matrix along `k`.
+#[cube]
+fn k_axis(rank: u32, #[comptime] vector_matrix: bool) -> u32 {
+    if comptime!(vector_matrix) {
+        rank - 2
+    } else {
+        rank - 1
+    }
+}
+
+/// The offset in elements of the given batch of the output in a tensor, whose batch axes of size
+/// 1 are broadcast.
+///
+/// Batches past the end of the output wrap around, so they always give valid offsets.
+#[cube]
+fn batch_offset<E: CubePrimitive, O: CubePrimitive>(
+    tensor: &Tensor<E>,
+    out: &Tensor<O>,
+    batch: u32,
+) -> u32 {
+    let rank = out.rank();
+    let mut remainder = batch;
+    let mut offset = 0;
+
+    for i in 0..rank - 2 {
+        let axis = rank - 3 - i;
+        let coordinate = remainder % out.shape(axis);
+        remainder /= out.shape(axis);
+        offset += coordinate % tensor.shape(axis) * tensor.stride(axis);
+    }
+
+    offset
+}
+
+/// Multiply each row of the matrix with the vector, with one cube per output.
+///
+/// Each unit accumulates the products of a strided part of the lines along `k`, which are then
+/// summed within each subcube and finally by the first unit.
+#[cube(launch_unchecked)]
+pub(crate) fn gemv_reduce_kernel<F: Float>(
+    matrix: &Tensor<Line<F>>,
+    vector: &Tensor<Line<F>>,
+    out: &mut Tensor<F>,
+    #[comptime] vector_matrix: bool,
+    #[comptime] cube_size: u32,
+) {
+    let rank = out.rank();
+    let row_axis = row_axis(rank, vector_matrix);
+    let k_axis = k_axis(rank, vector_matrix);
+    let line_size = matrix.line_size();
+
+    let num_rows = out.shape(row_axis);
+    let row = CUBE_POS % num_rows;
+    let batch = CUBE_POS / num_rows;
+
+    let matrix_offset = batch_offset(matrix, out, batch) + row * matrix.stride(row_axis);
+    let vector_offset = batch_offset(vector, out, batch);
+    let matrix_stride = line_size * matrix.stride(k_axis);
+    let vector_stride = line_size * vector.stride(row_axis);
+
+    let mut sum = Line::empty(line_size).fill(F::new(0.0));
+    for i in range_stepped(UNIT_POS, matrix.shape(k_axis) / line_size, CUBE_DIM) {
+        sum += matrix[(matrix_offset + i * matrix_stride) / line_size]
+            * vector[(vector_offset + i * vector_stride) / line_size];
+    }
+
+    let mut unit_sum = F::new(0.0);
+    #[unroll]
+    for lane in 0..line_size {
+        unit_sum += sum[lane];
+    }
+    let subcube_sum = subcube_sum(unit_sum);
+
+    // There is at most one subcube per unit.
+    let mut sums = SharedMemory::<F>::new(cube_size);
+    if subcube_elect() {
+        sums[UNIT_POS / SUBCUBE_DIM] = subcube_sum;
+    }
+    sync_units();
+
+    let mut num_outputs = num_rows;
+    for i in 0..rank - 2 {
+        num_outputs *= out.shape(i);
+    }
+
+    if UNIT_POS == 0 && CUBE_POS < num_outputs {
+        let mut total = F::new(0.0);
+        for subcube in 0..(CUBE_DIM - 1) / SUBCUBE_DIM + 1 {
+            total += sums[subcube];
+        }
+
+        out[batch_offset(out, out, batch) + row * out.stride(row_axis)] = total;
+    }
+}
+
+/// Multiply each row of the matrix with the vector, with one unit per line of outputs.
+///
+/// Each unit accumulates the lines of consecutive rows scaled by each value of the vector, which
+/// reads contiguous lines when the rows are the contiguous axis of the matrix.
+#[cube(launch_unchecked)]
+pub(crate) fn gemv_columns_kernel<F: Float>(
+    matrix: &Tensor<Line<F>>,
+    vector: &Tensor<F>,
+    out: &mut Tensor<Line<F>>,
+    #[comptime] vector_matrix: bool,
+) {
+    let rank = out.rank();
+    let row_axis = row_axis(rank, vector_matrix);
+    let k_axis = k_axis(rank, vector_matrix);
+    let line_size = matrix.line_size();
+
+    let num_row_lines = out.shape(row_axis) / line_size;
+    let row = ABSOLUTE_POS % num_row_lines * line_size;
+    let batch = ABSOLUTE_POS / num_row_lines;
+
+    let mut num_batches = 1;
+    for i in 0..rank - 2 {
+        num_batches *= out.shape(i);
+    }
+    if batch >= num_batches {
+        return;
+    }
+
+    let matrix_offset = batch_offset(matrix, out, batch) + row * matrix.stride(row_axis);
+    let vector_offset = batch_offset(vector, out, batch);
+
+    let mut sum = Line::empty(line_size).fill(F::new(0.0));
+    for i in 0..matrix.shape(k_axis) {
+        let value = vector[vector_offset + i * vector.stride(row_axis)];
+        sum += matrix[(matrix_offset + i * matrix.stride(k_axis)) / line_size]
+            * Line::empty(line_size).fill(value);
+    }
+
+    out[(batch_offset(out, out, batch) + row * out.stride(row_axis)) / line_size] = sum;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cubecl_core::host::{HostTensor, HostUnit};
+
+    #[test]
+    fn columns_of_transposed_matrix_times_vector() {
+        // The rhs of shape [3, 2], stored column major, times the lhs vector of shape [1, 3].
+        let matrix = HostTensor::new_strided(
+            (1..=6).map(|value| vec![value as f32]).collect(),
+            vec![3, 2],
+            vec![1, 3],
+        );
+        let vector = HostTensor::new(vec![1.0, -1.0, 2.0], vec![1, 3]);
+        let mut out = HostTensor::new(vec![vec![0.0]; 2], vec![1, 2]);
+
+        for unit in 0..2 {
+            gemv_columns_kernel::host::<f32>(
+                HostUnit::default().with_unit_pos(unit, 0, 0),
+                &matrix,
+                &vector,
+                &mut out,
+                true,
+            );
+        }
+
+        assert_eq!(out.data, vec![vec![5.0], vec![11.0]]);
+    }
+}
@@ -0,0 +1,104 @@
+use std::cmp::min;
+
+use cubecl_core::{calculate_cube_count_elemwise, prelude::*, Feature};
+
+use crate::tensor::{line_size_along, TensorHandle};
+
+use super::base::{gemv_columns_kernel, gemv_reduce_kernel};
+
+/// The number of units reducing each output.
+const CUBE_SIZE: u32 = 64;
+
+/// Whether the matmul of the given shapes is a matrix-vector or a vector-matrix product, which
+/// is when `m` or `n` is 1.
+pub fn is_gemv(lhs_shape: &[usize], rhs_shape: &[usize]) -> bool {
+    lhs_shape[lhs_shape.len() - 2] == 1 || rhs_shape[rhs_shape.len() - 1] == 1
+}
+
+/// Matrix multiplication where `m` or `n` is 1.
+pub fn gemv<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandle<R, F>,
+    rhs: TensorHandle<R, F>,
+    out: TensorHandle<R, F>,
+) -> TensorHandle<R, F> {
+    gemv_ref::<R, F>(client, lhs.as_ref(), rhs.as_ref(), out.as_ref());
+
+    out
+}
+
+/// Matrix multiplication where `m` or `n` is 1.
+///
+/// The matrix is the rhs when `m` is 1, and the lhs otherwise. When its rows are contiguous
+/// along `k`, each output is reduced by a cube with subcube sums, otherwise each unit computes a
+/// line of consecutive outputs. Any layout is supported, batch axes of size 1 being broadcast.
+pub fn gemv_ref<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandleRef<'_, R>,
+    rhs: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+) {
+    assert!(
+        is_gemv(lhs.shape, rhs.shape),
+        "Can't run a matrix-vector product of {:?} and {:?}",
+        lhs.shape,
+        rhs.shape
+    );
+
+    let rank = out.shape.len();
+    let num_outputs: usize = out.shape.iter().product();
+    if num_outputs == 0 {
+        return;
+    }
+
+    let vector_matrix = rhs.shape[rank - 1] != 1;
+    let (matrix, vector) = match vector_matrix {
+        true => (rhs, lhs),
+        false => (lhs, rhs),
+    };
+    let (row_axis, k_axis) = match vector_matrix {
+        true => (rank - 1, rank - 2),
+        false => (rank - 2, rank - 1),
+    };
+
+    let line_sizes = R::supported_line_sizes();
+    let subcube = client.properties().feature_enabled(Feature::Subcube);
+
+    if subcube && matrix.strides[k_axis] == 1 {
+        let line_size = min(
+            line_size_along(line_sizes, matrix.shape, matrix.strides, k_axis),
+            line_size_along(line_sizes, vector.shape, vector.strides, row_axis),
+        );
+
+        unsafe {
+            gemv_reduce_kernel::launch_unchecked::<F, R>(
+                client,
+                calculate_cube_count_elemwise(num_outputs, CubeDim::new(1, 1, 1)),
+                CubeDim::new(CUBE_SIZE, 1, 1),
+                matrix.as_tensor_arg(line_size),
+                vector.as_tensor_arg(line_size),
+                out.as_tensor_arg(1),
+                vector_matrix,
+                CUBE_SIZE,
+            );
+        }
+    } else {
+        let line_size = min(
+            line_size_along(line_sizes, matrix.shape, matrix.strides, row_axis),
+            line_size_along(line_sizes, out.shape, out.strides, row_axis),
+        );
+        let cube_dim = CubeDim::default();
+
+        unsafe {
+            gemv_columns_kernel::launch_unchecked::<F, R>(
+                client,
+                calculate_cube_count_elemwise(num_outputs / line_size as usize, cube_dim),
+                cube_dim,
+                matrix.as_tensor_arg(line_size),
+                vector.as_tensor_arg(1),
+                out.as_tensor_arg(line_size),
+                vector_matrix,
+            );
+        }
+    }
+}
@@ -0,0 +1,6 @@
+mod base;
+mod launch;
+
+pub use launch::gemv as launch;
+pub use launch::gemv_ref as launch_ref;
+pub use launch::is_gemv;
@@ -2,5 +2,7 @@
 pub mod cmma_matmul;
 /// Matmul using Accelerator
 pub mod cmma_old;
+/// Matrix-vector and vector-matrix products
+pub mod gemv;
 /// Non-cooperative Matmul
 pub mod tiling2d;
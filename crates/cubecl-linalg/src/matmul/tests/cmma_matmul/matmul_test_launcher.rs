@@ -5,15 +5,19 @@ use cubecl_core::server::Handle;
 use cubecl_core::CubeElement;
 use cubecl_core::Feature;
 
+use crate::matmul;
 use crate::matmul::components::batch;
+use crate::matmul::components::global::{Activation, Epilogue, EpilogueArgs};
 use crate::matmul::components::Ident;
 use crate::matmul::components::MatmulProblem;
 use crate::matmul::components::MatrixLayout;
 use crate::matmul::kernels::cmma_matmul;
+use crate::matmul::kernels::cmma_matmul::{BatchStrategy, MatmulEpilogue};
 use crate::matmul::tests::test_utils::CastInto;
 use crate::tensor::TensorHandle;
 
 use crate::matmul::tests::test_utils::assert_equals_approx;
+use crate::matmul::tests::test_utils::broadcast_batches;
 use crate::matmul::tests::test_utils::generate_random_data;
 use crate::matmul::tests::test_utils::matmul_cpu_reference;
 
@@ -35,7 +39,7 @@ pub fn test_matmul_internal<MM, EG, ES, B, R>(
 ) where
     EG: Float + CubeElement + Display + CastInto<ES>,
     ES: Float + CubeElement + Display + CastInto<EG>,
-    MM: batch::Matmul<EG>,
+    MM: batch::Matmul<EG, EG>,
     B: batch::Config,
     R: Runtime,
 {
@@ -54,6 +58,8 @@ pub fn test_matmul_internal<MM, EG, ES, B, R>(
     let rhs = tensor_raw_parts::<EG, R>(&client, &problem, Ident::Rhs);
     let out = tensor_raw_parts::<EG, R>(&client, &problem, Ident::Out);
 
+    let placeholder = EpilogueArgs::<R>::placeholder::<EG>(&client);
+
     unsafe {
         MM::launch_unchecked(
             &client,
@@ -77,6 +83,7 @@ pub fn test_matmul_internal<MM, EG, ES, B, R>(
                 &out.shape,
                 problem.out_line_size,
             ),
+            EpilogueArgs::none::<EG>(&placeholder),
             config,
         );
     }
@@ -97,6 +104,25 @@ pub fn test_matmul_launch<EG: Float + CubeElement + Display + CastInto<EG>, R: R
     problem: MatmulProblem<EG>,
     disable_cmma: bool,
     device: &R::Device,
+) {
+    test_matmul_launch_with_batch_strategy::<EG, R>(
+        problem,
+        disable_cmma,
+        BatchStrategy::OneToOne,
+        device,
+    );
+}
+
+/// Test the correctness of the high-level Matmul with the given batch strategy on the given
+/// device, against a naive CPU implementation over the given problem
+pub fn test_matmul_launch_with_batch_strategy<
+    EG: Float + CubeElement + Display + CastInto<EG>,
+    R: Runtime,
+>(
+    problem: MatmulProblem<EG>,
+    disable_cmma: bool,
+    batch_strategy: BatchStrategy,
+    device: &R::Device,
 ) {
     let client: ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel> = R::client(device);
 
@@ -113,12 +139,13 @@ pub fn test_matmul_launch<EG: Float + CubeElement + Display + CastInto<EG>, R: R
     let rhs = tensor_raw_parts::<EG, R>(&client, &problem, Ident::Rhs);
     let out = tensor_raw_parts::<EG, R>(&client, &problem, Ident::Out);
 
-    let out = cmma_matmul::launch::<R, EG>(
+    let out = cmma_matmul::launch_with_batch_strategy::<R, EG>(
         &client,
         TensorHandle::new(lhs.shape, lhs.strides, lhs.handle),
         TensorHandle::new(rhs.shape, rhs.strides, rhs.handle),
         TensorHandle::new(out.shape, out.strides, out.handle),
         disable_cmma,
+        batch_strategy,
     );
 
     assert_result::<EG, EG, R>(
@@ -132,6 +159,361 @@ pub fn test_matmul_launch<EG: Float + CubeElement + Display + CastInto<EG>, R: R
     );
 }
 
+/// Test the correctness of the high-level Matmul on the given device, against a naive CPU
+/// implementation over the given problem, without copying inputs of uncommon layouts
+///
+/// The batch axes of the lhs are stored between its rows and columns, and the rhs, which must
+/// have no batch axes, is expanded over those of the lhs with strides of 0.
+pub fn test_matmul_launch_strided<EG: Float + CubeElement + Display + CastInto<EG>, R: Runtime>(
+    problem: MatmulProblem<EG>,
+    device: &R::Device,
+) {
+    assert!(
+        problem.lhs_layout == MatrixLayout::RowMajor && problem.batches.1.is_empty(),
+        "The lhs must be row major and the rhs must have no batch axes"
+    );
+    let client: ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel> = R::client(device);
+
+    if !(client.properties().feature_enabled(Feature::Subcube)
+        && client
+            .properties()
+            .feature_enabled(Feature::Type(EG::as_elem())))
+    {
+        // Can't execute the test.
+        return;
+    }
+
+    let lhs_data: Vec<EG> = generate_random_data(tensor_size(&problem, Ident::Lhs));
+    let rhs = tensor_raw_parts::<EG, R>(&client, &problem, Ident::Rhs);
+    let out = tensor_raw_parts::<EG, R>(&client, &problem, Ident::Out);
+
+    let num_batches = num_batches(&problem, Ident::Lhs);
+    let (m, k) = (problem.m, problem.k);
+    let mut lhs_stored = lhs_data.clone();
+    for b in 0..num_batches {
+        for i in 0..m {
+            for l in 0..k {
+                lhs_stored[i * num_batches * k + b * k + l] = lhs_data[b * m * k + i * k + l];
+            }
+        }
+    }
+
+    let lhs_shape = shape(&problem, Ident::Lhs);
+    let mut lhs_strides = strides(&problem, Ident::Lhs);
+    let rank = lhs_shape.len();
+    for stride in lhs_strides.iter_mut().take(rank - 2) {
+        *stride /= m;
+    }
+    lhs_strides[rank - 2] = num_batches * k;
+
+    let rhs_shape = [&lhs_shape[..rank - 2], &rhs.shape].concat();
+    let rhs_strides = [&vec![0; rank - 2], &rhs.strides[..]].concat();
+    let lhs = client.create(EG::as_bytes(&lhs_stored));
+    let elem_size = EG::as_elem().size();
+
+    cmma_matmul::launch_ref::<R, EG>(
+        &client,
+        unsafe { TensorHandleRef::from_raw_parts(&lhs, &lhs_strides, &lhs_shape, elem_size) },
+        unsafe {
+            TensorHandleRef::from_raw_parts(&rhs.handle, &rhs_strides, &rhs_shape, elem_size)
+        },
+        unsafe {
+            TensorHandleRef::from_raw_parts(&out.handle, &out.strides, &out.shape, elem_size)
+        },
+        false,
+    );
+
+    assert_result::<EG, EG, R>(
+        &lhs_data,
+        &rhs.original_data.unwrap(),
+        &problem,
+        &client,
+        out.handle,
+        // We cannot assume the inner precision of the matmul, therefore we need a permissive epsilon
+        Some(10e-2),
+    );
+}
+
+/// Test the correctness of the autotuned Matmul on the given device,
+/// against a naive CPU implementation over the given problem
+pub fn test_matmul_autotune<EG: Float + CubeElement + Display + CastInto<EG>, R: Runtime>(
+    problem: MatmulProblem<EG>,
+    device: &R::Device,
+) {
+    let client: ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel> = R::client(device);
+
+    if !client
+        .properties()
+        .feature_enabled(Feature::Type(EG::as_elem()))
+    {
+        // Can't execute the test.
+        return;
+    }
+
+    let lhs = tensor_raw_parts::<EG, R>(&client, &problem, Ident::Lhs);
+    let rhs = tensor_raw_parts::<EG, R>(&client, &problem, Ident::Rhs);
+    let out = tensor_raw_parts::<EG, R>(&client, &problem, Ident::Out);
+    let elem_size = EG::as_elem().size();
+
+    unsafe {
+        matmul::launch_autotune::<R, EG>(
+            &client,
+            TensorHandleRef::from_raw_parts(&lhs.handle, &lhs.strides, &lhs.shape, elem_size),
+            TensorHandleRef::from_raw_parts(&rhs.handle, &rhs.strides, &rhs.shape, elem_size),
+            TensorHandleRef::from_raw_parts(&out.handle, &out.strides, &out.shape, elem_size),
+        );
+    }
+
+    assert_result::<EG, EG, R>(
+        &lhs.original_data.unwrap(),
+        &rhs.original_data.unwrap(),
+        &problem,
+        &client,
+        out.handle,
+        // Strategies of different inner precisions can be selected
+        Some(10e-2),
+    );
+}
+
+/// Test the correctness of the high-level Matmul with a fused epilogue on the given device,
+/// against a naive CPU implementation over the given problem
+///
+/// The output is initialized with random data, and the epilogue uses `alpha = 0.5`,
+/// `beta = -2` and random bias and residual when enabled.
+pub fn test_matmul_launch_with_epilogue<
+    EG: Float + CubeElement + Display + CastInto<EG>,
+    R: Runtime,
+>(
+    problem: MatmulProblem<EG>,
+    epilogue: Epilogue,
+    device: &R::Device,
+) {
+    let client: ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel> = R::client(device);
+
+    if !(client.properties().feature_enabled(Feature::Subcube)
+        && client
+            .properties()
+            .feature_enabled(Feature::Type(EG::as_elem())))
+    {
+        // Can't execute the test.
+        return;
+    }
+
+    let (alpha, beta) = (0.5, -2.0);
+
+    let lhs = tensor_raw_parts::<EG, R>(&client, &problem, Ident::Lhs);
+    let rhs = tensor_raw_parts::<EG, R>(&client, &problem, Ident::Rhs);
+    let out_shape = shape(&problem, Ident::Out);
+    let out_strides = strides(&problem, Ident::Out);
+
+    let num_out = tensor_size(&problem, Ident::Out);
+    let out_data: Vec<EG> = generate_random_data(num_out);
+    let bias_data: Vec<EG> = generate_random_data::<EG>(problem.n)
+        .into_iter()
+        .rev()
+        .collect();
+    let residual_data: Vec<EG> = generate_random_data::<EG>(num_out)
+        .into_iter()
+        .rev()
+        .collect();
+
+    let out = client.create(EG::as_bytes(&out_data));
+    let bias = client.create(EG::as_bytes(&bias_data));
+    let residual = client.create(EG::as_bytes(&residual_data));
+    let bias_shape = [problem.n];
+    let elem_size = EG::as_elem().size();
+
+    let mut matmul_epilogue = MatmulEpilogue::new().activation(epilogue.activation);
+    if epilogue.alpha {
+        matmul_epilogue = matmul_epilogue.alpha(alpha);
+    }
+    if epilogue.beta {
+        matmul_epilogue = matmul_epilogue.beta(beta);
+    }
+    if epilogue.bias {
+        matmul_epilogue = matmul_epilogue
+            .bias(unsafe { TensorHandleRef::from_raw_parts(&bias, &[1], &bias_shape, elem_size) });
+    }
+    if epilogue.residual {
+        matmul_epilogue = matmul_epilogue.residual(unsafe {
+            TensorHandleRef::from_raw_parts(&residual, &out_strides, &out_shape, elem_size)
+        });
+    }
+
+    cmma_matmul::launch_ref_with_epilogue::<R, EG>(
+        &client,
+        unsafe {
+            TensorHandleRef::from_raw_parts(&lhs.handle, &lhs.strides, &lhs.shape, elem_size)
+        },
+        unsafe {
+            TensorHandleRef::from_raw_parts(&rhs.handle, &rhs.strides, &rhs.shape, elem_size)
+        },
+        unsafe { TensorHandleRef::from_raw_parts(&out, &out_strides, &out_shape, elem_size) },
+        false,
+        matmul_epilogue,
+    );
+
+    let product = matmul_cpu_reference::<EG, EG>(
+        &lhs.original_data.unwrap(),
+        &rhs.original_data.unwrap(),
+        &problem,
+    );
+    let expected: Vec<EG> = product
+        .iter()
+        .enumerate()
+        .map(|(i, value)| {
+            let mut value = value.to_f32().unwrap();
+            if epilogue.alpha {
+                value *= alpha;
+            }
+            if epilogue.beta {
+                value += beta * out_data[i].to_f32().unwrap();
+            }
+            if epilogue.bias {
+                value += bias_data[i % problem.n].to_f32().unwrap();
+            }
+            value = match epilogue.activation {
+                Activation::Identity => value,
+                Activation::Relu => value.max(0.0),
+                Activation::Gelu => {
+                    value * 0.5 * (1.0 + erf_approx(value * std::f32::consts::FRAC_1_SQRT_2))
+                }
+                Activation::Silu => value / (1.0 + (-value).exp()),
+            };
+            if epilogue.residual {
+                value += residual_data[i].to_f32().unwrap();
+            }
+            EG::new(value)
+        })
+        .collect();
+
+    // We cannot assume the inner precision of the matmul, therefore we need a permissive epsilon
+    if let Err(e) = assert_equals_approx::<R, EG>(&client, out, &expected, 10e-2) {
+        panic!("{}", e);
+    }
+}
+
+/// Test the correctness of the quantized Matmul on the given device,
+/// against a naive CPU implementation over the given problem
+///
+/// Inputs are small `i8` values, dequantized with a per-tensor scale, or with random
+/// per-channel scales when `per_channel` is set.
+pub fn test_matmul_launch_quantized<EO: Float + CubeElement + Display, R: Runtime>(
+    problem: MatmulProblem<EO>,
+    per_channel: bool,
+    device: &R::Device,
+) {
+    let client: ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel> = R::client(device);
+
+    if !(client.properties().feature_enabled(Feature::Subcube)
+        && client
+            .properties()
+            .feature_enabled(Feature::Type(i8::as_elem()))
+        && client
+            .properties()
+            .feature_enabled(Feature::Type(EO::as_elem())))
+    {
+        // Can't execute the test.
+        return;
+    }
+
+    let alpha = 0.02;
+    let batches = problem.num_batches();
+    let generate = |num_elements: usize, seed: usize| -> Vec<i8> {
+        (0..num_elements)
+            .map(|i| ((i * 7 + seed) % 17) as i8 - 8)
+            .collect()
+    };
+
+    let lhs_data = generate(tensor_size(&problem, Ident::Lhs), 3);
+    let rhs_data = generate(tensor_size(&problem, Ident::Rhs), 11);
+    let lhs_stored = match problem.lhs_layout {
+        MatrixLayout::RowMajor => lhs_data.clone(),
+        MatrixLayout::ColMajor => transpose(
+            &lhs_data,
+            num_batches(&problem, Ident::Lhs),
+            problem.m,
+            problem.k,
+        ),
+    };
+    let rhs_stored = match problem.rhs_layout {
+        MatrixLayout::RowMajor => rhs_data.clone(),
+        MatrixLayout::ColMajor => transpose(
+            &rhs_data,
+            num_batches(&problem, Ident::Rhs),
+            problem.k,
+            problem.n,
+        ),
+    };
+    let scale_data: Vec<EO> = generate_random_data(problem.n);
+
+    let lhs = client.create(i8::as_bytes(&lhs_stored));
+    let rhs = client.create(i8::as_bytes(&rhs_stored));
+    let out = client.empty(tensor_size(&problem, Ident::Out) * EO::as_elem().size());
+    let scale = client.create(EO::as_bytes(&scale_data));
+
+    let (lhs_shape, lhs_strides) = (shape(&problem, Ident::Lhs), strides(&problem, Ident::Lhs));
+    let (rhs_shape, rhs_strides) = (shape(&problem, Ident::Rhs), strides(&problem, Ident::Rhs));
+    let (out_shape, out_strides) = (shape(&problem, Ident::Out), strides(&problem, Ident::Out));
+    let scale_shape = [problem.n];
+    let elem_size = EO::as_elem().size();
+
+    let mut epilogue = MatmulEpilogue::new();
+    if per_channel {
+        epilogue = epilogue.scale(unsafe {
+            TensorHandleRef::from_raw_parts(&scale, &[1], &scale_shape, elem_size)
+        });
+    } else {
+        epilogue = epilogue.alpha(alpha);
+    }
+
+    cmma_matmul::launch_ref_quantized::<R, EO>(
+        &client,
+        unsafe { TensorHandleRef::from_raw_parts(&lhs, &lhs_strides, &lhs_shape, 1) },
+        unsafe { TensorHandleRef::from_raw_parts(&rhs, &rhs_strides, &rhs_shape, 1) },
+        unsafe { TensorHandleRef::from_raw_parts(&out, &out_strides, &out_shape, elem_size) },
+        false,
+        epilogue,
+    )
+    .unwrap();
+
+    let (m, n, k) = (problem.m, problem.n, problem.k);
+    let mut expected = Vec::with_capacity(batches * m * n);
+    for b in 0..batches {
+        let (lhs_batch, rhs_batch) = broadcast_batches(&problem, b);
+        for i in 0..m {
+            for j in 0..n {
+                let acc: i32 = (0..k)
+                    .map(|l| {
+                        lhs_data[lhs_batch * m * k + i * k + l] as i32
+                            * rhs_data[rhs_batch * k * n + l * n + j] as i32
+                    })
+                    .sum();
+                let scale = match per_channel {
+                    true => scale_data[j].to_f32().unwrap(),
+                    false => alpha,
+                };
+                expected.push(EO::new(acc as f32 * scale));
+            }
+        }
+    }
+
+    // The accumulation is exact, only the dequantization rounds
+    if let Err(e) = assert_equals_approx::<R, EO>(&client, out, &expected, 10e-3) {
+        panic!("{}", e);
+    }
+}
+
+/// Abramowitz and Stegun approximation of the error function, with an error below 1.5e-7
+fn erf_approx(x: f32) -> f32 {
+    let t = 1.0 / (1.0 + 0.3275911 * x.abs());
+    let polynomial =
+        t * (0.2548296 + t * (-0.28449672 + t * (1.4214138 + t * (-1.4531521 + t * 1.0614054))));
+    let erf = 1.0 - polynomial * (-x * x).exp();
+
+    erf.copysign(x)
+}
+
 fn tensor_raw_parts<EG: Float + CubeElement, R: Runtime>(
     client: &ComputeClient<R::Server, R::Channel>,
     problem: &MatmulProblem<EG>,
@@ -142,9 +524,12 @@ fn tensor_raw_parts<EG: Float + CubeElement, R: Runtime>(
             let original_data: Vec<EG> = generate_random_data(tensor_size(problem, Ident::Lhs));
             let data = match problem.lhs_layout {
                 MatrixLayout::RowMajor => original_data.clone(),
-                MatrixLayout::ColMajor => {
-                    transpose::<EG>(&original_data, problem.num_batches(), problem.m, problem.k)
-                }
+                MatrixLayout::ColMajor => transpose::<EG>(
+                    &original_data,
+                    num_batches(problem, Ident::Lhs),
+                    problem.m,
+                    problem.k,
+                ),
             };
 
             TensorRawParts {
@@ -158,9 +543,12 @@ fn tensor_raw_parts<EG: Float + CubeElement, R: Runtime>(
             let original_data: Vec<EG> = generate_random_data(tensor_size(problem, Ident::Rhs));
             let data = match problem.rhs_layout {
                 MatrixLayout::RowMajor => original_data.clone(),
-                MatrixLayout::ColMajor => {
-                    transpose::<EG>(&original_data, problem.num_batches(), problem.k, problem.n)
-                }
+                MatrixLayout::ColMajor => transpose::<EG>(
+                    &original_data,
+                    num_batches(problem, Ident::Rhs),
+                    problem.k,
+                    problem.n,
+                ),
             };
 
             TensorRawParts {
@@ -235,21 +623,33 @@ fn assert_result<
     }
 }
 
+/// Returns the batch shape of the identified tensor, inferred by the problem definition
+fn batches<EG: Numeric>(problem: &MatmulProblem<EG>, ident: Ident) -> Vec<usize> {
+    match ident {
+        Ident::Lhs => problem.batches.0.clone(),
+        Ident::Rhs => problem.batches.1.clone(),
+        Ident::Out => problem.out_batches(),
+    }
+}
+
+/// Returns the number of batches of the identified tensor, inferred by the problem definition
+fn num_batches<EG: Numeric>(problem: &MatmulProblem<EG>, ident: Ident) -> usize {
+    batches(problem, ident).iter().product()
+}
+
 /// Returns the total number of elements for the identified tensor, inferred by the problem definition
 fn tensor_size<EG: Numeric>(problem: &MatmulProblem<EG>, ident: Ident) -> usize {
     match ident {
-        Ident::Lhs => problem.num_batches() * problem.m * problem.k,
-        Ident::Rhs => problem.num_batches() * problem.k * problem.n,
-        Ident::Out => problem.num_batches() * problem.m * problem.n,
+        Ident::Lhs => num_batches(problem, ident) * problem.m * problem.k,
+        Ident::Rhs => num_batches(problem, ident) * problem.k * problem.n,
+        Ident::Out => num_batches(problem, ident) * problem.m * problem.n,
     }
 }
 
 /// Returns the shape of the identified tensor, inferred by the problem definition
 fn shape<EG: Numeric>(problem: &MatmulProblem<EG>, ident: Ident) -> Vec<usize> {
-    problem
-        .batches
-        .iter()
-        .cloned()
+    batches(problem, ident)
+        .into_iter()
         .chain(match ident {
             Ident::Lhs => vec![problem.m, problem.k],
             Ident::Rhs => vec![problem.k, problem.n],
@@ -260,7 +660,8 @@ fn shape<EG: Numeric>(problem: &MatmulProblem<EG>, ident: Ident) -> Vec<usize> {
 
 /// Returns the stride of the identified tensor, inferred by the problem definition
 pub(crate) fn strides<EG: Numeric>(problem: &MatmulProblem<EG>, ident: Ident) -> Vec<usize> {
-    let mut strides = Vec::with_capacity(problem.batches.len() + 2);
+    let batches = batches(problem, ident);
+    let mut strides = Vec::with_capacity(batches.len() + 2);
 
     let (last_batch, x, y) = match ident {
         Ident::Lhs => match problem.lhs_layout {
@@ -277,11 +678,13 @@ pub(crate) fn strides<EG: Numeric>(problem: &MatmulProblem<EG>, ident: Ident) ->
     strides.push(y);
     strides.push(x);
 
-    if !problem.batches.is_empty() {
-        strides.push(last_batch);
+    if !batches.is_empty() {
+        let mut stride = last_batch;
+        strides.push(stride);
 
-        for b in problem.batches.iter().rev().take(problem.batches.len() - 1) {
-            strides.push(last_batch * b)
+        for b in batches.iter().rev().take(batches.len() - 1) {
+            stride *= b;
+            strides.push(stride)
         }
     }
 
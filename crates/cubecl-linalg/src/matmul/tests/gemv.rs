@@ -0,0 +1,194 @@
+use std::fmt::Display;
+
+use cubecl_core::{prelude::*, CubeElement};
+
+use crate::{
+    matmul::{self, kernels::gemv},
+    tensor::TensorHandle,
+};
+
+use super::test_utils::{assert_equals_approx, generate_random_data};
+
+/// A tensor of the given shape whose data is laid out with the given strides.
+struct StridedTensor {
+    shape: Vec<usize>,
+    strides: Vec<usize>,
+    data: Vec<f32>,
+}
+
+impl StridedTensor {
+    /// A random tensor, with the two last axes swapped in memory when `transposed`.
+    fn random(shape: Vec<usize>, transposed: bool) -> Self {
+        let rank = shape.len();
+        let mut order: Vec<usize> = (0..rank).collect();
+        if transposed {
+            order.swap(rank - 2, rank - 1);
+        }
+
+        let mut strides = vec![0; rank];
+        let mut stride = 1;
+        for axis in order.into_iter().rev() {
+            strides[axis] = stride;
+            stride *= shape[axis];
+        }
+
+        let data = generate_random_data::<f32>(shape.iter().product());
+        Self {
+            shape,
+            strides,
+            data,
+        }
+    }
+
+    fn get(&self, index: &[usize]) -> f32 {
+        let offset: usize = index
+            .iter()
+            .zip(&self.shape)
+            .zip(&self.strides)
+            .map(|((index, shape), stride)| (index % shape) * stride)
+            .sum();
+        self.data[offset]
+    }
+
+    fn handle<R: Runtime, F: Float + CubeElement>(
+        &self,
+        client: &ComputeClient<R::Server, R::Channel>,
+    ) -> TensorHandle<R, F> {
+        let data: Vec<F> = self.data.iter().map(|value| F::new(*value)).collect();
+        TensorHandle::new(
+            self.shape.clone(),
+            self.strides.clone(),
+            client.create(F::as_bytes(&data)),
+        )
+    }
+}
+
+/// The contiguous product of the tensors, whose batch axes of size 1 are broadcast.
+fn matmul_reference<F: Float + CubeElement>(
+    lhs: &StridedTensor,
+    rhs: &StridedTensor,
+    out_shape: &[usize],
+) -> Vec<F> {
+    let rank = out_shape.len();
+    let (m, n, k) = (
+        out_shape[rank - 2],
+        out_shape[rank - 1],
+        lhs.shape[rank - 1],
+    );
+    let num_batches: usize = out_shape[..rank - 2].iter().product();
+    let mut out = Vec::with_capacity(num_batches * m * n);
+
+    for batch in 0..num_batches {
+        let mut index = vec![0; rank];
+        let mut remainder = batch;
+        for axis in (0..rank - 2).rev() {
+            index[axis] = remainder % out_shape[axis];
+            remainder /= out_shape[axis];
+        }
+
+        for i in 0..m {
+            for j in 0..n {
+                let mut sum = 0.0;
+                for k_ in 0..k {
+                    index[rank - 2] = i;
+                    index[rank - 1] = k_;
+                    let lhs_value = lhs.get(&index);
+                    index[rank - 2] = k_;
+                    index[rank - 1] = j;
+                    sum += lhs_value * rhs.get(&index);
+                }
+                out.push(F::new(sum));
+            }
+        }
+    }
+
+    out
+}
+
+fn test_gemv<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+    lhs: StridedTensor,
+    rhs: StridedTensor,
+    out_shape: Vec<usize>,
+) {
+    let client = R::client(device);
+    let expected = matmul_reference::<F>(&lhs, &rhs, &out_shape);
+
+    let out = gemv::launch::<R, F>(
+        &client,
+        lhs.handle(&client),
+        rhs.handle(&client),
+        TensorHandle::empty(&client, out_shape),
+    );
+
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.01) {
+        panic!("{}", e);
+    }
+}
+
+pub fn test_gemv_matrix_vector<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    test_gemv::<R, F>(
+        device,
+        StridedTensor::random(vec![2, 64, 256], false),
+        StridedTensor::random(vec![2, 256, 1], false),
+        vec![2, 64, 1],
+    );
+}
+
+pub fn test_gemv_vector_matrix<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    test_gemv::<R, F>(
+        device,
+        StridedTensor::random(vec![3, 1, 96], false),
+        StridedTensor::random(vec![3, 96, 64], false),
+        vec![3, 1, 64],
+    );
+}
+
+pub fn test_gemv_transposed_matrix_vector<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    test_gemv::<R, F>(
+        device,
+        StridedTensor::random(vec![2, 48, 40], true),
+        StridedTensor::random(vec![2, 40, 1], false),
+        vec![2, 48, 1],
+    );
+}
+
+pub fn test_gemv_transposed_vector_matrix<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    test_gemv::<R, F>(
+        device,
+        StridedTensor::random(vec![2, 1, 130], false),
+        StridedTensor::random(vec![2, 130, 24], true),
+        vec![2, 1, 24],
+    );
+}
+
+pub fn test_gemv_broadcast_batches<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    test_gemv::<R, F>(
+        device,
+        StridedTensor::random(vec![2, 1, 1, 32], false),
+        StridedTensor::random(vec![1, 3, 32, 16], false),
+        vec![2, 3, 1, 16],
+    );
+}
+
+pub fn test_gemv_launch_ref<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let client = R::client(device);
+    let lhs = StridedTensor::random(vec![1, 1, 64], false);
+    let rhs = StridedTensor::random(vec![1, 64, 32], false);
+    let expected = matmul_reference::<F>(&lhs, &rhs, &[1, 1, 32]);
+
+    let lhs = lhs.handle::<R, F>(&client);
+    let rhs = rhs.handle::<R, F>(&client);
+    let out = TensorHandle::<R, F>::empty(&client, vec![1, 1, 32]);
+    matmul::launch_ref::<R, F>(&client, lhs.as_ref(), rhs.as_ref(), out.as_ref());
+
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.01) {
+        panic!("{}", e);
+    }
+}
@@ -2,6 +2,7 @@
 
 pub mod cmma_matmul;
 pub mod cmma_old;
+pub mod gemv;
 mod test_macros;
-mod test_utils;
+pub(crate) mod test_utils;
 pub mod tiling2d;
@@ -79,8 +79,8 @@ macro_rules! testgen_matmul_internal {
 
                     type TileMatmul = $tile_matmul_type<ES, EA>;
                     type StageMatmul = stage::row_accumulate::Matmul<ES, EG, EA, TileMatmul, StageSize>;
-                    type GlobalMatmul = global::homogeneous::Matmul<EG, ES, StageMatmul>;
-                    type BatchMatmul = batch::one_to_one::Matmul<EG, ES, GlobalMatmul>;
+                    type GlobalMatmul = global::homogeneous::Matmul<EG, EG, ES, StageMatmul>;
+                    type BatchMatmul = batch::one_to_one::Matmul<EG, EG, ES, GlobalMatmul>;
 
                     let config = make_cmma_config::<
                         EG,
@@ -100,7 +100,7 @@ macro_rules! testgen_matmul_internal {
                     m: 300,
                     n: 300,
                     k: 300,
-                    batches: vec![3, 4],
+                    batches: (vec![3, 4], vec![3, 4]),
                     lhs_layout: MatrixLayout::ColMajor,
                     rhs_layout: MatrixLayout::ColMajor,
                     lhs_line_size: 4,
@@ -125,7 +125,7 @@ macro_rules! testgen_matmul_internal {
                     m: 108,
                     n: 108,
                     k: 243,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::ColMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -150,7 +150,7 @@ macro_rules! testgen_matmul_internal {
                     m: 256,
                     n: 256,
                     k: 256,
-                    batches: vec![3, 4],
+                    batches: (vec![3, 4], vec![3, 4]),
                     lhs_layout: MatrixLayout::ColMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 2,
@@ -175,7 +175,7 @@ macro_rules! testgen_matmul_internal {
                     m: 256,
                     n: 256,
                     k: 256,
-                    batches: vec![3],
+                    batches: (vec![3], vec![3]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::ColMajor,
                     lhs_line_size: 4,
@@ -200,7 +200,7 @@ macro_rules! testgen_matmul_internal {
                     m: 16,
                     n: 16,
                     k: 16,
-                    batches: vec![3],
+                    batches: (vec![3], vec![3]),
                     lhs_layout: MatrixLayout::ColMajor,
                     rhs_layout: MatrixLayout::ColMajor,
                     lhs_line_size: 4,
@@ -225,7 +225,7 @@ macro_rules! testgen_matmul_internal {
                     m: 16,
                     n: 16,
                     k: 16,
-                    batches: vec![3],
+                    batches: (vec![3], vec![3]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -250,7 +250,7 @@ macro_rules! testgen_matmul_internal {
                     m: 256,
                     n: 256,
                     k: 256,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::ColMajor,
                     lhs_line_size: 4,
@@ -278,7 +278,7 @@ macro_rules! testgen_matmul_internal {
                     m: 32,
                     n: 32,
                     k: 32,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::ColMajor,
                     rhs_layout: MatrixLayout::ColMajor,
                     lhs_line_size: 4,
@@ -306,7 +306,7 @@ macro_rules! testgen_matmul_internal {
                     m: 32,
                     n: 32,
                     k: 32,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -331,7 +331,7 @@ macro_rules! testgen_matmul_internal {
                     m: 16,
                     n: 14,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::ColMajor,
                     lhs_line_size: 4,
@@ -356,7 +356,7 @@ macro_rules! testgen_matmul_internal {
                     m: 16,
                     n: 12,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -381,7 +381,7 @@ macro_rules! testgen_matmul_internal {
                     m: 16,
                     n: 16,
                     k: 12,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -406,7 +406,7 @@ macro_rules! testgen_matmul_internal {
                     m: 60,
                     n: 60,
                     k: 120,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -431,7 +431,7 @@ macro_rules! testgen_matmul_internal {
                     m: 16,
                     n: 16,
                     k: 36,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -457,7 +457,7 @@ macro_rules! testgen_matmul_internal {
                     m: 12,
                     n: 12,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -482,7 +482,7 @@ macro_rules! testgen_matmul_internal {
                     m: 16,
                     n: 16,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 1,
@@ -508,7 +508,7 @@ macro_rules! testgen_matmul_internal {
                     m: 16,
                     n: 16,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -534,7 +534,7 @@ macro_rules! testgen_matmul_internal {
                     m: 16,
                     n: 16,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::ColMajor,
                     lhs_line_size: 4,
@@ -560,7 +560,7 @@ macro_rules! testgen_matmul_internal {
                     m: 16,
                     n: 16,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -589,7 +589,7 @@ macro_rules! testgen_matmul_internal {
                     m: 16,
                     n: 16,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -618,7 +618,7 @@ macro_rules! testgen_matmul_internal {
                     m: 16,
                     n: 16,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::ColMajor,
                     lhs_line_size: 4,
@@ -647,7 +647,7 @@ macro_rules! testgen_matmul_internal {
                     m: 32,
                     n: 8,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -675,7 +675,7 @@ macro_rules! testgen_matmul_internal {
                     m: 32,
                     n: 8,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::ColMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -703,7 +703,7 @@ macro_rules! testgen_matmul_internal {
                     m: 32,
                     n: 8,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -731,7 +731,7 @@ macro_rules! testgen_matmul_internal {
                     m: 32,
                     n: 8,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::ColMajor,
                     lhs_line_size: 4,
@@ -760,7 +760,7 @@ macro_rules! testgen_matmul_internal {
                     m: 8,
                     n: 32,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -788,7 +788,7 @@ macro_rules! testgen_matmul_internal {
                     m: 8,
                     n: 32,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::ColMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -816,7 +816,7 @@ macro_rules! testgen_matmul_internal {
                     m: 8,
                     n: 32,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -844,7 +844,7 @@ macro_rules! testgen_matmul_internal {
                     m: 8,
                     n: 32,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::ColMajor,
                     lhs_line_size: 4,
@@ -872,7 +872,7 @@ macro_rules! testgen_matmul_internal {
                     m: 256,
                     n: 256,
                     k: 256,
-                    batches: vec![3, 4],
+                    batches: (vec![3, 4], vec![3, 4]),
                     lhs_layout: MatrixLayout::ColMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 2,
@@ -900,7 +900,7 @@ macro_rules! testgen_matmul_internal {
                     m: 16,
                     n: 16,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::ColMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -926,7 +926,7 @@ macro_rules! testgen_matmul_internal {
                     m: 16,
                     n: 16,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::ColMajor,
                     rhs_layout: MatrixLayout::ColMajor,
                     lhs_line_size: 4,
@@ -952,7 +952,7 @@ macro_rules! testgen_matmul_internal {
                     m: 32,
                     n: 8,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -979,7 +979,7 @@ macro_rules! testgen_matmul_internal {
                     m: 32,
                     n: 8,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::ColMajor,
                     lhs_line_size: 1,
@@ -1006,7 +1006,7 @@ macro_rules! testgen_matmul_internal {
                     m: 32,
                     n: 8,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::ColMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -1033,7 +1033,7 @@ macro_rules! testgen_matmul_internal {
                     m: 32,
                     n: 8,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::ColMajor,
                     rhs_layout: MatrixLayout::ColMajor,
                     lhs_line_size: 4,
@@ -1059,7 +1059,7 @@ macro_rules! testgen_matmul_internal {
                     m: 8,
                     n: 32,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -1085,7 +1085,7 @@ macro_rules! testgen_matmul_internal {
                     m: 8,
                     n: 32,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::ColMajor,
                     lhs_line_size: 4,
@@ -1112,7 +1112,7 @@ macro_rules! testgen_matmul_internal {
                     m: 8,
                     n: 32,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::ColMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -1139,7 +1139,7 @@ macro_rules! testgen_matmul_internal {
                     m: 8,
                     n: 32,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::ColMajor,
                     rhs_layout: MatrixLayout::ColMajor,
                     lhs_line_size: 4,
@@ -1165,7 +1165,7 @@ macro_rules! testgen_matmul_internal {
                     m: 16,
                     n: 16,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 2,
@@ -1191,7 +1191,7 @@ macro_rules! testgen_matmul_internal {
                     m: 16,
                     n: 16,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -1217,7 +1217,7 @@ macro_rules! testgen_matmul_internal {
                     m: 32,
                     n: 32,
                     k: 32,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -1246,7 +1246,7 @@ macro_rules! testgen_matmul_internal {
                     m: 16,
                     n: 16,
                     k: 32,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -1272,7 +1272,7 @@ macro_rules! testgen_matmul_internal {
                     m: 16,
                     n: 16,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::ColMajor,
                     rhs_layout: MatrixLayout::ColMajor,
                     lhs_line_size: 4,
@@ -1298,7 +1298,7 @@ macro_rules! testgen_matmul_internal {
                     m: 16,
                     n: 16,
                     k: 128,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -1324,7 +1324,7 @@ macro_rules! testgen_matmul_internal {
                     m: 32,
                     n: 16,
                     k: 128,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -1350,7 +1350,7 @@ macro_rules! testgen_matmul_internal {
                     m: 32,
                     n: 32,
                     k: 224,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -1376,7 +1376,7 @@ macro_rules! testgen_matmul_internal {
                     m: 16,
                     n: 32,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -1402,7 +1402,7 @@ macro_rules! testgen_matmul_internal {
                     m: 32,
                     n: 32,
                     k: 32,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -1428,7 +1428,7 @@ macro_rules! testgen_matmul_internal {
                     m: 32,
                     n: 32,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -1454,7 +1454,7 @@ macro_rules! testgen_matmul_internal {
                     m: 16,
                     n: 32,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -1479,7 +1479,7 @@ macro_rules! testgen_matmul_internal {
                     m: 16,
                     n: 16,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -1504,7 +1504,7 @@ macro_rules! testgen_matmul_internal {
                     m: 32,
                     n: 32,
                     k: 32,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::ColMajor,
                     lhs_line_size: 4,
@@ -1529,7 +1529,7 @@ macro_rules! testgen_matmul_internal {
                     m: 32,
                     n: 32,
                     k: 32,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::ColMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -1554,7 +1554,7 @@ macro_rules! testgen_matmul_internal {
                     m: 32,
                     n: 32,
                     k: 32,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::ColMajor,
                     rhs_layout: MatrixLayout::ColMajor,
                     lhs_line_size: 4,
@@ -1579,7 +1579,7 @@ macro_rules! testgen_matmul_internal {
                     m: 32,
                     n: 16,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -1604,7 +1604,7 @@ macro_rules! testgen_matmul_internal {
                     m: 32,
                     n: 8,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::ColMajor,
                     rhs_layout: MatrixLayout::ColMajor,
                     lhs_line_size: 1,
@@ -1629,7 +1629,7 @@ macro_rules! testgen_matmul_internal {
                     m: 128,
                     n: 16,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 1,
@@ -1654,7 +1654,7 @@ macro_rules! testgen_matmul_internal {
                     m: 64,
                     n: 64,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -1679,7 +1679,7 @@ macro_rules! testgen_matmul_internal {
                     m: 64,
                     n: 64,
                     k: 32,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -1704,7 +1704,7 @@ macro_rules! testgen_matmul_internal {
                     m: 32,
                     n: 32,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -1729,7 +1729,7 @@ macro_rules! testgen_matmul_internal {
                     m: 32,
                     n: 32,
                     k: 32,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -1754,7 +1754,7 @@ macro_rules! testgen_matmul_internal {
                     m: 32,
                     n: 8,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -1779,7 +1779,7 @@ macro_rules! testgen_matmul_internal {
                     m: 8,
                     n: 32,
                     k: 16,
-                    batches: vec![],
+                    batches: (vec![], vec![]),
                     lhs_layout: MatrixLayout::RowMajor,
                     rhs_layout: MatrixLayout::RowMajor,
                     lhs_line_size: 4,
@@ -2,7 +2,13 @@
 #[macro_export]
 macro_rules! testgen_matmul_launch {
     ($eg:ty) => {
-        use cubecl_linalg::matmul::tests::cmma_matmul::matmul_test_launcher::test_matmul_launch;
+        use cubecl_linalg::matmul::components::global::{Activation, Epilogue};
+        use cubecl_linalg::matmul::kernels::cmma_matmul::BatchStrategy;
+        use cubecl_linalg::matmul::tests::cmma_matmul::matmul_test_launcher::{
+            test_matmul_autotune, test_matmul_launch, test_matmul_launch_quantized,
+            test_matmul_launch_strided, test_matmul_launch_with_batch_strategy,
+            test_matmul_launch_with_epilogue,
+        };
         use cubecl_linalg::tensor::TensorHandle;
 
         #[test]
@@ -12,7 +18,7 @@ macro_rules! testgen_matmul_launch {
                 m: 300,
                 n: 200,
                 k: 250,
-                batches: vec![3, 4],
+                batches: (vec![3, 4], vec![3, 4]),
                 lhs_layout: MatrixLayout::ColMajor,
                 rhs_layout: MatrixLayout::RowMajor,
                 lhs_line_size: 4,
@@ -23,5 +29,359 @@ macro_rules! testgen_matmul_launch {
 
             test_matmul_launch::<EG, TestRuntime>(problem, false, &Default::default());
         }
+
+        #[test]
+        pub fn test_launch_matmul_plane_mma_b2_g100x60x70_row_col() {
+            type EG = $eg;
+            let problem = MatmulProblem::<EG> {
+                m: 100,
+                n: 60,
+                k: 70,
+                batches: (vec![2], vec![2]),
+                lhs_layout: MatrixLayout::RowMajor,
+                rhs_layout: MatrixLayout::ColMajor,
+                lhs_line_size: 2,
+                rhs_line_size: 2,
+                out_line_size: 4,
+                _element: PhantomData,
+            };
+
+            test_matmul_launch::<EG, TestRuntime>(problem, true, &Default::default());
+        }
+
+        #[test]
+        pub fn test_launch_matmul_quantized_per_tensor_b2_g60x40x64() {
+            type EG = $eg;
+            let problem = MatmulProblem::<EG> {
+                m: 60,
+                n: 40,
+                k: 64,
+                batches: (vec![2], vec![2]),
+                lhs_layout: MatrixLayout::RowMajor,
+                rhs_layout: MatrixLayout::RowMajor,
+                lhs_line_size: 4,
+                rhs_line_size: 4,
+                out_line_size: 4,
+                _element: PhantomData,
+            };
+
+            test_matmul_launch_quantized::<EG, TestRuntime>(problem, false, &Default::default());
+        }
+
+        #[test]
+        pub fn test_launch_matmul_quantized_per_channel_g33x64x100_col_row() {
+            type EG = $eg;
+            let problem = MatmulProblem::<EG> {
+                m: 33,
+                n: 64,
+                k: 100,
+                batches: (vec![], vec![]),
+                lhs_layout: MatrixLayout::ColMajor,
+                rhs_layout: MatrixLayout::RowMajor,
+                lhs_line_size: 1,
+                rhs_line_size: 4,
+                out_line_size: 4,
+                _element: PhantomData,
+            };
+
+            test_matmul_launch_quantized::<EG, TestRuntime>(problem, true, &Default::default());
+        }
+
+        #[test]
+        pub fn test_launch_matmul_broadcast_b2x1_b3_g60x40x50_col_row() {
+            type EG = $eg;
+            let problem = MatmulProblem::<EG> {
+                m: 60,
+                n: 40,
+                k: 50,
+                batches: (vec![2, 1], vec![3]),
+                lhs_layout: MatrixLayout::ColMajor,
+                rhs_layout: MatrixLayout::RowMajor,
+                lhs_line_size: 4,
+                rhs_line_size: 4,
+                out_line_size: 4,
+                _element: PhantomData,
+            };
+
+            test_matmul_launch::<EG, TestRuntime>(problem, false, &Default::default());
+        }
+
+        #[test]
+        pub fn test_launch_matmul_highly_permuted_b2x3_g40x32x48() {
+            type EG = $eg;
+            let problem = MatmulProblem::<EG> {
+                m: 40,
+                n: 32,
+                k: 48,
+                batches: (vec![2, 3], vec![]),
+                lhs_layout: MatrixLayout::RowMajor,
+                rhs_layout: MatrixLayout::RowMajor,
+                lhs_line_size: 4,
+                rhs_line_size: 4,
+                out_line_size: 4,
+                _element: PhantomData,
+            };
+
+            test_matmul_launch_strided::<EG, TestRuntime>(problem, &Default::default());
+        }
+
+        #[test]
+        pub fn test_launch_matmul_split_k_g16x32x1000() {
+            type EG = $eg;
+            let problem = MatmulProblem::<EG> {
+                m: 16,
+                n: 32,
+                k: 1000,
+                batches: (vec![], vec![]),
+                lhs_layout: MatrixLayout::RowMajor,
+                rhs_layout: MatrixLayout::RowMajor,
+                lhs_line_size: 4,
+                rhs_line_size: 4,
+                out_line_size: 4,
+                _element: PhantomData,
+            };
+
+            test_matmul_launch_with_batch_strategy::<EG, TestRuntime>(
+                problem,
+                false,
+                BatchStrategy::SplitK { num_splits: 6 },
+                &Default::default(),
+            );
+        }
+
+        #[test]
+        pub fn test_launch_matmul_split_k_b2_g40x20x300_col_row() {
+            type EG = $eg;
+            let problem = MatmulProblem::<EG> {
+                m: 40,
+                n: 20,
+                k: 300,
+                batches: (vec![2], vec![2]),
+                lhs_layout: MatrixLayout::ColMajor,
+                rhs_layout: MatrixLayout::RowMajor,
+                lhs_line_size: 4,
+                rhs_line_size: 4,
+                out_line_size: 4,
+                _element: PhantomData,
+            };
+
+            test_matmul_launch_with_batch_strategy::<EG, TestRuntime>(
+                problem,
+                false,
+                BatchStrategy::SplitK { num_splits: 3 },
+                &Default::default(),
+            );
+        }
+
+        #[test]
+        pub fn test_launch_matmul_split_k_broadcast_b1x2_b3x1_g20x24x400() {
+            type EG = $eg;
+            let problem = MatmulProblem::<EG> {
+                m: 20,
+                n: 24,
+                k: 400,
+                batches: (vec![1, 2], vec![3, 1]),
+                lhs_layout: MatrixLayout::RowMajor,
+                rhs_layout: MatrixLayout::ColMajor,
+                lhs_line_size: 4,
+                rhs_line_size: 4,
+                out_line_size: 4,
+                _element: PhantomData,
+            };
+
+            test_matmul_launch_with_batch_strategy::<EG, TestRuntime>(
+                problem,
+                false,
+                BatchStrategy::SplitK { num_splits: 4 },
+                &Default::default(),
+            );
+        }
+
+        #[test]
+        pub fn test_launch_matmul_stream_k_b2x3_g100x60x300() {
+            type EG = $eg;
+            let problem = MatmulProblem::<EG> {
+                m: 100,
+                n: 60,
+                k: 300,
+                batches: (vec![2, 3], vec![2, 3]),
+                lhs_layout: MatrixLayout::RowMajor,
+                rhs_layout: MatrixLayout::ColMajor,
+                lhs_line_size: 4,
+                rhs_line_size: 4,
+                out_line_size: 4,
+                _element: PhantomData,
+            };
+
+            test_matmul_launch_with_batch_strategy::<EG, TestRuntime>(
+                problem,
+                false,
+                BatchStrategy::StreamK { num_cubes: 7 },
+                &Default::default(),
+            );
+        }
+
+        #[test]
+        pub fn test_launch_matmul_stream_k_more_cubes_than_iterations() {
+            type EG = $eg;
+            let problem = MatmulProblem::<EG> {
+                m: 16,
+                n: 16,
+                k: 40,
+                batches: (vec![], vec![]),
+                lhs_layout: MatrixLayout::RowMajor,
+                rhs_layout: MatrixLayout::RowMajor,
+                lhs_line_size: 4,
+                rhs_line_size: 4,
+                out_line_size: 4,
+                _element: PhantomData,
+            };
+
+            test_matmul_launch_with_batch_strategy::<EG, TestRuntime>(
+                problem,
+                false,
+                BatchStrategy::StreamK { num_cubes: 64 },
+                &Default::default(),
+            );
+        }
+
+        #[test]
+        pub fn test_launch_matmul_epilogue_alpha_beta_b2_g60x40x50() {
+            type EG = $eg;
+            let problem = MatmulProblem::<EG> {
+                m: 60,
+                n: 40,
+                k: 50,
+                batches: (vec![2], vec![2]),
+                lhs_layout: MatrixLayout::RowMajor,
+                rhs_layout: MatrixLayout::RowMajor,
+                lhs_line_size: 4,
+                rhs_line_size: 4,
+                out_line_size: 4,
+                _element: PhantomData,
+            };
+
+            test_matmul_launch_with_epilogue::<EG, TestRuntime>(
+                problem,
+                Epilogue::default().with_alpha().with_beta(),
+                &Default::default(),
+            );
+        }
+
+        #[test]
+        pub fn test_launch_matmul_epilogue_bias_gelu_g33x64x70_col_row() {
+            type EG = $eg;
+            let problem = MatmulProblem::<EG> {
+                m: 33,
+                n: 64,
+                k: 70,
+                batches: (vec![], vec![]),
+                lhs_layout: MatrixLayout::ColMajor,
+                rhs_layout: MatrixLayout::RowMajor,
+                lhs_line_size: 4,
+                rhs_line_size: 4,
+                out_line_size: 4,
+                _element: PhantomData,
+            };
+
+            test_matmul_launch_with_epilogue::<EG, TestRuntime>(
+                problem,
+                Epilogue::default()
+                    .with_bias()
+                    .with_activation(Activation::Gelu),
+                &Default::default(),
+            );
+        }
+
+        #[test]
+        pub fn test_launch_matmul_epilogue_relu_residual_b3_g64x32x48() {
+            type EG = $eg;
+            let problem = MatmulProblem::<EG> {
+                m: 64,
+                n: 32,
+                k: 48,
+                batches: (vec![3], vec![3]),
+                lhs_layout: MatrixLayout::RowMajor,
+                rhs_layout: MatrixLayout::RowMajor,
+                lhs_line_size: 4,
+                rhs_line_size: 4,
+                out_line_size: 4,
+                _element: PhantomData,
+            };
+
+            test_matmul_launch_with_epilogue::<EG, TestRuntime>(
+                problem,
+                Epilogue::default()
+                    .with_activation(Activation::Relu)
+                    .with_residual(),
+                &Default::default(),
+            );
+        }
+
+        #[test]
+        pub fn test_launch_matmul_epilogue_full_chain_silu_g40x20x30() {
+            type EG = $eg;
+            let problem = MatmulProblem::<EG> {
+                m: 40,
+                n: 20,
+                k: 30,
+                batches: (vec![], vec![]),
+                lhs_layout: MatrixLayout::RowMajor,
+                rhs_layout: MatrixLayout::RowMajor,
+                lhs_line_size: 4,
+                rhs_line_size: 4,
+                out_line_size: 4,
+                _element: PhantomData,
+            };
+
+            test_matmul_launch_with_epilogue::<EG, TestRuntime>(
+                problem,
+                Epilogue::default()
+                    .with_alpha()
+                    .with_beta()
+                    .with_bias()
+                    .with_activation(Activation::Silu)
+                    .with_residual(),
+                &Default::default(),
+            );
+        }
+
+        #[test]
+        pub fn test_matmul_autotune_g64x64x64() {
+            type EG = $eg;
+            let problem = MatmulProblem::<EG> {
+                m: 64,
+                n: 64,
+                k: 64,
+                batches: (vec![], vec![]),
+                lhs_layout: MatrixLayout::RowMajor,
+                rhs_layout: MatrixLayout::RowMajor,
+                lhs_line_size: 4,
+                rhs_line_size: 4,
+                out_line_size: 4,
+                _element: PhantomData,
+            };
+
+            test_matmul_autotune::<EG, TestRuntime>(problem, &Default::default());
+        }
+
+        #[test]
+        pub fn test_matmul_autotune_b2_g100x60x40_col_row() {
+            type EG = $eg;
+            let problem = MatmulProblem::<EG> {
+                m: 100,
+                n: 60,
+                k: 40,
+                batches: (vec![2], vec![2]),
+                lhs_layout: MatrixLayout::ColMajor,
+                rhs_layout: MatrixLayout::RowMajor,
+                lhs_line_size: 4,
+                rhs_line_size: 4,
+                out_line_size: 4,
+                _element: PhantomData,
+            };
+
+            test_matmul_autotune::<EG, TestRuntime>(problem, &Default::default());
+        }
     };
 }
@@ -0,0 +1,82 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_gemv {
+    () => {
+        mod gemv {
+            $crate::testgen_gemv!(f32);
+        }
+    };
+    ($float:ident) => {
+            use super::*;
+            use cubecl_linalg::matmul::tests;
+            use cubecl_core::flex32;
+
+            pub type FloatT = $float;
+
+            $crate::testgen_gemv_matmul!();
+    };
+    ([$($float:ident),*]) => {
+        mod gemv {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$float _ty>] {
+                    use super::*;
+
+                    $crate::testgen_gemv!($float);
+                })*
+            }
+        }
+    };
+}
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_gemv_matmul {
+    () => {
+        use super::*;
+
+        #[test]
+        pub fn test_gemv_matrix_vector() {
+            cubecl_linalg::matmul::tests::gemv::test_gemv_matrix_vector::<TestRuntime, FloatT>(
+                &Default::default(),
+            )
+        }
+
+        #[test]
+        pub fn test_gemv_vector_matrix() {
+            cubecl_linalg::matmul::tests::gemv::test_gemv_vector_matrix::<TestRuntime, FloatT>(
+                &Default::default(),
+            )
+        }
+
+        #[test]
+        pub fn test_gemv_transposed_matrix_vector() {
+            cubecl_linalg::matmul::tests::gemv::test_gemv_transposed_matrix_vector::<
+                TestRuntime,
+                FloatT,
+            >(&Default::default())
+        }
+
+        #[test]
+        pub fn test_gemv_transposed_vector_matrix() {
+            cubecl_linalg::matmul::tests::gemv::test_gemv_transposed_vector_matrix::<
+                TestRuntime,
+                FloatT,
+            >(&Default::default())
+        }
+
+        #[test]
+        pub fn test_gemv_broadcast_batches() {
+            cubecl_linalg::matmul::tests::gemv::test_gemv_broadcast_batches::<TestRuntime, FloatT>(
+                &Default::default(),
+            )
+        }
+
+        #[test]
+        pub fn test_gemv_launch_ref() {
+            cubecl_linalg::matmul::tests::gemv::test_gemv_launch_ref::<TestRuntime, FloatT>(
+                &Default::default(),
+            )
+        }
+    };
+}
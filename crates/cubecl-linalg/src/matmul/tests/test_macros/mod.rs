@@ -1,3 +1,4 @@
 mod cmma;
 mod cmma_old;
+mod gemv;
 mod tiling2d;
@@ -3,9 +3,9 @@ use std::fmt::Display;
 use cubecl_core::{
     client::ComputeClient,
     flex32,
-    prelude::{Float, Numeric},
+    prelude::{CubePrimitive, Float, Numeric},
     server::Handle,
-    CubeElement, Runtime,
+    CubeElement, Feature, Runtime,
 };
 
 use crate::{matmul::components::MatmulProblem, tensor::TensorHandle};
@@ -152,11 +152,13 @@ where
     let mut out = vec![EG::from_int(0); m * n * b];
 
     for b_ in 0..b {
+        let (lhs_batch, rhs_batch) = broadcast_batches(problem, b_);
+
         for i in 0..m {
             for j in 0..n {
                 for k_ in 0..k {
-                    let lhs_index = b_ * m * k + i * k + k_;
-                    let rhs_index = b_ * k * n + k_ * n + j;
+                    let lhs_index = lhs_batch * m * k + i * k + k_;
+                    let rhs_index = rhs_batch * k * n + k_ * n + j;
                     let out_index = b_ * m * n + i * n + j;
 
                     let l: ES = lhs[lhs_index].cast_into();
@@ -173,6 +175,29 @@ where
     out
 }
 
+/// Returns the batches of the lhs and rhs that are multiplied into the given batch of the
+/// output, whose axes of size 1 are broadcast
+pub(crate) fn broadcast_batches<EG: Numeric>(
+    problem: &MatmulProblem<EG>,
+    out_batch: usize,
+) -> (usize, usize) {
+    let out_batches = problem.out_batches();
+    let index = |batches: &[usize]| {
+        let (mut index, mut stride, mut remainder) = (0, 1, out_batch);
+        for (i, out_size) in out_batches.iter().rev().enumerate() {
+            let coordinate = remainder % out_size;
+            remainder /= out_size;
+            if let Some(axis) = batches.len().checked_sub(i + 1) {
+                index += coordinate % batches[axis] * stride;
+                stride *= batches[axis];
+            }
+        }
+        index
+    };
+
+    (index(&problem.batches.0), index(&problem.batches.1))
+}
+
 /// Deprecated
 pub(crate) struct MatmulTestCase {
     pub m: usize,
@@ -265,3 +290,54 @@ impl MatmulTestCase {
         client.empty(x * y * core::mem::size_of::<f32>())
     }
 }
+
+/// Whether a test can't run on the device, because it lacks subcube operations or the given
+/// element type.
+pub(crate) fn should_skip<R: Runtime, E: CubePrimitive>(
+    client: &ComputeClient<R::Server, R::Channel>,
+) -> bool {
+    !(client.properties().feature_enabled(Feature::Subcube)
+        && client
+            .properties()
+            .feature_enabled(Feature::Type(E::as_elem())))
+}
+
+/// Uploads the data as a contiguous tensor of the given shape.
+pub(crate) fn create_tensor<R: Runtime, E: CubeElement + CubePrimitive>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    shape: Vec<usize>,
+    data: &[E],
+) -> TensorHandle<R, E> {
+    TensorHandle::new_contiguous(shape, client.create(E::as_bytes(data)))
+}
+
+/// Uploads the values, converted to `F`, as a contiguous tensor of the given shape.
+pub(crate) fn create_float_tensor<R: Runtime, F: Float + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    shape: Vec<usize>,
+    data: &[f32],
+) -> TensorHandle<R, F> {
+    let data: Vec<F> = data.iter().map(|value| F::new(*value)).collect();
+    create_tensor(client, shape, &data)
+}
+
+/// Reads the content of the tensor back.
+pub(crate) fn read_tensor<R: Runtime, E: CubeElement + CubePrimitive>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    tensor: &TensorHandle<R, E>,
+) -> Vec<E> {
+    E::from_bytes(&client.read(tensor.handle.clone().binding())).to_vec()
+}
+
+/// Reads the content of the tensor back, converted to `f32`.
+pub(crate) fn read_f32<R: Runtime, F: Float + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    tensor: &TensorHandle<R, F>,
+) -> Vec<f32> {
+    to_f32(&read_tensor(client, tensor))
+}
+
+/// Converts the values to `f32`.
+pub(crate) fn to_f32<F: Float>(data: &[F]) -> Vec<f32> {
+    data.iter().map(|value| value.to_f32().unwrap()).collect()
+}
@@ -0,0 +1,194 @@
+use cubecl_core::{
+    self as cubecl,
+    ir::Elem,
+    prelude::*,
+    tune,
+    tune::{local_tuner, tune_with, LocalTuner},
+    AutotuneKey, Feature,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::tensor::{matrix_layout, MatrixLayout, TensorHandle};
+
+use super::kernels::{
+    cmma_matmul::{self, dispatch::check_availability, dispatch::CmmaLaunchDispatch},
+    cmma_old::{self, config::PredefinedCmmaConfig, is_available},
+    tiling2d,
+};
+
+/// Autotune key representative of matmul versions.
+#[derive(AutotuneKey, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MatmulAutotuneKey {
+    #[autotune(anchor)]
+    m: usize,
+    #[autotune(anchor)]
+    n: usize,
+    #[autotune(anchor)]
+    k: usize,
+    #[autotune(anchor)]
+    batch: usize,
+    lhs_layout: MatrixLayout,
+    rhs_layout: MatrixLayout,
+    elem: Elem,
+}
+
+/// Block size presets of the old cmma matmul that are benchmarked, in the order of their
+/// operations.
+const CMMA_OLD_PRESETS: [PredefinedCmmaConfig; 7] = [
+    PredefinedCmmaConfig::M128K16,
+    PredefinedCmmaConfig::M64K32,
+    PredefinedCmmaConfig::M64K16,
+    PredefinedCmmaConfig::M32K32,
+    PredefinedCmmaConfig::M32K16,
+    PredefinedCmmaConfig::M32K16N64,
+    PredefinedCmmaConfig::M16K32N64,
+];
+
+fn create_key<R: Runtime, EG: Float + CubeElement>(
+    _client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandle<R, EG>,
+    rhs: &TensorHandle<R, EG>,
+    out: &TensorHandle<R, EG>,
+) -> MatmulAutotuneKey {
+    let rank = out.shape.len();
+
+    // Batches of the lhs and rhs can be broadcast, so they are counted on the output.
+    MatmulAutotuneKey::new(
+        out.shape[rank - 2],
+        out.shape[rank - 1],
+        lhs.shape[lhs.shape.len() - 1],
+        out.shape[..rank - 2].iter().product(),
+        matrix_layout(&lhs.strides),
+        matrix_layout(&rhs.strides),
+        EG::as_elem(),
+    )
+}
+
+fn should_run<R: Runtime, EG: Float + CubeElement>(
+    op: &MatmulOps<R, EG>,
+    _key: &MatmulAutotuneKey,
+    index: usize,
+) -> bool {
+    let num_cmma_old = CMMA_OLD_PRESETS.len();
+
+    match index {
+        // Accelerated would fall back on PlaneMma without cmma instructions.
+        0 => check_availability::<CmmaLaunchDispatch, R>(&op.client).is_ok(),
+        1 => op.client.properties().feature_enabled(Feature::Subcube),
+        index if index < 2 + num_cmma_old => {
+            is_available::<R, EG>(&op.client, &CMMA_OLD_PRESETS[index - 2].into()).is_ok()
+        }
+        _ => true,
+    }
+}
+
+#[tune(
+    operations(
+        matmul_accelerated,
+        matmul_plane_mma,
+        matmul_cmma_old_m128k16,
+        matmul_cmma_old_m64k32,
+        matmul_cmma_old_m64k16,
+        matmul_cmma_old_m32k32,
+        matmul_cmma_old_m32k16,
+        matmul_cmma_old_m32k16n64,
+        matmul_cmma_old_m16k32n64,
+        matmul_tiling2d
+    ),
+    create_key = create_key,
+    should_run = should_run
+)]
+fn matmul_ops<R: Runtime, EG: Float + CubeElement>(
+    key: MatmulAutotuneKey,
+    client: ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandle<R, EG>,
+    rhs: TensorHandle<R, EG>,
+    out: TensorHandle<R, EG>,
+) {
+    let out = TensorHandle::empty(client, out.shape.clone());
+
+    tune_with!(client.clone(), lhs.clone(), rhs.clone(), out)
+}
+
+fn matmul_accelerated<R: Runtime, EG: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandle<R, EG>,
+    rhs: TensorHandle<R, EG>,
+    out: TensorHandle<R, EG>,
+) {
+    cmma_matmul::launch_ref::<R, EG>(&client, lhs.as_ref(), rhs.as_ref(), out.as_ref(), false);
+}
+
+fn matmul_plane_mma<R: Runtime, EG: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandle<R, EG>,
+    rhs: TensorHandle<R, EG>,
+    out: TensorHandle<R, EG>,
+) {
+    cmma_matmul::launch_ref::<R, EG>(&client, lhs.as_ref(), rhs.as_ref(), out.as_ref(), true);
+}
+
+macro_rules! cmma_old_operation {
+    ($name:ident, $preset:ident) => {
+        fn $name<R: Runtime, EG: Float + CubeElement>(
+            client: ComputeClient<R::Server, R::Channel>,
+            lhs: TensorHandle<R, EG>,
+            rhs: TensorHandle<R, EG>,
+            out: TensorHandle<R, EG>,
+        ) {
+            cmma_old::launch_ref::<R, EG>(
+                &client,
+                lhs.as_ref(),
+                rhs.as_ref(),
+                out.as_ref(),
+                PredefinedCmmaConfig::$preset.into(),
+            );
+        }
+    };
+}
+
+cmma_old_operation!(matmul_cmma_old_m128k16, M128K16);
+cmma_old_operation!(matmul_cmma_old_m64k32, M64K32);
+cmma_old_operation!(matmul_cmma_old_m64k16, M64K16);
+cmma_old_operation!(matmul_cmma_old_m32k32, M32K32);
+cmma_old_operation!(matmul_cmma_old_m32k16, M32K16);
+cmma_old_operation!(matmul_cmma_old_m32k16n64, M32K16N64);
+cmma_old_operation!(matmul_cmma_old_m16k32n64, M16K32N64);
+
+fn matmul_tiling2d<R: Runtime, EG: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandle<R, EG>,
+    rhs: TensorHandle<R, EG>,
+    out: TensorHandle<R, EG>,
+) {
+    tiling2d::launch_ref::<R, EG>(
+        &client,
+        lhs.as_ref(),
+        rhs.as_ref(),
+        out.as_ref(),
+        Default::default(),
+    );
+}
+
+/// Execute the fastest matmul strategy for the given tensors.
+pub(crate) fn matmul_autotune<R: Runtime, EG: Float + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandleRef<'_, R>,
+    rhs: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+) {
+    static TUNER: LocalTuner<MatmulAutotuneKey, String> = local_tuner!();
+
+    let lhs =
+        TensorHandle::<R, EG>::new(lhs.shape.to_vec(), lhs.strides.to_vec(), lhs.handle.clone());
+    let rhs =
+        TensorHandle::<R, EG>::new(rhs.shape.to_vec(), rhs.strides.to_vec(), rhs.handle.clone());
+    let out =
+        TensorHandle::<R, EG>::new(out.shape.to_vec(), out.strides.to_vec(), out.handle.clone());
+
+    TUNER.execute(
+        &R::name().to_string(),
+        client,
+        Box::new(MatmulOps::<R, EG>::new(client.clone(), lhs, rhs, out)),
+    );
+}
@@ -0,0 +1,224 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise, tensor_line_size};
+
+use crate::tensor::TensorHandle;
+
+use super::{layer_norm_kernel, rms_norm_kernel, softmax_kernel};
+
+/// The number of units normalizing a row together, must be a power of two.
+pub(crate) const CUBE_SIZE: u32 = 256;
+
+/// Softmax over the last axis of the input.
+pub fn softmax<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, F>,
+) -> TensorHandle<R, F> {
+    launch_softmax(client, input, false)
+}
+
+/// Logarithm of the softmax over the last axis of the input, computed without taking the
+/// logarithm of small probabilities.
+pub fn log_softmax<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, F>,
+) -> TensorHandle<R, F> {
+    launch_softmax(client, input, true)
+}
+
+/// Layer normalization over the last axis of the input, where `weight` and `bias` are contiguous
+/// tensors of the size of the last axis.
+pub fn layer_norm<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, F>,
+    weight: TensorHandle<R, F>,
+    bias: TensorHandle<R, F>,
+    epsilon: f32,
+) -> TensorHandle<R, F> {
+    check_parameter(&input, &weight, "weight");
+    check_parameter(&input, &bias, "bias");
+
+    let output = TensorHandle::empty(client, input.shape.clone());
+    let line_size = line_size::<R>(&input);
+    let (cube_count, cube_dim) = cube_settings(&input);
+
+    unsafe {
+        layer_norm_kernel::launch_unchecked::<F, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_arg(line_size),
+            weight.as_arg(line_size),
+            bias.as_arg(line_size),
+            output.as_arg(line_size),
+            ScalarArg::new(epsilon),
+            CUBE_SIZE,
+        );
+    }
+
+    output
+}
+
+/// Root mean square normalization over the last axis of the input, where `weight` is a
+/// contiguous tensor of the size of the last axis.
+pub fn rms_norm<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, F>,
+    weight: TensorHandle<R, F>,
+    epsilon: f32,
+) -> TensorHandle<R, F> {
+    check_parameter(&input, &weight, "weight");
+
+    let output = TensorHandle::empty(client, input.shape.clone());
+    let line_size = line_size::<R>(&input);
+    let (cube_count, cube_dim) = cube_settings(&input);
+
+    unsafe {
+        rms_norm_kernel::launch_unchecked::<F, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_arg(line_size),
+            weight.as_arg(line_size),
+            output.as_arg(line_size),
+            ScalarArg::new(epsilon),
+            CUBE_SIZE,
+        );
+    }
+
+    output
+}
+
+fn launch_softmax<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, F>,
+    log: bool,
+) -> TensorHandle<R, F> {
+    let output = TensorHandle::empty(client, input.shape.clone());
+    let line_size = line_size::<R>(&input);
+    let (cube_count, cube_dim) = cube_settings(&input);
+
+    unsafe {
+        softmax_kernel::launch_unchecked::<F, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_arg(line_size),
+            output.as_arg(line_size),
+            CUBE_SIZE,
+            log,
+        );
+    }
+
+    output
+}
+
+fn check_parameter<R: Runtime, F: Float>(
+    input: &TensorHandle<R, F>,
+    parameter: &TensorHandle<R, F>,
+    name: &str,
+) {
+    let length = input.shape.last().copied().unwrap_or(1);
+
+    assert!(
+        parameter.shape == [length] && parameter.strides == [1],
+        "The {name} must be a contiguous tensor of shape [{length}], got shape {:?} and strides {:?}",
+        parameter.shape,
+        parameter.strides,
+    );
+}
+
+/// The line size along the last axis, which must also divide the offset of every row.
+fn line_size<R: Runtime>(input: &TensorHandle<R, impl Float>) -> u8 {
+    let rank = input.shape.len();
+    let line_size = tensor_line_size(
+        R::supported_line_sizes(),
+        &input.shape,
+        &input.strides,
+        rank - 1,
+    );
+
+    match input.strides[..rank - 1]
+        .iter()
+        .all(|stride| stride % line_size as usize == 0)
+    {
+        true => line_size,
+        false => 1,
+    }
+}
+
+/// One cube per row.
+fn cube_settings<R: Runtime>(input: &TensorHandle<R, impl Float>) -> (CubeCount, CubeDim) {
+    let rank = input.shape.len();
+    let num_rows: usize = input.shape[..rank - 1].iter().product();
+    let cube_count = calculate_cube_count_elemwise(num_rows, CubeDim::new(1, 1, 1));
+
+    (cube_count, CubeDim::new(CUBE_SIZE, 1, 1))
+}
+
+/// Whether the row is part of the tensor, since there can be more cubes than rows.
+#[cube]
+pub(crate) fn is_valid_row<F: Float>(output: &Tensor<Line<F>>, row: u32) -> bool {
+    let mut num_rows = 1;
+    for i in 0..output.rank() - 1 {
+        num_rows *= output.shape(i);
+    }
+
+    row < num_rows
+}
+
+/// The line offset of the row in the input, given a contiguous output of the same shape.
+///
+/// Rows past the end of the tensor wrap around, so they always read valid lines.
+#[cube]
+pub(crate) fn row_offset<F: Float>(
+    input: &Tensor<Line<F>>,
+    output: &Tensor<Line<F>>,
+    row: u32,
+) -> u32 {
+    let rank = input.rank();
+    let position = row * output.shape(rank - 1);
+    let mut offset = 0;
+
+    for i in 0..rank - 1 {
+        let coordinate = position / output.stride(i) % output.shape(i);
+        offset += coordinate * input.stride(i);
+    }
+
+    offset / input.line_size()
+}
+
+/// Merge the states of all units of the cube with a tree reduction in shared memory, returning
+/// the merged state to every unit.
+#[cube]
+pub(crate) fn merge_units<M: MergeState>(state: M, #[comptime] cube_size: u32) -> M {
+    let mut shared = M::init_shared(cube_size);
+    M::store(state, &mut shared, UNIT_POS);
+    sync_units();
+
+    let mut num_active = CUBE_DIM / 2;
+    while num_active > 0 {
+        if UNIT_POS < num_active {
+            let merged = M::merge(
+                M::load(&shared, UNIT_POS),
+                M::load(&shared, UNIT_POS + num_active),
+            );
+            M::store(merged, &mut shared, UNIT_POS);
+        }
+        sync_units();
+        num_active /= 2;
+    }
+
+    M::load(&shared, 0)
+}
+
+/// Running statistics of a row that can be merged in any order.
+#[cube]
+pub(crate) trait MergeState: CubeType + Sized {
+    /// Shared memory holding one state per unit.
+    type Shared: CubeType;
+
+    fn init_shared(#[comptime] cube_size: u32) -> Self::Shared;
+    fn store(state: Self, shared: &mut Self::Shared, index: u32);
+    fn load(shared: &Self::Shared, index: u32) -> Self;
+    fn merge(lhs: Self, rhs: Self) -> Self;
+}
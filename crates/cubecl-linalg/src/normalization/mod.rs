@@ -0,0 +1,11 @@
+mod base;
+mod norm;
+mod softmax;
+
+/// Tests for normalization kernels
+#[cfg(feature = "export_tests")]
+pub mod tests;
+
+pub use base::*;
+pub(crate) use norm::*;
+pub(crate) use softmax::*;
@@ -0,0 +1,230 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+use super::{is_valid_row, merge_units, row_offset, MergeState};
+
+/// The number of elements of a row with their mean and the sum of their squared differences
+/// to the mean, updated with Welford's algorithm so the row is only read once.
+#[derive(CubeType)]
+pub(crate) struct WelfordState {
+    count: f32,
+    mean: f32,
+    m2: f32,
+}
+
+#[derive(CubeType)]
+pub(crate) struct WelfordShared {
+    count: SharedMemory<f32>,
+    mean: SharedMemory<f32>,
+    m2: SharedMemory<f32>,
+}
+
+#[cube]
+impl WelfordState {
+    fn new(count: f32, mean: f32, m2: f32) -> WelfordState {
+        WelfordState { count, mean, m2 }
+    }
+}
+
+#[cube]
+impl MergeState for WelfordState {
+    type Shared = WelfordShared;
+
+    fn init_shared(#[comptime] cube_size: u32) -> WelfordShared {
+        WelfordShared {
+            count: SharedMemory::new(cube_size),
+            mean: SharedMemory::new(cube_size),
+            m2: SharedMemory::new(cube_size),
+        }
+    }
+
+    fn store(state: Self, shared: &mut WelfordShared, index: u32) {
+        shared.count[index] = state.count;
+        shared.mean[index] = state.mean;
+        shared.m2[index] = state.m2;
+    }
+
+    fn load(shared: &WelfordShared, index: u32) -> Self {
+        WelfordState::new(shared.count[index], shared.mean[index], shared.m2[index])
+    }
+
+    /// Chan's parallel update, where merging with an empty state is a no-op.
+    fn merge(lhs: Self, rhs: Self) -> Self {
+        let count = lhs.count + rhs.count;
+        let ratio = select(count == 0.0, 0.0, rhs.count / count);
+        let delta = rhs.mean - lhs.mean;
+        let mean = lhs.mean + delta * ratio;
+        let m2 = lhs.m2 + rhs.m2 + delta * delta * lhs.count * ratio;
+        WelfordState::new(count, mean, m2)
+    }
+}
+
+/// A sum of squares, the state of the root mean square.
+#[derive(CubeType)]
+pub(crate) struct SquareSumState {
+    sum: f32,
+}
+
+#[cube]
+impl MergeState for SquareSumState {
+    type Shared = SharedMemory<f32>;
+
+    fn init_shared(#[comptime] cube_size: u32) -> SharedMemory<f32> {
+        SharedMemory::new(cube_size)
+    }
+
+    fn store(state: Self, shared: &mut SharedMemory<f32>, index: u32) {
+        shared[index] = state.sum;
+    }
+
+    fn load(shared: &SharedMemory<f32>, index: u32) -> Self {
+        SquareSumState { sum: shared[index] }
+    }
+
+    fn merge(lhs: Self, rhs: Self) -> Self {
+        SquareSumState {
+            sum: lhs.sum + rhs.sum,
+        }
+    }
+}
+
+/// Layer normalization of each row of the last axis, with one cube per row. The statistics are
+/// accumulated in f32 for every lane, then merged across lanes and units.
+#[cube(launch_unchecked)]
+pub(crate) fn layer_norm_kernel<F: Float>(
+    input: &Tensor<Line<F>>,
+    weight: &Tensor<Line<F>>,
+    bias: &Tensor<Line<F>>,
+    output: &mut Tensor<Line<F>>,
+    epsilon: f32,
+    #[comptime] cube_size: u32,
+) {
+    let line_size = input.line_size();
+    let length = input.shape(input.rank() - 1);
+    let num_lines = length / line_size;
+    let input_offset = row_offset::<F>(input, output, CUBE_POS);
+    let output_offset = CUBE_POS * num_lines;
+
+    let mut count = 0.0;
+    let mut mean = Line::empty(line_size).fill(0.0);
+    let mut m2 = Line::empty(line_size).fill(0.0);
+
+    for i in range_stepped(UNIT_POS, num_lines, CUBE_DIM) {
+        let value = Line::<f32>::cast_from(input[input_offset + i]);
+        count += 1.0;
+        let delta = value - mean;
+        mean += delta / Line::empty(line_size).fill(count);
+        m2 += delta * (value - mean);
+    }
+
+    let mut row_count = 0.0;
+    let mut row_mean = 0.0;
+    let mut row_m2 = 0.0;
+    #[unroll]
+    for lane in 0..line_size {
+        let lhs = WelfordState::new(row_count, row_mean, row_m2);
+        let merged = WelfordState::merge(lhs, WelfordState::new(count, mean[lane], m2[lane]));
+        row_count = merged.count;
+        row_mean = merged.mean;
+        row_m2 = merged.m2;
+    }
+    let state = WelfordState::new(row_count, row_mean, row_m2);
+    let state = merge_units::<WelfordState>(state, cube_size);
+
+    if is_valid_row::<F>(output, CUBE_POS) {
+        let variance = state.m2 / f32::cast_from(length);
+        let mean = Line::empty(line_size).fill(state.mean);
+        let inv_std = Line::empty(line_size).fill(f32::recip(f32::sqrt(variance + epsilon)));
+
+        for i in range_stepped(UNIT_POS, num_lines, CUBE_DIM) {
+            let value = Line::<f32>::cast_from(input[input_offset + i]);
+            let normalized = (value - mean) * inv_std;
+            let result =
+                normalized * Line::<f32>::cast_from(weight[i]) + Line::<f32>::cast_from(bias[i]);
+            output[output_offset + i] = Line::cast_from(result);
+        }
+    }
+}
+
+/// Root mean square normalization of each row of the last axis, with one cube per row.
+#[cube(launch_unchecked)]
+pub(crate) fn rms_norm_kernel<F: Float>(
+    input: &Tensor<Line<F>>,
+    weight: &Tensor<Line<F>>,
+    output: &mut Tensor<Line<F>>,
+    epsilon: f32,
+    #[comptime] cube_size: u32,
+) {
+    let line_size = input.line_size();
+    let length = input.shape(input.rank() - 1);
+    let num_lines = length / line_size;
+    let input_offset = row_offset::<F>(input, output, CUBE_POS);
+    let output_offset = CUBE_POS * num_lines;
+
+    let mut sum = Line::empty(line_size).fill(0.0);
+
+    for i in range_stepped(UNIT_POS, num_lines, CUBE_DIM) {
+        let value = Line::<f32>::cast_from(input[input_offset + i]);
+        sum += value * value;
+    }
+
+    let mut row_sum = 0.0;
+    #[unroll]
+    for lane in 0..line_size {
+        row_sum += sum[lane];
+    }
+    let state = merge_units::<SquareSumState>(SquareSumState { sum: row_sum }, cube_size);
+
+    if is_valid_row::<F>(output, CUBE_POS) {
+        let mean_square = state.sum / f32::cast_from(length);
+        let inv_rms = Line::empty(line_size).fill(f32::recip(f32::sqrt(mean_square + epsilon)));
+
+        for i in range_stepped(UNIT_POS, num_lines, CUBE_DIM) {
+            let value = Line::<f32>::cast_from(input[input_offset + i]);
+            let result = value * inv_rms * Line::<f32>::cast_from(weight[i]);
+            output[output_offset + i] = Line::cast_from(result);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cubecl_core::host::{HostTensor, HostUnit};
+
+    #[test]
+    fn layer_norm_of_lines() {
+        let input = HostTensor::new(vec![vec![1.0, 2.0], vec![3.0, 6.0]], vec![1, 4]);
+        let weight = HostTensor::new(vec![vec![1.0, 2.0], vec![1.0, 1.0]], vec![4]);
+        let bias = HostTensor::new(vec![vec![0.0, 0.0], vec![0.0, 1.0]], vec![4]);
+        let mut output = HostTensor::new(vec![vec![0.0; 2]; 2], vec![1, 4]);
+
+        layer_norm_kernel::host::<f32>(
+            HostUnit::default(),
+            &input,
+            &weight,
+            &bias,
+            &mut output,
+            0.0,
+            1,
+        );
+
+        // Mean of 3 and standard deviation of sqrt(3.5).
+        let std = 3.5f32.sqrt();
+        let expected = [-2.0 / std, -2.0 / std, 0.0, 3.0 / std + 1.0];
+        for (actual, expected) in output.data.concat().iter().zip(expected) {
+            assert!((actual - expected).abs() < 1e-6, "{actual} != {expected}");
+        }
+    }
+
+    #[test]
+    fn rms_norm_of_lines() {
+        let input = HostTensor::new(vec![vec![1.0, -1.0], vec![1.0, -1.0]], vec![1, 4]);
+        let weight = HostTensor::new(vec![vec![1.0, 2.0], vec![3.0, 4.0]], vec![4]);
+        let mut output = HostTensor::new(vec![vec![0.0; 2]; 2], vec![1, 4]);
+
+        rms_norm_kernel::host::<f32>(HostUnit::default(), &input, &weight, &mut output, 0.0, 1);
+
+        assert_eq!(output.data.concat(), vec![1.0, -2.0, 3.0, -4.0]);
+    }
+}
@@ -0,0 +1,146 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+use super::{is_valid_row, merge_units, row_offset, MergeState};
+
+/// The maximum of a row with the sum of the exponentials of its elements minus that maximum,
+/// updated online so the row is only read once.
+#[derive(CubeType)]
+pub(crate) struct SoftmaxState {
+    max: f32,
+    sum: f32,
+}
+
+#[derive(CubeType)]
+pub(crate) struct SoftmaxShared {
+    max: SharedMemory<f32>,
+    sum: SharedMemory<f32>,
+}
+
+#[cube]
+impl SoftmaxState {
+    fn new(max: f32, sum: f32) -> SoftmaxState {
+        SoftmaxState { max, sum }
+    }
+}
+
+#[cube]
+impl MergeState for SoftmaxState {
+    type Shared = SoftmaxShared;
+
+    fn init_shared(#[comptime] cube_size: u32) -> SoftmaxShared {
+        SoftmaxShared {
+            max: SharedMemory::new(cube_size),
+            sum: SharedMemory::new(cube_size),
+        }
+    }
+
+    fn store(state: Self, shared: &mut SoftmaxShared, index: u32) {
+        shared.max[index] = state.max;
+        shared.sum[index] = state.sum;
+    }
+
+    fn load(shared: &SoftmaxShared, index: u32) -> Self {
+        SoftmaxState::new(shared.max[index], shared.sum[index])
+    }
+
+    fn merge(lhs: Self, rhs: Self) -> Self {
+        let max = f32::max(lhs.max, rhs.max);
+        let sum = lhs.sum * f32::exp(lhs.max - max) + rhs.sum * f32::exp(rhs.max - max);
+        SoftmaxState::new(max, sum)
+    }
+}
+
+/// Softmax of each row of the last axis, with one cube per row. The statistics are accumulated
+/// in f32 for every lane, then merged across lanes and units.
+#[cube(launch_unchecked)]
+pub(crate) fn softmax_kernel<F: Float>(
+    input: &Tensor<Line<F>>,
+    output: &mut Tensor<Line<F>>,
+    #[comptime] cube_size: u32,
+    #[comptime] log: bool,
+) {
+    let line_size = input.line_size();
+    let num_lines = input.shape(input.rank() - 1) / line_size;
+    let input_offset = row_offset::<F>(input, output, CUBE_POS);
+    let output_offset = CUBE_POS * num_lines;
+
+    let mut max = Line::empty(line_size).fill(f32::MIN);
+    let mut sum = Line::empty(line_size).fill(0.0);
+
+    for i in range_stepped(UNIT_POS, num_lines, CUBE_DIM) {
+        let value = Line::<f32>::cast_from(input[input_offset + i]);
+        let new_max = Line::<f32>::max(max, value);
+        sum = sum * Line::<f32>::exp(max - new_max) + Line::<f32>::exp(value - new_max);
+        max = new_max;
+    }
+
+    let mut row_max = f32::MIN;
+    let mut row_sum = 0.0;
+    #[unroll]
+    for lane in 0..line_size {
+        let lhs = SoftmaxState::new(row_max, row_sum);
+        let merged = SoftmaxState::merge(lhs, SoftmaxState::new(max[lane], sum[lane]));
+        row_max = merged.max;
+        row_sum = merged.sum;
+    }
+    let state = merge_units::<SoftmaxState>(SoftmaxState::new(row_max, row_sum), cube_size);
+
+    if is_valid_row::<F>(output, CUBE_POS) {
+        let max = Line::empty(line_size).fill(state.max);
+        let sum = Line::empty(line_size).fill(state.sum);
+
+        for i in range_stepped(UNIT_POS, num_lines, CUBE_DIM) {
+            let value = Line::<f32>::cast_from(input[input_offset + i]) - max;
+            let result = if log {
+                value - Line::<f32>::log(sum)
+            } else {
+                Line::<f32>::exp(value) / sum
+            };
+            output[output_offset + i] = Line::cast_from(result);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cubecl_core::host::{HostTensor, HostUnit};
+
+    fn softmax_host(log: bool) -> Vec<f32> {
+        let rows = vec![vec![1.0, 2.0, 3.0, 4.0], vec![-1.0, 0.0, 1000.0, 0.5]];
+        let input = HostTensor::new(rows.clone(), vec![2, 4]);
+        let mut output = HostTensor::new(vec![vec![0.0; 4]; 2], vec![2, 4]);
+
+        for row in 0..2 {
+            softmax_kernel::host::<f32>(
+                HostUnit::default().with_cube_pos(row, 0, 0),
+                &input,
+                &mut output,
+                1,
+                log,
+            );
+        }
+
+        output.data.concat()
+    }
+
+    #[test]
+    fn softmax_of_lines_is_stable() {
+        let actual = softmax_host(false);
+        let sum: f32 = [1.0f32, 2.0, 3.0, 4.0].iter().map(|x| x.exp()).sum();
+
+        for (i, value) in [1.0f32, 2.0, 3.0, 4.0].iter().enumerate() {
+            assert!((actual[i] - value.exp() / sum).abs() < 1e-6);
+        }
+        assert_eq!(&actual[4..], &[0.0, 0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn log_softmax_doesnt_underflow() {
+        let actual = softmax_host(true);
+
+        assert_eq!(actual[6], 0.0);
+        assert!((actual[4] + 1001.0).abs() < 1e-3);
+    }
+}
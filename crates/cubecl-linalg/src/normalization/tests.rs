@@ -0,0 +1,237 @@
+#![allow(missing_docs)]
+
+use std::fmt::Display;
+
+use cubecl_core::{prelude::*, CubeElement};
+
+use crate::{
+    matmul::tests::test_utils::{
+        assert_equals_approx, create_tensor, generate_random_data, to_f32,
+    },
+    normalization,
+};
+
+/// Rows of the last axis, which is contiguous in every test case.
+struct NormalizationTestCase {
+    num_rows: usize,
+    length: usize,
+}
+
+impl NormalizationTestCase {
+    fn shape(&self) -> Vec<usize> {
+        vec![self.num_rows, self.length]
+    }
+
+    fn rows<F: Float + CubeElement>(&self, data: &[F]) -> Vec<Vec<f32>> {
+        data.chunks(self.length).map(to_f32).collect()
+    }
+}
+
+const CASES: [NormalizationTestCase; 3] = [
+    // Vectorized, with more lines than units.
+    NormalizationTestCase {
+        num_rows: 6,
+        length: 1200,
+    },
+    // Not vectorized, with fewer elements than units.
+    NormalizationTestCase {
+        num_rows: 5,
+        length: 33,
+    },
+    // A single element.
+    NormalizationTestCase {
+        num_rows: 3,
+        length: 1,
+    },
+];
+
+fn to_elements<F: Float>(rows: Vec<Vec<f32>>) -> Vec<F> {
+    rows.into_iter().flatten().map(F::new).collect()
+}
+
+fn softmax_reference(row: &[f32]) -> Vec<f32> {
+    let max = row.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let sum: f32 = row.iter().map(|value| (value - max).exp()).sum();
+    row.iter().map(|value| (value - max).exp() / sum).collect()
+}
+
+fn test_softmax_kind<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device, log: bool) {
+    let client = R::client(device);
+
+    for case in CASES {
+        // Large values check that the maximum is subtracted before the exponential.
+        let data: Vec<F> = generate_random_data::<F>(case.num_rows * case.length)
+            .into_iter()
+            .map(|value| F::new(value.to_f32().unwrap() * 50.0))
+            .collect();
+        let input = create_tensor::<R, F>(&client, case.shape(), &data);
+
+        let (output, expected) = match log {
+            false => (
+                normalization::softmax(&client, input),
+                case.rows(&data)
+                    .iter()
+                    .map(|row| softmax_reference(row))
+                    .collect(),
+            ),
+            true => (
+                normalization::log_softmax(&client, input),
+                case.rows(&data)
+                    .iter()
+                    .map(|row| softmax_reference(row).iter().map(|p| p.ln()).collect())
+                    .collect(),
+            ),
+        };
+
+        if let Err(e) =
+            assert_equals_approx::<R, F>(&client, output.handle, &to_elements(expected), 1e-4)
+        {
+            panic!("{}", e);
+        }
+    }
+}
+
+pub fn test_softmax<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    test_softmax_kind::<R, F>(device, false);
+}
+
+pub fn test_log_softmax<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    test_softmax_kind::<R, F>(device, true);
+}
+
+pub fn test_layer_norm<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let client = R::client(device);
+    let epsilon = 1e-5;
+
+    for case in CASES {
+        // An offset checks that the variance doesn't cancel out.
+        let data: Vec<F> = generate_random_data::<F>(case.num_rows * case.length)
+            .into_iter()
+            .map(|value| F::new(value.to_f32().unwrap() + 100.0))
+            .collect();
+        let weight = generate_random_data::<F>(case.length);
+        let bias: Vec<F> = weight.iter().rev().copied().collect();
+        let input = create_tensor::<R, F>(&client, case.shape(), &data);
+        let weight_tensor = create_tensor::<R, F>(&client, vec![case.length], &weight);
+        let bias_tensor = create_tensor::<R, F>(&client, vec![case.length], &bias);
+
+        let output = normalization::layer_norm(&client, input, weight_tensor, bias_tensor, epsilon);
+
+        let expected = case
+            .rows(&data)
+            .iter()
+            .map(|row| {
+                let mean = row.iter().sum::<f32>() / row.len() as f32;
+                let variance =
+                    row.iter().map(|value| (value - mean).powi(2)).sum::<f32>() / row.len() as f32;
+                let inv_std = 1.0 / (variance + epsilon).sqrt();
+
+                row.iter()
+                    .enumerate()
+                    .map(|(i, value)| {
+                        (value - mean) * inv_std * weight[i].to_f32().unwrap()
+                            + bias[i].to_f32().unwrap()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        if let Err(e) =
+            assert_equals_approx::<R, F>(&client, output.handle, &to_elements(expected), 1e-3)
+        {
+            panic!("{}", e);
+        }
+    }
+}
+
+pub fn test_rms_norm<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let client = R::client(device);
+    let epsilon = 1e-5;
+
+    for case in CASES {
+        let data = generate_random_data::<F>(case.num_rows * case.length);
+        let weight = generate_random_data::<F>(case.length);
+        let input = create_tensor::<R, F>(&client, case.shape(), &data);
+        let weight_tensor = create_tensor::<R, F>(&client, vec![case.length], &weight);
+
+        let output = normalization::rms_norm(&client, input, weight_tensor, epsilon);
+
+        let expected = case
+            .rows(&data)
+            .iter()
+            .map(|row| {
+                let mean_square =
+                    row.iter().map(|value| value * value).sum::<f32>() / row.len() as f32;
+                let inv_rms = 1.0 / (mean_square + epsilon).sqrt();
+
+                row.iter()
+                    .enumerate()
+                    .map(|(i, value)| value * inv_rms * weight[i].to_f32().unwrap())
+                    .collect()
+            })
+            .collect();
+
+        if let Err(e) =
+            assert_equals_approx::<R, F>(&client, output.handle, &to_elements(expected), 1e-3)
+        {
+            panic!("{}", e);
+        }
+    }
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_normalization {
+    () => {
+        mod normalization {
+            $crate::testgen_normalization!(f32);
+        }
+    };
+    ($float:ident) => {
+            use super::*;
+            use cubecl_linalg::normalization::tests;
+            use cubecl_core::flex32;
+
+            pub type FloatT = $float;
+
+            $crate::testgen_normalization_ops!();
+    };
+    ([$($float:ident),*]) => {
+        mod normalization {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$float _ty>] {
+                    use super::*;
+
+                    $crate::testgen_normalization!($float);
+                })*
+            }
+        }
+    };
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_normalization_ops {
+    () => {
+        #[test]
+        pub fn test_softmax() {
+            tests::test_softmax::<TestRuntime, FloatT>(&Default::default())
+        }
+
+        #[test]
+        pub fn test_log_softmax() {
+            tests::test_log_softmax::<TestRuntime, FloatT>(&Default::default())
+        }
+
+        #[test]
+        pub fn test_layer_norm() {
+            tests::test_layer_norm::<TestRuntime, FloatT>(&Default::default())
+        }
+
+        #[test]
+        pub fn test_rms_norm() {
+            tests::test_rms_norm::<TestRuntime, FloatT>(&Default::default())
+        }
+    };
+}
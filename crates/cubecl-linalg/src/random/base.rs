@@ -0,0 +1,140 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise};
+
+use crate::tensor::TensorHandle;
+
+use super::kernel::{bernoulli_kernel, normal_kernel, uniform_kernel};
+
+/// The number of samples drawn by each unit, one per word of a Philox block.
+const SAMPLES_PER_UNIT: usize = 4;
+
+/// The distribution of the samples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Distribution {
+    /// Uniform samples in `[low, high)`.
+    Uniform { low: f32, high: f32 },
+    /// Normal samples with the given mean and standard deviation.
+    Normal { mean: f32, std: f32 },
+    /// Ones with the given probability, and zeros otherwise.
+    Bernoulli { probability: f32 },
+}
+
+/// Create a tensor of the given shape filled with samples of the distribution.
+///
+/// The samples only depend on the seed, the offset and their position in row major order, so
+/// they are the same on every runtime. Drawing several tensors from the same seed requires a
+/// different offset for each of them.
+pub fn random<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    shape: Vec<usize>,
+    distribution: Distribution,
+    seed: u64,
+    offset: u64,
+) -> TensorHandle<R, F> {
+    let output = TensorHandle::empty(client, shape);
+
+    launch::<R, F>(client, output.as_ref(), distribution, seed, offset);
+
+    output
+}
+
+/// Fill the output with samples of the distribution, in the order of its elements in row major
+/// order whatever its strides.
+pub fn launch<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    output: TensorHandleRef<'_, R>,
+    distribution: Distribution,
+    seed: u64,
+    offset: u64,
+) {
+    let num_elements: usize = output.shape.iter().product();
+    if num_elements == 0 {
+        return;
+    }
+
+    let cube_dim = CubeDim::default();
+    let cube_count =
+        calculate_cube_count_elemwise(num_elements.div_ceil(SAMPLES_PER_UNIT), cube_dim);
+    let seed_lo = ScalarArg::new(seed as u32);
+    let seed_hi = ScalarArg::new((seed >> 32) as u32);
+    let offset_lo = ScalarArg::new(offset as u32);
+    let offset_hi = ScalarArg::new((offset >> 32) as u32);
+
+    unsafe {
+        match distribution {
+            Distribution::Uniform { low, high } => uniform_kernel::launch_unchecked::<F, R>(
+                client,
+                cube_count,
+                cube_dim,
+                output.as_tensor_arg(1),
+                seed_lo,
+                seed_hi,
+                offset_lo,
+                offset_hi,
+                ScalarArg::new(low),
+                ScalarArg::new(high),
+            ),
+            Distribution::Normal { mean, std } => normal_kernel::launch_unchecked::<F, R>(
+                client,
+                cube_count,
+                cube_dim,
+                output.as_tensor_arg(1),
+                seed_lo,
+                seed_hi,
+                offset_lo,
+                offset_hi,
+                ScalarArg::new(mean),
+                ScalarArg::new(std),
+            ),
+            Distribution::Bernoulli { probability } => bernoulli_kernel::launch_unchecked::<F, R>(
+                client,
+                cube_count,
+                cube_dim,
+                output.as_tensor_arg(1),
+                seed_lo,
+                seed_hi,
+                offset_lo,
+                offset_hi,
+                ScalarArg::new(probability),
+            ),
+        }
+    }
+}
+
+/// Create a tensor of samples uniformly distributed in `[low, high)`.
+pub fn uniform<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    shape: Vec<usize>,
+    low: f32,
+    high: f32,
+    seed: u64,
+) -> TensorHandle<R, F> {
+    random(client, shape, Distribution::Uniform { low, high }, seed, 0)
+}
+
+/// Create a tensor of samples of a normal distribution.
+pub fn normal<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    shape: Vec<usize>,
+    mean: f32,
+    std: f32,
+    seed: u64,
+) -> TensorHandle<R, F> {
+    random(client, shape, Distribution::Normal { mean, std }, seed, 0)
+}
+
+/// Create a tensor filled with ones with the given probability, and zeros otherwise.
+pub fn bernoulli<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    shape: Vec<usize>,
+    probability: f32,
+    seed: u64,
+) -> TensorHandle<R, F> {
+    random(
+        client,
+        shape,
+        Distribution::Bernoulli { probability },
+        seed,
+        0,
+    )
+}
@@ -0,0 +1,88 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+/// Fill four consecutive elements with samples uniformly distributed in `[low, high)`.
+#[cube(launch_unchecked)]
+pub(crate) fn uniform_kernel<F: Float>(
+    output: &mut Tensor<F>,
+    seed_lo: u32,
+    seed_hi: u32,
+    offset_lo: u32,
+    offset_hi: u32,
+    low: f32,
+    high: f32,
+) {
+    let bits = random_bits(seed_lo, seed_hi, offset_lo, offset_hi);
+    let start = ABSOLUTE_POS * 4;
+    let range = high - low;
+
+    write_sample(output, start, low + range * uniform_from_bits(bits.x0));
+    write_sample(output, start + 1, low + range * uniform_from_bits(bits.x1));
+    write_sample(output, start + 2, low + range * uniform_from_bits(bits.x2));
+    write_sample(output, start + 3, low + range * uniform_from_bits(bits.x3));
+}
+
+/// Fill four consecutive elements with samples of a normal distribution.
+#[cube(launch_unchecked)]
+pub(crate) fn normal_kernel<F: Float>(
+    output: &mut Tensor<F>,
+    seed_lo: u32,
+    seed_hi: u32,
+    offset_lo: u32,
+    offset_hi: u32,
+    mean: f32,
+    std: f32,
+) {
+    let bits = random_bits(seed_lo, seed_hi, offset_lo, offset_hi);
+    let start = ABSOLUTE_POS * 4;
+    let (z0, z1) = normal_from_bits(bits.x0, bits.x1);
+    let (z2, z3) = normal_from_bits(bits.x2, bits.x3);
+
+    write_sample(output, start, mean + std * z0);
+    write_sample(output, start + 1, mean + std * z1);
+    write_sample(output, start + 2, mean + std * z2);
+    write_sample(output, start + 3, mean + std * z3);
+}
+
+/// Fill four consecutive elements with ones with the given probability, and zeros otherwise.
+#[cube(launch_unchecked)]
+pub(crate) fn bernoulli_kernel<F: Float>(
+    output: &mut Tensor<F>,
+    seed_lo: u32,
+    seed_hi: u32,
+    offset_lo: u32,
+    offset_hi: u32,
+    probability: f32,
+) {
+    let bits = random_bits(seed_lo, seed_hi, offset_lo, offset_hi);
+    let start = ABSOLUTE_POS * 4;
+
+    write_sample(output, start, bernoulli(bits.x0, probability));
+    write_sample(output, start + 1, bernoulli(bits.x1, probability));
+    write_sample(output, start + 2, bernoulli(bits.x2, probability));
+    write_sample(output, start + 3, bernoulli(bits.x3, probability));
+}
+
+#[cube]
+fn bernoulli(bits: u32, probability: f32) -> f32 {
+    select(uniform_from_bits(bits) < probability, 1.0, 0.0)
+}
+
+/// Write the sample at the given position of the tensor in row major order, unless the position
+/// is past the last element.
+#[cube]
+fn write_sample<F: Float>(output: &mut Tensor<F>, position: u32, value: f32) {
+    let mut offset = 0;
+    let mut remaining = position;
+
+    for i in 0..output.rank() {
+        let dim = output.rank() - i - 1;
+        offset += remaining % output.shape(dim) * output.stride(dim);
+        remaining /= output.shape(dim);
+    }
+
+    // Positions past the last element wrap around the first dimension.
+    if remaining == 0 {
+        output[offset] = F::cast_from(value);
+    }
+}
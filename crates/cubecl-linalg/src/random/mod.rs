@@ -0,0 +1,8 @@
+mod base;
+mod kernel;
+
+/// Tests for random kernels
+#[cfg(feature = "export_tests")]
+pub mod tests;
+
+pub use base::*;
@@ -0,0 +1,215 @@
+#![allow(missing_docs)]
+
+use cubecl_core::{prelude::*, CubeElement};
+
+use crate::{
+    random::{self, Distribution},
+    tensor::TensorHandle,
+};
+
+/// Philox4x32-10 on the host, with the counter used for the samples of each unit.
+fn reference_bits(seed: u64, offset: u64, unit: u32) -> [u32; 4] {
+    let mut x = [unit, 0, offset as u32, (offset >> 32) as u32];
+    let mut key = [seed as u32, (seed >> 32) as u32];
+
+    for round in 0..10 {
+        if round > 0 {
+            key[0] = key[0].wrapping_add(0x9E37_79B9);
+            key[1] = key[1].wrapping_add(0xBB67_AE85);
+        }
+        let product0 = 0xD251_1F53u64 * x[0] as u64;
+        let product1 = 0xCD9E_8D57u64 * x[2] as u64;
+        x = [
+            (product1 >> 32) as u32 ^ x[1] ^ key[0],
+            product1 as u32,
+            (product0 >> 32) as u32 ^ x[3] ^ key[1],
+            product0 as u32,
+        ];
+    }
+
+    x
+}
+
+fn reference_uniform(seed: u64, offset: u64, num_elements: usize) -> Vec<f32> {
+    (0..num_elements)
+        .map(|i| {
+            let bits = reference_bits(seed, offset, (i / 4) as u32)[i % 4];
+            (bits >> 8) as f32 / (1 << 24) as f32
+        })
+        .collect()
+}
+
+fn read<R: Runtime, F: Float + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    tensor: &TensorHandle<R, F>,
+) -> Vec<f32> {
+    let data = client.read(tensor.handle.clone().binding());
+    F::from_bytes(&data)
+        .iter()
+        .map(|value| value.to_f32().unwrap())
+        .collect()
+}
+
+fn mean_and_variance(data: &[f32]) -> (f32, f32) {
+    let mean = data.iter().sum::<f32>() / data.len() as f32;
+    let variance = data.iter().map(|x| (x - mean).powi(2)).sum::<f32>() / data.len() as f32;
+    (mean, variance)
+}
+
+pub fn test_uniform_matches_reference<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    let seed = 0x0123_4567_89AB_CDEF;
+    let offset = 1 << 40;
+
+    let output = random::random::<R, F>(
+        &client,
+        vec![7, 301],
+        Distribution::Uniform {
+            low: 0.0,
+            high: 1.0,
+        },
+        seed,
+        offset,
+    );
+    let actual = read(&client, &output);
+    let expected = reference_uniform(seed, offset, 7 * 301);
+
+    for (i, (actual, expected)) in actual.iter().zip(&expected).enumerate() {
+        let expected = F::new(*expected).to_f32().unwrap();
+        assert_eq!(*actual, expected, "Samples differ at index {i}");
+    }
+}
+
+pub fn test_uniform_range<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+
+    let output = random::uniform::<R, F>(&client, vec![100, 1000], -3.0, 5.0, 42);
+    let actual = read(&client, &output);
+
+    assert!(actual.iter().all(|x| (-3.0..=5.0).contains(x)));
+    let (mean, variance) = mean_and_variance(&actual);
+    assert!((mean - 1.0).abs() < 0.05, "mean={mean}");
+    assert!((variance - 64.0 / 12.0).abs() < 0.1, "variance={variance}");
+}
+
+pub fn test_normal_moments<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+
+    let output = random::normal::<R, F>(&client, vec![100, 1000], 1.5, 2.0, 7);
+    let actual = read(&client, &output);
+
+    assert!(actual.iter().all(|x| x.is_finite()));
+    let (mean, variance) = mean_and_variance(&actual);
+    assert!((mean - 1.5).abs() < 0.05, "mean={mean}");
+    assert!((variance - 4.0).abs() < 0.1, "variance={variance}");
+}
+
+pub fn test_bernoulli_matches_reference<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    let seed = 3;
+    let offset = 0;
+
+    let output = random::bernoulli::<R, F>(&client, vec![10_000], 0.3, seed);
+    let actual = read(&client, &output);
+    let expected: Vec<f32> = reference_uniform(seed, offset, 10_000)
+        .into_iter()
+        .map(|u| if u < 0.3 { 1.0 } else { 0.0 })
+        .collect();
+
+    assert_eq!(actual, expected);
+    let proportion = actual.iter().sum::<f32>() / actual.len() as f32;
+    assert!((proportion - 0.3).abs() < 0.02, "proportion={proportion}");
+}
+
+pub fn test_offsets_are_independent<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    let distribution = Distribution::Normal {
+        mean: 0.0,
+        std: 1.0,
+    };
+
+    let first = random::random::<R, F>(&client, vec![1000], distribution, 11, 0);
+    let again = random::random::<R, F>(&client, vec![1000], distribution, 11, 0);
+    let next = random::random::<R, F>(&client, vec![1000], distribution, 11, 1);
+
+    let first = read(&client, &first);
+    assert_eq!(first, read(&client, &again));
+
+    let next = read(&client, &next);
+    let num_equal = first.iter().zip(&next).filter(|(a, b)| a == b).count();
+    assert!(num_equal < 10, "{num_equal} samples are repeated");
+}
+
+pub fn test_strided_output<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    let seed = 5;
+
+    // A transposed [30, 20] tensor, filled in the row major order of its shape.
+    let output = TensorHandle::<R, F>::new(
+        vec![30, 20],
+        vec![1, 30],
+        client.empty(600 * core::mem::size_of::<F>()),
+    );
+    random::launch::<R, F>(
+        &client,
+        output.as_ref(),
+        Distribution::Uniform {
+            low: 0.0,
+            high: 1.0,
+        },
+        seed,
+        0,
+    );
+    let actual = read(&client, &output);
+    let expected = reference_uniform(seed, 0, 600);
+
+    for row in 0..30 {
+        for col in 0..20 {
+            let expected = F::new(expected[row * 20 + col]).to_f32().unwrap();
+            assert_eq!(actual[col * 30 + row], expected);
+        }
+    }
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_random {
+    () => {
+        mod random {
+            use super::*;
+            use cubecl_linalg::random::tests;
+
+            pub type FloatT = f32;
+
+            #[test]
+            pub fn test_uniform_matches_reference() {
+                tests::test_uniform_matches_reference::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_uniform_range() {
+                tests::test_uniform_range::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_normal_moments() {
+                tests::test_normal_moments::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_bernoulli_matches_reference() {
+                tests::test_bernoulli_matches_reference::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_offsets_are_independent() {
+                tests::test_offsets_are_independent::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_strided_output() {
+                tests::test_strided_output::<TestRuntime, FloatT>(&Default::default())
+            }
+        }
+    };
+}
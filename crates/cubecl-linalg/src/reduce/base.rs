@@ -0,0 +1,181 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, Feature};
+
+use crate::tensor::TensorHandle;
+
+use super::{
+    naive, shared, subcube, tune::reduce_autotune, ArgMax, ArgMin, Max, Mean, Min, Prod,
+    ReduceInstruction, Sum,
+};
+
+/// The algorithm used to reduce an axis.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReduceStrategy {
+    /// Each unit reduces a whole axis.
+    Naive,
+    /// Each cube reduces an axis, merging the values of its units with a tree reduction in
+    /// shared memory.
+    SharedMemory,
+    /// Each cube reduces an axis, merging the values of its units with subcube operations.
+    ///
+    /// Requires [Feature::Subcube].
+    Subcube,
+    /// Select the fastest strategy with autotune.
+    #[default]
+    Autotune,
+}
+
+/// Reduce the given axis of the input into a new tensor, where the reduced axis has a size of 1.
+pub fn reduce<
+    R: Runtime,
+    EI: Numeric + CubeElement,
+    EO: Numeric + CubeElement,
+    I: ReduceInstruction<EI>,
+>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, EI>,
+    axis: usize,
+    strategy: ReduceStrategy,
+) -> TensorHandle<R, EO> {
+    let mut shape = input.shape.clone();
+    shape[axis] = 1;
+    let output = TensorHandle::empty(client, shape);
+
+    launch::<R, EI, EO, I>(client, input.as_ref(), output.as_ref(), axis, strategy);
+
+    output
+}
+
+/// Reduce the given axis of the input into the output, which must be contiguous and have the
+/// shape of the input with a size of 1 for the reduced axis.
+pub fn launch<
+    R: Runtime,
+    EI: Numeric + CubeElement,
+    EO: Numeric + CubeElement,
+    I: ReduceInstruction<EI>,
+>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    output: TensorHandleRef<'_, R>,
+    axis: usize,
+    strategy: ReduceStrategy,
+) {
+    assert!(
+        axis < input.shape.len(),
+        "Can't reduce axis {axis} of a tensor of rank {}",
+        input.shape.len()
+    );
+    assert!(
+        input
+            .shape
+            .iter()
+            .enumerate()
+            .all(|(i, size)| match i == axis {
+                true => output.shape[i] == 1,
+                false => output.shape[i] == *size,
+            }),
+        "The output shape {:?} doesn't match the input shape {:?} reduced along axis {axis}",
+        output.shape,
+        input.shape,
+    );
+
+    match strategy {
+        ReduceStrategy::Naive => naive::launch::<R, EI, EO, I>(client, input, output, axis),
+        ReduceStrategy::SharedMemory => shared::launch::<R, EI, EO, I>(client, input, output, axis),
+        ReduceStrategy::Subcube => {
+            assert!(
+                client.properties().feature_enabled(Feature::Subcube),
+                "The subcube reduce strategy requires the subcube feature"
+            );
+            subcube::launch::<R, EI, EO, I>(client, input, output, axis)
+        }
+        ReduceStrategy::Autotune => reduce_autotune::<R, EI, EO, I>(client, input, output, axis),
+    }
+}
+
+/// Sum the elements along the given axis.
+pub fn sum<R: Runtime, E: Numeric + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, E>,
+    axis: usize,
+    strategy: ReduceStrategy,
+) -> TensorHandle<R, E> {
+    reduce::<R, E, E, Sum>(client, input, axis, strategy)
+}
+
+/// Multiply the elements along the given axis.
+pub fn prod<R: Runtime, E: Numeric + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, E>,
+    axis: usize,
+    strategy: ReduceStrategy,
+) -> TensorHandle<R, E> {
+    reduce::<R, E, E, Prod>(client, input, axis, strategy)
+}
+
+/// Average the elements along the given axis.
+pub fn mean<R: Runtime, E: Numeric + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, E>,
+    axis: usize,
+    strategy: ReduceStrategy,
+) -> TensorHandle<R, E> {
+    reduce::<R, E, E, Mean>(client, input, axis, strategy)
+}
+
+/// The minimum of the elements along the given axis.
+pub fn min<R: Runtime, E: Numeric + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, E>,
+    axis: usize,
+    strategy: ReduceStrategy,
+) -> TensorHandle<R, E> {
+    reduce::<R, E, E, Min>(client, input, axis, strategy)
+}
+
+/// The maximum of the elements along the given axis.
+pub fn max<R: Runtime, E: Numeric + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, E>,
+    axis: usize,
+    strategy: ReduceStrategy,
+) -> TensorHandle<R, E> {
+    reduce::<R, E, E, Max>(client, input, axis, strategy)
+}
+
+/// The index of the minimum along the given axis.
+pub fn argmin<R: Runtime, E: Numeric + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, E>,
+    axis: usize,
+    strategy: ReduceStrategy,
+) -> TensorHandle<R, u32> {
+    reduce::<R, E, u32, ArgMin>(client, input, axis, strategy)
+}
+
+/// The index of the maximum along the given axis.
+pub fn argmax<R: Runtime, E: Numeric + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, E>,
+    axis: usize,
+    strategy: ReduceStrategy,
+) -> TensorHandle<R, u32> {
+    reduce::<R, E, u32, ArgMax>(client, input, axis, strategy)
+}
+
+/// The offset in the input of the first element reduced into the output at the given position.
+#[cube]
+pub(crate) fn reduce_offset<EI: Numeric, EO: Numeric>(
+    input: &Tensor<EI>,
+    output: &Tensor<EO>,
+    position: u32,
+) -> u32 {
+    let mut offset = 0;
+
+    for i in 0..input.rank() {
+        let coordinate = position / output.stride(i) % output.shape(i);
+        offset += coordinate * input.stride(i);
+    }
+
+    offset
+}
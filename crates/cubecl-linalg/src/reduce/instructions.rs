@@ -0,0 +1,221 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+use std::fmt::Debug;
+
+/// The index of an accumulator that didn't reduce any element yet.
+pub const NULL_INDEX: u32 = u32::MAX;
+
+/// An operation reducing the elements of an axis into a single value.
+///
+/// The accumulator is a value with the index along the reduced axis of the element it comes
+/// from, which is only meaningful for the reductions returning an index. An accumulator that
+/// didn't reduce any element has the [null index](NULL_INDEX).
+#[cube]
+pub trait ReduceInstruction<EI: Numeric>: Debug + Send + Sync + 'static {
+    /// The value of an empty reduction.
+    fn null_value() -> EI;
+
+    /// Merge the element at the given index into the accumulator.
+    fn accumulate(acc_value: EI, acc_index: u32, value: EI, index: u32) -> (EI, u32);
+
+    /// Merge the accumulators of all the units in the subcube.
+    fn accumulate_subcube(value: EI, index: u32) -> (EI, u32);
+
+    /// The output of the reduction, where `length` is the size of the reduced axis.
+    fn output<EO: Numeric>(value: EI, index: u32, length: u32) -> EO;
+}
+
+/// Sum of the elements.
+#[derive(Debug)]
+pub struct Sum;
+
+/// Product of the elements.
+#[derive(Debug)]
+pub struct Prod;
+
+/// Average of the elements.
+#[derive(Debug)]
+pub struct Mean;
+
+/// Minimum of the elements.
+#[derive(Debug)]
+pub struct Min;
+
+/// Maximum of the elements.
+#[derive(Debug)]
+pub struct Max;
+
+/// Index of the minimum, the first one when it occurs several times.
+#[derive(Debug)]
+pub struct ArgMin;
+
+/// Index of the maximum, the first one when it occurs several times.
+#[derive(Debug)]
+pub struct ArgMax;
+
+#[cube]
+impl<EI: Numeric> ReduceInstruction<EI> for Sum {
+    fn null_value() -> EI {
+        EI::from_int(0)
+    }
+
+    fn accumulate(acc_value: EI, acc_index: u32, value: EI, _index: u32) -> (EI, u32) {
+        (acc_value + value, acc_index)
+    }
+
+    fn accumulate_subcube(value: EI, index: u32) -> (EI, u32) {
+        (subcube_sum(value), index)
+    }
+
+    fn output<EO: Numeric>(value: EI, _index: u32, _length: u32) -> EO {
+        EO::cast_from(value)
+    }
+}
+
+#[cube]
+impl<EI: Numeric> ReduceInstruction<EI> for Prod {
+    fn null_value() -> EI {
+        EI::from_int(1)
+    }
+
+    fn accumulate(acc_value: EI, acc_index: u32, value: EI, _index: u32) -> (EI, u32) {
+        (acc_value * value, acc_index)
+    }
+
+    fn accumulate_subcube(value: EI, index: u32) -> (EI, u32) {
+        (subcube_prod(value), index)
+    }
+
+    fn output<EO: Numeric>(value: EI, _index: u32, _length: u32) -> EO {
+        EO::cast_from(value)
+    }
+}
+
+#[cube]
+impl<EI: Numeric> ReduceInstruction<EI> for Mean {
+    fn null_value() -> EI {
+        EI::from_int(0)
+    }
+
+    fn accumulate(acc_value: EI, acc_index: u32, value: EI, _index: u32) -> (EI, u32) {
+        (acc_value + value, acc_index)
+    }
+
+    fn accumulate_subcube(value: EI, index: u32) -> (EI, u32) {
+        (subcube_sum(value), index)
+    }
+
+    fn output<EO: Numeric>(value: EI, _index: u32, length: u32) -> EO {
+        EO::cast_from(value / EI::cast_from(length))
+    }
+}
+
+#[cube]
+impl<EI: Numeric> ReduceInstruction<EI> for Min {
+    fn null_value() -> EI {
+        EI::MAX
+    }
+
+    fn accumulate(acc_value: EI, acc_index: u32, value: EI, index: u32) -> (EI, u32) {
+        let replace = is_better(acc_index, index, value < acc_value);
+        (
+            select(replace, value, acc_value),
+            select(replace, index, acc_index),
+        )
+    }
+
+    fn accumulate_subcube(value: EI, index: u32) -> (EI, u32) {
+        let min = subcube_min(value);
+        let candidate = select(value == min, index, NULL_INDEX);
+        (min, subcube_min(candidate))
+    }
+
+    fn output<EO: Numeric>(value: EI, _index: u32, _length: u32) -> EO {
+        EO::cast_from(value)
+    }
+}
+
+#[cube]
+impl<EI: Numeric> ReduceInstruction<EI> for Max {
+    fn null_value() -> EI {
+        EI::MIN
+    }
+
+    fn accumulate(acc_value: EI, acc_index: u32, value: EI, index: u32) -> (EI, u32) {
+        let replace = is_better(acc_index, index, value > acc_value);
+        (
+            select(replace, value, acc_value),
+            select(replace, index, acc_index),
+        )
+    }
+
+    fn accumulate_subcube(value: EI, index: u32) -> (EI, u32) {
+        let max = subcube_max(value);
+        let candidate = select(value == max, index, NULL_INDEX);
+        (max, subcube_min(candidate))
+    }
+
+    fn output<EO: Numeric>(value: EI, _index: u32, _length: u32) -> EO {
+        EO::cast_from(value)
+    }
+}
+
+#[cube]
+impl<EI: Numeric> ReduceInstruction<EI> for ArgMin {
+    fn null_value() -> EI {
+        EI::MAX
+    }
+
+    fn accumulate(acc_value: EI, acc_index: u32, value: EI, index: u32) -> (EI, u32) {
+        let first = value == acc_value && index < acc_index;
+        let replace = is_better(acc_index, index, value < acc_value || first);
+        (
+            select(replace, value, acc_value),
+            select(replace, index, acc_index),
+        )
+    }
+
+    fn accumulate_subcube(value: EI, index: u32) -> (EI, u32) {
+        let min = subcube_min(value);
+        let candidate = select(value == min, index, NULL_INDEX);
+        (min, subcube_min(candidate))
+    }
+
+    fn output<EO: Numeric>(_value: EI, index: u32, _length: u32) -> EO {
+        EO::cast_from(index)
+    }
+}
+
+#[cube]
+impl<EI: Numeric> ReduceInstruction<EI> for ArgMax {
+    fn null_value() -> EI {
+        EI::MIN
+    }
+
+    fn accumulate(acc_value: EI, acc_index: u32, value: EI, index: u32) -> (EI, u32) {
+        let first = value == acc_value && index < acc_index;
+        let replace = is_better(acc_index, index, value > acc_value || first);
+        (
+            select(replace, value, acc_value),
+            select(replace, index, acc_index),
+        )
+    }
+
+    fn accumulate_subcube(value: EI, index: u32) -> (EI, u32) {
+        let max = subcube_max(value);
+        let candidate = select(value == max, index, NULL_INDEX);
+        (max, subcube_min(candidate))
+    }
+
+    fn output<EO: Numeric>(_value: EI, index: u32, _length: u32) -> EO {
+        EO::cast_from(index)
+    }
+}
+
+/// Whether an element replaces the accumulator of a comparison, given the result of the
+/// comparison. Null accumulators are always replaced and null elements never replace anything,
+/// so the comparison doesn't depend on the null value.
+#[cube]
+fn is_better(acc_index: u32, index: u32, comparison: bool) -> bool {
+    index != NULL_INDEX && (acc_index == NULL_INDEX || comparison)
+}
@@ -0,0 +1,14 @@
+mod base;
+mod instructions;
+pub(crate) mod naive;
+mod shared;
+mod subcube;
+mod tune;
+
+/// Tests for reduce kernels
+#[cfg(feature = "export_tests")]
+pub mod tests;
+
+pub use base::*;
+pub use instructions::*;
+pub use tune::ReduceAutotuneKey;
@@ -0,0 +1,98 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise};
+
+use super::{reduce_offset, ReduceInstruction, NULL_INDEX};
+
+/// Reduce an axis per unit, where each unit reads all the elements of its axis.
+#[cube(launch_unchecked)]
+pub(crate) fn reduce_naive_kernel<EI: Numeric, EO: Numeric, I: ReduceInstruction<EI>>(
+    input: &Tensor<EI>,
+    output: &mut Tensor<EO>,
+    axis: u32,
+) {
+    if ABSOLUTE_POS >= output.len() {
+        return;
+    }
+
+    let offset = reduce_offset::<EI, EO>(input, output, ABSOLUTE_POS);
+    let stride = input.stride(axis);
+    let length = input.shape(axis);
+
+    let mut value = I::null_value();
+    let mut index = NULL_INDEX;
+
+    for i in 0..length {
+        let (acc_value, acc_index) = I::accumulate(value, index, input[offset + i * stride], i);
+        value = acc_value;
+        index = acc_index;
+    }
+
+    output[ABSOLUTE_POS] = I::output::<EO>(value, index, length);
+}
+
+/// Launch the naive reduce kernel.
+pub(crate) fn launch<R: Runtime, EI: Numeric, EO: Numeric, I: ReduceInstruction<EI>>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    output: TensorHandleRef<'_, R>,
+    axis: usize,
+) {
+    let num_outputs: usize = output.shape.iter().product();
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(num_outputs, cube_dim);
+
+    unsafe {
+        reduce_naive_kernel::launch_unchecked::<EI, EO, I, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_tensor_arg(1),
+            output.as_tensor_arg(1),
+            ScalarArg::new(axis as u32),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reduce::{ArgMax, ArgMin, Mean, Min, Sum};
+    use cubecl_core::host::{HostTensor, HostUnit};
+
+    fn reduce_host<I: ReduceInstruction<f32>>(axis: u32) -> Vec<f32> {
+        let input = HostTensor::new(vec![1.0, 5.0, 3.0, 4.0, 2.0, 6.0], vec![2, 3]);
+        let mut shape = vec![2, 3];
+        shape[axis as usize] = 1;
+        let num_outputs = shape.iter().product::<u32>() as usize;
+        let mut output = HostTensor::new(vec![0.0; num_outputs], shape);
+
+        for position in 0..num_outputs as u32 {
+            reduce_naive_kernel::host::<f32, f32, I>(
+                HostUnit::default().with_unit_pos(position, 0, 0),
+                &input,
+                &mut output,
+                axis,
+            );
+        }
+
+        output.data
+    }
+
+    #[test]
+    fn sum_of_each_axis() {
+        assert_eq!(reduce_host::<Sum>(0), vec![5.0, 7.0, 9.0]);
+        assert_eq!(reduce_host::<Sum>(1), vec![9.0, 12.0]);
+    }
+
+    #[test]
+    fn mean_and_min() {
+        assert_eq!(reduce_host::<Mean>(1), vec![3.0, 4.0]);
+        assert_eq!(reduce_host::<Min>(0), vec![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn arg_reductions_return_indices() {
+        assert_eq!(reduce_host::<ArgMax>(1), vec![1.0, 2.0]);
+        assert_eq!(reduce_host::<ArgMin>(0), vec![0.0, 1.0, 0.0]);
+    }
+}
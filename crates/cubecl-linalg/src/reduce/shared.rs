@@ -0,0 +1,82 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise};
+
+use super::{reduce_offset, ReduceInstruction, NULL_INDEX};
+
+/// The number of units reducing an axis together, must be a power of two.
+pub(crate) const CUBE_SIZE: u32 = 256;
+
+/// Reduce an axis per cube, where each unit reduces a strided part of the axis before merging
+/// the values of all units with a tree reduction in shared memory.
+#[cube(launch_unchecked)]
+pub(crate) fn reduce_shared_kernel<EI: Numeric, EO: Numeric, I: ReduceInstruction<EI>>(
+    input: &Tensor<EI>,
+    output: &mut Tensor<EO>,
+    axis: u32,
+    #[comptime] cube_size: u32,
+) {
+    // Cubes past the last output must still reach the synchronizations, the offset wraps around
+    // so they only read valid elements.
+    let offset = reduce_offset::<EI, EO>(input, output, CUBE_POS);
+    let stride = input.stride(axis);
+    let length = input.shape(axis);
+
+    let mut value = I::null_value();
+    let mut index = NULL_INDEX;
+
+    for i in range_stepped(UNIT_POS, length, CUBE_DIM) {
+        let (acc_value, acc_index) = I::accumulate(value, index, input[offset + i * stride], i);
+        value = acc_value;
+        index = acc_index;
+    }
+
+    let mut values = SharedMemory::<EI>::new(cube_size);
+    let mut indices = SharedMemory::<u32>::new(cube_size);
+    values[UNIT_POS] = value;
+    indices[UNIT_POS] = index;
+    sync_units();
+
+    let mut num_active = CUBE_DIM / 2;
+    while num_active > 0 {
+        if UNIT_POS < num_active {
+            let other = UNIT_POS + num_active;
+            let (acc_value, acc_index) = I::accumulate(
+                values[UNIT_POS],
+                indices[UNIT_POS],
+                values[other],
+                indices[other],
+            );
+            values[UNIT_POS] = acc_value;
+            indices[UNIT_POS] = acc_index;
+        }
+        sync_units();
+        num_active /= 2;
+    }
+
+    if UNIT_POS == 0 && CUBE_POS < output.len() {
+        output[CUBE_POS] = I::output::<EO>(values[0], indices[0], length);
+    }
+}
+
+/// Launch the shared memory reduce kernel.
+pub(crate) fn launch<R: Runtime, EI: Numeric, EO: Numeric, I: ReduceInstruction<EI>>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    output: TensorHandleRef<'_, R>,
+    axis: usize,
+) {
+    let num_outputs: usize = output.shape.iter().product();
+    let cube_count = calculate_cube_count_elemwise(num_outputs, CubeDim::new(1, 1, 1));
+
+    unsafe {
+        reduce_shared_kernel::launch_unchecked::<EI, EO, I, R>(
+            client,
+            cube_count,
+            CubeDim::new(CUBE_SIZE, 1, 1),
+            input.as_tensor_arg(1),
+            output.as_tensor_arg(1),
+            ScalarArg::new(axis as u32),
+            CUBE_SIZE,
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise};
+
+use super::{reduce_offset, shared::CUBE_SIZE, ReduceInstruction, NULL_INDEX};
+
+/// Reduce an axis per cube, where each unit reduces a strided part of the axis before merging
+/// the values of each subcube with subcube operations. The values of the subcubes are then
+/// merged by the first unit.
+#[cube(launch_unchecked)]
+pub(crate) fn reduce_subcube_kernel<EI: Numeric, EO: Numeric, I: ReduceInstruction<EI>>(
+    input: &Tensor<EI>,
+    output: &mut Tensor<EO>,
+    axis: u32,
+    #[comptime] cube_size: u32,
+) {
+    // Cubes past the last output must still reach the synchronizations, the offset wraps around
+    // so they only read valid elements.
+    let offset = reduce_offset::<EI, EO>(input, output, CUBE_POS);
+    let stride = input.stride(axis);
+    let length = input.shape(axis);
+
+    let mut value = I::null_value();
+    let mut index = NULL_INDEX;
+
+    for i in range_stepped(UNIT_POS, length, CUBE_DIM) {
+        let (acc_value, acc_index) = I::accumulate(value, index, input[offset + i * stride], i);
+        value = acc_value;
+        index = acc_index;
+    }
+
+    let (subcube_value, subcube_index) = I::accumulate_subcube(value, index);
+
+    // There is at most one subcube per unit.
+    let mut values = SharedMemory::<EI>::new(cube_size);
+    let mut indices = SharedMemory::<u32>::new(cube_size);
+    if subcube_elect() {
+        let subcube = UNIT_POS / SUBCUBE_DIM;
+        values[subcube] = subcube_value;
+        indices[subcube] = subcube_index;
+    }
+    sync_units();
+
+    if UNIT_POS == 0 && CUBE_POS < output.len() {
+        let mut value = I::null_value();
+        let mut index = NULL_INDEX;
+
+        for subcube in 0..(CUBE_DIM - 1) / SUBCUBE_DIM + 1 {
+            let (acc_value, acc_index) =
+                I::accumulate(value, index, values[subcube], indices[subcube]);
+            value = acc_value;
+            index = acc_index;
+        }
+
+        output[CUBE_POS] = I::output::<EO>(value, index, length);
+    }
+}
+
+/// Launch the subcube reduce kernel.
+pub(crate) fn launch<R: Runtime, EI: Numeric, EO: Numeric, I: ReduceInstruction<EI>>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    output: TensorHandleRef<'_, R>,
+    axis: usize,
+) {
+    let num_outputs: usize = output.shape.iter().product();
+    let cube_count = calculate_cube_count_elemwise(num_outputs, CubeDim::new(1, 1, 1));
+
+    unsafe {
+        reduce_subcube_kernel::launch_unchecked::<EI, EO, I, R>(
+            client,
+            cube_count,
+            CubeDim::new(CUBE_SIZE, 1, 1),
+            input.as_tensor_arg(1),
+            output.as_tensor_arg(1),
+            ScalarArg::new(axis as u32),
+            CUBE_SIZE,
+        );
+    }
+}
@@ -0,0 +1,353 @@
+#![allow(missing_docs)]
+
+use cubecl_core::{prelude::*, CubeElement, Feature};
+
+use crate::{
+    matmul::tests::test_utils::{read_f32, read_tensor},
+    reduce::{self, ReduceStrategy},
+    tensor::TensorHandle,
+};
+
+type ReduceFn<R, EI, EO> = fn(
+    &ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel>,
+    TensorHandle<R, EI>,
+    usize,
+    ReduceStrategy,
+) -> TensorHandle<R, EO>;
+
+/// A reduction test case, where the input is a permutation of a contiguous tensor.
+struct ReduceTestCase {
+    shape: Vec<usize>,
+    strides: Vec<usize>,
+    axis: usize,
+}
+
+impl ReduceTestCase {
+    fn contiguous(shape: Vec<usize>, axis: usize) -> Self {
+        let mut strides = vec![1; shape.len()];
+        for i in (0..shape.len() - 1).rev() {
+            strides[i] = strides[i + 1] * shape[i + 1];
+        }
+
+        Self {
+            shape,
+            strides,
+            axis,
+        }
+    }
+
+    fn num_elements(&self) -> usize {
+        self.shape.iter().product()
+    }
+
+    fn output_shape(&self) -> Vec<usize> {
+        let mut shape = self.shape.clone();
+        shape[self.axis] = 1;
+        shape
+    }
+
+    /// The input values along the reduced axis of each output, in the order of the output.
+    fn axes(&self, data: &[f32]) -> Vec<Vec<f32>> {
+        let output_shape = self.output_shape();
+        let num_outputs: usize = output_shape.iter().product();
+
+        (0..num_outputs)
+            .map(|position| {
+                let mut remaining = position;
+                let mut offset = 0;
+                for i in (0..output_shape.len()).rev() {
+                    offset += remaining % output_shape[i] * self.strides[i];
+                    remaining /= output_shape[i];
+                }
+
+                (0..self.shape[self.axis])
+                    .map(|i| data[offset + i * self.strides[self.axis]])
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Values with many duplicates, to check the index returned by arg reductions.
+fn generate_data(num_elements: usize, low: f32, high: f32) -> Vec<f32> {
+    (0..num_elements)
+        .map(|i| low + (high - low) * ((i * 7919) % 97) as f32 / 96.0)
+        .collect()
+}
+
+fn should_skip<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    strategy: ReduceStrategy,
+) -> bool {
+    // Can't execute the test.
+    strategy == ReduceStrategy::Subcube && !client.properties().feature_enabled(Feature::Subcube)
+}
+
+fn create_input<R: Runtime, F: Float + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    case: &ReduceTestCase,
+    data: &[f32],
+) -> TensorHandle<R, F> {
+    let data: Vec<F> = data.iter().map(|value| F::new(*value)).collect();
+    let handle = client.create(F::as_bytes(&data));
+
+    TensorHandle::new(case.shape.clone(), case.strides.clone(), handle)
+}
+
+fn assert_equals_approx<R: Runtime, F: Float + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    output: TensorHandle<R, F>,
+    expected: &[f32],
+    epsilon: f32,
+) {
+    let actual = read_f32(client, &output);
+
+    assert_eq!(actual.len(), expected.len());
+    for (i, (actual, expected)) in actual.iter().zip(expected).enumerate() {
+        let allowed_error = epsilon * expected.abs().max(1.0);
+        assert!(
+            (actual - expected).abs() <= allowed_error,
+            "Values differ more than epsilon: index={i} actual={actual}, expected={expected}"
+        );
+    }
+}
+
+fn assert_indices<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    output: TensorHandle<R, u32>,
+    expected: &[u32],
+) {
+    assert_eq!(read_tensor(client, &output), expected);
+}
+
+fn test_float_reduce<R: Runtime, F: Float + CubeElement>(
+    device: &R::Device,
+    case: ReduceTestCase,
+    strategy: ReduceStrategy,
+    data: Vec<f32>,
+    launch: ReduceFn<R, F, F>,
+    reference: fn(&[f32]) -> f32,
+) {
+    let client = R::client(device);
+    if should_skip::<R>(&client, strategy) {
+        return;
+    }
+
+    let input = create_input::<R, F>(&client, &case, &data);
+    let output = launch(&client, input, case.axis, strategy);
+    let expected: Vec<f32> = case
+        .axes(&data)
+        .iter()
+        .map(|axis| reference(axis))
+        .collect();
+
+    assert_eq!(output.shape, case.output_shape());
+    assert_equals_approx::<R, F>(&client, output, &expected, 1e-3);
+}
+
+fn test_index_reduce<R: Runtime, F: Float + CubeElement>(
+    device: &R::Device,
+    case: ReduceTestCase,
+    strategy: ReduceStrategy,
+    launch: ReduceFn<R, F, u32>,
+    is_better: fn(f32, f32) -> bool,
+) {
+    let client = R::client(device);
+    if should_skip::<R>(&client, strategy) {
+        return;
+    }
+
+    let data = generate_data(case.num_elements(), -1.0, 1.0);
+    let input = create_input::<R, F>(&client, &case, &data);
+    let output = launch(&client, input, case.axis, strategy);
+    let expected: Vec<u32> = case
+        .axes(&data)
+        .iter()
+        .map(|axis| {
+            let mut best = 0;
+            for (i, value) in axis.iter().enumerate() {
+                if is_better(*value, axis[best]) {
+                    best = i;
+                }
+            }
+            best as u32
+        })
+        .collect();
+
+    assert_indices::<R>(&client, output, &expected);
+}
+
+pub fn test_sum_inner_axis<R: Runtime, F: Float + CubeElement>(
+    device: &R::Device,
+    strategy: ReduceStrategy,
+) {
+    let case = ReduceTestCase::contiguous(vec![4, 3, 300], 2);
+    let data = generate_data(case.num_elements(), -1.0, 1.0);
+
+    test_float_reduce::<R, F>(device, case, strategy, data, reduce::sum, |axis| {
+        axis.iter().sum()
+    });
+}
+
+pub fn test_sum_outer_axis<R: Runtime, F: Float + CubeElement>(
+    device: &R::Device,
+    strategy: ReduceStrategy,
+) {
+    let case = ReduceTestCase::contiguous(vec![300, 3, 4], 0);
+    let data = generate_data(case.num_elements(), -1.0, 1.0);
+
+    test_float_reduce::<R, F>(device, case, strategy, data, reduce::sum, |axis| {
+        axis.iter().sum()
+    });
+}
+
+pub fn test_sum_strided_input<R: Runtime, F: Float + CubeElement>(
+    device: &R::Device,
+    strategy: ReduceStrategy,
+) {
+    let case = ReduceTestCase {
+        shape: vec![3, 300, 4],
+        strides: vec![1, 12, 3],
+        axis: 1,
+    };
+    let data = generate_data(case.num_elements(), -1.0, 1.0);
+
+    test_float_reduce::<R, F>(device, case, strategy, data, reduce::sum, |axis| {
+        axis.iter().sum()
+    });
+}
+
+pub fn test_prod<R: Runtime, F: Float + CubeElement>(device: &R::Device, strategy: ReduceStrategy) {
+    let case = ReduceTestCase::contiguous(vec![6, 10], 1);
+    let data = generate_data(case.num_elements(), 0.5, 1.5);
+
+    test_float_reduce::<R, F>(device, case, strategy, data, reduce::prod, |axis| {
+        axis.iter().product()
+    });
+}
+
+pub fn test_mean<R: Runtime, F: Float + CubeElement>(device: &R::Device, strategy: ReduceStrategy) {
+    let case = ReduceTestCase::contiguous(vec![5, 300], 1);
+    let data = generate_data(case.num_elements(), -1.0, 1.0);
+
+    test_float_reduce::<R, F>(device, case, strategy, data, reduce::mean, |axis| {
+        axis.iter().sum::<f32>() / axis.len() as f32
+    });
+}
+
+pub fn test_min<R: Runtime, F: Float + CubeElement>(device: &R::Device, strategy: ReduceStrategy) {
+    let case = ReduceTestCase::contiguous(vec![300, 5], 0);
+    let data = generate_data(case.num_elements(), -1.0, 1.0);
+
+    test_float_reduce::<R, F>(device, case, strategy, data, reduce::min, |axis| {
+        axis.iter().copied().fold(f32::INFINITY, f32::min)
+    });
+}
+
+pub fn test_max<R: Runtime, F: Float + CubeElement>(device: &R::Device, strategy: ReduceStrategy) {
+    let case = ReduceTestCase::contiguous(vec![5, 300], 1);
+    let data = generate_data(case.num_elements(), -1.0, 1.0);
+
+    test_float_reduce::<R, F>(device, case, strategy, data, reduce::max, |axis| {
+        axis.iter().copied().fold(f32::NEG_INFINITY, f32::max)
+    });
+}
+
+pub fn test_argmin<R: Runtime, F: Float + CubeElement>(
+    device: &R::Device,
+    strategy: ReduceStrategy,
+) {
+    let case = ReduceTestCase::contiguous(vec![4, 300], 1);
+
+    test_index_reduce::<R, F>(device, case, strategy, reduce::argmin, |value, best| {
+        value < best
+    });
+}
+
+pub fn test_argmax<R: Runtime, F: Float + CubeElement>(
+    device: &R::Device,
+    strategy: ReduceStrategy,
+) {
+    let case = ReduceTestCase::contiguous(vec![300, 4], 0);
+
+    test_index_reduce::<R, F>(device, case, strategy, reduce::argmax, |value, best| {
+        value > best
+    });
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_reduce {
+    () => {
+        mod reduce {
+            $crate::testgen_reduce!(f32);
+        }
+    };
+    ($float:ident) => {
+        use super::*;
+        use cubecl_linalg::reduce::ReduceStrategy;
+
+        pub type FloatT = $float;
+
+        $crate::testgen_reduce_strategy!(naive, ReduceStrategy::Naive);
+        $crate::testgen_reduce_strategy!(shared_memory, ReduceStrategy::SharedMemory);
+        $crate::testgen_reduce_strategy!(subcube, ReduceStrategy::Subcube);
+        $crate::testgen_reduce_strategy!(autotune, ReduceStrategy::Autotune);
+    };
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_reduce_strategy {
+    ($name:ident, $strategy:expr) => {
+        mod $name {
+            use super::*;
+            use cubecl_linalg::reduce::tests;
+
+            #[test]
+            pub fn test_sum_inner_axis() {
+                tests::test_sum_inner_axis::<TestRuntime, FloatT>(&Default::default(), $strategy)
+            }
+
+            #[test]
+            pub fn test_sum_outer_axis() {
+                tests::test_sum_outer_axis::<TestRuntime, FloatT>(&Default::default(), $strategy)
+            }
+
+            #[test]
+            pub fn test_sum_strided_input() {
+                tests::test_sum_strided_input::<TestRuntime, FloatT>(&Default::default(), $strategy)
+            }
+
+            #[test]
+            pub fn test_prod() {
+                tests::test_prod::<TestRuntime, FloatT>(&Default::default(), $strategy)
+            }
+
+            #[test]
+            pub fn test_mean() {
+                tests::test_mean::<TestRuntime, FloatT>(&Default::default(), $strategy)
+            }
+
+            #[test]
+            pub fn test_min() {
+                tests::test_min::<TestRuntime, FloatT>(&Default::default(), $strategy)
+            }
+
+            #[test]
+            pub fn test_max() {
+                tests::test_max::<TestRuntime, FloatT>(&Default::default(), $strategy)
+            }
+
+            #[test]
+            pub fn test_argmin() {
+                tests::test_argmin::<TestRuntime, FloatT>(&Default::default(), $strategy)
+            }
+
+            #[test]
+            pub fn test_argmax() {
+                tests::test_argmax::<TestRuntime, FloatT>(&Default::default(), $strategy)
+            }
+        }
+    };
+}
@@ -0,0 +1,150 @@
+use cubecl::prelude::*;
+use cubecl_core::{
+    self as cubecl,
+    ir::Elem,
+    tune,
+    tune::{local_tuner, tune_with, LocalTuner},
+    AutotuneKey, Feature,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::tensor::TensorHandle;
+
+use super::{naive, shared, subcube, ReduceInstruction};
+
+/// Autotune key representative of reduce versions.
+#[derive(AutotuneKey, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ReduceAutotuneKey {
+    #[autotune(anchor)]
+    reduce_axis_length: usize,
+    #[autotune(anchor)]
+    reduce_axis_stride: usize,
+    #[autotune(anchor)]
+    num_outputs: usize,
+    elem_input: Elem,
+    elem_output: Elem,
+    instruction: String,
+}
+
+fn create_key<R: Runtime, EI: Numeric + CubeElement, EO: Numeric + CubeElement, I>(
+    _client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandle<R, EI>,
+    output: &TensorHandle<R, EO>,
+    axis: &usize,
+) -> ReduceAutotuneKey
+where
+    I: ReduceInstruction<EI>,
+{
+    // The tuner is shared by every element type and instruction, so they are part of the key.
+    ReduceAutotuneKey::new(
+        input.shape[*axis],
+        input.strides[*axis],
+        output.shape.iter().product(),
+        EI::as_elem(),
+        EO::as_elem(),
+        core::any::type_name::<I>().to_string(),
+    )
+}
+
+fn should_run<R: Runtime, EI: Numeric + CubeElement, EO: Numeric + CubeElement, I>(
+    op: &ReduceOps<R, EI, EO, I>,
+    _key: &ReduceAutotuneKey,
+    index: usize,
+) -> bool
+where
+    I: ReduceInstruction<EI>,
+{
+    match index {
+        // The subcube strategy can't run without subcube support.
+        2 => op.client.properties().feature_enabled(Feature::Subcube),
+        _ => true,
+    }
+}
+
+#[tune(
+    operations(reduce_naive, reduce_shared, reduce_subcube),
+    create_key = create_key::<R, EI, EO, I>,
+    should_run = should_run
+)]
+fn reduce_ops<R: Runtime, EI: Numeric + CubeElement, EO: Numeric + CubeElement, I>(
+    key: ReduceAutotuneKey,
+    client: ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, EI>,
+    output: TensorHandle<R, EO>,
+    axis: usize,
+) where
+    I: ReduceInstruction<EI>,
+{
+    let output = TensorHandle::empty(client, output.shape.clone());
+
+    tune_with!(client.clone(), input.clone(), output, *axis)
+}
+
+fn reduce_naive<R: Runtime, EI: Numeric + CubeElement, EO: Numeric + CubeElement, I>(
+    client: ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, EI>,
+    output: TensorHandle<R, EO>,
+    axis: usize,
+) where
+    I: ReduceInstruction<EI>,
+{
+    naive::launch::<R, EI, EO, I>(&client, input.as_ref(), output.as_ref(), axis);
+}
+
+fn reduce_shared<R: Runtime, EI: Numeric + CubeElement, EO: Numeric + CubeElement, I>(
+    client: ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, EI>,
+    output: TensorHandle<R, EO>,
+    axis: usize,
+) where
+    I: ReduceInstruction<EI>,
+{
+    shared::launch::<R, EI, EO, I>(&client, input.as_ref(), output.as_ref(), axis);
+}
+
+fn reduce_subcube<R: Runtime, EI: Numeric + CubeElement, EO: Numeric + CubeElement, I>(
+    client: ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, EI>,
+    output: TensorHandle<R, EO>,
+    axis: usize,
+) where
+    I: ReduceInstruction<EI>,
+{
+    subcube::launch::<R, EI, EO, I>(&client, input.as_ref(), output.as_ref(), axis);
+}
+
+/// Execute the fastest reduce strategy for the given input and output.
+pub(crate) fn reduce_autotune<R: Runtime, EI, EO, I>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    output: TensorHandleRef<'_, R>,
+    axis: usize,
+) where
+    EI: Numeric + CubeElement,
+    EO: Numeric + CubeElement,
+    I: ReduceInstruction<EI>,
+{
+    static TUNER: LocalTuner<ReduceAutotuneKey, String> = local_tuner!();
+
+    let input = TensorHandle::<R, EI>::new(
+        input.shape.to_vec(),
+        input.strides.to_vec(),
+        input.handle.clone(),
+    );
+    let output = TensorHandle::<R, EO>::new(
+        output.shape.to_vec(),
+        output.strides.to_vec(),
+        output.handle.clone(),
+    );
+
+    TUNER.execute(
+        &R::name().to_string(),
+        client,
+        Box::new(ReduceOps::<R, EI, EO, I>::new(
+            client.clone(),
+            input,
+            output,
+            axis,
+        )),
+    );
+}
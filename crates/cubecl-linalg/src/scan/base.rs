@@ -0,0 +1,174 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise, Feature};
+
+use crate::reduce::{Max, Min, Prod, Sum};
+use crate::tensor::TensorHandle;
+
+use super::kernel::{scan_kernel, scan_reduce_kernel};
+use super::ScanInstruction;
+
+/// The number of units scanning a tile together.
+const CUBE_SIZE: u32 = 256;
+/// The number of consecutive elements of a tile scanned by each unit.
+const ITEMS_PER_UNIT: u32 = 4;
+
+/// Whether the element at a position is part of the prefix written at that position.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScanKind {
+    /// The prefix includes the element, the first output being the first element.
+    #[default]
+    Inclusive,
+    /// The prefix stops before the element, the first output being the identity of the operator.
+    Exclusive,
+}
+
+/// Scan the given axis of the input into a new contiguous tensor of the same shape.
+///
+/// Requires [Feature::Subcube].
+pub fn scan<R: Runtime, E: Numeric + CubeElement, I: ScanInstruction<E>>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, E>,
+    axis: usize,
+    kind: ScanKind,
+) -> TensorHandle<R, E> {
+    let output = TensorHandle::empty(client, input.shape.clone());
+
+    launch::<R, E, I>(client, input.as_ref(), output.as_ref(), axis, kind);
+
+    output
+}
+
+/// Scan the given axis of the input into the output, which must have the shape of the input.
+///
+/// Each axis is split into tiles, which are first combined into partials. The partials are
+/// scanned recursively before each tile is scanned starting from the prefix of the previous
+/// tiles.
+///
+/// Requires [Feature::Subcube].
+pub fn launch<R: Runtime, E: Numeric + CubeElement, I: ScanInstruction<E>>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    output: TensorHandleRef<'_, R>,
+    axis: usize,
+    kind: ScanKind,
+) {
+    assert!(
+        axis < input.shape.len(),
+        "Can't scan axis {axis} of a tensor of rank {}",
+        input.shape.len()
+    );
+    assert_eq!(
+        input.shape, output.shape,
+        "The output shape doesn't match the input shape"
+    );
+    assert!(
+        client.properties().feature_enabled(Feature::Subcube),
+        "The scan requires the subcube feature"
+    );
+
+    let length = input.shape[axis];
+    let num_elements: usize = input.shape.iter().product();
+    if num_elements == 0 {
+        return;
+    }
+    let num_rows = num_elements / length;
+    let tile_size = (CUBE_SIZE * ITEMS_PER_UNIT) as usize;
+    let num_tiles = length.div_ceil(tile_size);
+
+    let cube_count = calculate_cube_count_elemwise(num_rows * num_tiles, CubeDim::new(1, 1, 1));
+    let cube_dim = CubeDim::new(CUBE_SIZE, 1, 1);
+    let axis_arg = ScalarArg::new(axis as u32);
+    let inclusive = kind == ScanKind::Inclusive;
+
+    if num_tiles == 1 {
+        // Safety: the partials are never read when there is a single tile.
+        unsafe {
+            scan_kernel::launch_unchecked::<E, I, R>(
+                client,
+                cube_count,
+                cube_dim,
+                input.as_tensor_arg(1),
+                input.as_tensor_arg(1),
+                output.as_tensor_arg(1),
+                axis_arg,
+                CUBE_SIZE,
+                ITEMS_PER_UNIT,
+                inclusive,
+                false,
+            );
+        }
+        return;
+    }
+
+    let partials = TensorHandle::<R, E>::empty(client, vec![num_rows, num_tiles]);
+    unsafe {
+        scan_reduce_kernel::launch_unchecked::<E, I, R>(
+            client,
+            cube_count.clone(),
+            cube_dim,
+            input.as_tensor_arg(1),
+            partials.as_ref().as_tensor_arg(1),
+            axis_arg,
+            CUBE_SIZE,
+            CUBE_SIZE * ITEMS_PER_UNIT,
+        );
+    }
+
+    let prefixes = scan::<R, E, I>(client, partials, 1, ScanKind::Exclusive);
+
+    unsafe {
+        scan_kernel::launch_unchecked::<E, I, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_tensor_arg(1),
+            prefixes.as_ref().as_tensor_arg(1),
+            output.as_tensor_arg(1),
+            ScalarArg::new(axis as u32),
+            CUBE_SIZE,
+            ITEMS_PER_UNIT,
+            inclusive,
+            true,
+        );
+    }
+}
+
+/// Cumulative sum of the elements along the given axis.
+pub fn cumsum<R: Runtime, E: Numeric + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, E>,
+    axis: usize,
+    kind: ScanKind,
+) -> TensorHandle<R, E> {
+    scan::<R, E, Sum>(client, input, axis, kind)
+}
+
+/// Cumulative product of the elements along the given axis.
+pub fn cumprod<R: Runtime, E: Numeric + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, E>,
+    axis: usize,
+    kind: ScanKind,
+) -> TensorHandle<R, E> {
+    scan::<R, E, Prod>(client, input, axis, kind)
+}
+
+/// Cumulative maximum of the elements along the given axis.
+pub fn cummax<R: Runtime, E: Numeric + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, E>,
+    axis: usize,
+    kind: ScanKind,
+) -> TensorHandle<R, E> {
+    scan::<R, E, Max>(client, input, axis, kind)
+}
+
+/// Cumulative minimum of the elements along the given axis.
+pub fn cummin<R: Runtime, E: Numeric + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, E>,
+    axis: usize,
+    kind: ScanKind,
+) -> TensorHandle<R, E> {
+    scan::<R, E, Min>(client, input, axis, kind)
+}
@@ -0,0 +1,81 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+use std::fmt::Debug;
+
+use crate::reduce::{Max, Min, Prod, Sum};
+
+/// An associative and commutative binary operator to scan with.
+///
+/// The operators of the [reductions](crate::reduce) that combine values are reused, so that
+/// scanning with [Sum] computes a cumulative sum.
+#[cube]
+pub trait ScanInstruction<E: Numeric>: Debug + Send + Sync + 'static {
+    /// The identity of the operator, which is the value of an empty prefix.
+    fn identity() -> E;
+
+    /// Combine two values.
+    fn combine(lhs: E, rhs: E) -> E;
+
+    /// Combine the values of all the units in the subcube.
+    fn combine_subcube(value: E) -> E;
+}
+
+#[cube]
+impl<E: Numeric> ScanInstruction<E> for Sum {
+    fn identity() -> E {
+        E::from_int(0)
+    }
+
+    fn combine(lhs: E, rhs: E) -> E {
+        lhs + rhs
+    }
+
+    fn combine_subcube(value: E) -> E {
+        subcube_sum(value)
+    }
+}
+
+#[cube]
+impl<E: Numeric> ScanInstruction<E> for Prod {
+    fn identity() -> E {
+        E::from_int(1)
+    }
+
+    fn combine(lhs: E, rhs: E) -> E {
+        lhs * rhs
+    }
+
+    fn combine_subcube(value: E) -> E {
+        subcube_prod(value)
+    }
+}
+
+#[cube]
+impl<E: Numeric> ScanInstruction<E> for Max {
+    fn identity() -> E {
+        E::MIN
+    }
+
+    fn combine(lhs: E, rhs: E) -> E {
+        select(rhs > lhs, rhs, lhs)
+    }
+
+    fn combine_subcube(value: E) -> E {
+        subcube_max(value)
+    }
+}
+
+#[cube]
+impl<E: Numeric> ScanInstruction<E> for Min {
+    fn identity() -> E {
+        E::MAX
+    }
+
+    fn combine(lhs: E, rhs: E) -> E {
+        select(rhs < lhs, rhs, lhs)
+    }
+
+    fn combine_subcube(value: E) -> E {
+        subcube_min(value)
+    }
+}
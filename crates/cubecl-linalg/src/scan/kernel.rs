@@ -0,0 +1,157 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+use super::{cube_scan, ScanInstruction};
+
+/// Combine each tile of the scanned axis into the partials, of shape `[num_rows, num_tiles]`.
+///
+/// The order in which the elements of a tile are combined doesn't matter, so they are read
+/// with the same strided pattern as the reductions.
+#[cube(launch_unchecked)]
+pub(crate) fn scan_reduce_kernel<E: Numeric, I: ScanInstruction<E>>(
+    input: &Tensor<E>,
+    partials: &mut Tensor<E>,
+    axis: u32,
+    #[comptime] cube_size: u32,
+    #[comptime] tile_size: u32,
+) {
+    let length = input.shape(axis);
+    let num_tiles = (length - 1) / tile_size + 1;
+    let row = CUBE_POS / num_tiles;
+    let tile = CUBE_POS % num_tiles;
+    // Cubes past the last tile must still reach the synchronizations.
+    let is_valid = row < num_rows(input, axis);
+
+    let offset = row_offset(input, row, axis);
+    let stride = input.stride(axis);
+    let tile_start = tile * tile_size;
+
+    let mut value = I::identity();
+    for i in range_stepped(UNIT_POS, tile_size, cube_size) {
+        let position = tile_start + i;
+        if is_valid && position < length {
+            value = I::combine(value, input[offset + position * stride]);
+        }
+    }
+
+    let (_, total) = cube_scan::<E, I>(value, cube_size, true);
+
+    if UNIT_POS == 0 && is_valid {
+        partials[row * partials.stride(0) + tile * partials.stride(1)] = total;
+    }
+}
+
+/// Scan each tile of the scanned axis, starting from the exclusive scan of the partials of the
+/// previous tiles when there is more than one tile.
+///
+/// The tile is staged in shared memory, so that it is read and written coalesced while each
+/// unit scans consecutive elements.
+#[cube(launch_unchecked)]
+pub(crate) fn scan_kernel<E: Numeric, I: ScanInstruction<E>>(
+    input: &Tensor<E>,
+    partials: &Tensor<E>,
+    output: &mut Tensor<E>,
+    axis: u32,
+    #[comptime] cube_size: u32,
+    #[comptime] items_per_unit: u32,
+    #[comptime] inclusive: bool,
+    #[comptime] has_partials: bool,
+) {
+    let tile_size = comptime!(cube_size * items_per_unit);
+    let length = input.shape(axis);
+    let num_tiles = (length - 1) / tile_size + 1;
+    let row = CUBE_POS / num_tiles;
+    let tile = CUBE_POS % num_tiles;
+    // Cubes past the last tile must still reach the synchronizations.
+    let is_valid = row < num_rows(input, axis);
+
+    let input_offset = row_offset(input, row, axis);
+    let output_offset = row_offset(output, row, axis);
+    let input_stride = input.stride(axis);
+    let output_stride = output.stride(axis);
+    let tile_start = tile * tile_size;
+
+    let mut values = SharedMemory::<E>::new(tile_size);
+    for i in 0..items_per_unit {
+        let index = i * cube_size + UNIT_POS;
+        let position = tile_start + index;
+        let mut value = I::identity();
+        if is_valid && position < length {
+            value = input[input_offset + position * input_stride];
+        }
+        values[index] = value;
+    }
+    sync_units();
+
+    let start = UNIT_POS * items_per_unit;
+    let mut unit_total = I::identity();
+    for i in 0..items_per_unit {
+        unit_total = I::combine(unit_total, values[start + i]);
+    }
+
+    let (unit_prefix, _) = cube_scan::<E, I>(unit_total, cube_size, false);
+
+    let mut prefix = unit_prefix;
+    if comptime!(has_partials) {
+        // Invalid cubes never write their output, so they can read any partial.
+        let index = select(
+            is_valid,
+            row * partials.stride(0) + tile * partials.stride(1),
+            0,
+        );
+        prefix = I::combine(partials[index], unit_prefix);
+    }
+
+    // Each unit only overwrites its own elements.
+    for i in 0..items_per_unit {
+        let value = values[start + i];
+        let next = I::combine(prefix, value);
+        if comptime!(inclusive) {
+            values[start + i] = next;
+        } else {
+            values[start + i] = prefix;
+        }
+        prefix = next;
+    }
+    sync_units();
+
+    for i in 0..items_per_unit {
+        let index = i * cube_size + UNIT_POS;
+        let position = tile_start + index;
+        if is_valid && position < length {
+            output[output_offset + position * output_stride] = values[index];
+        }
+    }
+}
+
+/// The number of independent scans, which is the number of elements for a size of 1 along
+/// the scanned axis.
+#[cube]
+fn num_rows<E: Numeric>(tensor: &Tensor<E>, axis: u32) -> u32 {
+    let mut num_rows = 1;
+    for i in 0..tensor.rank() {
+        if i != axis {
+            num_rows *= tensor.shape(i);
+        }
+    }
+    num_rows
+}
+
+/// The offset of the first element of the given row, the rows being ordered as a contiguous
+/// tensor with a size of 1 along the scanned axis.
+#[cube]
+fn row_offset<E: Numeric>(tensor: &Tensor<E>, row: u32, axis: u32) -> u32 {
+    let rank = tensor.rank();
+    let mut remaining = row;
+    let mut offset = 0;
+
+    for i in 0..rank {
+        let dim = rank - 1 - i;
+        if dim != axis {
+            offset += remaining % tensor.shape(dim) * tensor.stride(dim);
+            remaining /= tensor.shape(dim);
+        }
+    }
+
+    offset
+}
@@ -0,0 +1,12 @@
+mod base;
+mod instructions;
+mod kernel;
+mod primitives;
+
+/// Tests for scan kernels
+#[cfg(feature = "export_tests")]
+pub mod tests;
+
+pub use base::*;
+pub use instructions::*;
+pub use primitives::{cube_scan, subcube_scan};
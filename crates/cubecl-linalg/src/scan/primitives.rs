@@ -0,0 +1,71 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+use super::ScanInstruction;
+
+/// Scan the values of the units of a subcube, in the order of their position in the subcube.
+///
+/// The prefixes are built in log steps (Hillis-Steele): at each step, every unit combines its
+/// partial prefix with the one of the unit `offset` positions before it, read with a subcube
+/// shuffle, before doubling the offset. All units of the subcube must call it.
+#[cube]
+pub fn subcube_scan<E: Numeric, I: ScanInstruction<E>>(value: E, #[comptime] inclusive: bool) -> E {
+    let lane = UNIT_POS % SUBCUBE_DIM;
+    let mut prefix = value;
+    let mut offset = 1u32;
+
+    while offset < SUBCUBE_DIM {
+        // Units without a unit `offset` positions before them read a wrapped one and ignore it.
+        let other = subcube_shuffle(prefix, (lane + SUBCUBE_DIM - offset) % SUBCUBE_DIM);
+        if lane >= offset {
+            prefix = I::combine(other, prefix);
+        }
+        offset *= 2;
+    }
+
+    if comptime!(!inclusive) {
+        // The exclusive prefix of a unit is the inclusive prefix of the previous one.
+        let previous = subcube_shuffle(prefix, (lane + SUBCUBE_DIM - 1) % SUBCUBE_DIM);
+        prefix = select(lane > 0, previous, I::identity());
+    }
+
+    prefix
+}
+
+/// Scan the values of the units of a cube, in the order of their position, returning the prefix
+/// of the unit with the combination of all the values of the cube.
+///
+/// Each subcube is scanned before the totals of the previous subcubes are added to it through
+/// shared memory. All units of the cube must call it.
+#[cube]
+pub fn cube_scan<E: Numeric, I: ScanInstruction<E>>(
+    value: E,
+    #[comptime] cube_size: u32,
+    #[comptime] inclusive: bool,
+) -> (E, E) {
+    let subcube = UNIT_POS / SUBCUBE_DIM;
+    let lane = UNIT_POS % SUBCUBE_DIM;
+    let subcube_prefix = subcube_scan::<E, I>(value, inclusive);
+    let subcube_total = I::combine_subcube(value);
+
+    // There is at most one subcube per unit.
+    let mut totals = SharedMemory::<E>::new(cube_size);
+    if lane == 0 {
+        totals[subcube] = subcube_total;
+    }
+    sync_units();
+
+    let num_subcubes = (CUBE_DIM - 1) / SUBCUBE_DIM + 1;
+    let mut prefix = I::identity();
+    let mut total = I::identity();
+    for i in 0..num_subcubes {
+        if i == subcube {
+            prefix = total;
+        }
+        total = I::combine(total, totals[i]);
+    }
+    // The totals may be overwritten by the next scan.
+    sync_units();
+
+    (I::combine(prefix, subcube_prefix), total)
+}
@@ -0,0 +1,244 @@
+#![allow(missing_docs)]
+
+use cubecl_core::{prelude::*, CubeElement};
+
+use crate::{
+    matmul::tests::test_utils::{read_f32, should_skip},
+    scan::{self, ScanKind},
+    tensor::TensorHandle,
+};
+
+type ScanFn<R, E> = fn(
+    &ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel>,
+    TensorHandle<R, E>,
+    usize,
+    ScanKind,
+) -> TensorHandle<R, E>;
+
+/// A scan test case, where the input is a permutation of a contiguous tensor.
+struct ScanTestCase {
+    shape: Vec<usize>,
+    strides: Vec<usize>,
+    axis: usize,
+    kind: ScanKind,
+}
+
+impl ScanTestCase {
+    fn contiguous(shape: Vec<usize>, axis: usize, kind: ScanKind) -> Self {
+        let mut strides = vec![1; shape.len()];
+        for i in (0..shape.len() - 1).rev() {
+            strides[i] = strides[i + 1] * shape[i + 1];
+        }
+
+        Self {
+            shape,
+            strides,
+            axis,
+            kind,
+        }
+    }
+
+    fn num_elements(&self) -> usize {
+        self.shape.iter().product()
+    }
+
+    /// Scan the input with the given operator, returning a contiguous output.
+    fn reference(&self, data: &[f32], identity: f32, combine: fn(f32, f32) -> f32) -> Vec<f32> {
+        let rank = self.shape.len();
+        let mut output = vec![0.0; self.num_elements()];
+        let mut output_strides = vec![1; rank];
+        for i in (0..rank - 1).rev() {
+            output_strides[i] = output_strides[i + 1] * self.shape[i + 1];
+        }
+
+        let num_rows = self.num_elements() / self.shape[self.axis];
+        for row in 0..num_rows {
+            let mut remaining = row;
+            let mut input_offset = 0;
+            let mut output_offset = 0;
+            for i in (0..rank).rev().filter(|i| *i != self.axis) {
+                let coordinate = remaining % self.shape[i];
+                input_offset += coordinate * self.strides[i];
+                output_offset += coordinate * output_strides[i];
+                remaining /= self.shape[i];
+            }
+
+            let mut prefix = identity;
+            for i in 0..self.shape[self.axis] {
+                let value = data[input_offset + i * self.strides[self.axis]];
+                let next = combine(prefix, value);
+                output[output_offset + i * output_strides[self.axis]] = match self.kind {
+                    ScanKind::Inclusive => next,
+                    ScanKind::Exclusive => prefix,
+                };
+                prefix = next;
+            }
+        }
+
+        output
+    }
+}
+
+/// Small integers, so that every partial sum is exact whatever the order of the additions.
+fn generate_data(num_elements: usize, values: &[f32]) -> Vec<f32> {
+    (0..num_elements)
+        .map(|i| values[(i * 7919) % 97 % values.len()])
+        .collect()
+}
+
+fn test_scan<R: Runtime, F: Float + CubeElement>(
+    device: &R::Device,
+    case: ScanTestCase,
+    data: Vec<f32>,
+    launch: ScanFn<R, F>,
+    identity: f32,
+    combine: fn(f32, f32) -> f32,
+) {
+    let client = R::client(device);
+    if should_skip::<R, F>(&client) {
+        return;
+    }
+
+    let input: Vec<F> = data.iter().map(|value| F::new(*value)).collect();
+    let handle = client.create(F::as_bytes(&input));
+    let input = TensorHandle::new(case.shape.clone(), case.strides.clone(), handle);
+
+    let output = launch(&client, input, case.axis, case.kind);
+    let expected = case.reference(&data, identity, combine);
+
+    assert_eq!(output.shape, case.shape);
+
+    let actual = read_f32(&client, &output);
+    assert_eq!(actual.len(), expected.len());
+    for (i, (actual, expected)) in actual.iter().zip(&expected).enumerate() {
+        assert!(
+            (actual - expected).abs() <= 1e-3 * expected.abs().max(1.0),
+            "Values differ more than epsilon: index={i} actual={actual}, expected={expected}"
+        );
+    }
+}
+
+pub fn test_cumsum_single_tile<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let case = ScanTestCase::contiguous(vec![4, 3, 300], 2, ScanKind::Inclusive);
+    let data = generate_data(case.num_elements(), &[-1.0, 0.0, 1.0, 2.0]);
+
+    test_scan::<R, F>(device, case, data, scan::cumsum, 0.0, |a, b| a + b);
+}
+
+pub fn test_cumsum_multiple_tiles<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let case = ScanTestCase::contiguous(vec![3, 2500], 1, ScanKind::Inclusive);
+    let data = generate_data(case.num_elements(), &[-1.0, 0.0, 1.0]);
+
+    test_scan::<R, F>(device, case, data, scan::cumsum, 0.0, |a, b| a + b);
+}
+
+pub fn test_cumsum_exclusive_outer_axis<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let case = ScanTestCase::contiguous(vec![1500, 3, 2], 0, ScanKind::Exclusive);
+    let data = generate_data(case.num_elements(), &[-1.0, 0.0, 1.0]);
+
+    test_scan::<R, F>(device, case, data, scan::cumsum, 0.0, |a, b| a + b);
+}
+
+pub fn test_cumsum_strided_input<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let case = ScanTestCase {
+        shape: vec![3, 1100, 4],
+        strides: vec![1, 12, 3],
+        axis: 1,
+        kind: ScanKind::Inclusive,
+    };
+    let data = generate_data(case.num_elements(), &[-1.0, 0.0, 1.0]);
+
+    test_scan::<R, F>(device, case, data, scan::cumsum, 0.0, |a, b| a + b);
+}
+
+pub fn test_cumsum_recursive_partials<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    // More tiles than fit in a single tile, so the partials are scanned in several passes.
+    let case = ScanTestCase::contiguous(vec![1, 1_100_000], 1, ScanKind::Exclusive);
+    let data: Vec<f32> = (0..case.num_elements())
+        .map(|i| match i % 1009 {
+            0 => 1.0,
+            500 => -1.0,
+            _ => 0.0,
+        })
+        .collect();
+
+    test_scan::<R, F>(device, case, data, scan::cumsum, 0.0, |a, b| a + b);
+}
+
+pub fn test_cumprod<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let case = ScanTestCase::contiguous(vec![2, 1300], 1, ScanKind::Inclusive);
+    let data = generate_data(case.num_elements(), &[1.0, -1.0, 1.0]);
+
+    test_scan::<R, F>(device, case, data, scan::cumprod, 1.0, |a, b| a * b);
+}
+
+pub fn test_cummax<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let case = ScanTestCase::contiguous(vec![4, 3000], 1, ScanKind::Inclusive);
+    let data: Vec<f32> = (0..case.num_elements())
+        .map(|i| ((i * 7919) % 3001) as f32 / 3000.0 - 0.5)
+        .collect();
+
+    test_scan::<R, F>(device, case, data, scan::cummax, f32::MIN, f32::max);
+}
+
+pub fn test_cummin<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let case = ScanTestCase::contiguous(vec![2000, 3], 0, ScanKind::Inclusive);
+    let data: Vec<f32> = (0..case.num_elements())
+        .map(|i| ((i * 7919) % 3001) as f32 / 3000.0 - 0.5)
+        .collect();
+
+    test_scan::<R, F>(device, case, data, scan::cummin, f32::MAX, f32::min);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_scan {
+    () => {
+        mod scan {
+            use super::*;
+            use cubecl_linalg::scan::tests;
+
+            pub type FloatT = f32;
+
+            #[test]
+            pub fn test_cumsum_single_tile() {
+                tests::test_cumsum_single_tile::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_cumsum_multiple_tiles() {
+                tests::test_cumsum_multiple_tiles::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_cumsum_exclusive_outer_axis() {
+                tests::test_cumsum_exclusive_outer_axis::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_cumsum_strided_input() {
+                tests::test_cumsum_strided_input::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_cumsum_recursive_partials() {
+                tests::test_cumsum_recursive_partials::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_cumprod() {
+                tests::test_cumprod::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_cummax() {
+                tests::test_cummax::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_cummin() {
+                tests::test_cummin::<TestRuntime, FloatT>(&Default::default())
+            }
+        }
+    };
+}
@@ -0,0 +1,362 @@
+use std::{cmp::min, ops::Range};
+
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise};
+
+use crate::{
+    matmul,
+    tensor::{self, TensorHandle},
+};
+
+use super::kernel::{
+    cholesky_block_kernel, lower_triangle_kernel, lu_panel_kernel, subtract_block_kernel,
+    triangular_block_kernel,
+};
+
+/// The number of columns factored or solved by a single kernel, the rest of the matrix being
+/// updated with a matmul after each block.
+const BLOCK_SIZE: usize = 32;
+/// The number of units of the cubes factoring a block of each matrix.
+const CUBE_SIZE: u32 = 256;
+
+/// The triangle holding the values of a triangular matrix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Triangle {
+    /// The values are on and below the diagonal.
+    Lower,
+    /// The values are on and above the diagonal.
+    Upper,
+}
+
+/// The lower triangular factor `L` of each symmetric positive definite matrix of the input,
+/// such that `A = L * Lᵀ`, in a new contiguous tensor of the same shape.
+///
+/// The axes before the two last ones are batches, and only the lower triangle of each matrix is
+/// read. The columns are factored by blocks: each diagonal block is factored by a single cube,
+/// the panel below it is solved against it and the trailing matrix is updated with a matmul.
+/// Matrices that aren't positive definite give NaN values.
+pub fn cholesky<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+) -> TensorHandle<R, F> {
+    let size = check_square(input.shape);
+    let shape = input.shape.to_vec();
+    let matrices = batched::<R, F>(client, input);
+    let num_batches = matrices.shape[0];
+    if num_batches * size == 0 {
+        return reshape(matrices, &shape);
+    }
+
+    for start in (0..size).step_by(BLOCK_SIZE) {
+        let end = min(start + BLOCK_SIZE, size);
+
+        unsafe {
+            cholesky_block_kernel::launch_unchecked::<F, R>(
+                client,
+                calculate_cube_count_elemwise(num_batches, CubeDim::new(1, 1, 1)),
+                CubeDim::new(CUBE_SIZE, 1, 1),
+                matrices.as_arg(1),
+                ScalarArg::new(start as u32),
+                ScalarArg::new((end - start) as u32),
+            );
+        }
+
+        if end == size {
+            break;
+        }
+
+        // The panel `L21 = A21 * L11⁻ᵀ` is the solution of `L11 * L21ᵀ = A21ᵀ`.
+        solve_block::<R, F>(
+            client,
+            &matrices,
+            start..end,
+            end..size,
+            Triangle::Lower,
+            false,
+            true,
+        );
+
+        // A22 -= L21 * L21ᵀ
+        let panel = block::<R, F>(client, &matrices, end..size, start..end);
+        let panel_transposed = tensor::permute::<R, F>(panel.as_ref(), &[0, 2, 1]);
+        subtract_product::<R, F>(
+            client,
+            &matrices,
+            panel.as_ref(),
+            panel_transposed.as_ref(),
+            end,
+            end,
+        );
+    }
+
+    let num_elements = matrices.shape.iter().product();
+    let cube_dim = CubeDim::default();
+    unsafe {
+        lower_triangle_kernel::launch_unchecked::<F, R>(
+            client,
+            calculate_cube_count_elemwise(num_elements, cube_dim),
+            cube_dim,
+            matrices.as_arg(1),
+        );
+    }
+
+    reshape(matrices, &shape)
+}
+
+/// The LU factorization with partial pivoting `P * A = L * U` of each square matrix of the
+/// input, returned as a new contiguous tensor of the same shape and the pivots.
+///
+/// The factors are packed in a single matrix, the unit diagonal of `L` being implicit. The pivots
+/// have the shape of the input without its last axis, and give for each row `i` the row that
+/// was swapped with it once the rows before it were factored, so `P` applies these swaps in
+/// order. The columns are factored by panels in a single cube, and the trailing matrix is updated
+/// with a matmul after each panel.
+pub fn lu<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+) -> (TensorHandle<R, F>, TensorHandle<R, u32>) {
+    let size = check_square(input.shape);
+    let shape = input.shape.to_vec();
+    let pivots_shape = &shape[..shape.len() - 1];
+    let matrices = batched::<R, F>(client, input);
+    let num_batches = matrices.shape[0];
+    let pivots = TensorHandle::<R, u32>::empty(client, vec![num_batches, size]);
+    if num_batches * size == 0 {
+        return (reshape(matrices, &shape), reshape(pivots, pivots_shape));
+    }
+
+    for start in (0..size).step_by(BLOCK_SIZE) {
+        let end = min(start + BLOCK_SIZE, size);
+
+        unsafe {
+            lu_panel_kernel::launch_unchecked::<F, R>(
+                client,
+                calculate_cube_count_elemwise(num_batches, CubeDim::new(1, 1, 1)),
+                CubeDim::new(CUBE_SIZE, 1, 1),
+                matrices.as_arg(1),
+                pivots.as_arg(1),
+                ScalarArg::new(start as u32),
+                ScalarArg::new((end - start) as u32),
+                CUBE_SIZE,
+            );
+        }
+
+        if end == size {
+            break;
+        }
+
+        // U12 = L11⁻¹ * A12
+        solve_block::<R, F>(
+            client,
+            &matrices,
+            start..end,
+            end..size,
+            Triangle::Lower,
+            true,
+            false,
+        );
+
+        // A22 -= L21 * U12
+        let lower = block::<R, F>(client, &matrices, end..size, start..end);
+        let upper = block::<R, F>(client, &matrices, start..end, end..size);
+        subtract_product::<R, F>(client, &matrices, lower.as_ref(), upper.as_ref(), end, end);
+    }
+
+    (reshape(matrices, &shape), reshape(pivots, pivots_shape))
+}
+
+/// The solution `X` of `A * X = B` for each triangular matrix `A` of the input and matrix `B`
+/// of the right-hand side, in a new contiguous tensor of the shape of the right-hand side.
+///
+/// The axes before the two last ones are batches and must match. Only the given triangle of
+/// each matrix is read, and with `unit_diagonal` its diagonal is taken as ones. A transposed
+/// matrix can be solved by giving a [permuted](tensor::permute) view with the other triangle.
+/// The rows are solved by blocks, the remaining rows being updated with a matmul after each one.
+pub fn trsm<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    rhs: TensorHandleRef<'_, R>,
+    triangle: Triangle,
+    unit_diagonal: bool,
+) -> TensorHandle<R, F> {
+    let size = check_square(input.shape);
+    let rank = input.shape.len();
+    assert!(
+        rhs.shape.len() == rank && rhs.shape[..rank - 1] == input.shape[..rank - 1],
+        "The right-hand side of shape {:?} doesn't match the matrices of shape {:?}",
+        rhs.shape,
+        input.shape
+    );
+
+    let shape = rhs.shape.to_vec();
+    let num_cols = shape[rank - 1];
+    let num_elements: usize = shape.iter().product();
+    if num_elements == 0 {
+        return TensorHandle::empty(client, shape);
+    }
+
+    // The right-hand side is solved in place next to the matrices, so that a single tensor is
+    // bound by the kernels.
+    let total_cols = size + num_cols;
+    let num_batches = num_elements / (size * num_cols);
+    let combined = reshape(
+        tensor::concat::<R, F>(client, &[input, rhs], rank - 1),
+        &[num_batches, size, total_cols],
+    );
+
+    let starts = (0..size).step_by(BLOCK_SIZE);
+    let starts: Vec<usize> = match triangle {
+        Triangle::Lower => starts.collect(),
+        Triangle::Upper => starts.rev().collect(),
+    };
+
+    for start in starts {
+        let end = min(start + BLOCK_SIZE, size);
+        solve_block::<R, F>(
+            client,
+            &combined,
+            start..end,
+            size..total_cols,
+            triangle,
+            unit_diagonal,
+            false,
+        );
+
+        // The rows left to solve are below the block for a lower triangle, and above it for an
+        // upper triangle.
+        let remaining = match triangle {
+            Triangle::Lower => end..size,
+            Triangle::Upper => 0..start,
+        };
+        if !remaining.is_empty() {
+            let row_start = remaining.start;
+            let matrix = block::<R, F>(client, &combined, remaining, start..end);
+            let solved = block::<R, F>(client, &combined, start..end, size..total_cols);
+            subtract_product::<R, F>(
+                client,
+                &combined,
+                matrix.as_ref(),
+                solved.as_ref(),
+                row_start,
+                size,
+            );
+        }
+    }
+
+    let solution = block::<R, F>(client, &combined, 0..size, size..total_cols);
+    reshape(solution, &shape)
+}
+
+/// Solve the rows `rows` of the columns `cols` of the matrices, or of their transpose with
+/// `transposed`, against the diagonal block of the same rows.
+fn solve_block<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    matrices: &TensorHandle<R, F>,
+    rows: Range<usize>,
+    cols: Range<usize>,
+    triangle: Triangle,
+    unit_diagonal: bool,
+    transposed: bool,
+) {
+    let num_units = matrices.shape[0] * cols.len();
+    let cube_dim = CubeDim::default();
+
+    unsafe {
+        triangular_block_kernel::launch_unchecked::<F, R>(
+            client,
+            calculate_cube_count_elemwise(num_units, cube_dim),
+            cube_dim,
+            matrices.as_arg(1),
+            ScalarArg::new(rows.start as u32),
+            ScalarArg::new(rows.len() as u32),
+            ScalarArg::new(cols.start as u32),
+            ScalarArg::new(cols.len() as u32),
+            triangle == Triangle::Lower,
+            unit_diagonal,
+            transposed,
+        );
+    }
+}
+
+/// Subtract the product of the blocks from the block of the matrices whose first element is at
+/// `row_start` and `col_start`.
+fn subtract_product<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    matrices: &TensorHandle<R, F>,
+    lhs: TensorHandleRef<'_, R>,
+    rhs: TensorHandleRef<'_, R>,
+    row_start: usize,
+    col_start: usize,
+) {
+    let product =
+        TensorHandle::<R, F>::empty(client, vec![lhs.shape[0], lhs.shape[1], rhs.shape[2]]);
+    matmul::launch_ref::<R, F>(client, lhs, rhs, product.as_ref());
+
+    let num_elements = product.shape.iter().product();
+    let cube_dim = CubeDim::default();
+    unsafe {
+        subtract_block_kernel::launch_unchecked::<F, R>(
+            client,
+            calculate_cube_count_elemwise(num_elements, cube_dim),
+            cube_dim,
+            matrices.as_arg(1),
+            product.as_arg(1),
+            ScalarArg::new(row_start as u32),
+            ScalarArg::new(col_start as u32),
+        );
+    }
+}
+
+/// A contiguous copy of the given rows and columns of the matrices.
+///
+/// The blocks given to matmul are copied since its kernels pick their line sizes from the shapes
+/// only, which would misread rows whose stride isn't a multiple of the line size.
+fn block<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    matrices: &TensorHandle<R, F>,
+    rows: Range<usize>,
+    cols: Range<usize>,
+) -> TensorHandle<R, F> {
+    let view = tensor::slice::<R, F>(
+        client,
+        matrices.as_ref(),
+        &[0..matrices.shape[0], rows, cols],
+    );
+
+    tensor::into_contiguous::<R, F>(client, view.as_ref())
+}
+
+/// A contiguous copy of the tensor with its batch axes merged into one, of shape
+/// `[batches, rows, cols]`.
+fn batched<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    tensor: TensorHandleRef<'_, R>,
+) -> TensorHandle<R, F> {
+    let rank = tensor.shape.len();
+    let shape = [
+        tensor.shape[..rank - 2].iter().product(),
+        tensor.shape[rank - 2],
+        tensor.shape[rank - 1],
+    ];
+
+    reshape(tensor::into_contiguous::<R, F>(client, tensor), &shape)
+}
+
+/// The contiguous tensor with the given shape of the same number of elements.
+fn reshape<R: Runtime, E: CubePrimitive>(
+    tensor: TensorHandle<R, E>,
+    shape: &[usize],
+) -> TensorHandle<R, E> {
+    TensorHandle::new_contiguous(shape.to_vec(), tensor.handle)
+}
+
+/// The size of the square matrices of the tensor, which must be of rank 2 or more.
+fn check_square(shape: &[usize]) -> usize {
+    let rank = shape.len();
+    assert!(
+        rank >= 2 && shape[rank - 1] == shape[rank - 2],
+        "The input of shape {shape:?} isn't made of square matrices"
+    );
+
+    shape[rank - 1]
+}
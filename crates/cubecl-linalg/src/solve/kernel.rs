@@ -0,0 +1,274 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+/// The offset of the element at the given row and column of a matrix of the batch.
+#[cube]
+fn offset<E: CubePrimitive>(tensor: &Tensor<E>, batch: u32, row: u32, col: u32) -> u32 {
+    batch * tensor.stride(0) + row * tensor.stride(1) + col * tensor.stride(2)
+}
+
+/// Cholesky factorization of the diagonal block of `size` starting at `start`, in place, with one
+/// cube per matrix.
+///
+/// The lower triangle of the block is overwritten with its factor, one column at a time, and
+/// its upper triangle is left untouched.
+#[cube(launch_unchecked)]
+pub(crate) fn cholesky_block_kernel<F: Float>(matrices: &mut Tensor<F>, start: u32, size: u32) {
+    let batch = CUBE_POS;
+    if batch >= matrices.shape(0) {
+        return;
+    }
+
+    for k in 0..size {
+        let pivot = start + k;
+        if UNIT_POS == 0 {
+            let index = offset(matrices, batch, pivot, pivot);
+            matrices[index] = F::sqrt(matrices[index]);
+        }
+        sync_storage();
+
+        let diagonal = matrices[offset(matrices, batch, pivot, pivot)];
+        for row in range_stepped(pivot + 1 + UNIT_POS, start + size, CUBE_DIM) {
+            let index = offset(matrices, batch, row, pivot);
+            matrices[index] /= diagonal;
+        }
+        sync_storage();
+
+        let remaining = size - k - 1;
+        for i in range_stepped(UNIT_POS, remaining * remaining, CUBE_DIM) {
+            let row = pivot + 1 + i / remaining;
+            let col = pivot + 1 + i % remaining;
+            if col <= row {
+                let update = matrices[offset(matrices, batch, row, pivot)]
+                    * matrices[offset(matrices, batch, col, pivot)];
+                let index = offset(matrices, batch, row, col);
+                matrices[index] -= update;
+            }
+        }
+        sync_storage();
+    }
+}
+
+/// LU factorization with partial pivoting of the panel of the columns `start..start + size`,
+/// from the row `start` to the last one, in place, with one cube per matrix.
+///
+/// The row of largest magnitude of each column is found with a tree reduction in shared memory
+/// and swapped with the diagonal row over all the columns, so that the interchange also applies
+/// to the columns factored before and after the panel. The index of the swapped row is written
+/// to the pivots.
+#[cube(launch_unchecked)]
+pub(crate) fn lu_panel_kernel<F: Float>(
+    matrices: &mut Tensor<F>,
+    pivots: &mut Tensor<u32>,
+    start: u32,
+    size: u32,
+    #[comptime] cube_size: u32,
+) {
+    let batch = CUBE_POS;
+    if batch >= matrices.shape(0) {
+        return;
+    }
+
+    let num_rows = matrices.shape(1);
+    let num_cols = matrices.shape(2);
+    let mut magnitudes = SharedMemory::<F>::new(cube_size);
+    let mut rows = SharedMemory::<u32>::new(cube_size);
+
+    for k in 0..size {
+        let pivot = start + k;
+
+        let mut best = F::new(-1.0);
+        let mut best_row = pivot;
+        for row in range_stepped(pivot + UNIT_POS, num_rows, CUBE_DIM) {
+            let magnitude = F::abs(matrices[offset(matrices, batch, row, pivot)]);
+            if magnitude > best {
+                best = magnitude;
+                best_row = row;
+            }
+        }
+        magnitudes[UNIT_POS] = best;
+        rows[UNIT_POS] = best_row;
+        sync_units();
+
+        // Among rows of equal magnitude, the first one is kept.
+        let mut num_active = CUBE_DIM / 2;
+        while num_active > 0 {
+            if UNIT_POS < num_active {
+                let magnitude = magnitudes[UNIT_POS + num_active];
+                let row = rows[UNIT_POS + num_active];
+                let mut better = magnitude > magnitudes[UNIT_POS];
+                if magnitude == magnitudes[UNIT_POS] {
+                    better = row < rows[UNIT_POS];
+                }
+                if better {
+                    magnitudes[UNIT_POS] = magnitude;
+                    rows[UNIT_POS] = row;
+                }
+            }
+            sync_units();
+            num_active /= 2;
+        }
+
+        let pivot_row = rows[0];
+        sync_units();
+
+        if UNIT_POS == 0 {
+            pivots[batch * pivots.stride(0) + pivot * pivots.stride(1)] = pivot_row;
+        }
+        if pivot_row != pivot {
+            for col in range_stepped(UNIT_POS, num_cols, CUBE_DIM) {
+                let lhs = offset(matrices, batch, pivot, col);
+                let rhs = offset(matrices, batch, pivot_row, col);
+                let value = matrices[lhs];
+                matrices[lhs] = matrices[rhs];
+                matrices[rhs] = value;
+            }
+        }
+        sync_storage();
+
+        let diagonal = matrices[offset(matrices, batch, pivot, pivot)];
+        for row in range_stepped(pivot + 1 + UNIT_POS, num_rows, CUBE_DIM) {
+            let index = offset(matrices, batch, row, pivot);
+            matrices[index] /= diagonal;
+        }
+        sync_storage();
+
+        let remaining_rows = num_rows - pivot - 1;
+        let remaining_cols = start + size - pivot - 1;
+        for i in range_stepped(UNIT_POS, remaining_rows * remaining_cols, CUBE_DIM) {
+            let row = pivot + 1 + i / remaining_cols;
+            let col = pivot + 1 + i % remaining_cols;
+            let update = matrices[offset(matrices, batch, row, pivot)]
+                * matrices[offset(matrices, batch, pivot, col)];
+            let index = offset(matrices, batch, row, col);
+            matrices[index] -= update;
+        }
+        sync_storage();
+    }
+}
+
+/// The offset of the element of the right-hand side at the given row and column, which is
+/// stored transposed with `transposed`.
+#[cube]
+fn rhs_offset<F: Float>(
+    matrices: &Tensor<F>,
+    batch: u32,
+    row: u32,
+    col: u32,
+    #[comptime] transposed: bool,
+) -> u32 {
+    if comptime!(transposed) {
+        offset(matrices, batch, col, row)
+    } else {
+        offset(matrices, batch, row, col)
+    }
+}
+
+/// Solve the triangular system of the diagonal block of `size` starting at `start`, in place,
+/// with one unit per column of the right-hand side.
+///
+/// The right-hand side is made of the rows `start..start + size` of the columns
+/// `col_start..col_start + num_cols` of the same matrices, or of their transpose with
+/// `transposed`, and must not overlap the block. Only the `lower` or upper triangle of the
+/// block is read, and with `unit_diagonal` its diagonal is taken as ones.
+#[cube(launch_unchecked)]
+pub(crate) fn triangular_block_kernel<F: Float>(
+    matrices: &mut Tensor<F>,
+    start: u32,
+    size: u32,
+    col_start: u32,
+    num_cols: u32,
+    #[comptime] lower: bool,
+    #[comptime] unit_diagonal: bool,
+    #[comptime] transposed: bool,
+) {
+    let batch = ABSOLUTE_POS / num_cols;
+    let col = col_start + ABSOLUTE_POS % num_cols;
+    if batch >= matrices.shape(0) {
+        return;
+    }
+
+    for i in 0..size {
+        // The rows solved before the current one are above it for a lower triangle, and below
+        // it for an upper triangle.
+        let mut row = i;
+        let mut solved_start = 0;
+        let mut solved_end = i;
+        if comptime!(!lower) {
+            row = size - 1 - i;
+            solved_start = row + 1;
+            solved_end = size;
+        }
+
+        let index = rhs_offset(matrices, batch, start + row, col, transposed);
+        let mut value = matrices[index];
+        for j in solved_start..solved_end {
+            value -= matrices[offset(matrices, batch, start + row, start + j)]
+                * matrices[rhs_offset(matrices, batch, start + j, col, transposed)];
+        }
+        if comptime!(!unit_diagonal) {
+            value /= matrices[offset(matrices, batch, start + row, start + row)];
+        }
+        matrices[index] = value;
+    }
+}
+
+/// Subtract the contiguous product from the block of the matrices whose first element is at
+/// `row_start` and `col_start`.
+#[cube(launch_unchecked)]
+pub(crate) fn subtract_block_kernel<F: Float>(
+    matrices: &mut Tensor<F>,
+    product: &Tensor<F>,
+    row_start: u32,
+    col_start: u32,
+) {
+    if ABSOLUTE_POS >= product.len() {
+        return;
+    }
+
+    let batch = ABSOLUTE_POS / product.stride(0);
+    let row = ABSOLUTE_POS / product.stride(1) % product.shape(1);
+    let col = ABSOLUTE_POS % product.shape(2);
+    let index = offset(matrices, batch, row_start + row, col_start + col);
+    matrices[index] -= product[ABSOLUTE_POS];
+}
+
+/// Zero the elements above the diagonal of the contiguous matrices.
+#[cube(launch_unchecked)]
+pub(crate) fn lower_triangle_kernel<F: Float>(matrices: &mut Tensor<F>) {
+    if ABSOLUTE_POS >= matrices.len() {
+        return;
+    }
+
+    let row = ABSOLUTE_POS / matrices.stride(1) % matrices.shape(1);
+    let col = ABSOLUTE_POS % matrices.shape(2);
+    if col > row {
+        matrices[ABSOLUTE_POS] = F::new(0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cubecl_core::host::{HostTensor, HostUnit};
+
+    #[test]
+    fn triangular_block_solves_lower_system() {
+        // The lower triangle [[2, 0], [1, 4]] next to the right-hand side [4, 10].
+        let mut matrices = HostTensor::new(vec![2.0, 9.0, 4.0, 1.0, 4.0, 10.0], vec![1, 2, 3]);
+
+        triangular_block_kernel::host::<f32>(
+            HostUnit::default().with_unit_pos(0, 0, 0),
+            &mut matrices,
+            0,
+            2,
+            2,
+            1,
+            true,
+            false,
+            false,
+        );
+
+        assert_eq!(matrices.data, vec![2.0, 9.0, 2.0, 1.0, 4.0, 2.0]);
+    }
+}
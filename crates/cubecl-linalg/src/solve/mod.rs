@@ -0,0 +1,8 @@
+mod base;
+mod kernel;
+
+/// Tests for factorization and triangular solve kernels
+#[cfg(feature = "export_tests")]
+pub mod tests;
+
+pub use base::*;
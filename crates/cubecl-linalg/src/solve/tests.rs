@@ -0,0 +1,303 @@
+#![allow(missing_docs)]
+
+use cubecl_core::{prelude::*, CubeElement};
+
+use crate::{
+    matmul::tests::test_utils::{create_float_tensor, read_tensor},
+    solve::{self, Triangle},
+    tensor::{self, TensorHandle},
+};
+
+fn create<R: Runtime, F: Float + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    shape: Vec<usize>,
+    data: &[f64],
+) -> TensorHandle<R, F> {
+    let data: Vec<f32> = data.iter().map(|value| *value as f32).collect();
+    create_float_tensor(client, shape, &data)
+}
+
+fn read<R: Runtime, F: Float + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    tensor: &TensorHandle<R, F>,
+) -> Vec<f64> {
+    read_tensor(client, tensor)
+        .iter()
+        .map(|value| value.to_f64().unwrap())
+        .collect()
+}
+
+/// Uniform values in `[-1, 1]`, rounded to `f32` so that they are exactly uploaded.
+fn random(num_values: usize, seed: u64) -> Vec<f64> {
+    let mut state = seed;
+    (0..num_values)
+        .map(|_| {
+            state = (1664525 * state + 1013904223) % (1 << 32);
+            (state as f64 / (1u64 << 32) as f64 * 2.0 - 1.0) as f32 as f64
+        })
+        .collect()
+}
+
+/// The products `lhs * rhs` of the contiguous batches of matrices.
+fn matmul(lhs: &[f64], rhs: &[f64], m: usize, k: usize, n: usize) -> Vec<f64> {
+    let num_batches = lhs.len() / (m * k);
+    let mut out = vec![0.0; num_batches * m * n];
+
+    for batch in 0..num_batches {
+        let (lhs, rhs) = (&lhs[batch * m * k..], &rhs[batch * k * n..]);
+        for i in 0..m {
+            for j in 0..n {
+                out[batch * m * n + i * n + j] =
+                    (0..k).map(|p| lhs[i * k + p] * rhs[p * n + j]).sum();
+            }
+        }
+    }
+
+    out
+}
+
+/// The transposes of the contiguous batches of square matrices.
+fn transpose(matrices: &[f64], size: usize) -> Vec<f64> {
+    let mut out = vec![0.0; matrices.len()];
+    for (batch, matrix) in matrices.chunks(size * size).enumerate() {
+        for i in 0..size {
+            for j in 0..size {
+                out[batch * size * size + j * size + i] = matrix[i * size + j];
+            }
+        }
+    }
+    out
+}
+
+/// Random symmetric positive definite matrices `B * Bᵀ / size + I`, rounded to `f32`.
+fn random_spd(num_batches: usize, size: usize, seed: u64) -> Vec<f64> {
+    let b = random(num_batches * size * size, seed);
+    let mut matrices = matmul(&b, &transpose(&b, size), size, size, size);
+
+    for (i, value) in matrices.iter_mut().enumerate() {
+        *value /= size as f64;
+        if (i % (size * size)) % (size + 1) == 0 {
+            *value += 1.0;
+        }
+        *value = *value as f32 as f64;
+    }
+
+    matrices
+}
+
+/// The Cholesky factors of the contiguous batches of matrices, rounded to `f32`.
+fn host_cholesky(matrices: &[f64], size: usize) -> Vec<f64> {
+    let mut factors = vec![0.0; matrices.len()];
+
+    for (a, l) in matrices
+        .chunks(size * size)
+        .zip(factors.chunks_mut(size * size))
+    {
+        for j in 0..size {
+            let sum: f64 = (0..j).map(|p| l[j * size + p] * l[j * size + p]).sum();
+            l[j * size + j] = (a[j * size + j] - sum).sqrt();
+            for i in j + 1..size {
+                let sum: f64 = (0..j).map(|p| l[i * size + p] * l[j * size + p]).sum();
+                l[i * size + j] = (a[i * size + j] - sum) / l[j * size + j];
+            }
+        }
+    }
+
+    factors.iter().map(|value| *value as f32 as f64).collect()
+}
+
+fn assert_close(actual: &[f64], expected: &[f64], epsilon: f64) {
+    assert_eq!(actual.len(), expected.len());
+    for (i, (actual, expected)) in actual.iter().zip(expected).enumerate() {
+        assert!(
+            (actual - expected).abs() <= epsilon * expected.abs().max(1.0),
+            "Values differ at {i}: {actual} != {expected}"
+        );
+    }
+}
+
+pub fn test_cholesky<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    let size = 70;
+    let matrices = random_spd(6, size, 7);
+    let input = create::<R, F>(&client, vec![2, 3, size, size], &matrices);
+
+    let factors = solve::cholesky::<R, F>(&client, input.as_ref());
+    assert_eq!(factors.shape, vec![2, 3, size, size]);
+
+    let factors = read(&client, &factors);
+    for (i, value) in factors.iter().enumerate() {
+        let (row, col) = (i / size % size, i % size);
+        assert!(
+            col <= row || *value == 0.0,
+            "The factor isn't lower triangular"
+        );
+    }
+
+    let product = matmul(&factors, &transpose(&factors, size), size, size, size);
+    assert_close(&product, &matrices, 1e-3);
+}
+
+pub fn test_lu<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    let size = 75;
+    let num_batches = 3;
+    let matrices = random(num_batches * size * size, 11);
+    let input = create::<R, F>(&client, vec![num_batches, size, size], &matrices);
+
+    let (factors, pivots) = solve::lu::<R, F>(&client, input.as_ref());
+    assert_eq!(pivots.shape, vec![num_batches, size]);
+
+    let factors = read(&client, &factors);
+    let pivots = read_tensor(&client, &pivots);
+
+    let mut lower = vec![0.0; factors.len()];
+    let mut upper = vec![0.0; factors.len()];
+    for (i, value) in factors.iter().enumerate() {
+        let (row, col) = (i / size % size, i % size);
+        match row.cmp(&col) {
+            std::cmp::Ordering::Greater => lower[i] = *value,
+            std::cmp::Ordering::Equal => {
+                lower[i] = 1.0;
+                upper[i] = *value;
+            }
+            std::cmp::Ordering::Less => upper[i] = *value,
+        }
+    }
+
+    // Partial pivoting bounds the multipliers by one.
+    assert!(lower.iter().all(|value| value.abs() <= 1.0 + 1e-6));
+
+    let mut permuted = matrices.clone();
+    for batch in 0..num_batches {
+        let matrix = &mut permuted[batch * size * size..(batch + 1) * size * size];
+        for row in 0..size {
+            let pivot = pivots[batch * size + row] as usize;
+            assert!(
+                pivot >= row && pivot < size,
+                "Invalid pivot {pivot} of row {row}"
+            );
+            for col in 0..size {
+                matrix.swap(row * size + col, pivot * size + col);
+            }
+        }
+    }
+
+    let product = matmul(&lower, &upper, size, size, size);
+    assert_close(&product, &permuted, 1e-3);
+}
+
+fn test_trsm<R: Runtime, F: Float + CubeElement>(
+    device: &R::Device,
+    triangle: Triangle,
+    unit_diagonal: bool,
+) {
+    let client = R::client(device);
+    let (num_batches, size, num_cols) = (3, 70, 5);
+    let mut factors = host_cholesky(&random_spd(num_batches, size, 3), size);
+    if unit_diagonal {
+        // The diagonal must be ignored.
+        for matrix in factors.chunks_mut(size * size) {
+            for i in 0..size {
+                matrix[i * (size + 1)] = 3.0;
+            }
+        }
+    }
+    let rhs = random(num_batches * size * num_cols, 5);
+
+    let input = create::<R, F>(&client, vec![num_batches, size, size], &factors);
+    let input = match triangle {
+        Triangle::Lower => input,
+        Triangle::Upper => tensor::permute::<R, F>(input.as_ref(), &[0, 2, 1]),
+    };
+    let rhs_tensor = create::<R, F>(&client, vec![num_batches, size, num_cols], &rhs);
+
+    let solution = solve::trsm::<R, F>(
+        &client,
+        input.as_ref(),
+        rhs_tensor.as_ref(),
+        triangle,
+        unit_diagonal,
+    );
+    assert_eq!(solution.shape, vec![num_batches, size, num_cols]);
+
+    if unit_diagonal {
+        for matrix in factors.chunks_mut(size * size) {
+            for i in 0..size {
+                matrix[i * (size + 1)] = 1.0;
+            }
+        }
+    }
+    let matrices = match triangle {
+        Triangle::Lower => factors,
+        Triangle::Upper => transpose(&factors, size),
+    };
+
+    let product = matmul(&matrices, &read(&client, &solution), size, size, num_cols);
+    assert_close(&product, &rhs, 1e-3);
+}
+
+pub fn test_trsm_lower<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    test_trsm::<R, F>(device, Triangle::Lower, false);
+}
+
+pub fn test_trsm_upper_transposed<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    test_trsm::<R, F>(device, Triangle::Upper, false);
+}
+
+pub fn test_trsm_unit_diagonal<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    test_trsm::<R, F>(device, Triangle::Lower, true);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_solve {
+    () => {
+        mod solve {
+            $crate::testgen_solve!(f32);
+        }
+    };
+    ($float:ident) => {
+            use super::*;
+            use cubecl_linalg::solve::tests;
+
+            pub type FloatT = $float;
+
+            #[test]
+            pub fn test_cholesky() {
+                tests::test_cholesky::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_lu() {
+                tests::test_lu::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_trsm_lower() {
+                tests::test_trsm_lower::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_trsm_upper_transposed() {
+                tests::test_trsm_upper_transposed::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_trsm_unit_diagonal() {
+                tests::test_trsm_unit_diagonal::<TestRuntime, FloatT>(&Default::default())
+            }
+    };
+    ([$($float:ident),*]) => {
+        mod solve {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$float _ty>] {
+                    use super::*;
+
+                    $crate::testgen_solve!($float);
+                })*
+            }
+        }
+    };
+}
@@ -0,0 +1,329 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise, Feature};
+
+use crate::reduce::Sum;
+use crate::scan::{self, ScanKind};
+use crate::tensor::{into_contiguous, TensorHandle};
+
+use super::kernel::{
+    decode_kernel, encode_kernel, histogram_kernel, scatter_kernel, segment_indices_kernel,
+    take_kernel,
+};
+use super::RadixKey;
+
+/// The number of keys of a tile, each unit moving one key.
+const CUBE_SIZE: u32 = 256;
+/// The number of bits of the keys sorted by each pass.
+const RADIX_BITS: u32 = 4;
+/// The number of distinct digits of a pass.
+const RADIX: u32 = 1 << RADIX_BITS;
+
+/// The order of the sorted keys.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    /// The smallest key first.
+    #[default]
+    Ascending,
+    /// The largest key first.
+    Descending,
+}
+
+/// Sort all the keys of the tensor, read in row major order, into a new contiguous tensor of the
+/// same shape.
+///
+/// Requires [Feature::Subcube].
+pub fn sort<R: Runtime, K: RadixKey>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    keys: TensorHandle<R, K>,
+    order: SortOrder,
+) -> TensorHandle<R, K> {
+    radix_sort::<R, K, u32>(client, keys, None, false, order).0
+}
+
+/// Sort all the keys of the tensor along with their values, which must have the shape of the
+/// keys.
+///
+/// The sort is stable, so values with equal keys keep their order.
+///
+/// Requires [Feature::Subcube].
+pub fn sort_pairs<R: Runtime, K: RadixKey, V: Numeric + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    keys: TensorHandle<R, K>,
+    values: TensorHandle<R, V>,
+    order: SortOrder,
+) -> (TensorHandle<R, K>, TensorHandle<R, V>) {
+    let (keys, values) = radix_sort(client, keys, Some(values), false, order);
+    (keys, values.unwrap())
+}
+
+/// Sort the keys along the last axis, each row being sorted independently.
+///
+/// Requires [Feature::Subcube].
+pub fn segmented_sort<R: Runtime, K: RadixKey>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    keys: TensorHandle<R, K>,
+    order: SortOrder,
+) -> TensorHandle<R, K> {
+    radix_sort::<R, K, u32>(client, keys, None, true, order).0
+}
+
+/// Sort the keys along the last axis along with their values, each row being sorted
+/// independently.
+///
+/// Requires [Feature::Subcube].
+pub fn segmented_sort_pairs<R: Runtime, K: RadixKey, V: Numeric + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    keys: TensorHandle<R, K>,
+    values: TensorHandle<R, V>,
+    order: SortOrder,
+) -> (TensorHandle<R, K>, TensorHandle<R, V>) {
+    let (keys, values) = radix_sort(client, keys, Some(values), true, order);
+    (keys, values.unwrap())
+}
+
+/// The indices along the last axis that sort each row of the keys.
+///
+/// Equal keys keep their order, so the indices are those of a stable sort.
+///
+/// Requires [Feature::Subcube].
+pub fn argsort<R: Runtime, K: RadixKey>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    keys: TensorHandle<R, K>,
+    order: SortOrder,
+) -> TensorHandle<R, u32> {
+    let indices = segment_indices::<R>(client, &keys.shape);
+    segmented_sort_pairs(client, keys, indices, order).1
+}
+
+/// The first `k` keys of each row along the last axis once sorted, along with their indices.
+///
+/// [SortOrder::Descending] selects the largest keys, and [SortOrder::Ascending] the smallest.
+///
+/// Requires [Feature::Subcube].
+pub fn topk<R: Runtime, K: RadixKey>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, K>,
+    k: usize,
+    order: SortOrder,
+) -> (TensorHandle<R, K>, TensorHandle<R, u32>) {
+    let rank = input.shape.len();
+    let segment_length = input.shape[rank - 1];
+    assert!(
+        k <= segment_length,
+        "Can't take the top {k} of an axis of size {segment_length}"
+    );
+
+    let indices = segment_indices::<R>(client, &input.shape);
+    let (keys, indices) = segmented_sort_pairs(client, input, indices, order);
+
+    let mut shape = keys.shape.clone();
+    shape[rank - 1] = k;
+
+    (
+        take::<R, K>(client, &keys, shape.clone(), k),
+        take::<R, u32>(client, &indices, shape, k),
+    )
+}
+
+/// Sort the keys and the values with one pass per digit of [RADIX_BITS] bits, from the least
+/// significant to the most significant.
+///
+/// Each pass counts the digits of each tile, scans the counts into the offset of each tile for
+/// each digit and moves the keys to their offset plus their rank in their tile.
+fn radix_sort<R: Runtime, K: RadixKey, V: Numeric + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    keys: TensorHandle<R, K>,
+    values: Option<TensorHandle<R, V>>,
+    segmented: bool,
+    order: SortOrder,
+) -> (TensorHandle<R, K>, Option<TensorHandle<R, V>>) {
+    assert!(
+        client.properties().feature_enabled(Feature::Subcube),
+        "The radix sort requires the subcube feature"
+    );
+    if let Some(values) = &values {
+        assert_eq!(
+            keys.shape, values.shape,
+            "The shape of the values doesn't match the shape of the keys"
+        );
+    }
+
+    let shape = keys.shape.clone();
+    let num_elements: usize = shape.iter().product();
+    if num_elements == 0 {
+        return (keys, values);
+    }
+
+    let segment_length = match segmented {
+        true => shape[shape.len() - 1],
+        false => num_elements,
+    };
+    let num_segments = num_elements / segment_length;
+    let num_tiles = segment_length.div_ceil(CUBE_SIZE as usize);
+    let descending = order == SortOrder::Descending;
+
+    let keys = contiguous(client, keys);
+    let mut values = values.map(|values| contiguous(client, values));
+
+    let elemwise_dim = CubeDim::default();
+    let elemwise_count = calculate_cube_count_elemwise(num_elements, elemwise_dim);
+    let mut bits = TensorHandle::<R, u32>::empty(client, shape.clone());
+    unsafe {
+        encode_kernel::launch_unchecked::<K, R>(
+            client,
+            elemwise_count.clone(),
+            elemwise_dim,
+            keys.as_ref().as_tensor_arg(1),
+            bits.as_ref().as_tensor_arg(1),
+            descending,
+        );
+    }
+
+    let cube_count = calculate_cube_count_elemwise(num_segments * num_tiles, CubeDim::new(1, 1, 1));
+    let cube_dim = CubeDim::new(CUBE_SIZE, 1, 1);
+    let num_segments_arg = || ScalarArg::new(num_segments as u32);
+    let segment_length_arg = || ScalarArg::new(segment_length as u32);
+
+    for shift in (0..u32::BITS).step_by(RADIX_BITS as usize) {
+        let histograms =
+            TensorHandle::<R, u32>::empty(client, vec![num_segments, RADIX as usize * num_tiles]);
+        unsafe {
+            histogram_kernel::launch_unchecked::<R>(
+                client,
+                cube_count.clone(),
+                cube_dim,
+                bits.as_ref().as_tensor_arg(1),
+                histograms.as_ref().as_tensor_arg(1),
+                num_segments_arg(),
+                segment_length_arg(),
+                ScalarArg::new(shift),
+                CUBE_SIZE,
+                RADIX_BITS,
+            );
+        }
+
+        let offsets = scan::scan::<R, u32, Sum>(client, histograms, 1, ScanKind::Exclusive);
+
+        let bits_out = TensorHandle::<R, u32>::empty(client, shape.clone());
+        let values_out = values
+            .as_ref()
+            .map(|_| TensorHandle::<R, V>::empty(client, shape.clone()));
+
+        // The keys are used as placeholders for the values, which are never accessed when
+        // there are none.
+        let values_in_arg = match &values {
+            Some(values) => values.as_arg(1),
+            None => bits.as_arg(1),
+        };
+        let values_out_arg = match &values_out {
+            Some(values) => values.as_arg(1),
+            None => bits_out.as_arg(1),
+        };
+
+        unsafe {
+            scatter_kernel::launch_unchecked::<V, R>(
+                client,
+                cube_count.clone(),
+                cube_dim,
+                bits.as_ref().as_tensor_arg(1),
+                values_in_arg,
+                offsets.as_ref().as_tensor_arg(1),
+                bits_out.as_ref().as_tensor_arg(1),
+                values_out_arg,
+                num_segments_arg(),
+                segment_length_arg(),
+                ScalarArg::new(shift),
+                CUBE_SIZE,
+                RADIX_BITS,
+                values.is_some(),
+            );
+        }
+
+        bits = bits_out;
+        values = values_out;
+    }
+
+    let output = TensorHandle::<R, K>::empty(client, shape);
+    unsafe {
+        decode_kernel::launch_unchecked::<K, R>(
+            client,
+            elemwise_count,
+            elemwise_dim,
+            bits.as_ref().as_tensor_arg(1),
+            output.as_ref().as_tensor_arg(1),
+            descending,
+        );
+    }
+
+    (output, values)
+}
+
+/// A tensor of the given shape filled with the position of each element along the last axis.
+fn segment_indices<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    shape: &[usize],
+) -> TensorHandle<R, u32> {
+    let indices = TensorHandle::<R, u32>::empty(client, shape.to_vec());
+    let num_elements: usize = shape.iter().product();
+    if num_elements == 0 {
+        return indices;
+    }
+
+    let cube_dim = CubeDim::default();
+    unsafe {
+        segment_indices_kernel::launch_unchecked::<R>(
+            client,
+            calculate_cube_count_elemwise(num_elements, cube_dim),
+            cube_dim,
+            indices.as_ref().as_tensor_arg(1),
+            ScalarArg::new(shape[shape.len() - 1] as u32),
+        );
+    }
+
+    indices
+}
+
+/// Copy the first `k` elements along the last axis of a contiguous tensor.
+fn take<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandle<R, E>,
+    shape: Vec<usize>,
+    k: usize,
+) -> TensorHandle<R, E> {
+    let segment_length = input.shape[input.shape.len() - 1];
+    let output = TensorHandle::<R, E>::empty(client, shape);
+    let num_elements: usize = output.shape.iter().product();
+    if num_elements == 0 {
+        return output;
+    }
+
+    let cube_dim = CubeDim::default();
+    unsafe {
+        take_kernel::launch_unchecked::<E, R>(
+            client,
+            calculate_cube_count_elemwise(num_elements, cube_dim),
+            cube_dim,
+            input.as_ref().as_tensor_arg(1),
+            output.as_ref().as_tensor_arg(1),
+            ScalarArg::new(segment_length as u32),
+            ScalarArg::new(k as u32),
+        );
+    }
+
+    output
+}
+
+fn contiguous<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    tensor: TensorHandle<R, E>,
+) -> TensorHandle<R, E> {
+    let mut expected = 1;
+    for (size, stride) in tensor.shape.iter().zip(&tensor.strides).rev() {
+        if *size != 1 && *stride != expected {
+            return into_contiguous::<R, E>(client, tensor.as_ref());
+        }
+        expected *= size;
+    }
+
+    tensor
+}
@@ -0,0 +1,173 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+use crate::reduce::Sum;
+use crate::scan::cube_scan;
+
+use super::RadixKey;
+
+/// Map the keys to bits sorted in ascending order, flipping them to sort in descending order.
+#[cube(launch_unchecked)]
+pub(crate) fn encode_kernel<K: RadixKey>(
+    keys: &Tensor<K>,
+    bits: &mut Tensor<u32>,
+    #[comptime] descending: bool,
+) {
+    let flip = comptime!(if descending { 0xFFFF_FFFFu32 } else { 0u32 });
+
+    if ABSOLUTE_POS < bits.len() {
+        bits[ABSOLUTE_POS] = K::to_radix(keys[ABSOLUTE_POS]) ^ flip;
+    }
+}
+
+/// Recover the keys from the bits written by [encode_kernel].
+#[cube(launch_unchecked)]
+pub(crate) fn decode_kernel<K: RadixKey>(
+    bits: &Tensor<u32>,
+    keys: &mut Tensor<K>,
+    #[comptime] descending: bool,
+) {
+    let flip = comptime!(if descending { 0xFFFF_FFFFu32 } else { 0u32 });
+
+    if ABSOLUTE_POS < keys.len() {
+        keys[ABSOLUTE_POS] = K::from_radix(bits[ABSOLUTE_POS] ^ flip);
+    }
+}
+
+/// Count the digits of each tile of each segment into the histograms, of shape
+/// `[num_segments, radix * num_tiles]`.
+///
+/// The counts of a segment are ordered by digit then by tile, so that their exclusive scan is
+/// the position of the first key of each tile with each digit in the sorted segment.
+#[cube(launch_unchecked)]
+pub(crate) fn histogram_kernel(
+    bits: &Tensor<u32>,
+    histograms: &mut Tensor<u32>,
+    num_segments: u32,
+    segment_length: u32,
+    shift: u32,
+    #[comptime] cube_size: u32,
+    #[comptime] radix_bits: u32,
+) {
+    let radix = comptime!(1u32 << radix_bits);
+    let num_tiles = (segment_length - 1) / cube_size + 1;
+    let segment = CUBE_POS / num_tiles;
+    let tile = CUBE_POS % num_tiles;
+    let position = tile * cube_size + UNIT_POS;
+
+    let counts = SharedMemory::<AtomicU32>::new(radix);
+    if UNIT_POS < radix {
+        AtomicU32::store(&counts[UNIT_POS], 0);
+    }
+    sync_units();
+
+    if segment < num_segments && position < segment_length {
+        let digit = (bits[segment * segment_length + position] >> shift) & comptime!(radix - 1);
+        AtomicU32::add(&counts[digit], 1);
+    }
+    sync_units();
+
+    if segment < num_segments && UNIT_POS < radix {
+        let index = (segment * radix + UNIT_POS) * num_tiles + tile;
+        histograms[index] = AtomicU32::load(&counts[UNIT_POS]);
+    }
+}
+
+/// Move each key, and its value when there are values, to its position in the segment sorted by
+/// the digit at `shift`.
+///
+/// The keys of a tile are first sorted by digit in shared memory, splitting them on one bit at a
+/// time, which keeps keys with the same digit in their order. The rank of a key among the keys
+/// of the tile with the same digit is then added to the offset of the tile for that digit.
+#[allow(clippy::too_many_arguments)]
+#[cube(launch_unchecked)]
+pub(crate) fn scatter_kernel<V: Numeric>(
+    bits_in: &Tensor<u32>,
+    values_in: &Tensor<V>,
+    offsets: &Tensor<u32>,
+    bits_out: &mut Tensor<u32>,
+    values_out: &mut Tensor<V>,
+    num_segments: u32,
+    segment_length: u32,
+    shift: u32,
+    #[comptime] cube_size: u32,
+    #[comptime] radix_bits: u32,
+    #[comptime] has_values: bool,
+) {
+    let radix = comptime!(1u32 << radix_bits);
+    let num_tiles = (segment_length - 1) / cube_size + 1;
+    let segment = CUBE_POS / num_tiles;
+    let tile = CUBE_POS % num_tiles;
+    let segment_offset = segment * segment_length;
+    let tile_offset = tile * cube_size;
+    let position = tile_offset + UNIT_POS;
+
+    // Padding units get the last digit, so they are sorted after every key of the tile.
+    let mut key = 0xFFFF_FFFFu32;
+    if segment < num_segments && position < segment_length {
+        key = bits_in[segment_offset + position];
+    }
+
+    let mut digits = SharedMemory::<u32>::new(cube_size);
+    let mut sources = SharedMemory::<u32>::new(cube_size);
+    let mut digit = (key >> shift) & comptime!(radix - 1);
+    let mut source = UNIT_POS;
+
+    for bit in 0..radix_bits {
+        let flag = (digit >> bit) & 1;
+        let (ones_before, num_ones) = cube_scan::<u32, Sum>(flag, cube_size, false);
+        let destination = select(
+            flag == 1,
+            CUBE_DIM - num_ones + ones_before,
+            UNIT_POS - ones_before,
+        );
+
+        digits[destination] = digit;
+        sources[destination] = source;
+        sync_units();
+
+        digit = digits[UNIT_POS];
+        source = sources[UNIT_POS];
+        sync_units();
+    }
+
+    let mut digit_starts = SharedMemory::<u32>::new(radix);
+    let previous = digits[Max::max(UNIT_POS, 1) - 1];
+    if UNIT_POS == 0 || previous != digit {
+        digit_starts[digit] = UNIT_POS;
+    }
+    sync_units();
+
+    let source_position = tile_offset + source;
+    if segment < num_segments && source_position < segment_length {
+        let offset = offsets[(segment * radix + digit) * num_tiles + tile];
+        let destination = segment_offset + offset + UNIT_POS - digit_starts[digit];
+
+        bits_out[destination] = bits_in[segment_offset + source_position];
+        if comptime!(has_values) {
+            values_out[destination] = values_in[segment_offset + source_position];
+        }
+    }
+}
+
+/// Write the position of each element in its segment.
+#[cube(launch_unchecked)]
+pub(crate) fn segment_indices_kernel(indices: &mut Tensor<u32>, segment_length: u32) {
+    if ABSOLUTE_POS < indices.len() {
+        indices[ABSOLUTE_POS] = ABSOLUTE_POS % segment_length;
+    }
+}
+
+/// Copy the first `k` elements of each segment.
+#[cube(launch_unchecked)]
+pub(crate) fn take_kernel<E: Numeric>(
+    input: &Tensor<E>,
+    output: &mut Tensor<E>,
+    segment_length: u32,
+    k: u32,
+) {
+    if ABSOLUTE_POS < output.len() {
+        let segment = ABSOLUTE_POS / k;
+        output[ABSOLUTE_POS] = input[segment * segment_length + ABSOLUTE_POS % k];
+    }
+}
@@ -0,0 +1,52 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+/// A key that can be sorted by bits, through a mapping to unsigned integers that preserves its
+/// ordering.
+#[cube]
+pub trait RadixKey: Numeric + CubeElement {
+    /// Map the key to bits ordered like the key.
+    fn to_radix(key: Self) -> u32;
+
+    /// Recover the key from its bits.
+    fn from_radix(bits: u32) -> Self;
+}
+
+#[cube]
+impl RadixKey for u32 {
+    fn to_radix(key: u32) -> u32 {
+        key
+    }
+
+    fn from_radix(bits: u32) -> u32 {
+        bits
+    }
+}
+
+#[cube]
+impl RadixKey for i32 {
+    fn to_radix(key: i32) -> u32 {
+        // Flipping the sign bit moves the negative values before the positive ones.
+        u32::bitcast_from(key) ^ 0x8000_0000u32
+    }
+
+    fn from_radix(bits: u32) -> i32 {
+        i32::bitcast_from(bits ^ 0x8000_0000u32)
+    }
+}
+
+#[cube]
+impl RadixKey for f32 {
+    fn to_radix(key: f32) -> u32 {
+        // Negative values are ordered backward by their bits, so all their bits are flipped.
+        // NaNs with the sign bit unset are sorted after positive infinity.
+        let bits = u32::bitcast_from(key);
+        let mask = select(bits >= 0x8000_0000u32, 0xFFFF_FFFFu32, 0x8000_0000u32);
+        bits ^ mask
+    }
+
+    fn from_radix(bits: u32) -> f32 {
+        let mask = select(bits >= 0x8000_0000u32, 0x8000_0000u32, 0xFFFF_FFFFu32);
+        f32::bitcast_from(bits ^ mask)
+    }
+}
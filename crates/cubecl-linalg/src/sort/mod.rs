@@ -0,0 +1,10 @@
+mod base;
+mod kernel;
+mod key;
+
+/// Tests for sort kernels
+#[cfg(feature = "export_tests")]
+pub mod tests;
+
+pub use base::*;
+pub use key::*;
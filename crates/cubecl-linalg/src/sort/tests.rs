@@ -0,0 +1,264 @@
+#![allow(missing_docs)]
+
+use cubecl_core::prelude::*;
+
+use crate::{
+    matmul::tests::test_utils::{create_tensor, read_tensor, should_skip},
+    sort::{self, SortOrder},
+};
+
+/// Deterministic pseudo random numbers, spread over every bit.
+fn random_bits(num_elements: usize, seed: u32) -> Vec<u32> {
+    let mut state = seed;
+    (0..num_elements)
+        .map(|_| {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            state ^ (state >> 16)
+        })
+        .collect()
+}
+
+/// The indices of a stable sort of each row of the given length.
+fn reference_argsort<K: PartialOrd + Copy>(
+    keys: &[K],
+    segment_length: usize,
+    order: SortOrder,
+) -> Vec<u32> {
+    keys.chunks(segment_length)
+        .flat_map(|row| {
+            let mut indices: Vec<u32> = (0..row.len() as u32).collect();
+            indices.sort_by(|a, b| {
+                let (a, b) = (row[*a as usize], row[*b as usize]);
+                match order {
+                    SortOrder::Ascending => a.partial_cmp(&b).unwrap(),
+                    SortOrder::Descending => b.partial_cmp(&a).unwrap(),
+                }
+            });
+            indices
+        })
+        .collect()
+}
+
+pub fn test_sort_u32<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    if should_skip::<R, u32>(&client) {
+        return;
+    }
+
+    let keys = random_bits(5000, 1);
+    let output = sort::sort(
+        &client,
+        create_tensor::<R, u32>(&client, vec![50, 100], &keys),
+        SortOrder::Ascending,
+    );
+
+    let mut expected = keys.clone();
+    expected.sort();
+    assert_eq!(output.shape, vec![50, 100]);
+    assert_eq!(read_tensor(&client, &output), expected);
+}
+
+pub fn test_sort_i32_descending<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    if should_skip::<R, i32>(&client) {
+        return;
+    }
+
+    let keys: Vec<i32> = random_bits(3000, 2)
+        .into_iter()
+        .map(|bits| bits as i32)
+        .collect();
+    let output = sort::sort(
+        &client,
+        create_tensor::<R, i32>(&client, vec![3000], &keys),
+        SortOrder::Descending,
+    );
+
+    let mut expected = keys.clone();
+    expected.sort_by(|a, b| b.cmp(a));
+    assert_eq!(read_tensor(&client, &output), expected);
+}
+
+pub fn test_sort_f32<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    if should_skip::<R, f32>(&client) {
+        return;
+    }
+
+    let mut keys: Vec<f32> = random_bits(2000, 3)
+        .into_iter()
+        .map(|bits| (bits % 20_001) as f32 / 100.0 - 100.0)
+        .collect();
+    keys[7] = f32::INFINITY;
+    keys[11] = f32::NEG_INFINITY;
+    keys[13] = -0.5;
+    let output = sort::sort(
+        &client,
+        create_tensor::<R, f32>(&client, vec![2000], &keys),
+        SortOrder::Ascending,
+    );
+
+    let mut expected = keys.clone();
+    expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(read_tensor(&client, &output), expected);
+}
+
+pub fn test_sort_pairs_is_stable<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    if should_skip::<R, u32>(&client) {
+        return;
+    }
+
+    // Few distinct keys, so most values share their key with many others.
+    let keys: Vec<u32> = random_bits(4000, 4)
+        .into_iter()
+        .map(|bits| bits % 7)
+        .collect();
+    let values: Vec<u32> = (0..4000).collect();
+    let (sorted_keys, sorted_values) = sort::sort_pairs(
+        &client,
+        create_tensor::<R, u32>(&client, vec![4000], &keys),
+        create_tensor::<R, u32>(&client, vec![4000], &values),
+        SortOrder::Ascending,
+    );
+
+    let expected = reference_argsort(&keys, 4000, SortOrder::Ascending);
+    let expected_keys: Vec<u32> = expected.iter().map(|i| keys[*i as usize]).collect();
+    assert_eq!(read_tensor(&client, &sorted_keys), expected_keys);
+    assert_eq!(read_tensor(&client, &sorted_values), expected);
+}
+
+pub fn test_segmented_sort_pairs<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    if should_skip::<R, f32>(&client) {
+        return;
+    }
+
+    let shape = vec![2, 3, 700];
+    let keys: Vec<f32> = random_bits(4200, 5)
+        .into_iter()
+        .map(|bits| (bits % 1000) as f32 - 500.0)
+        .collect();
+    let values: Vec<f32> = (0..4200).map(|i| i as f32).collect();
+    let (sorted_keys, sorted_values) = sort::segmented_sort_pairs(
+        &client,
+        create_tensor::<R, f32>(&client, shape.clone(), &keys),
+        create_tensor::<R, f32>(&client, shape.clone(), &values),
+        SortOrder::Descending,
+    );
+
+    let indices = reference_argsort(&keys, 700, SortOrder::Descending);
+    let positions: Vec<usize> = indices
+        .iter()
+        .enumerate()
+        .map(|(i, index)| i / 700 * 700 + *index as usize)
+        .collect();
+    let expected_keys: Vec<f32> = positions.iter().map(|i| keys[*i]).collect();
+    let expected_values: Vec<f32> = positions.iter().map(|i| values[*i]).collect();
+
+    assert_eq!(sorted_keys.shape, shape);
+    assert_eq!(read_tensor(&client, &sorted_keys), expected_keys);
+    assert_eq!(read_tensor(&client, &sorted_values), expected_values);
+}
+
+pub fn test_argsort<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    if should_skip::<R, i32>(&client) {
+        return;
+    }
+
+    let keys: Vec<i32> = random_bits(3 * 300, 6)
+        .into_iter()
+        .map(|bits| (bits % 50) as i32 - 25)
+        .collect();
+    let output = sort::argsort(
+        &client,
+        create_tensor::<R, i32>(&client, vec![3, 300], &keys),
+        SortOrder::Descending,
+    );
+
+    assert_eq!(
+        read_tensor(&client, &output),
+        reference_argsort(&keys, 300, SortOrder::Descending)
+    );
+}
+
+pub fn test_topk<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    if should_skip::<R, f32>(&client) {
+        return;
+    }
+
+    let keys: Vec<f32> = random_bits(4 * 1000, 7)
+        .into_iter()
+        .map(|bits| (bits % 100_000) as f32 / 1000.0)
+        .collect();
+    let (values, indices) = sort::topk(
+        &client,
+        create_tensor::<R, f32>(&client, vec![4, 1000], &keys),
+        10,
+        SortOrder::Descending,
+    );
+
+    let sorted = reference_argsort(&keys, 1000, SortOrder::Descending);
+    let expected_indices: Vec<u32> = sorted
+        .chunks(1000)
+        .flat_map(|row| row[..10].to_vec())
+        .collect();
+    let expected_values: Vec<f32> = expected_indices
+        .iter()
+        .enumerate()
+        .map(|(i, index)| keys[i / 10 * 1000 + *index as usize])
+        .collect();
+
+    assert_eq!(values.shape, vec![4, 10]);
+    assert_eq!(indices.shape, vec![4, 10]);
+    assert_eq!(read_tensor(&client, &values), expected_values);
+    assert_eq!(read_tensor(&client, &indices), expected_indices);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_sort {
+    () => {
+        mod sort {
+            use super::*;
+            use cubecl_linalg::sort::tests;
+
+            #[test]
+            pub fn test_sort_u32() {
+                tests::test_sort_u32::<TestRuntime>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_sort_i32_descending() {
+                tests::test_sort_i32_descending::<TestRuntime>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_sort_f32() {
+                tests::test_sort_f32::<TestRuntime>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_sort_pairs_is_stable() {
+                tests::test_sort_pairs_is_stable::<TestRuntime>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_segmented_sort_pairs() {
+                tests::test_segmented_sort_pairs::<TestRuntime>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_argsort() {
+                tests::test_argsort::<TestRuntime>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_topk() {
+                tests::test_topk::<TestRuntime>(&Default::default())
+            }
+        }
+    };
+}
@@ -0,0 +1,179 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise, Feature};
+use cubecl_runtime::server::Handle;
+
+use crate::reduce::Sum;
+use crate::scan::{self, ScanKind};
+use crate::tensor::TensorHandle;
+
+use super::kernel::{count_nonzeros_kernel, dense_to_csr_kernel, spmm_kernel, spmv_kernel};
+use super::SparseTensorHandle;
+
+/// The number of units of each cube.
+const CUBE_SIZE: u32 = 256;
+/// The subcube size for which one subcube is launched per row, the kernels striding over the
+/// rows with any other size.
+const SUBCUBE_SIZE: u32 = 32;
+
+/// Multiply the sparse matrix by the vector, of shape `[num_cols]`, into a contiguous tensor of
+/// shape `[num_rows]`.
+///
+/// Requires [Feature::Subcube].
+pub fn spmv<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    sparse: &SparseTensorHandle<R, F>,
+    vector: TensorHandleRef<'_, R>,
+) -> TensorHandle<R, F> {
+    assert_eq!(
+        vector.shape,
+        &[sparse.shape[1]],
+        "The vector doesn't match the columns of the sparse matrix"
+    );
+    check_subcube::<R>(client);
+
+    let output = TensorHandle::empty(client, vec![sparse.shape[0]]);
+    if sparse.shape[0] == 0 {
+        return output;
+    }
+
+    let sparse = sparse.to_csr(client);
+    let (num_offsets, nnz) = ([sparse.shape[0] + 1], [sparse.nnz.max(1)]);
+
+    unsafe {
+        spmv_kernel::launch_unchecked::<F, R>(
+            client,
+            cube_count(sparse.shape[0]),
+            CubeDim::new(CUBE_SIZE, 1, 1),
+            buffer::<R, u32>(&sparse.rows, &num_offsets),
+            buffer::<R, u32>(&sparse.cols, &nnz),
+            buffer::<R, F>(&sparse.values, &nnz),
+            vector.as_tensor_arg(1),
+            output.as_ref().as_tensor_arg(1),
+        );
+    }
+
+    output
+}
+
+/// Multiply the sparse matrix by the dense matrix, of shape `[num_cols, n]`, into a contiguous
+/// tensor of shape `[num_rows, n]`.
+///
+/// Requires [Feature::Subcube].
+pub fn spmm<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    sparse: &SparseTensorHandle<R, F>,
+    dense: TensorHandleRef<'_, R>,
+) -> TensorHandle<R, F> {
+    assert!(
+        dense.shape.len() == 2 && dense.shape[0] == sparse.shape[1],
+        "Can't multiply a sparse matrix of shape {:?} by a dense matrix of shape {:?}",
+        sparse.shape,
+        dense.shape
+    );
+    check_subcube::<R>(client);
+
+    let output = TensorHandle::empty(client, vec![sparse.shape[0], dense.shape[1]]);
+    if sparse.shape[0] == 0 || dense.shape[1] == 0 {
+        return output;
+    }
+
+    let sparse = sparse.to_csr(client);
+    let (num_offsets, nnz) = ([sparse.shape[0] + 1], [sparse.nnz.max(1)]);
+
+    unsafe {
+        spmm_kernel::launch_unchecked::<F, R>(
+            client,
+            cube_count(sparse.shape[0]),
+            CubeDim::new(CUBE_SIZE, 1, 1),
+            buffer::<R, u32>(&sparse.rows, &num_offsets),
+            buffer::<R, u32>(&sparse.cols, &nnz),
+            buffer::<R, F>(&sparse.values, &nnz),
+            dense.as_tensor_arg(1),
+            output.as_ref().as_tensor_arg(1),
+        );
+    }
+
+    output
+}
+
+/// Convert the dense matrix to the [Csr](super::SparseFormat::Csr) format, keeping its nonzero
+/// elements.
+///
+/// The nonzero elements of each row are counted, then the exclusive scan of the counts gives
+/// the offsets of the rows, the last of which is read back to allocate the elements.
+///
+/// Requires [Feature::Subcube].
+pub fn dense_to_csr<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    dense: TensorHandleRef<'_, R>,
+) -> SparseTensorHandle<R, F> {
+    assert_eq!(
+        dense.shape.len(),
+        2,
+        "Only matrices can be converted to a sparse format, got shape {:?}",
+        dense.shape
+    );
+    check_subcube::<R>(client);
+
+    let shape = [dense.shape[0], dense.shape[1]];
+    // The last count is left at zero, so that its offset is the number of elements.
+    let counts = TensorHandle::<R, u32>::zeros(client, vec![shape[0] + 1]);
+    if shape[0] > 0 {
+        unsafe {
+            count_nonzeros_kernel::launch_unchecked::<F, R>(
+                client,
+                cube_count(shape[0]),
+                CubeDim::new(CUBE_SIZE, 1, 1),
+                dense.as_tensor_arg(1),
+                counts.as_ref().as_tensor_arg(1),
+            );
+        }
+    }
+
+    let offsets = scan::scan::<R, u32, Sum>(client, counts, 0, ScanKind::Exclusive);
+    let nnz = *u32::from_bytes(&client.read(offsets.handle.clone().binding()))
+        .last()
+        .unwrap() as usize;
+
+    // Buffers can't be empty.
+    let cols = client.empty(nnz.max(1) * u32::as_elem().size());
+    let values = client.empty(nnz.max(1) * F::as_elem().size());
+
+    if nnz > 0 {
+        let num_elements = [nnz];
+        unsafe {
+            dense_to_csr_kernel::launch_unchecked::<F, R>(
+                client,
+                cube_count(shape[0]),
+                CubeDim::new(CUBE_SIZE, 1, 1),
+                dense.as_tensor_arg(1),
+                offsets.as_ref().as_tensor_arg(1),
+                buffer::<R, u32>(&cols, &num_elements),
+                buffer::<R, F>(&values, &num_elements),
+            );
+        }
+    }
+
+    SparseTensorHandle::csr(shape, nnz, offsets.handle, cols, values)
+}
+
+fn check_subcube<R: Runtime>(client: &ComputeClient<R::Server, R::Channel>) {
+    assert!(
+        client.properties().feature_enabled(Feature::Subcube),
+        "Sparse kernels require the subcube feature"
+    );
+}
+
+/// One subcube per row when subcubes have [SUBCUBE_SIZE] units.
+fn cube_count(num_rows: usize) -> CubeCount {
+    let rows_per_cube = (CUBE_SIZE / SUBCUBE_SIZE) as usize;
+    calculate_cube_count_elemwise(num_rows.div_ceil(rows_per_cube), CubeDim::new(1, 1, 1))
+}
+
+/// A contiguous buffer of the given length as a tensor argument.
+fn buffer<'a, R: Runtime, E: CubePrimitive>(
+    handle: &'a Handle,
+    length: &'a [usize; 1],
+) -> TensorArg<'a, R> {
+    unsafe { TensorArg::from_raw_parts::<E>(handle, &[1], length, 1) }
+}
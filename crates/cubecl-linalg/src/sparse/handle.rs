@@ -0,0 +1,146 @@
+use std::marker::PhantomData;
+
+use cubecl_core::prelude::*;
+use cubecl_runtime::server::Handle;
+
+use crate::histogram;
+use crate::reduce::Sum;
+use crate::scan::{self, ScanKind};
+use crate::tensor::TensorHandle;
+
+/// How the coordinates of the nonzero elements of a [SparseTensorHandle] are stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SparseFormat {
+    /// Compressed sparse rows: the nonzero elements are sorted by row, and the rows buffer holds
+    /// `num_rows + 1` offsets, the elements of row `i` being those of `offsets[i]..offsets[i + 1]`.
+    Csr,
+    /// Coordinates: the rows buffer holds the row of each nonzero element, sorted by row.
+    Coo,
+}
+
+/// Sparse matrix representation, made of three contiguous [server handles](Handle) of `u32`
+/// rows, `u32` columns and `E` values of its nonzero elements.
+pub struct SparseTensorHandle<R, E>
+where
+    R: Runtime,
+    E: CubePrimitive,
+{
+    /// How the rows of the nonzero elements are stored.
+    pub format: SparseFormat,
+    /// The row offsets with [Csr](SparseFormat::Csr), or the row of each nonzero element with
+    /// [Coo](SparseFormat::Coo).
+    pub rows: Handle,
+    /// The column of each nonzero element.
+    pub cols: Handle,
+    /// The value of each nonzero element.
+    pub values: Handle,
+    /// The number of rows and columns of the matrix.
+    pub shape: [usize; 2],
+    /// The number of nonzero elements.
+    pub nnz: usize,
+    elem: PhantomData<E>,
+    runtime: PhantomData<R>,
+}
+
+impl<R, E> core::fmt::Debug for SparseTensorHandle<R, E>
+where
+    R: Runtime,
+    E: CubePrimitive,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "SparseTensor {{ format: {:?}, shape: {:?}, nnz: {}, runtime: {}, dtype: {}}}",
+            self.format,
+            self.shape,
+            self.nnz,
+            R::name(),
+            core::any::type_name::<E>(),
+        ))
+    }
+}
+
+impl<R, E> Clone for SparseTensorHandle<R, E>
+where
+    R: Runtime,
+    E: CubePrimitive,
+{
+    fn clone(&self) -> Self {
+        Self {
+            format: self.format,
+            rows: self.rows.clone(),
+            cols: self.cols.clone(),
+            values: self.values.clone(),
+            shape: self.shape,
+            nnz: self.nnz,
+            elem: PhantomData,
+            runtime: PhantomData,
+        }
+    }
+}
+
+impl<R, E> SparseTensorHandle<R, E>
+where
+    R: Runtime,
+    E: CubePrimitive,
+{
+    /// Create a new sparse matrix in the [Csr](SparseFormat::Csr) format.
+    pub fn csr(
+        shape: [usize; 2],
+        nnz: usize,
+        row_offsets: Handle,
+        cols: Handle,
+        values: Handle,
+    ) -> Self {
+        Self::new(SparseFormat::Csr, shape, nnz, row_offsets, cols, values)
+    }
+
+    /// Create a new sparse matrix in the [Coo](SparseFormat::Coo) format.
+    pub fn coo(shape: [usize; 2], nnz: usize, rows: Handle, cols: Handle, values: Handle) -> Self {
+        Self::new(SparseFormat::Coo, shape, nnz, rows, cols, values)
+    }
+
+    fn new(
+        format: SparseFormat,
+        shape: [usize; 2],
+        nnz: usize,
+        rows: Handle,
+        cols: Handle,
+        values: Handle,
+    ) -> Self {
+        Self {
+            format,
+            rows,
+            cols,
+            values,
+            shape,
+            nnz,
+            elem: PhantomData,
+            runtime: PhantomData,
+        }
+    }
+
+    /// The same matrix in the [Csr](SparseFormat::Csr) format, sharing the columns and values.
+    ///
+    /// The offsets of the rows are the exclusive scan of the number of elements of each row.
+    ///
+    /// Requires [Feature::Subcube](cubecl_core::Feature::Subcube) with
+    /// [Coo](SparseFormat::Coo).
+    pub fn to_csr(&self, client: &ComputeClient<R::Server, R::Channel>) -> Self {
+        if self.format == SparseFormat::Csr {
+            return self.clone();
+        }
+
+        let rows = TensorHandle::<R, u32>::new_contiguous(vec![self.nnz], self.rows.clone());
+        // The last bin is always empty, so that its offset is the number of elements.
+        let counts = histogram::bincount::<R, u32>(client, rows.as_ref(), self.shape[0] + 1);
+        let offsets = scan::scan::<R, u32, Sum>(client, counts, 0, ScanKind::Exclusive);
+
+        Self::csr(
+            self.shape,
+            self.nnz,
+            offsets.handle,
+            self.cols.clone(),
+            self.values.clone(),
+        )
+    }
+}
@@ -0,0 +1,171 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+use crate::reduce::Sum;
+use crate::scan::subcube_scan;
+
+/// The first row of the subcube of the unit, with one row per subcube of each cube.
+#[cube]
+fn first_row() -> u32 {
+    CUBE_POS * (CUBE_DIM / SUBCUBE_DIM) + UNIT_POS / SUBCUBE_DIM
+}
+
+/// The step between the rows of a subcube, so that the subcubes stride over all the rows
+/// whatever their size.
+#[cube]
+fn row_step() -> u32 {
+    CUBE_COUNT * (CUBE_DIM / SUBCUBE_DIM)
+}
+
+/// Multiply the CSR matrix by the vector, with one row per subcube.
+///
+/// The units of a subcube stride over the elements of the row, whose products are summed over
+/// the subcube.
+#[cube(launch_unchecked)]
+pub(crate) fn spmv_kernel<F: Float>(
+    row_offsets: &Tensor<u32>,
+    cols: &Tensor<u32>,
+    values: &Tensor<F>,
+    vector: &Tensor<F>,
+    output: &mut Tensor<F>,
+) {
+    let lane = UNIT_POS % SUBCUBE_DIM;
+
+    for row in range_stepped(first_row(), output.len(), row_step()) {
+        let mut sum = F::new(0.0);
+        for i in range_stepped(row_offsets[row] + lane, row_offsets[row + 1], SUBCUBE_DIM) {
+            sum += values[i] * vector[cols[i] * vector.stride(0)];
+        }
+
+        let sum = subcube_sum(sum);
+        if lane == 0 {
+            output[row] = sum;
+        }
+    }
+}
+
+/// Multiply the CSR matrix by the dense matrix into the contiguous output, with one row per
+/// subcube.
+///
+/// The units of a subcube stride over the columns of the output, so that they read consecutive
+/// elements of each row of the dense matrix.
+#[cube(launch_unchecked)]
+pub(crate) fn spmm_kernel<F: Float>(
+    row_offsets: &Tensor<u32>,
+    cols: &Tensor<u32>,
+    values: &Tensor<F>,
+    dense: &Tensor<F>,
+    output: &mut Tensor<F>,
+) {
+    let lane = UNIT_POS % SUBCUBE_DIM;
+    let num_cols = output.shape(1);
+
+    for row in range_stepped(first_row(), output.shape(0), row_step()) {
+        let start = row_offsets[row];
+        let end = row_offsets[row + 1];
+
+        for col in range_stepped(lane, num_cols, SUBCUBE_DIM) {
+            let mut sum = F::new(0.0);
+            for i in start..end {
+                sum += values[i] * dense[cols[i] * dense.stride(0) + col * dense.stride(1)];
+            }
+            output[row * num_cols + col] = sum;
+        }
+    }
+}
+
+/// Count the nonzero elements of each row of the dense matrix, with one row per subcube.
+#[cube(launch_unchecked)]
+pub(crate) fn count_nonzeros_kernel<F: Float>(dense: &Tensor<F>, counts: &mut Tensor<u32>) {
+    let lane = UNIT_POS % SUBCUBE_DIM;
+
+    for row in range_stepped(first_row(), dense.shape(0), row_step()) {
+        let mut count = 0u32;
+        for col in range_stepped(lane, dense.shape(1), SUBCUBE_DIM) {
+            if dense[row * dense.stride(0) + col * dense.stride(1)] != F::new(0.0) {
+                count += 1;
+            }
+        }
+
+        let count = subcube_sum(count);
+        if lane == 0 {
+            counts[row] = count;
+        }
+    }
+}
+
+/// Write the columns and values of the nonzero elements of each row of the dense matrix from
+/// the offset of the row, with one row per subcube.
+///
+/// The subcube visits the columns in chunks of one per unit, and each nonzero element is
+/// written after those of the previous units of the chunk, so that the columns stay sorted.
+#[cube(launch_unchecked)]
+pub(crate) fn dense_to_csr_kernel<F: Float>(
+    dense: &Tensor<F>,
+    row_offsets: &Tensor<u32>,
+    cols: &mut Tensor<u32>,
+    values: &mut Tensor<F>,
+) {
+    let lane = UNIT_POS % SUBCUBE_DIM;
+    let num_cols = dense.shape(1);
+
+    for row in range_stepped(first_row(), dense.shape(0), row_step()) {
+        let mut offset = row_offsets[row];
+
+        for chunk in range_stepped(0, num_cols, SUBCUBE_DIM) {
+            let col = chunk + lane;
+            let mut value = F::new(0.0);
+            if col < num_cols {
+                value = dense[row * dense.stride(0) + col * dense.stride(1)];
+            }
+
+            let nonzero = select(value != F::new(0.0), 1u32, 0u32);
+            let position = offset + subcube_scan::<u32, Sum>(nonzero, false);
+            if nonzero == 1 {
+                cols[position] = col;
+                values[position] = value;
+            }
+            offset += subcube_sum(nonzero);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cubecl_core::host::{HostTensor, HostUnit};
+
+    #[test]
+    fn dense_to_csr_keeps_columns_sorted() {
+        // The dense matrix [[0, 2, 0, 3], [0, 0, 0, 0], [1, 0, 0, 4]].
+        let dense = HostTensor::new(
+            vec![0.0, 2.0, 0.0, 3.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 4.0],
+            vec![3, 4],
+        );
+        let row_offsets = HostTensor::new(vec![0, 2, 2, 4], vec![4]);
+        let mut cols = HostTensor::new(vec![0; 4], vec![4]);
+        let mut values = HostTensor::new(vec![0.0; 4], vec![4]);
+
+        dense_to_csr_kernel::host::<f32>(
+            HostUnit::default(),
+            &dense,
+            &row_offsets,
+            &mut cols,
+            &mut values,
+        );
+        assert_eq!(cols.data, vec![1, 3, 0, 3]);
+        assert_eq!(values.data, vec![2.0, 3.0, 1.0, 4.0]);
+
+        let vector = HostTensor::new(vec![1.0, 2.0, 3.0, 4.0], vec![4]);
+        let mut output = HostTensor::new(vec![0.0; 3], vec![3]);
+        spmv_kernel::host::<f32>(
+            HostUnit::default(),
+            &row_offsets,
+            &cols,
+            &values,
+            &vector,
+            &mut output,
+        );
+        assert_eq!(output.data, vec![16.0, 0.0, 17.0]);
+    }
+}
@@ -0,0 +1,10 @@
+mod base;
+mod handle;
+mod kernel;
+
+/// Tests for sparse kernels
+#[cfg(feature = "export_tests")]
+pub mod tests;
+
+pub use base::*;
+pub use handle::*;
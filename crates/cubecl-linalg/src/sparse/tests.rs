@@ -0,0 +1,254 @@
+#![allow(missing_docs)]
+
+use cubecl_core::{prelude::*, CubeElement};
+
+use crate::{
+    matmul::tests::test_utils::{create_float_tensor, read_f32, should_skip, to_f32},
+    sparse::{self, SparseFormat, SparseTensorHandle},
+    tensor,
+};
+
+fn read_u32<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    handle: &cubecl_runtime::server::Handle,
+    length: usize,
+) -> Vec<u32> {
+    u32::from_bytes(&client.read(handle.clone().binding()))[..length].to_vec()
+}
+
+/// A dense matrix with about one nonzero element out of ten, and a few empty rows.
+fn random_sparse(num_rows: usize, num_cols: usize) -> Vec<f32> {
+    let mut state = 3u64;
+    (0..num_rows * num_cols)
+        .map(|i| {
+            state = (1664525 * state + 1013904223) % (1 << 32);
+            let value = state as f64 / (1u64 << 32) as f64;
+            match (i / num_cols) % 7 != 3 && value < 0.1 {
+                true => (value * 40.0 - 2.0) as f32,
+                false => 0.0,
+            }
+        })
+        .collect()
+}
+
+/// The row offsets, columns and values of the nonzero elements of the dense matrix.
+fn host_csr(dense: &[f32], num_cols: usize) -> (Vec<u32>, Vec<u32>, Vec<f32>) {
+    let mut offsets = vec![0];
+    let (mut cols, mut values) = (Vec::new(), Vec::new());
+
+    for row in dense.chunks(num_cols) {
+        for (col, value) in row.iter().enumerate() {
+            if *value != 0.0 {
+                cols.push(col as u32);
+                values.push(*value);
+            }
+        }
+        offsets.push(cols.len() as u32);
+    }
+
+    (offsets, cols, values)
+}
+
+fn host_matmul(lhs: &[f32], rhs: &[f32], m: usize, k: usize, n: usize) -> Vec<f32> {
+    let mut out = vec![0.0; m * n];
+    for i in 0..m {
+        for j in 0..n {
+            out[i * n + j] = (0..k).map(|p| lhs[i * k + p] * rhs[p * n + j]).sum();
+        }
+    }
+    out
+}
+
+fn create_sparse<R: Runtime, F: Float + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    dense: &[f32],
+    shape: [usize; 2],
+    format: SparseFormat,
+) -> SparseTensorHandle<R, F> {
+    let (offsets, cols, values) = host_csr(dense, shape[1]);
+    let values: Vec<F> = values.iter().map(|value| F::new(*value)).collect();
+    let (cols, values, nnz) = (
+        client.create(u32::as_bytes(&cols)),
+        client.create(F::as_bytes(&values)),
+        values.len(),
+    );
+
+    match format {
+        SparseFormat::Csr => SparseTensorHandle::csr(
+            shape,
+            nnz,
+            client.create(u32::as_bytes(&offsets)),
+            cols,
+            values,
+        ),
+        SparseFormat::Coo => {
+            let rows: Vec<u32> = offsets
+                .windows(2)
+                .enumerate()
+                .flat_map(|(row, range)| vec![row as u32; (range[1] - range[0]) as usize])
+                .collect();
+            SparseTensorHandle::coo(
+                shape,
+                nnz,
+                client.create(u32::as_bytes(&rows)),
+                cols,
+                values,
+            )
+        }
+    }
+}
+
+fn assert_close(actual: &[f32], expected: &[f32]) {
+    assert_eq!(actual.len(), expected.len());
+    for (i, (actual, expected)) in actual.iter().zip(expected).enumerate() {
+        assert!(
+            (actual - expected).abs() <= 1e-3 * expected.abs().max(1.0),
+            "Values differ at {i}: {actual} != {expected}"
+        );
+    }
+}
+
+pub fn test_spmv<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    if should_skip::<R, F>(&client) {
+        return;
+    }
+
+    let (num_rows, num_cols) = (300, 500);
+    let dense = random_sparse(num_rows, num_cols);
+    let vector: Vec<f32> = (0..num_cols).map(|i| (i % 13) as f32 - 6.0).collect();
+    let sparse = create_sparse::<R, F>(&client, &dense, [num_rows, num_cols], SparseFormat::Csr);
+    let vector_tensor = create_float_tensor::<R, F>(&client, vec![num_cols], &vector);
+
+    let output = sparse::spmv::<R, F>(&client, &sparse, vector_tensor.as_ref());
+
+    assert_eq!(output.shape, vec![num_rows]);
+    let expected = host_matmul(&dense, &vector, num_rows, num_cols, 1);
+    assert_close(&read_f32(&client, &output), &expected);
+}
+
+pub fn test_spmm<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    if should_skip::<R, F>(&client) {
+        return;
+    }
+
+    let (m, k, n) = (200, 150, 40);
+    let dense = random_sparse(m, k);
+    let rhs: Vec<f32> = (0..k * n).map(|i| (i % 17) as f32 / 4.0 - 2.0).collect();
+    let sparse = create_sparse::<R, F>(&client, &dense, [m, k], SparseFormat::Csr);
+    let rhs_tensor = create_float_tensor::<R, F>(&client, vec![k, n], &rhs);
+
+    let output = sparse::spmm::<R, F>(&client, &sparse, rhs_tensor.as_ref());
+
+    assert_eq!(output.shape, vec![m, n]);
+    assert_close(
+        &read_f32(&client, &output),
+        &host_matmul(&dense, &rhs, m, k, n),
+    );
+}
+
+pub fn test_spmm_coo_transposed<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    if should_skip::<R, F>(&client) {
+        return;
+    }
+
+    let (m, k, n) = (90, 120, 33);
+    let dense = random_sparse(m, k);
+    let rhs: Vec<f32> = (0..k * n).map(|i| (i % 11) as f32 / 2.0 - 2.5).collect();
+    let sparse = create_sparse::<R, F>(&client, &dense, [m, k], SparseFormat::Coo);
+    // The dense matrix is stored with its columns contiguous.
+    let mut rhs_transposed = vec![0.0; k * n];
+    for p in 0..k {
+        for j in 0..n {
+            rhs_transposed[j * k + p] = rhs[p * n + j];
+        }
+    }
+    let rhs_tensor = create_float_tensor::<R, F>(&client, vec![n, k], &rhs_transposed);
+    let rhs_tensor = tensor::permute::<R, F>(rhs_tensor.as_ref(), &[1, 0]);
+
+    let output = sparse::spmm::<R, F>(&client, &sparse, rhs_tensor.as_ref());
+
+    assert_close(
+        &read_f32(&client, &output),
+        &host_matmul(&dense, &rhs, m, k, n),
+    );
+}
+
+pub fn test_coo_to_csr<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    if should_skip::<R, F>(&client) {
+        return;
+    }
+
+    let (num_rows, num_cols) = (70, 90);
+    let dense = random_sparse(num_rows, num_cols);
+    let sparse = create_sparse::<R, F>(&client, &dense, [num_rows, num_cols], SparseFormat::Coo);
+
+    let csr = sparse.to_csr(&client);
+
+    assert_eq!(csr.format, SparseFormat::Csr);
+    let (offsets, _, _) = host_csr(&dense, num_cols);
+    assert_eq!(read_u32::<R>(&client, &csr.rows, num_rows + 1), offsets);
+}
+
+pub fn test_dense_to_csr<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    if should_skip::<R, F>(&client) {
+        return;
+    }
+
+    let (num_rows, num_cols) = (130, 300);
+    let dense = random_sparse(num_rows, num_cols);
+    let input = create_float_tensor::<R, F>(&client, vec![num_rows, num_cols], &dense);
+
+    let sparse = sparse::dense_to_csr::<R, F>(&client, input.as_ref());
+
+    let (offsets, cols, values) = host_csr(&dense, num_cols);
+    assert_eq!(sparse.shape, [num_rows, num_cols]);
+    assert_eq!(sparse.nnz, values.len());
+    assert_eq!(read_u32::<R>(&client, &sparse.rows, num_rows + 1), offsets);
+    assert_eq!(read_u32::<R>(&client, &sparse.cols, sparse.nnz), cols);
+
+    let data = client.read(sparse.values.clone().binding());
+    assert_close(&to_f32(F::from_bytes(&data)), &values);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_sparse {
+    () => {
+        mod sparse {
+            use super::*;
+            use cubecl_linalg::sparse::tests;
+
+            pub type FloatT = f32;
+
+            #[test]
+            pub fn test_spmv() {
+                tests::test_spmv::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_spmm() {
+                tests::test_spmm::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_spmm_coo_transposed() {
+                tests::test_spmm_coo_transposed::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_coo_to_csr() {
+                tests::test_coo_to_csr::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_dense_to_csr() {
+                tests::test_dense_to_csr::<TestRuntime, FloatT>(&Default::default())
+            }
+        }
+    };
+}
@@ -0,0 +1,89 @@
+use super::TensorHandle;
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise};
+
+/// Concatenate the tensors along the given axis into a new contiguous tensor.
+///
+/// Every tensor must have the same shape except along the axis.
+pub fn concat<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    inputs: &[TensorHandleRef<'_, R>],
+    axis: usize,
+) -> TensorHandle<R, E> {
+    assert!(!inputs.is_empty(), "Can't concatenate zero tensors");
+
+    let mut shape = inputs[0].shape.to_vec();
+    assert!(
+        axis < shape.len(),
+        "Can't concatenate along axis {axis} tensors of rank {}",
+        shape.len()
+    );
+
+    shape[axis] = 0;
+    for input in inputs {
+        assert!(
+            input.shape.len() == shape.len()
+                && input
+                    .shape
+                    .iter()
+                    .zip(&shape)
+                    .enumerate()
+                    .all(|(i, (a, b))| i == axis || a == b),
+            "The shape {:?} doesn't match the shape {:?} outside of axis {axis}",
+            input.shape,
+            inputs[0].shape,
+        );
+        shape[axis] += input.shape[axis];
+    }
+
+    let output = TensorHandle::<R, E>::empty(client, shape);
+    let cube_dim = CubeDim::default();
+    let mut axis_offset = 0;
+
+    for input in inputs {
+        let num_elements: usize = input.shape.iter().product();
+        if num_elements > 0 {
+            unsafe {
+                copy_into_kernel::launch_unchecked::<E, R>(
+                    client,
+                    calculate_cube_count_elemwise(num_elements, cube_dim),
+                    cube_dim,
+                    input.as_tensor_arg(1),
+                    output.as_ref().as_tensor_arg(1),
+                    ScalarArg::new(axis as u32),
+                    ScalarArg::new(axis_offset as u32),
+                );
+            }
+        }
+        axis_offset += input.shape[axis];
+    }
+
+    output
+}
+
+/// Copy the input into the output, starting at `axis_offset` along the axis.
+#[cube(launch_unchecked)]
+fn copy_into_kernel<E: Numeric>(
+    input: &Tensor<E>,
+    output: &mut Tensor<E>,
+    axis: u32,
+    axis_offset: u32,
+) {
+    let rank = input.rank();
+    let mut input_offset = 0;
+    let mut output_offset = axis_offset * output.stride(axis);
+    let mut remaining = ABSOLUTE_POS;
+
+    for i in 0..rank {
+        let dim = rank - 1 - i;
+        let coordinate = remaining % input.shape(dim);
+        input_offset += coordinate * input.stride(dim);
+        output_offset += coordinate * output.stride(dim);
+        remaining /= input.shape(dim);
+    }
+
+    // Positions past the last element wrap around the first dimension.
+    if remaining == 0 {
+        output[output_offset] = input[input_offset];
+    }
+}
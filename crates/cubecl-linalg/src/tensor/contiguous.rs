@@ -120,3 +120,28 @@ pub fn into_contiguous_prefetch<R: Runtime, E: CubePrimitive>(
 
     output
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cubecl_core::host::{HostTensor, HostUnit};
+
+    #[test]
+    fn index_offset_with_contiguous_layout() {
+        let tensor = HostTensor::new_strided(vec![vec![0.0]; 6], vec![2, 3], vec![1, 2]);
+        let layout = HostTensor::new(vec![vec![0.0]; 6], vec![2, 3]);
+
+        for (offset_layout, expected) in [(0, 0), (1, 2), (3, 1), (4, 3), (5, 5)] {
+            let offset = index_offset_with_layout::host::<f32, f32>(
+                HostUnit::default(),
+                &tensor,
+                &layout,
+                offset_layout,
+                0,
+                2,
+                false,
+            );
+            assert_eq!(offset, expected);
+        }
+    }
+}
@@ -1,4 +1,6 @@
-#[derive(PartialEq, Eq, Debug)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
 /// Layout for matrix tensors, i.e. tensors whose interpretation
 /// is a bunch of batched matrices of 2 dimensions
 pub enum MatrixLayout {
@@ -62,10 +64,49 @@ pub fn matrix_layout(strides: &[usize]) -> MatrixLayout {
     }
 }
 
+/// The largest of the given line sizes along the axis, which must be contiguous, such that
+/// every line of the tensor is aligned whatever the strides of its other axes.
+pub fn line_size_along(line_sizes: &[u8], shape: &[usize], strides: &[usize], axis: usize) -> u8 {
+    if strides[axis] != 1 {
+        return 1;
+    }
+
+    line_sizes
+        .iter()
+        .copied()
+        .find(|line_size| {
+            let line_size = *line_size as usize;
+            shape[axis] % line_size == 0
+                && shape
+                    .iter()
+                    .zip(strides)
+                    .enumerate()
+                    .all(|(i, (shape, stride))| i == axis || *shape == 1 || stride % line_size == 0)
+        })
+        .unwrap_or(1)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn line_size_is_aligned_with_permuted_batches() {
+        // E.g., tensor w/ shape [12, 3, 8] viewed as [3, 12, 8]
+        let (shape, strides) = (&[3, 12, 8], &[8, 24, 1]);
+        assert_eq!(line_size_along(&[4, 2, 1], shape, strides, 2), 4);
+
+        let (shape, strides) = (&[3, 12, 6], &[6, 18, 1]);
+        assert_eq!(line_size_along(&[4, 2, 1], shape, strides, 2), 2);
+    }
+
+    #[test]
+    fn line_size_ignores_broadcast_batches() {
+        let (shape, strides) = (&[5, 12, 8], &[0, 1, 12]);
+        assert_eq!(line_size_along(&[4, 2, 1], shape, strides, 1), 4);
+        assert_eq!(line_size_along(&[4, 2, 1], shape, strides, 2), 1);
+    }
+
     #[test]
     fn layout_is_contiguous() {
         let strides = &[8, 4, 2, 1];
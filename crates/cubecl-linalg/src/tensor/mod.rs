@@ -1,7 +1,19 @@
 mod base;
+mod concat;
 mod contiguous;
 mod layout;
+mod pad;
+mod transpose;
+mod view;
+
+/// Tests for tensor layout kernels
+#[cfg(feature = "export_tests")]
+pub mod tests;
 
 pub use base::*;
+pub use concat::*;
 pub use contiguous::*;
 pub use layout::*;
+pub use pad::*;
+pub use transpose::*;
+pub use view::*;
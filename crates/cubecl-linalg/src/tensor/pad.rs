@@ -0,0 +1,117 @@
+use super::TensorHandle;
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise};
+
+/// How the padding is filled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PadMode {
+    /// Fill the padding with a constant.
+    Constant(f32),
+    /// Mirror the tensor around its first and last elements, which are not repeated.
+    Reflect,
+}
+
+/// Pad each axis of the tensor with the `(before, after)` number of elements, into a new
+/// contiguous tensor.
+///
+/// With [PadMode::Reflect], the padding must be smaller than the size of its axis.
+pub fn pad<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    padding: &[(usize, usize)],
+    mode: PadMode,
+) -> TensorHandle<R, E> {
+    let rank = input.shape.len();
+    assert_eq!(
+        padding.len(),
+        rank,
+        "The padding must have one entry per dimension"
+    );
+
+    let shape: Vec<usize> = input
+        .shape
+        .iter()
+        .zip(padding)
+        .map(|(size, (before, after))| before + size + after)
+        .collect();
+
+    if mode == PadMode::Reflect {
+        for (axis, (size, (before, after))) in input.shape.iter().zip(padding).enumerate() {
+            assert!(
+                before < size && after < size,
+                "Can't reflect a padding of {:?} along the axis {axis} of size {size}",
+                (before, after)
+            );
+        }
+    }
+
+    let output = TensorHandle::<R, E>::empty(client, shape);
+    let num_elements: usize = output.shape.iter().product();
+    if num_elements == 0 {
+        return output;
+    }
+
+    let before: Vec<u32> = padding.iter().map(|(before, _)| *before as u32).collect();
+    let before = client.create(u32::as_bytes(&before));
+    let (value, reflect) = match mode {
+        PadMode::Constant(value) => (value, false),
+        PadMode::Reflect => (0.0, true),
+    };
+
+    let cube_dim = CubeDim::default();
+    unsafe {
+        pad_kernel::launch_unchecked::<E, R>(
+            client,
+            calculate_cube_count_elemwise(num_elements, cube_dim),
+            cube_dim,
+            input.as_tensor_arg(1),
+            output.as_ref().as_tensor_arg(1),
+            ArrayArg::from_raw_parts::<u32>(&before, rank, 1),
+            ScalarArg::new(value),
+            reflect,
+        );
+    }
+
+    output
+}
+
+#[cube(launch_unchecked)]
+fn pad_kernel<E: Numeric>(
+    input: &Tensor<E>,
+    output: &mut Tensor<E>,
+    before: &Array<u32>,
+    value: f32,
+    #[comptime] reflect: bool,
+) {
+    if ABSOLUTE_POS >= output.len() {
+        return;
+    }
+
+    let rank = input.rank();
+    let mut offset = 0;
+    let mut is_padding = false;
+    let mut remaining = ABSOLUTE_POS;
+
+    for i in 0..rank {
+        let dim = rank - 1 - i;
+        let size = i32::cast_from(input.shape(dim));
+        let mut coordinate =
+            i32::cast_from(remaining % output.shape(dim)) - i32::cast_from(before[dim]);
+        remaining /= output.shape(dim);
+
+        if comptime!(reflect) {
+            coordinate = select(coordinate < 0, -coordinate, coordinate);
+            coordinate = select(coordinate >= size, 2 * (size - 1) - coordinate, coordinate);
+        } else {
+            is_padding = is_padding || coordinate < 0 || coordinate >= size;
+        }
+
+        offset += u32::cast_from(coordinate) * input.stride(dim);
+    }
+
+    if is_padding {
+        output[ABSOLUTE_POS] = E::cast_from(value);
+    } else {
+        output[ABSOLUTE_POS] = input[offset];
+    }
+}
@@ -0,0 +1,217 @@
+#![allow(missing_docs)]
+
+use cubecl_core::{prelude::*, CubeElement};
+
+use crate::tensor::{self, PadMode, TensorHandle};
+
+fn create<R: Runtime, F: Float + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    shape: Vec<usize>,
+) -> (TensorHandle<R, F>, Vec<f32>) {
+    let num_elements: usize = shape.iter().product();
+    let data: Vec<f32> = (0..num_elements).map(|i| i as f32).collect();
+    let input: Vec<F> = data.iter().map(|value| F::new(*value)).collect();
+    let handle = client.create(F::as_bytes(&input));
+
+    (TensorHandle::new_contiguous(shape, handle), data)
+}
+
+/// Read the elements of the tensor in row major order, whatever its layout.
+fn read<R: Runtime, F: Float + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    tensor: &TensorHandle<R, F>,
+) -> Vec<f32> {
+    let tensor = tensor::into_contiguous::<R, F>(client, tensor.as_ref());
+    let data = client.read(tensor.handle.binding());
+
+    F::from_bytes(&data)
+        .iter()
+        .map(|value| value.to_f32().unwrap())
+        .collect()
+}
+
+/// The elements of a view of the data in row major order.
+fn reference_view(data: &[f32], shape: &[usize], strides: &[usize], offset: usize) -> Vec<f32> {
+    let num_elements: usize = shape.iter().product();
+
+    (0..num_elements)
+        .map(|position| {
+            let mut remaining = position;
+            let mut index = offset;
+            for (size, stride) in shape.iter().zip(strides).rev() {
+                index += remaining % size * stride;
+                remaining /= size;
+            }
+            data[index]
+        })
+        .collect()
+}
+
+pub fn test_permute<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    let (input, data) = create::<R, F>(&client, vec![2, 3, 4]);
+
+    let output = tensor::permute::<R, F>(input.as_ref(), &[2, 0, 1]);
+
+    assert_eq!(output.shape, vec![4, 2, 3]);
+    assert_eq!(
+        read(&client, &output),
+        reference_view(&data, &[4, 2, 3], &[1, 12, 4], 0)
+    );
+}
+
+pub fn test_transpose<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    let (input, data) = create::<R, F>(&client, vec![70, 3, 45]);
+    // A batch axis in the middle, so that the input is not contiguous.
+    let input = tensor::permute::<R, F>(input.as_ref(), &[1, 0, 2]);
+
+    let output = tensor::transpose::<R, F>(&client, input.as_ref());
+
+    assert_eq!(output.shape, vec![3, 45, 70]);
+    assert_eq!(output.strides, vec![45 * 70, 70, 1]);
+    assert_eq!(
+        read(&client, &output),
+        reference_view(&data, &[3, 45, 70], &[45, 1, 135], 0)
+    );
+}
+
+pub fn test_expand<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    let (input, data) = create::<R, F>(&client, vec![3, 1]);
+
+    let output = tensor::expand::<R, F>(input.as_ref(), vec![2, 3, 4]);
+
+    assert_eq!(output.strides, vec![0, 1, 0]);
+    assert_eq!(
+        read(&client, &output),
+        reference_view(&data, &[2, 3, 4], &[0, 1, 0], 0)
+    );
+}
+
+pub fn test_slice<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    let (input, data) = create::<R, F>(&client, vec![6, 8, 5]);
+
+    // Starting at the first element, so the slice is a view.
+    let view = tensor::slice::<R, F>(&client, input.as_ref(), &[0..4, 0..7]);
+    assert_eq!(view.shape, vec![4, 7, 5]);
+    assert_eq!(view.strides, input.strides);
+    assert_eq!(
+        read(&client, &view),
+        reference_view(&data, &[4, 7, 5], &[40, 5, 1], 0)
+    );
+
+    // An offset of a single element can't be bound, so the slice is copied.
+    let copy = tensor::narrow::<R, F>(&client, input.as_ref(), 2, 1, 3);
+    assert_eq!(copy.shape, vec![6, 8, 3]);
+    assert_eq!(
+        read(&client, &copy),
+        reference_view(&data, &[6, 8, 3], &[40, 5, 1], 1)
+    );
+}
+
+pub fn test_concat<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    let (lhs, lhs_data) = create::<R, F>(&client, vec![3, 2, 4]);
+    let (rhs, rhs_data) = create::<R, F>(&client, vec![4, 3, 5]);
+    // A permuted input of shape [3, 5, 4].
+    let rhs = tensor::permute::<R, F>(rhs.as_ref(), &[1, 2, 0]);
+
+    let output = tensor::concat::<R, F>(&client, &[lhs.as_ref(), rhs.as_ref()], 1);
+
+    assert_eq!(output.shape, vec![3, 7, 4]);
+    let lhs_data = reference_view(&lhs_data, &[3, 2, 4], &[8, 4, 1], 0);
+    let rhs_data = reference_view(&rhs_data, &[3, 5, 4], &[5, 1, 15], 0);
+    let expected: Vec<f32> = (0..3)
+        .flat_map(|i| {
+            lhs_data[i * 8..(i + 1) * 8]
+                .iter()
+                .chain(&rhs_data[i * 20..(i + 1) * 20])
+                .copied()
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    assert_eq!(read(&client, &output), expected);
+}
+
+pub fn test_pad_constant<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    let (input, data) = create::<R, F>(&client, vec![2, 3]);
+
+    let output = tensor::pad::<R, F>(
+        &client,
+        input.as_ref(),
+        &[(1, 0), (2, 1)],
+        PadMode::Constant(-1.0),
+    );
+
+    assert_eq!(output.shape, vec![3, 6]);
+    let mut expected = vec![-1.0; 18];
+    for row in 0..2 {
+        for col in 0..3 {
+            expected[(row + 1) * 6 + col + 2] = data[row * 3 + col];
+        }
+    }
+    assert_eq!(read(&client, &output), expected);
+}
+
+pub fn test_pad_reflect<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    let (input, _) = create::<R, F>(&client, vec![2, 4]);
+
+    let output = tensor::pad::<R, F>(&client, input.as_ref(), &[(1, 1), (2, 3)], PadMode::Reflect);
+
+    assert_eq!(output.shape, vec![4, 9]);
+    let row = |r: f32| [2.0, 1.0, 0.0, 1.0, 2.0, 3.0, 2.0, 1.0, 0.0].map(|c| r * 4.0 + c);
+    let expected: Vec<f32> = [1.0, 0.0, 1.0, 0.0].iter().flat_map(|r| row(*r)).collect();
+    assert_eq!(read(&client, &output), expected);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_tensor {
+    () => {
+        mod tensor {
+            use super::*;
+            use cubecl_linalg::tensor::tests;
+
+            pub type FloatT = f32;
+
+            #[test]
+            pub fn test_permute() {
+                tests::test_permute::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_transpose() {
+                tests::test_transpose::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_expand() {
+                tests::test_expand::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_slice() {
+                tests::test_slice::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_concat() {
+                tests::test_concat::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_pad_constant() {
+                tests::test_pad_constant::<TestRuntime, FloatT>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_pad_reflect() {
+                tests::test_pad_reflect::<TestRuntime, FloatT>(&Default::default())
+            }
+        }
+    };
+}
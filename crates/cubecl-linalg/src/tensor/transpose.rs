@@ -0,0 +1,98 @@
+use super::TensorHandle;
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, CubeCount};
+
+/// The number of rows and columns of the tile transposed by a cube.
+const TILE_SIZE: u32 = 32;
+/// The number of rows of the tile moved at once, each unit moving one element per row.
+const ROWS_PER_STEP: u32 = 8;
+
+/// Transpose the last two axes of the tensor into a new contiguous tensor.
+///
+/// Each cube transposes a tile through shared memory, so that both the reads and the writes are
+/// coalesced whatever the layout of the input.
+pub fn transpose<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+) -> TensorHandle<R, E> {
+    let rank = input.shape.len();
+    assert!(rank >= 2, "Can't transpose a tensor of rank {rank}");
+
+    let mut shape = input.shape.to_vec();
+    shape.swap(rank - 2, rank - 1);
+    let output = TensorHandle::<R, E>::empty(client, shape);
+
+    let num_elements: usize = input.shape.iter().product();
+    if num_elements == 0 {
+        return output;
+    }
+
+    let rows = input.shape[rank - 2] as u32;
+    let cols = input.shape[rank - 1] as u32;
+    let num_batches: usize = input.shape[..rank - 2].iter().product();
+    let cube_count = CubeCount::Static(
+        cols.div_ceil(TILE_SIZE),
+        rows.div_ceil(TILE_SIZE),
+        num_batches as u32,
+    );
+
+    unsafe {
+        transpose_kernel::launch_unchecked::<E, R>(
+            client,
+            cube_count,
+            CubeDim::new(TILE_SIZE, ROWS_PER_STEP, 1),
+            input.as_tensor_arg(1),
+            output.as_ref().as_tensor_arg(1),
+            TILE_SIZE,
+        );
+    }
+
+    output
+}
+
+#[cube(launch_unchecked)]
+fn transpose_kernel<E: Numeric>(
+    input: &Tensor<E>,
+    output: &mut Tensor<E>,
+    #[comptime] tile_size: u32,
+) {
+    let rank = input.rank();
+    let rows = input.shape(rank - 2);
+    let cols = input.shape(rank - 1);
+
+    let mut input_batch = 0;
+    let mut output_batch = 0;
+    let mut remaining = CUBE_POS_Z;
+    for i in 0..rank - 2 {
+        let dim = rank - 3 - i;
+        let coordinate = remaining % input.shape(dim);
+        input_batch += coordinate * input.stride(dim);
+        output_batch += coordinate * output.stride(dim);
+        remaining /= input.shape(dim);
+    }
+
+    // The padding column keeps the units reading a column of the tile on different banks.
+    let tile_stride = comptime!(tile_size + 1);
+    let mut tile = SharedMemory::<E>::new(comptime!(tile_size * (tile_size + 1)));
+    let row_start = CUBE_POS_Y * tile_size;
+    let col_start = CUBE_POS_X * tile_size;
+
+    for i in range_stepped(UNIT_POS_Y, tile_size, CUBE_DIM_Y) {
+        let row = row_start + i;
+        let col = col_start + UNIT_POS_X;
+        if row < rows && col < cols {
+            tile[i * tile_stride + UNIT_POS_X] =
+                input[input_batch + row * input.stride(rank - 2) + col * input.stride(rank - 1)];
+        }
+    }
+    sync_units();
+
+    for i in range_stepped(UNIT_POS_Y, tile_size, CUBE_DIM_Y) {
+        let row = col_start + i;
+        let col = row_start + UNIT_POS_X;
+        if row < cols && col < rows {
+            output[output_batch + row * output.stride(rank - 2) + col * output.stride(rank - 1)] =
+                tile[UNIT_POS_X * tile_stride + i];
+        }
+    }
+}
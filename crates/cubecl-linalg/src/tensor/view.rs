@@ -0,0 +1,159 @@
+use std::ops::Range;
+
+use super::{index_offset_with_layout, TensorHandle};
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise};
+
+/// A view of the tensor with its axes reordered, the axis `i` of the view being the axis
+/// `axes[i]` of the tensor.
+///
+/// No data is moved, [into_contiguous](super::into_contiguous) materializes the view.
+pub fn permute<R: Runtime, E: CubePrimitive>(
+    tensor: TensorHandleRef<'_, R>,
+    axes: &[usize],
+) -> TensorHandle<R, E> {
+    let rank = tensor.shape.len();
+    assert_eq!(
+        axes.len(),
+        rank,
+        "The permutation must have one axis per dimension"
+    );
+
+    let mut seen = vec![false; rank];
+    for axis in axes {
+        assert!(
+            *axis < rank && !seen[*axis],
+            "The axes {axes:?} are not a permutation of a tensor of rank {rank}"
+        );
+        seen[*axis] = true;
+    }
+
+    TensorHandle::new(
+        axes.iter().map(|axis| tensor.shape[*axis]).collect(),
+        axes.iter().map(|axis| tensor.strides[*axis]).collect(),
+        tensor.handle.clone(),
+    )
+}
+
+/// A view of the tensor broadcast to the given shape, with a stride of zero along the expanded
+/// axes.
+///
+/// The shapes are aligned on their last axis, and each axis of the tensor must either match
+/// the new shape or have a size of 1. Missing leading axes are added.
+pub fn expand<R: Runtime, E: CubePrimitive>(
+    tensor: TensorHandleRef<'_, R>,
+    shape: Vec<usize>,
+) -> TensorHandle<R, E> {
+    let rank = tensor.shape.len();
+    assert!(
+        shape.len() >= rank,
+        "Can't expand a tensor of rank {rank} to the shape {shape:?}"
+    );
+
+    let num_new_axes = shape.len() - rank;
+    let strides = shape
+        .iter()
+        .enumerate()
+        .map(|(i, size)| match i.checked_sub(num_new_axes) {
+            None => 0,
+            Some(axis) if tensor.shape[axis] == *size => tensor.strides[axis],
+            Some(axis) if tensor.shape[axis] == 1 => 0,
+            Some(axis) => panic!(
+                "Can't expand the axis {axis} of size {} to {size}",
+                tensor.shape[axis]
+            ),
+        })
+        .collect();
+
+    TensorHandle::new(shape, strides, tensor.handle.clone())
+}
+
+/// The elements of `start..start + length` along the given axis.
+///
+/// See [slice] for when the result is a view.
+pub fn narrow<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    tensor: TensorHandleRef<'_, R>,
+    axis: usize,
+    start: usize,
+    length: usize,
+) -> TensorHandle<R, E> {
+    let mut ranges: Vec<Range<usize>> = tensor.shape.iter().map(|size| 0..*size).collect();
+    ranges[axis] = start..start + length;
+
+    slice(client, tensor, &ranges)
+}
+
+/// The elements within the given range along each axis, the axes without a range being kept
+/// whole.
+///
+/// The result is a view of the tensor when its first element is at an offset aligned to the
+/// memory alignment of the runtime, since only those offsets can be bound to a kernel.
+/// Otherwise the elements are copied into a new contiguous tensor.
+pub fn slice<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    tensor: TensorHandleRef<'_, R>,
+    ranges: &[Range<usize>],
+) -> TensorHandle<R, E> {
+    assert!(
+        ranges.len() <= tensor.shape.len(),
+        "Can't slice {} axes of a tensor of rank {}",
+        ranges.len(),
+        tensor.shape.len()
+    );
+
+    let mut shape = tensor.shape.to_vec();
+    let mut offset = 0;
+    for (axis, range) in ranges.iter().enumerate() {
+        assert!(
+            range.start <= range.end && range.end <= shape[axis],
+            "The range {range:?} is out of bounds of the axis {axis} of size {}",
+            shape[axis]
+        );
+        shape[axis] = range.end - range.start;
+        offset += range.start * tensor.strides[axis];
+    }
+
+    let offset_bytes = (offset * E::as_elem().size()) as u64;
+    let alignment = client.properties().memory_properties().alignment;
+    if offset_bytes % alignment == 0 {
+        return TensorHandle::new(
+            shape,
+            tensor.strides.to_vec(),
+            tensor.handle.clone().offset_start(offset_bytes),
+        );
+    }
+
+    let output = TensorHandle::<R, E>::empty(client, shape.clone());
+    let num_elements: usize = shape.iter().product();
+    if num_elements == 0 {
+        return output;
+    }
+
+    // The whole tensor is bound with the shape of the slice, so that the offset of each
+    // element relative to the first one follows from the layout of the output.
+    let input = unsafe { TensorArg::from_raw_parts::<E>(tensor.handle, tensor.strides, &shape, 1) };
+    let cube_dim = CubeDim::default();
+    unsafe {
+        slice_kernel::launch_unchecked::<E, R>(
+            client,
+            calculate_cube_count_elemwise(num_elements, cube_dim),
+            cube_dim,
+            input,
+            output.as_ref().as_tensor_arg(1),
+            ScalarArg::new(offset as u32),
+        );
+    }
+
+    output
+}
+
+/// Copy the elements of the slice starting at `offset` into the contiguous output.
+#[cube(launch_unchecked)]
+fn slice_kernel<E: Numeric>(input: &Tensor<Line<E>>, output: &mut Tensor<Line<E>>, offset: u32) {
+    if ABSOLUTE_POS < output.len() {
+        let position =
+            index_offset_with_layout::<E, E>(input, output, ABSOLUTE_POS, 0, output.rank(), false);
+        output[ABSOLUTE_POS] = input[offset + position];
+    }
+}
@@ -1,7 +1,10 @@
-use crate::parse::cube_trait::{CubeTrait, CubeTraitImpl, CubeTraitImplItem, CubeTraitItem};
+use crate::{
+    generate::host::host_fn,
+    parse::cube_trait::{CubeTrait, CubeTraitImpl, CubeTraitImplItem, CubeTraitItem},
+};
 use proc_macro2::TokenStream;
-use quote::quote;
 use quote::ToTokens;
+use quote::{format_ident, quote};
 
 impl ToTokens for CubeTrait {
     fn to_tokens(&self, tokens: &mut TokenStream) {
@@ -14,6 +17,21 @@ impl ToTokens for CubeTrait {
         let name = &self.name;
         let generics = &self.generics;
         let fns = self.items.iter().filter_map(CubeTraitItem::func);
+        let host_fns = self
+            .items
+            .iter()
+            .filter_map(CubeTraitItem::func)
+            .filter_map(|func| {
+                let name = func.name.to_string();
+                let name = name.strip_prefix("__expand_").unwrap_or(&name);
+                let expand = &func.name;
+                host_fn(
+                    func,
+                    TokenStream::new(),
+                    format_ident!("__host_{name}"),
+                    quote![Self::#expand],
+                )
+            });
 
         let out = quote! {
             #(#attrs)*
@@ -25,6 +43,8 @@ impl ToTokens for CubeTrait {
                     #[allow(clippy::too_many_arguments)]
                     #fns;
                 )*
+
+                #(#host_fns)*
             }
         };
         tokens.extend(out);
@@ -1,6 +1,6 @@
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote, quote_spanned};
-use syn::{spanned::Spanned, Member, PathArguments};
+use syn::{spanned::Spanned, Member, Pat, PathArguments};
 
 use crate::{
     expression::{Block, ConstMatchArm, Expression},
@@ -90,6 +90,11 @@ impl Expression {
                 let expand_elem = frontend_type("ExpandElementTyped");
                 quote![#expand_elem::from_lit(#name)]
             }
+            Expression::Variable(var) if var.is_callable => {
+                // Callables can't be assumed to be `Clone`, so they are passed by reference.
+                let name = &var.name;
+                quote![&#name]
+            }
             Expression::Variable(var) => {
                 let name = &var.name;
                 if var.try_consume(context) {
@@ -151,6 +156,16 @@ impl Expression {
                     }
                 }
             }
+            Expression::FunctionCall { func, args, .. } if is_callable(func) => {
+                let (args, arg_names) = map_args(args, context);
+                let func = func.to_tokens(context);
+                quote! {
+                    {
+                        #(#args)*
+                        (#func)(context, #(#arg_names),*)
+                    }
+                }
+            }
             Expression::FunctionCall {
                 func,
                 args,
@@ -448,9 +463,18 @@ impl Expression {
                 body,
                 scope,
             } => {
+                let cube_context = prelude_type("CubeContext");
+                let cube_type = prelude_type("CubeType");
+                let params = params.iter().map(|param| match param {
+                    Pat::Type(param) => {
+                        let (pat, ty) = (&param.pat, &param.ty);
+                        quote![#pat: <#ty as #cube_type>::ExpandType]
+                    }
+                    param => quote![#param],
+                });
                 // Without knowing the closure type, we need to assume it's `FnMut`
                 let body = context.in_fn_mut(scope, |ctx| body.to_tokens(ctx));
-                quote![|context, #(#params),*| #body]
+                quote![|context: &mut #cube_context, #(#params),*| #body]
             }
             Expression::Verbatim { tokens, .. } => tokens.clone(),
             Expression::Block(block) => block.to_tokens(context),
@@ -520,7 +544,7 @@ fn map_args(args: &[Expression], context: &mut Context) -> (Vec<TokenStream>, Ve
         .iter()
         .zip(args.iter())
         .map(|(i, value)| {
-            if matches!(value, Expression::Closure { .. }) {
+            if is_callable(value) {
                 quote![]
             } else {
                 let tokens = value
@@ -534,7 +558,7 @@ fn map_args(args: &[Expression], context: &mut Context) -> (Vec<TokenStream>, Ve
         .into_iter()
         .zip(args.iter())
         .map(|(name, value)| {
-            if matches!(value, Expression::Closure { .. }) {
+            if is_callable(value) {
                 value.to_tokens(context)
             } else {
                 quote![#name.into()]
@@ -544,6 +568,17 @@ fn map_args(args: &[Expression], context: &mut Context) -> (Vec<TokenStream>, Ve
     (values, names)
 }
 
+/// Closures and callable variables are passed as is, since their type can't be inferred through
+/// `into()`.
+fn is_callable(value: &Expression) -> bool {
+    match value {
+        Expression::Closure { .. } => true,
+        Expression::Variable(var) => var.is_callable,
+        Expression::Reference { inner } => is_callable(inner),
+        _ => false,
+    }
+}
+
 /// Since we no longer (unnecessarily) init immutable locals, we do need to init all struct fields
 /// because of interior mutability.
 fn init_fields<'a>(
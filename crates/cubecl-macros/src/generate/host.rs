@@ -0,0 +1,228 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse_quote,
+    visit_mut::{self, VisitMut},
+    GenericArgument, GenericParam, Ident, Lifetime, PathArguments, PathSegment, Type,
+    TypeImplTrait, WherePredicate,
+};
+
+use crate::{
+    parse::kernel::{KernelParam, KernelReturns, KernelSignature},
+    paths::{host_type, prelude_type},
+};
+
+/// Generate a function executing the expand function on the host for a single unit, when the
+/// `host` feature is enabled.
+///
+/// Returns [None] when the signature can't be executed on the host, e.g. when it takes
+/// callables, `impl Trait` parameters, a receiver or returns a reference.
+pub fn host_fn(
+    sig: &KernelSignature,
+    vis: TokenStream,
+    name: Ident,
+    expand: TokenStream,
+) -> Option<TokenStream> {
+    if !cfg!(feature = "host") || !is_host_compatible(sig) {
+        return None;
+    }
+
+    let host_unit = host_type("HostUnit");
+    let host_arg = host_type("HostArg");
+    let host_execution = host_type("HostExecution");
+    let cube_context = prelude_type("CubeContext");
+
+    let returns = match &sig.returns {
+        KernelReturns::ExpandType(ty) => static_lifetimes(ty.clone()),
+        KernelReturns::Plain(_) => unreachable!("Plain returns aren't host compatible"),
+    };
+
+    let mut generics = sig.generics.clone();
+    let where_clause = generics.make_where_clause();
+    let mut predicates: Vec<WherePredicate> = where_clause.predicates.iter().cloned().collect();
+    for predicate in predicates.iter_mut() {
+        ReplaceElidedLifetimes.visit_where_predicate_mut(predicate);
+    }
+    // The bounds are higher-ranked so that they are accepted on concrete types, which would
+    // otherwise be rejected as trivial bounds when the type doesn't implement the trait.
+    let runtime_types = sig
+        .parameters
+        .iter()
+        .filter(|param| !param.is_const)
+        .map(|param| static_lifetimes(param.ty_owned()))
+        .chain(Some(returns.clone()));
+    for ty in runtime_types {
+        predicates.push(parse_quote![for<'__host> #ty: #host_arg]);
+    }
+    where_clause.predicates = predicates.into_iter().collect();
+
+    let params = sig.parameters.iter().map(|param| {
+        let name = &param.name;
+        let ty = static_lifetimes(param.ty_owned());
+
+        if param.is_const {
+            quote![#name: #ty]
+        } else if is_mut_ref(param) {
+            quote![#name: &mut <#ty as #host_arg>::Host]
+        } else if param.is_ref {
+            quote![#name: &<#ty as #host_arg>::Host]
+        } else {
+            quote![#name: <#ty as #host_arg>::Host]
+        }
+    });
+
+    let registers = sig
+        .parameters
+        .iter()
+        .filter(|param| !param.is_const)
+        .map(|param| {
+            let name = &param.name;
+            let var = format_ident!("__arg_{name}");
+            let ty = static_lifetimes(param.ty_owned());
+            let value = match param.is_ref {
+                true => quote![#name],
+                false => quote![&#name],
+            };
+
+            quote! {
+                let #var = <#ty as #host_arg>::register(#value, &mut __execution, &mut __context);
+            }
+        });
+
+    let args = sig.parameters.iter().map(|param| {
+        let name = &param.name;
+        let var = format_ident!("__arg_{name}");
+
+        if param.is_const {
+            quote![#name]
+        } else if is_mut_ref(param) {
+            quote![#var.clone()]
+        } else {
+            quote![#var]
+        }
+    });
+
+    let write_backs = sig
+        .parameters
+        .iter()
+        .filter(|param| !param.is_const && is_mut_ref(param))
+        .map(|param| {
+            let name = &param.name;
+            let var = format_ident!("__arg_{name}");
+            let ty = static_lifetimes(param.ty_owned());
+
+            quote! {
+                *#name = <#ty as #host_arg>::read(&#var, &__execution);
+            }
+        });
+
+    let turbofish = expand_generic_args(sig);
+    let (generics, _, where_clause) = generics.split_for_impl();
+
+    let doc = format!("Execute [{}()] on the host for a single unit.", sig.name);
+
+    Some(quote! {
+        #[doc = #doc]
+        #[allow(
+            clippy::too_many_arguments,
+            clippy::unit_arg,
+            clippy::multiple_bound_locations,
+            unused
+        )]
+        #vis fn #name #generics(
+            __unit: #host_unit,
+            #(#params),*
+        ) -> <#returns as #host_arg>::Host #where_clause {
+            let mut __context = #cube_context::default();
+            let mut __execution = #host_execution::new(__unit);
+
+            #(#registers)*
+            let __return = #expand #turbofish(&mut __context, #(#args),*);
+            __execution.execute(&__context.into_scope());
+
+            #(#write_backs)*
+            <#returns as #host_arg>::read(&__return, &__execution)
+        }
+    })
+}
+
+fn is_host_compatible(sig: &KernelSignature) -> bool {
+    let mut impl_trait = FindImplTrait(false);
+    for param in sig.parameters.iter() {
+        impl_trait.visit_type_mut(&mut param.ty.clone());
+    }
+
+    !impl_trait.0
+        && matches!(&sig.returns, KernelReturns::ExpandType(ty) if !matches!(ty, Type::Reference(_)))
+        && sig
+            .parameters
+            .iter()
+            .all(|param| !param.is_callable && param.name != "self")
+}
+
+fn is_mut_ref(param: &KernelParam) -> bool {
+    matches!(&param.ty, Type::Reference(reference) if reference.mutability.is_some())
+}
+
+/// The type and const generic arguments of the expand function. Lifetimes are inferred.
+fn expand_generic_args(sig: &KernelSignature) -> TokenStream {
+    let args: Vec<_> = sig
+        .generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(param) => Some(&param.ident),
+            GenericParam::Const(param) => Some(&param.ident),
+            GenericParam::Lifetime(_) => None,
+        })
+        .collect();
+
+    match args.is_empty() {
+        true => TokenStream::new(),
+        false => quote![::<#(#args),*>],
+    }
+}
+
+fn static_lifetimes(mut ty: Type) -> Type {
+    ReplaceElidedLifetimes.visit_type_mut(&mut ty);
+    ty
+}
+
+/// Elided lifetimes aren't allowed in where clauses, and each elided lifetime of a parameter
+/// would be a different lifetime, so they are all replaced by `'static`.
+///
+/// Lifetimes fully omitted from a path can't be detected without type information, so only the
+/// frontend slices, which are the only host arguments carrying a lifetime, are handled.
+struct ReplaceElidedLifetimes;
+
+impl VisitMut for ReplaceElidedLifetimes {
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        if lifetime.ident == "_" {
+            *lifetime = parse_quote!['static];
+        }
+    }
+
+    fn visit_path_segment_mut(&mut self, segment: &mut PathSegment) {
+        if segment.ident == "Slice" || segment.ident == "SliceMut" {
+            if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                let has_lifetime = args
+                    .args
+                    .iter()
+                    .any(|arg| matches!(arg, GenericArgument::Lifetime(_)));
+                if !has_lifetime {
+                    args.args.insert(0, parse_quote!['static]);
+                }
+            }
+        }
+
+        visit_mut::visit_path_segment_mut(self, segment);
+    }
+}
+
+struct FindImplTrait(bool);
+
+impl VisitMut for FindImplTrait {
+    fn visit_type_impl_trait_mut(&mut self, _: &mut TypeImplTrait) {
+        self.0 = true;
+    }
+}
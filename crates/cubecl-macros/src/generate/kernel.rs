@@ -37,6 +37,7 @@ impl ToTokens for KernelSignature {
 
         let name = &self.name;
         let generics = &self.generics;
+        let where_clause = &self.generics.where_clause;
         let return_type = match &self.returns {
             KernelReturns::ExpandType(ty) => quote![<#ty as #cube_type>::ExpandType],
             KernelReturns::Plain(ty) => quote![#ty],
@@ -54,7 +55,7 @@ impl ToTokens for KernelSignature {
                     self, // Always owned during expand.
                     context: &mut #cube_context,
                     #(#args),*
-                ) -> #return_type
+                ) -> #return_type #where_clause
             }
         } else {
             let args = &self.parameters;
@@ -62,7 +63,7 @@ impl ToTokens for KernelSignature {
                 fn #name #generics(
                     context: &mut #cube_context,
                     #(#args),*
-                ) -> #return_type
+                ) -> #return_type #where_clause
             }
         };
 
@@ -193,6 +194,7 @@ impl Launch {
 
     fn define_body(&self) -> TokenStream {
         let kernel_builder = prelude_type("KernelBuilder");
+        let definition_cache = prelude_type("KernelDefinitionCache");
         let runtime = prelude_type("Runtime");
         let compiler = core_type("Compiler");
         let io_map = self.io_mappings();
@@ -206,10 +208,15 @@ impl Launch {
         );
 
         quote! {
-            let mut builder = #kernel_builder::with_local_allocator(#allocator);
-            #io_map
-            expand #generics(&mut builder.context, #(#runtime_args.clone(),)* #(self.#comptime_args.clone()),*);
-            builder.build(self.settings.clone())
+            // Shared by every instantiation of the kernel, the id keeps them apart.
+            static DEFINITIONS: #definition_cache = #definition_cache::new();
+
+            DEFINITIONS.get_or_define(self.id(), || {
+                let mut builder = #kernel_builder::with_local_allocator(#allocator);
+                #io_map
+                expand #generics(&mut builder.context, #(#runtime_args.clone(),)* #(self.#comptime_args.clone()),*);
+                builder.build(self.settings.clone())
+            })
         }
     }
 
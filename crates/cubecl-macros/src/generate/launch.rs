@@ -4,6 +4,7 @@ use quote::{format_ident, quote, ToTokens};
 use syn::{parse_quote, Ident};
 
 use crate::{
+    generate::host::host_fn,
     parse::kernel::{KernelParam, Launch},
     paths::{core_path, core_type, prelude_type},
 };
@@ -17,6 +18,12 @@ impl ToTokens for Launch {
         let launch_unchecked = self.launch_unchecked();
         let dummy = self.create_dummy_kernel();
         let kernel = self.kernel_definition();
+        let host = host_fn(
+            &self.func.sig,
+            quote![pub],
+            format_ident!("host"),
+            quote![expand],
+        );
         let mut func = self.func.clone();
         func.sig.name = format_ident!("expand");
         let func = func.to_tokens_mut();
@@ -32,6 +39,7 @@ impl ToTokens for Launch {
                 #launch
                 #launch_unchecked
                 #dummy
+                #host
             }
         };
 
@@ -3,6 +3,7 @@ pub mod cube_impl;
 pub mod cube_trait;
 pub mod cube_type;
 pub mod expression;
+pub mod host;
 pub mod kernel;
 pub mod launch;
 pub mod statement;
@@ -7,11 +7,12 @@ use parse::{
     cube_impl::CubeImpl,
     cube_trait::{CubeTrait, CubeTraitImpl},
     cube_type::CubeType,
+    dispatch::ComptimeDispatch,
     helpers::{RemoveHelpers, ReplaceIndices},
     kernel::{from_tokens, Launch},
 };
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{quote, ToTokens};
 use syn::{visit_mut::VisitMut, Item};
 
 mod error;
@@ -151,6 +152,31 @@ pub fn comptime(input: TokenStream) -> TokenStream {
     quote![{ #tokens }].into()
 }
 
+/// Select the implementation associated with a comptime value, without adding a generic parameter
+/// for it.
+///
+/// Each arm maps a pattern of the comptime value to a type, and the body is duplicated for every
+/// arm with the alias replaced by that type. Inside a `#[cube]` function, the value must be known
+/// at comptime so that only the selected arm is expanded.
+///
+/// # Example
+/// ```ignored
+/// #[cube]
+/// fn execute<I: Numeric, O: Numeric>(..., #[comptime] kind: TileMatmulKind) {
+///     comptime_dispatch!(kind, {
+///         TileMatmulKind::Accelerated => Accelerated16x16x16<I, O>,
+///         TileMatmulKind::Plane => PlaneMma16x16x16<I, O>,
+///     }, |TMM| TMM::execute(lhs, rhs, out, config))
+/// }
+/// ```
+#[proc_macro]
+pub fn comptime_dispatch(input: TokenStream) -> TokenStream {
+    match syn::parse::<ComptimeDispatch>(input) {
+        Ok(dispatch) => dispatch.into_match().into_token_stream().into(),
+        Err(e) => e.into_compile_error().into(),
+    }
+}
+
 /// Implements display and initialization for autotune keys.
 ///
 /// # Helper
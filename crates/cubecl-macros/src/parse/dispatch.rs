@@ -0,0 +1,151 @@
+use quote::quote;
+use syn::{
+    braced,
+    parse::{Parse, ParseStream},
+    parse_quote,
+    punctuated::Punctuated,
+    visit_mut::{self, VisitMut},
+    Expr, ExprMatch, ExprPath, Ident, Pat, PathArguments, QSelf, Token, Type, TypePath,
+};
+
+/// A comptime dispatch table, mapping each variant of a comptime value to an implementation.
+///
+/// ```ignore
+/// comptime_dispatch!(kind, {
+///     TileMatmulKind::Accelerated => Accelerated16x16x16<I, O>,
+///     TileMatmulKind::Plane => PlaneMma16x16x16<I, O>,
+/// }, |TMM| TMM::execute(lhs, rhs, out, config))
+/// ```
+pub struct ComptimeDispatch {
+    value: Expr,
+    arms: Punctuated<DispatchArm, Token![,]>,
+    alias: Ident,
+    body: Expr,
+}
+
+struct DispatchArm {
+    pat: Pat,
+    ty: Type,
+}
+
+impl Parse for DispatchArm {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let pat = Pat::parse_multi_with_leading_vert(input)?;
+        input.parse::<Token![=>]>()?;
+        let ty = input.parse()?;
+        Ok(Self { pat, ty })
+    }
+}
+
+impl Parse for ComptimeDispatch {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let value = input.parse()?;
+        input.parse::<Token![,]>()?;
+
+        let content;
+        braced!(content in input);
+        let arms = content.parse_terminated(DispatchArm::parse, Token![,])?;
+        input.parse::<Token![,]>()?;
+
+        input.parse::<Token![|]>()?;
+        let alias = input.parse()?;
+        input.parse::<Token![|]>()?;
+        let body = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+
+        Ok(Self {
+            value,
+            arms,
+            alias,
+            body,
+        })
+    }
+}
+
+impl ComptimeDispatch {
+    /// Lower the dispatch table to a match on the comptime value, where each arm is the body with
+    /// the alias replaced by the implementation of that arm.
+    pub fn into_match(self) -> ExprMatch {
+        let value = self.value;
+        let arms = self.arms.into_iter().map(|arm| {
+            let pat = arm.pat;
+            let mut body = self.body.clone();
+            ReplaceAlias {
+                alias: &self.alias,
+                ty: &arm.ty,
+            }
+            .visit_expr_mut(&mut body);
+            quote![#pat => #body]
+        });
+
+        parse_quote! {
+            match #value {
+                #(#arms,)*
+            }
+        }
+    }
+}
+
+struct ReplaceAlias<'a> {
+    alias: &'a Ident,
+    ty: &'a Type,
+}
+
+impl ReplaceAlias<'_> {
+    fn starts_with_alias(&self, qself: &Option<QSelf>, path: &syn::Path) -> bool {
+        qself.is_none()
+            && path.leading_colon.is_none()
+            && path.segments.len() > 1
+            && &path.segments[0].ident == self.alias
+    }
+
+    /// Turns `Alias::Item` into `<Type>::Item`.
+    fn replace_qualified(&self, qself: &mut Option<QSelf>, path: &mut syn::Path) {
+        path.segments = path.segments.iter().skip(1).cloned().collect();
+        path.leading_colon = Some(Default::default());
+        *qself = Some(QSelf {
+            lt_token: Default::default(),
+            ty: Box::new(self.ty.clone()),
+            position: 0,
+            as_token: None,
+            gt_token: Default::default(),
+        });
+    }
+}
+
+impl VisitMut for ReplaceAlias<'_> {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        if let Type::Path(TypePath { qself, path }) = ty {
+            if qself.is_none() && path.is_ident(self.alias) {
+                *ty = self.ty.clone();
+                return;
+            }
+            if self.starts_with_alias(qself, path) {
+                self.replace_qualified(qself, path);
+            }
+        }
+        visit_mut::visit_type_mut(self, ty);
+    }
+
+    fn visit_expr_path_mut(&mut self, expr: &mut ExprPath) {
+        if self.starts_with_alias(&expr.qself, &expr.path) {
+            match self.ty {
+                // Expression paths are kept unqualified when possible, so that calls are still
+                // recognized as associated functions, e.g. `Type::<I, O>::execute`.
+                Type::Path(TypePath { qself: None, path }) => {
+                    let mut segments = path.segments.clone();
+                    for segment in segments.iter_mut() {
+                        if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                            args.colon2_token = Some(Default::default());
+                        }
+                    }
+                    segments.extend(expr.path.segments.iter().skip(1).cloned());
+                    expr.path.segments = segments;
+                    expr.path.leading_colon = path.leading_colon;
+                }
+                _ => self.replace_qualified(&mut expr.qself, &mut expr.path),
+            }
+        }
+        visit_mut::visit_expr_path_mut(self, expr);
+    }
+}
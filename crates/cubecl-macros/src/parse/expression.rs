@@ -9,11 +9,14 @@ use crate::{
     expression::{is_intrinsic, Block, ConstMatchArm, Expression},
     operator::Operator,
     scope::Context,
+    statement::Pattern,
 };
 
 use super::{
     branch::{expand_for_loop, expand_if, expand_loop, numeric_match},
+    dispatch::ComptimeDispatch,
     operator::{parse_binop, parse_unop},
+    statement::parse_pat,
 };
 
 impl Expression {
@@ -308,6 +311,10 @@ impl Expression {
                     tokens: quote![{ #tokens }],
                 }
             }
+            Expr::Macro(mac) if is_dispatch_macro(&mac.mac.path) => {
+                let dispatch: ComptimeDispatch = mac.mac.parse_body()?;
+                Expression::from_expr(Expr::Match(dispatch.into_match()), context)?
+            }
             Expr::Macro(mac) => Expression::Verbatim {
                 tokens: quote![#mac],
             },
@@ -338,10 +345,20 @@ impl Expression {
                 inner: Box::new(Expression::from_expr(*reference.expr, context)?),
             },
             Expr::Closure(expr) => {
-                let (body, scope) =
-                    context.in_scope(|ctx| Expression::from_expr(*expr.body, ctx))?;
+                let params: Vec<_> = expr.inputs.into_iter().collect();
+                let (body, scope) = context.in_scope(|ctx| {
+                    for param in params.iter() {
+                        let Pattern {
+                            ident,
+                            ty,
+                            is_ref,
+                            is_mut,
+                        } = parse_pat(param.clone())?;
+                        ctx.push_variable(ident, ty, false, is_ref, is_mut);
+                    }
+                    Expression::from_expr(*expr.body, ctx)
+                })?;
                 let body = Box::new(body);
-                let params = expr.inputs.into_iter().collect();
                 Expression::Closure {
                     params,
                     body,
@@ -443,9 +460,6 @@ fn is_slice(index: &Expression) -> bool {
 fn fn_associated_type(path: &Expression) -> Option<(Path, PathSegment)> {
     // All supported primitives. Primitives don't start with an uppercase letter
     const PRIMITIVES: &[&str] = &["bool", "i32", "i64", "u32", "f16", "bf16", "f32", "f64"];
-    if !matches!(path, Expression::Path { .. }) {
-        panic!("path: {path:?}");
-    }
     match path {
         Expression::Path { path, .. } => {
             let second_last = path.segments.iter().nth_back(1)?;
@@ -470,3 +484,8 @@ fn is_comptime_macro(path: &Path) -> bool {
     let path = path.to_token_stream().to_string();
     "::cubecl::comptime".ends_with(&path)
 }
+
+fn is_dispatch_macro(path: &Path) -> bool {
+    let path = path.to_token_stream().to_string();
+    "::cubecl::comptime_dispatch".ends_with(&path)
+}
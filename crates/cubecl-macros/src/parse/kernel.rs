@@ -3,8 +3,12 @@ use darling::{ast::NestedMeta, util::Flag, FromMeta};
 use proc_macro2::TokenStream;
 use std::iter;
 use syn::{
-    parse_quote, punctuated::Punctuated, spanned::Spanned, visit_mut::VisitMut, Expr, FnArg,
-    Generics, Ident, ItemFn, Signature, TraitItemFn, Type, Visibility,
+    parse_quote,
+    punctuated::Punctuated,
+    spanned::Spanned,
+    visit_mut::{self, VisitMut},
+    Expr, FnArg, Generics, Ident, ItemFn, ParenthesizedGenericArguments, PathArguments, ReturnType,
+    Signature, TraitBound, TraitItemFn, Type, TypeParamBound, Visibility, WherePredicate,
 };
 
 use super::{desugar::Desugar, helpers::is_comptime_attr, statement::parse_pat};
@@ -82,6 +86,7 @@ pub struct KernelParam {
     pub is_const: bool,
     pub is_mut: bool,
     pub is_ref: bool,
+    pub is_callable: bool,
 }
 
 impl KernelParam {
@@ -104,6 +109,7 @@ impl KernelParam {
                     is_const: false,
                     is_mut,
                     is_ref,
+                    is_callable: false,
                 });
             }
         };
@@ -124,6 +130,7 @@ impl KernelParam {
             is_const,
             is_mut,
             is_ref,
+            is_callable: false,
         })
     }
 
@@ -151,16 +158,17 @@ impl KernelParam {
 impl KernelSignature {
     pub fn from_signature(sig: Signature) -> syn::Result<Self> {
         let name = sig.ident;
-        let generics = sig.generics;
+        let mut generics = sig.generics;
         let returns = match sig.output {
             syn::ReturnType::Default => parse_quote![()],
             syn::ReturnType::Type(_, ty) => *ty,
         };
-        let parameters = sig
+        let mut parameters = sig
             .inputs
             .into_iter()
             .map(KernelParam::from_param)
             .collect::<Result<Vec<_>, _>>()?;
+        expand_callables(&mut generics, &mut parameters);
 
         Ok(KernelSignature {
             generics,
@@ -172,17 +180,18 @@ impl KernelSignature {
 
     pub fn from_trait_fn(function: TraitItemFn) -> syn::Result<Self> {
         let name = function.sig.ident;
-        let generics = function.sig.generics;
+        let mut generics = function.sig.generics;
         let returns = match function.sig.output {
             syn::ReturnType::Default => parse_quote![()],
             syn::ReturnType::Type(_, ty) => *ty,
         };
-        let parameters = function
+        let mut parameters = function
             .sig
             .inputs
             .into_iter()
             .map(KernelParam::from_param)
             .collect::<Result<Vec<_>, _>>()?;
+        expand_callables(&mut generics, &mut parameters);
 
         Ok(Self {
             generics,
@@ -279,3 +288,103 @@ fn strip_ref(ty: Type, is_ref: &mut bool, is_mut: &mut bool) -> Type {
         ty => ty,
     }
 }
+
+/// Closures are passed to `#[cube]` functions as comptime-known callables and are expanded at
+/// the call site. Parameters bound by `Fn`, `FnMut` or `FnOnce` are therefore kept as is, and the
+/// bounds are rewritten to the expand signature of the closure, i.e. `Fn(A) -> B` becomes
+/// `Fn(&mut CubeContext, <A as CubeType>::ExpandType) -> <B as CubeType>::ExpandType`.
+fn expand_callables(generics: &mut Generics, parameters: &mut [KernelParam]) {
+    let callables = callable_type_params(generics);
+
+    for param in parameters.iter_mut() {
+        let ty = param.ty_owned();
+        let is_callable = match &ty {
+            Type::ImplTrait(ty) => ty.bounds.iter().any(is_fn_bound),
+            Type::Path(ty) => ty
+                .path
+                .get_ident()
+                .map(|ident| callables.contains(ident))
+                .unwrap_or(false),
+            _ => false,
+        };
+
+        if is_callable {
+            let mut ty = param.ty.clone();
+            ExpandFnBounds.visit_type_mut(&mut ty);
+            param.normalized_ty = ty;
+            param.is_callable = true;
+        }
+    }
+
+    ExpandFnBounds.visit_generics_mut(generics);
+}
+
+fn callable_type_params(generics: &Generics) -> Vec<Ident> {
+    let params = generics
+        .type_params()
+        .filter(|param| param.bounds.iter().any(is_fn_bound))
+        .map(|param| param.ident.clone());
+    let predicates = generics
+        .where_clause
+        .iter()
+        .flat_map(|clause| clause.predicates.iter())
+        .filter_map(|predicate| match predicate {
+            WherePredicate::Type(predicate) if predicate.bounds.iter().any(is_fn_bound) => {
+                match &predicate.bounded_ty {
+                    Type::Path(ty) => ty.path.get_ident().cloned(),
+                    _ => None,
+                }
+            }
+            _ => None,
+        });
+
+    params.chain(predicates).collect()
+}
+
+fn is_fn_bound(bound: &TypeParamBound) -> bool {
+    match bound {
+        TypeParamBound::Trait(bound) => fn_trait_args(&bound.path).is_some(),
+        _ => false,
+    }
+}
+
+fn fn_trait_args(path: &syn::Path) -> Option<&ParenthesizedGenericArguments> {
+    let segment = path.segments.last()?;
+    let is_fn_trait = ["Fn", "FnMut", "FnOnce"]
+        .iter()
+        .any(|name| segment.ident == name);
+
+    match &segment.arguments {
+        PathArguments::Parenthesized(args) if is_fn_trait => Some(args),
+        _ => None,
+    }
+}
+
+struct ExpandFnBounds;
+
+impl VisitMut for ExpandFnBounds {
+    fn visit_trait_bound_mut(&mut self, bound: &mut TraitBound) {
+        if fn_trait_args(&bound.path).is_none() {
+            return visit_mut::visit_trait_bound_mut(self, bound);
+        }
+
+        let cube_context = prelude_type("CubeContext");
+        let cube_type = prelude_type("CubeType");
+        let segment = bound.path.segments.last_mut().unwrap();
+
+        if let PathArguments::Parenthesized(args) = &mut segment.arguments {
+            let inputs = args
+                .inputs
+                .iter()
+                .map(|ty| -> Type { parse_quote![<#ty as #cube_type>::ExpandType] });
+            args.inputs = iter::once(parse_quote![&mut #cube_context])
+                .chain(inputs)
+                .collect();
+
+            if let ReturnType::Type(_, ty) = &mut args.output {
+                let output = ty.clone();
+                **ty = parse_quote![<#output as #cube_type>::ExpandType];
+            }
+        }
+    }
+}
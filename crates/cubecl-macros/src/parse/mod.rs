@@ -6,6 +6,7 @@ pub mod cube_impl;
 pub mod cube_trait;
 pub mod cube_type;
 pub mod desugar;
+pub mod dispatch;
 pub mod expression;
 pub mod helpers;
 pub mod kernel;
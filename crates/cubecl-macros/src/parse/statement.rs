@@ -23,9 +23,13 @@ impl Statement {
                     is_mut,
                 } = parse_pat(local.pat)?;
                 let is_const = init.as_ref().map(|init| init.is_const()).unwrap_or(false);
+                let is_closure = matches!(init.as_deref(), Some(Expression::Closure { .. }));
 
-                let variable =
-                    context.push_variable(ident, ty, is_const && !is_mut, is_ref, is_mut);
+                let variable = if is_closure {
+                    context.push_callable(ident)
+                } else {
+                    context.push_variable(ident, ty, is_const && !is_mut, is_ref, is_mut)
+                };
                 Self::Local { variable, init }
             }
             Stmt::Expr(expr, semi) => {
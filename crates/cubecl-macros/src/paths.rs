@@ -17,6 +17,12 @@ const PRELUDE_PATH: LazyCell<Path> = LazyCell::new(|| {
     path
 });
 #[allow(clippy::declare_interior_mutable_const)]
+const HOST_PATH: LazyCell<Path> = LazyCell::new(|| {
+    let mut path = core_path();
+    path.segments.push(format_ident!("host").into());
+    path
+});
+#[allow(clippy::declare_interior_mutable_const)]
 const TUNE_PATH: LazyCell<Path> = LazyCell::new(|| {
     let mut path = core_path();
     path.segments.push(format_ident!("tune").into());
@@ -33,6 +39,11 @@ pub fn prelude_path() -> Path {
     PRELUDE_PATH.clone()
 }
 
+pub fn host_path() -> Path {
+    #[allow(clippy::borrow_interior_mutable_const)]
+    HOST_PATH.clone()
+}
+
 pub fn tune_path() -> Path {
     #[allow(clippy::borrow_interior_mutable_const)]
     TUNE_PATH.clone()
@@ -70,3 +81,10 @@ pub fn tune_type(ty: &str) -> Path {
     path.segments.push(ident.into());
     path
 }
+
+pub fn host_type(ty: &str) -> Path {
+    let mut path = host_path();
+    let ident = format_ident!("{ty}");
+    path.segments.push(ident.into());
+    path
+}
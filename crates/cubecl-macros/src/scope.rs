@@ -57,6 +57,7 @@ impl Context {
                 is_ref: false,
                 is_mut: false,
                 is_keyword: true,
+                is_callable: false,
                 use_count: AtomicUsize::new(0).into(),
                 level: 0,
             }
@@ -84,6 +85,7 @@ impl Context {
             is_ref,
             is_mut,
             is_keyword: false,
+            is_callable: false,
             use_count: AtomicUsize::new(0).into(),
             level: self.scopes.len() - 1,
         };
@@ -94,6 +96,19 @@ impl Context {
         var
     }
 
+    /// Push a comptime-known callable (closure), which is called with the context instead of
+    /// being expanded.
+    pub fn push_callable(&mut self, name: Ident) -> ManagedVar {
+        self.push_variable(name, None, false, false, false);
+        let var = self
+            .scopes
+            .last_mut()
+            .and_then(|scope| scope.last_mut())
+            .expect("Variable was just pushed");
+        var.is_callable = true;
+        var.clone()
+    }
+
     fn push_scope(&mut self) {
         self.level += 1;
         self.scopes.push(ManagedScope::default());
@@ -151,6 +166,7 @@ pub struct ManagedVar {
     pub is_ref: bool,
     pub is_mut: bool,
     pub is_keyword: bool,
+    pub is_callable: bool,
     use_count: Rc<AtomicUsize>,
     level: usize,
 }
@@ -169,6 +185,7 @@ impl From<KernelParam> for ManagedVar {
             ty: Some(value.ty),
             is_const: value.is_const,
             is_keyword: false,
+            is_callable: value.is_callable,
             use_count: AtomicUsize::new(0).into(),
             is_ref: value.is_ref,
             is_mut: value.is_mut,
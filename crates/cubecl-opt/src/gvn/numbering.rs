@@ -269,6 +269,9 @@ impl ValueTable {
                 (expr.into(), out_val)
             }
 
+            // The lane indices can't be numbered, so the output is always a new value
+            Operator::Shuffle(_) => Err(value_of_var(&out))?,
+
             Operator::Select(op) => {
                 let item = out.item;
                 let cond = self.lookup_or_add_var(&op.cond)?;
@@ -126,6 +126,10 @@ impl Optimizer {
                     visit_read(self, input)
                 }
             }
+            Operator::Shuffle(shuffle_operator) => {
+                visit_read(self, &mut shuffle_operator.lhs);
+                visit_read(self, &mut shuffle_operator.rhs);
+            }
             Operator::CopyMemory(copy_operator) => {
                 visit_read(self, &mut copy_operator.input);
                 visit_read(self, &mut copy_operator.in_index);
@@ -203,7 +207,9 @@ impl Optimizer {
     ) {
         match subcube {
             Subcube::Elect => {}
-            Subcube::Broadcast(binary_operator) => self.visit_binop(binary_operator, visit_read),
+            Subcube::Broadcast(binary_operator) | Subcube::Shuffle(binary_operator) => {
+                self.visit_binop(binary_operator, visit_read)
+            }
             Subcube::All(unary_operator)
             | Subcube::Any(unary_operator)
             | Subcube::Sum(unary_operator)
@@ -675,6 +675,44 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
                 self.composite_construct(ty, Some(out_id), values).unwrap();
                 self.write(&out, out_id);
             }
+            Operator::Shuffle(op) => {
+                let lhs_size = op.lhs.item.vectorization.map(|it| it.get()).unwrap_or(1) as u32;
+                let rhs_size = op.rhs.item.vectorization.map(|it| it.get()).unwrap_or(1) as u32;
+                let lhs = self.compile_variable(op.lhs);
+                let rhs = self.compile_variable(op.rhs);
+                let lhs_id = self.read(&lhs);
+                let rhs_id = self.read(&rhs);
+                let item = self.compile_item(out.item);
+                let out = self.compile_variable(out);
+                let out_id = self.write_id(&out);
+                let ty = item.id(self);
+
+                if lhs_size > 1 && rhs_size > 1 && op.indices.len() > 1 {
+                    self.vector_shuffle(ty, Some(out_id), lhs_id, rhs_id, op.indices)
+                        .unwrap();
+                } else {
+                    let elem_ty = item.elem().id(self);
+                    let mut lanes = op
+                        .indices
+                        .into_iter()
+                        .map(|index| match index < lhs_size {
+                            true => (lhs_id, lhs_size, index),
+                            false => (rhs_id, rhs_size, index - lhs_size),
+                        })
+                        .map(|(value, size, index)| match size > 1 {
+                            true => self
+                                .composite_extract(elem_ty, None, value, vec![index])
+                                .unwrap(),
+                            false => value,
+                        })
+                        .collect::<Vec<_>>();
+                    match lanes.len() {
+                        1 => self.copy_object(ty, Some(out_id), lanes.remove(0)).unwrap(),
+                        _ => self.composite_construct(ty, Some(out_id), lanes).unwrap(),
+                    };
+                }
+                self.write(&out, out_id);
+            }
             Operator::CopyMemory(op) => {
                 let input = self.compile_variable(op.input);
                 let in_index = self.compile_variable(op.in_index);
@@ -39,6 +39,13 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
                         .unwrap();
                 });
             }
+            Subcube::Shuffle(op) => {
+                self.capabilities.insert(Capability::GroupNonUniformShuffle);
+                self.compile_binary_op_no_cast(op, out, |b, _, ty, lhs, rhs, out| {
+                    b.group_non_uniform_shuffle(ty, Some(out), subgroup, lhs, rhs)
+                        .unwrap();
+                });
+            }
             Subcube::Sum(op) => {
                 self.compile_unary_op(op, out, |b, out_ty, ty, input, out| {
                     match out_ty.elem() {
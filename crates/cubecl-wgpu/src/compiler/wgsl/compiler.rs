@@ -529,6 +529,11 @@ impl WgslCompiler {
                 rhs: self.compile_variable(op.rhs),
                 out: self.compile_variable(out),
             },
+            cube::Subcube::Shuffle(op) => Subgroup::Shuffle {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(out),
+            },
             cube::Subcube::Sum(op) => Subgroup::Sum {
                 input: self.compile_variable(op.input),
                 out: self.compile_variable(out),
@@ -928,6 +933,12 @@ impl WgslCompiler {
                     .collect(),
                 out: self.compile_variable(out),
             },
+            cube::Operator::Shuffle(op) => wgsl::Instruction::Shuffle {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                indices: op.indices,
+                out: self.compile_variable(out),
+            },
             cube::Operator::CopyMemory(op) => wgsl::Instruction::Copy {
                 input: self.compile_variable(op.input),
                 in_index: self.compile_variable(op.in_index),
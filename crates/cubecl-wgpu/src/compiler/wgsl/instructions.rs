@@ -340,6 +340,12 @@ pub enum Instruction {
         inputs: Vec<Variable>,
         out: Variable,
     },
+    Shuffle {
+        lhs: Variable,
+        rhs: Variable,
+        indices: Vec<u32>,
+        out: Variable,
+    },
     Copy {
         input: Variable,
         in_index: Variable,
@@ -886,6 +892,38 @@ for (var {i}: {i_ty} = {start}; {i} {cmp} {end}; {increment}) {{
                 let out = out.fmt_left();
                 writeln!(f, "{out} = {item}({})", inputs.join(", "))
             }
+            Instruction::Shuffle {
+                lhs,
+                rhs,
+                indices,
+                out,
+            } => {
+                let item = out.item();
+                let lhs_size = lhs.item().vectorization_factor() as u32;
+                let out = out.fmt_left();
+
+                // Lanes of a single vector are selected with a swizzle, e.g. `lhs.xzyw`.
+                if lhs_size > 1 && indices.len() > 1 && indices.iter().all(|i| *i < lhs_size) {
+                    let swizzle = indices
+                        .iter()
+                        .map(|index| ['x', 'y', 'z', 'w'][*index as usize])
+                        .collect::<String>();
+                    return writeln!(f, "{out} = {lhs}.{swizzle};");
+                }
+
+                let lanes = indices
+                    .iter()
+                    .map(|index| match *index < lhs_size {
+                        true => lhs.index(*index as usize).to_string(),
+                        false => rhs.index((*index - lhs_size) as usize).to_string(),
+                    })
+                    .collect::<Vec<_>>();
+
+                match item {
+                    Item::Scalar(_) => writeln!(f, "{out} = {};", lanes[0]),
+                    item => writeln!(f, "{out} = {item}({});", lanes.join(", ")),
+                }
+            }
         }
     }
 }
@@ -20,6 +20,11 @@ pub enum Subgroup {
         rhs: Variable,
         out: Variable,
     },
+    Shuffle {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
     Sum {
         input: Variable,
         out: Variable,
@@ -54,6 +59,10 @@ impl Display for Subgroup {
                 let out = out.fmt_left();
                 writeln!(f, "{out} = subgroupBroadcast({lhs}, {rhs});")
             }
+            Subgroup::Shuffle { lhs, rhs, out } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = subgroupShuffle({lhs}, {rhs});")
+            }
             Subgroup::Sum { input, out } => {
                 let out = out.fmt_left();
                 writeln!(f, "{out} = subgroupAdd({input});")
@@ -27,6 +27,21 @@ mod tests {
     cubecl_core::testgen_all!();
     cubecl_linalg::testgen_plane_mma!([flex32, f32], f32);
     cubecl_linalg::testgen_tiling2d!([flex32, f32]);
+    cubecl_linalg::testgen_gemv!([flex32, f32]);
+    cubecl_linalg::testgen_reduce!();
+    cubecl_linalg::testgen_normalization!([flex32, f32]);
+    cubecl_linalg::testgen_conv!([flex32, f32]);
+    cubecl_linalg::testgen_attention!([flex32, f32]);
+    cubecl_linalg::testgen_scan!();
+    cubecl_linalg::testgen_sort!();
+    cubecl_linalg::testgen_random!();
+    cubecl_linalg::testgen_tensor!();
+    cubecl_linalg::testgen_index!();
+    cubecl_linalg::testgen_fft!();
+    cubecl_linalg::testgen_fusion!();
+    cubecl_linalg::testgen_solve!();
+    cubecl_linalg::testgen_histogram!();
+    cubecl_linalg::testgen_sparse!();
 }
 
 #[cfg(all(test, feature = "spirv"))]
@@ -0,0 +1,43 @@
+@group(0)
+@binding(0)
+var<storage, read_write> input_0_global: array<vec4<f32>>;
+
+@group(0)
+@binding(1)
+var<storage, read_write> output_0_global: array<vec4<f32>>;
+
+@group(0)
+@binding(2)
+var<storage, read_write> info: array<u32>;
+
+const WORKGROUP_SIZE_X = 16u;
+const WORKGROUP_SIZE_Y = 16u;
+const WORKGROUP_SIZE_Z = 1u;
+
+@compute
+@workgroup_size(16, 16, 1)
+fn main(
+    @builtin(global_invocation_id) global_id: vec3<u32>,
+    @builtin(local_invocation_index) local_idx: u32,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+let id = (global_id.z * num_workgroups.x * WORKGROUP_SIZE_X * num_workgroups.y * WORKGROUP_SIZE_Y) + (global_id.y * num_workgroups.x * WORKGROUP_SIZE_X) + global_id.x;
+var l_0_0: vec4<f32>;
+let _0 = info[3u];
+let _1 = id < _0;
+if _1 {
+let _2 = input_0_global[id];
+let _3 = _2.xy;
+let _4 = _2.zw;
+let _5 = _4.yx;
+let _6 = _3.yx;
+let _7 = vec4<f32>(_5[0], _5[1], _6[0], _6[1]);
+l_0_0 = _7;
+let _9 = local_idx % 4u;
+let _10 = local_idx % 2u;
+let _11 = _2[_10];
+l_0_0[_9] = _11;
+let _12 = l_0_0.xywz;
+output_0_global[id] = _12;
+}
+}
\ No newline at end of file
@@ -5,6 +5,7 @@ use cubecl_core::prelude::*;
 use cubecl_wgpu::WgpuRuntime;
 use execute_unary_kernel::ExecuteUnaryKernel;
 use kernel_sum::KernelSum;
+use line_shuffle_kernel::LineShuffleKernel;
 use pretty_assertions::assert_eq;
 use sequence_for_loop_kernel::SequenceForLoopKernel;
 use slice_assign_kernel::SliceAssignKernel;
@@ -107,3 +108,23 @@ pub fn constant_array() {
     let expected = include_str!("constant_array.wgsl").replace("\r\n", "\n");
     assert_eq!(compile(kernel), expected);
 }
+
+#[cube(launch, create_dummy_kernel)]
+fn line_shuffle_kernel<F: Float>(input: &Tensor<Line<F>>, out: &mut Tensor<Line<F>>) {
+    if ABSOLUTE_POS < out.len() {
+        let line = input[ABSOLUTE_POS];
+        let (low, high) = line.split(2);
+        let mut reversed = Line::concat(high.shuffle([1, 0]), low.shuffle([1, 0]));
+        reversed.insert(UNIT_POS % 4, line.extract(UNIT_POS % 2));
+
+        out[ABSOLUTE_POS] = reversed.swizzle([0, 1, 3, 2]);
+    }
+}
+
+#[test]
+pub fn line_shuffle() {
+    let kernel =
+        LineShuffleKernel::<f32, WgpuRuntime>::new(settings(16, 16), tensor_vec(4), tensor_vec(4));
+    let expected = include_str!("line_shuffle.wgsl").replace("\r\n", "\n");
+    assert_eq!(compile(kernel), expected);
+}